@@ -1,50 +1,39 @@
 //! Disk usage analysis and reporting
 
+use crate::categories::registry::{CategoryDescriptor, CategoryRegistry, ScanContext, ScanExtras};
+use crate::cleaner::DeleteOutcome;
 use crate::cli::ScanOptions;
 use crate::config::Config;
 use crate::output::{CategoryResult, OutputMode};
 use crate::progress;
+use crate::scan_events::ScanProgressEvent;
 use anyhow::Result;
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-
-/// Category of cleanable files
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Category {
-    Cache,
-    AppCache,
-    Temp,
-    Trash,
-    Build,
-    Downloads,
-    Large,
-    Old,
-    Browser,
-    System,
-    Empty,
-    Duplicates,
-    Applications,
+use std::sync::mpsc::Sender;
+
+/// Category of cleanable files: the registry category that found them
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Category {
+    /// Registry id of the category
+    pub id: String,
+    display_name: String,
+    custom: bool,
 }
 
 impl Category {
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            Category::Cache => "Cache",
-            Category::AppCache => "Application Cache",
-            Category::Temp => "Temp Files",
-            Category::Trash => "Trash",
-            Category::Build => "Build Artifacts",
-            Category::Downloads => "Old Downloads",
-            Category::Large => "Large Files",
-            Category::Old => "Old Files",
-            Category::Browser => "Browser Cache",
-            Category::System => "System Cache",
-            Category::Empty => "Empty Folders",
-            Category::Duplicates => "Duplicates",
-            Category::Applications => "Installed Applications",
+    pub fn new(descriptor: &CategoryDescriptor) -> Self {
+        Self {
+            id: descriptor.id.clone(),
+            display_name: descriptor.display_name.clone(),
+            custom: descriptor.custom,
         }
     }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
 }
 
 /// Represents a single cleanable file or directory
@@ -95,86 +84,55 @@ impl ScanResult {
     pub fn by_category(&self) -> HashMap<Category, Vec<&CleanableFile>> {
         let mut groups: HashMap<Category, Vec<&CleanableFile>> = HashMap::new();
         for file in &self.files {
-            groups.entry(file.category).or_default().push(file);
+            groups.entry(file.category.clone()).or_default().push(file);
         }
         groups
     }
 }
 
 /// Trait for scanners that can identify cleanable files
+///
+/// Each category in the [`CategoryRegistry`] is backed by a scanner. Library users
+/// can implement this trait and register their own category with
+/// [`CategoryRegistry::register`].
 pub trait Scanner: Send + Sync {
-    fn name(&self) -> &'static str;
-    fn category(&self) -> Category;
-    fn scan(
+    /// Scan for cleanable files
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult>;
+
+    /// Scan while sending progress events to the TUI
+    ///
+    /// The default runs a quiet [`Scanner::scan`]; started/finished events are then
+    /// sent by the registry on the scanner's behalf.
+    fn scan_with_progress(
         &self,
-        path: &Path,
-        options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>>;
+        ctx: &ScanContext,
+        _tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        self.scan(&ctx.quiet())
+    }
+
+    /// Whether `scan_with_progress` sends its own started/finished events
+    fn reports_progress(&self) -> bool {
+        false
+    }
+
+    /// Remove a single path found by this scanner
+    fn clean(&self, path: &Path, permanent: bool) -> Result<DeleteOutcome> {
+        crate::cleaner::delete_with_precheck(path, permanent)
+    }
 }
 
 /// Run all enabled scanners and aggregate results
 pub fn run_scan(path: &Path, options: &ScanOptions, config: &Config) -> Result<ScanResult> {
     // Clear git cache for fresh scan
     crate::git::clear_cache();
+    crate::project::policy::clear_cache();
 
     let mut result = ScanResult::new();
-    let mut scanners: Vec<Box<dyn Scanner>> = Vec::new();
-
-    // Build list of scanners based on options
-    if options.cache {
-        scanners.push(Box::new(CacheScannerAdapter));
-    }
-
-    if options.app_cache {
-        scanners.push(Box::new(AppCacheScannerAdapter));
-    }
-
-    if options.temp {
-        scanners.push(Box::new(TempScannerAdapter));
-    }
-
-    if options.trash {
-        scanners.push(Box::new(TrashScannerAdapter));
-    }
-
-    if options.build {
-        scanners.push(Box::new(BuildScannerAdapter));
-    }
-
-    if options.downloads {
-        scanners.push(Box::new(DownloadsScannerAdapter));
-    }
-
-    if options.large {
-        scanners.push(Box::new(LargeScannerAdapter));
-    }
-
-    if options.old {
-        scanners.push(Box::new(OldScannerAdapter));
-    }
-
-    if options.browser {
-        scanners.push(Box::new(BrowserScannerAdapter));
-    }
-
-    if options.system {
-        scanners.push(Box::new(SystemScannerAdapter));
-    }
-
-    if options.empty {
-        scanners.push(Box::new(EmptyScannerAdapter));
-    }
-
-    if options.duplicates {
-        scanners.push(Box::new(DuplicatesScannerAdapter));
-    }
-
-    if options.applications {
-        scanners.push(Box::new(ApplicationsScannerAdapter));
-    }
+    let registry = CategoryRegistry::with_config(config);
+    let descriptors = registry.enabled(options);
 
-    if scanners.is_empty() {
+    if descriptors.is_empty() {
         return Ok(result);
     }
 
@@ -182,12 +140,20 @@ pub fn run_scan(path: &Path, options: &ScanOptions, config: &Config) -> Result<S
     let spinner = progress::create_spinner("Scanning for cleanable files...");
 
     // Run scanners in parallel
-    let scan_results: Vec<(String, Result<Vec<CleanableFile>>)> = scanners
+    let extras = ScanExtras::default();
+    let ctx = ScanContext::new(path, options, config, OutputMode::Quiet, &extras);
+    let scan_results: Vec<(String, Result<Vec<CleanableFile>>)> = descriptors
         .par_iter()
-        .map(|scanner| {
-            let name = scanner.name().to_string();
-            let files = scanner.scan(path, options, config);
-            (name, files)
+        .map(|descriptor| {
+            let files = descriptor.scan(&ctx).map(|category_result| {
+                let reason = if descriptor.description.is_empty() {
+                    descriptor.display_name.as_str()
+                } else {
+                    descriptor.description.as_str()
+                };
+                convert_category_result(category_result, Category::new(descriptor), reason, config)
+            });
+            (descriptor.display_name.clone(), files)
         })
         .collect();
 
@@ -222,7 +188,7 @@ pub fn print_report(result: &ScanResult) {
         .map(|(cat, files)| {
             let count = files.len();
             let size: u64 = files.iter().map(|f| f.size).sum();
-            (*cat, count, size)
+            (cat.clone(), count, size)
         })
         .collect();
 
@@ -360,7 +326,7 @@ pub fn group_by_category(files: &[CleanableFile]) -> HashMap<Category, Vec<&Clea
     let mut groups: HashMap<Category, Vec<&CleanableFile>> = HashMap::new();
 
     for file in files {
-        groups.entry(file.category).or_default().push(file);
+        groups.entry(file.category.clone()).or_default().push(file);
     }
 
     groups
@@ -394,377 +360,6 @@ fn print_file_entry(path: &Path, size: u64, _depth: usize) {
     println!("  {}  {}", format_size(size), path.display());
 }
 
-// Scanner adapters that wrap existing category scan functions
-
-struct CacheScannerAdapter;
-impl Scanner for CacheScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Cache Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Cache
-    }
-
-    fn scan(
-        &self,
-        _path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::cache;
-        let result = cache::scan(std::path::Path::new(""), config, OutputMode::Normal)?;
-        Ok(convert_category_result(
-            result,
-            Category::Cache,
-            "Package manager cache",
-            config,
-        ))
-    }
-}
-
-struct AppCacheScannerAdapter;
-impl Scanner for AppCacheScannerAdapter {
-    fn name(&self) -> &'static str {
-        "App Cache Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::AppCache
-    }
-
-    fn scan(
-        &self,
-        _path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::app_cache;
-        let result = app_cache::scan(std::path::Path::new(""), config, OutputMode::Normal)?;
-        Ok(convert_category_result(
-            result,
-            Category::AppCache,
-            "Application cache",
-            config,
-        ))
-    }
-}
-
-struct TempScannerAdapter;
-impl Scanner for TempScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Temp Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Temp
-    }
-
-    fn scan(
-        &self,
-        _path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::temp;
-        let result = temp::scan(std::path::Path::new(""), config)?;
-        Ok(convert_category_result(
-            result,
-            Category::Temp,
-            "Temporary files",
-            config,
-        ))
-    }
-}
-
-struct TrashScannerAdapter;
-impl Scanner for TrashScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Trash Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Trash
-    }
-
-    fn scan(
-        &self,
-        _path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::trash;
-        let result = trash::scan()?;
-        Ok(convert_category_result(
-            result,
-            Category::Trash,
-            "Recycle Bin contents",
-            config,
-        ))
-    }
-}
-
-struct BuildScannerAdapter;
-impl Scanner for BuildScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Build Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Build
-    }
-
-    fn scan(
-        &self,
-        path: &Path,
-        options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::build;
-        let result = build::scan(
-            path,
-            options.project_age_days,
-            Some(&config.categories.build),
-            config,
-            OutputMode::Normal,
-        )?;
-        Ok(convert_category_result(
-            result,
-            Category::Build,
-            "Build artifacts from inactive projects",
-            config,
-        ))
-    }
-}
-
-struct DownloadsScannerAdapter;
-impl Scanner for DownloadsScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Downloads Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Downloads
-    }
-
-    fn scan(
-        &self,
-        _path: &Path,
-        options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::downloads;
-        let result = downloads::scan(
-            std::path::Path::new(""),
-            options.min_age_days,
-            config,
-            OutputMode::Normal,
-        )?;
-        Ok(convert_category_result(
-            result,
-            Category::Downloads,
-            "Old downloads",
-            config,
-        ))
-    }
-}
-
-struct LargeScannerAdapter;
-impl Scanner for LargeScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Large Files Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Large
-    }
-
-    fn scan(
-        &self,
-        _path: &Path,
-        options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::large;
-        let result = large::scan(
-            std::path::Path::new(""),
-            options.min_size_bytes,
-            config,
-            OutputMode::Normal,
-        )?;
-        Ok(convert_category_result(
-            result,
-            Category::Large,
-            "Large files",
-            config,
-        ))
-    }
-}
-
-struct OldScannerAdapter;
-impl Scanner for OldScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Old Files Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Old
-    }
-
-    fn scan(
-        &self,
-        path: &Path,
-        options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::old;
-        let result = old::scan(path, options.min_age_days, config, OutputMode::Normal)?;
-        Ok(convert_category_result(
-            result,
-            Category::Old,
-            "Old files",
-            config,
-        ))
-    }
-}
-
-struct BrowserScannerAdapter;
-impl Scanner for BrowserScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Browser Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Browser
-    }
-
-    fn scan(
-        &self,
-        path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::browser;
-        let result = browser::scan(path, config)?;
-        Ok(convert_category_result(
-            result,
-            Category::Browser,
-            "Browser cache",
-            config,
-        ))
-    }
-}
-
-struct SystemScannerAdapter;
-impl Scanner for SystemScannerAdapter {
-    fn name(&self) -> &'static str {
-        "System Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::System
-    }
-
-    fn scan(
-        &self,
-        path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::system;
-        let result = system::scan(path, config)?;
-        Ok(convert_category_result(
-            result,
-            Category::System,
-            "System cache",
-            config,
-        ))
-    }
-}
-
-struct EmptyScannerAdapter;
-impl Scanner for EmptyScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Empty Folders Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Empty
-    }
-
-    fn scan(
-        &self,
-        path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::empty;
-        let result = empty::scan(path, config)?;
-        Ok(convert_category_result(
-            result,
-            Category::Empty,
-            "Empty folders",
-            config,
-        ))
-    }
-}
-
-struct DuplicatesScannerAdapter;
-impl Scanner for DuplicatesScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Duplicates Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Duplicates
-    }
-
-    fn scan(
-        &self,
-        path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::duplicates;
-        let result =
-            duplicates::scan_with_config(path, Some(&config.categories.duplicates), config)?;
-        let category_result = result.to_category_result();
-        Ok(convert_category_result(
-            category_result,
-            Category::Duplicates,
-            "Duplicate files",
-            config,
-        ))
-    }
-}
-
-struct ApplicationsScannerAdapter;
-impl Scanner for ApplicationsScannerAdapter {
-    fn name(&self) -> &'static str {
-        "Applications Scanner"
-    }
-
-    fn category(&self) -> Category {
-        Category::Applications
-    }
-
-    fn scan(
-        &self,
-        _path: &Path,
-        _options: &ScanOptions,
-        config: &Config,
-    ) -> Result<Vec<CleanableFile>> {
-        use crate::categories::applications;
-        let result = applications::scan(std::path::Path::new(""), config, OutputMode::Normal)?;
-        Ok(convert_category_result(
-            result,
-            Category::Applications,
-            "Installed application",
-            config,
-        ))
-    }
-}
-
 /// Convert CategoryResult to Vec<CleanableFile>, filtering excluded paths
 fn convert_category_result(
     result: CategoryResult,
@@ -780,9 +375,12 @@ fn convert_category_result(
             // For Installed Applications we already computed real folder sizes during the scan
             // (registry EstimatedSize or a directory walk). `metadata.len()` is not meaningful
             // for directories on Windows and will show tiny values (e.g. 4 KiB).
-            let size = if category == Category::Applications {
+            let size = if category.id == "applications" {
                 crate::categories::applications::get_app_size(&path)
                     .unwrap_or_else(|| std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0))
+            } else if category.custom && path.is_dir() {
+                // Custom categories default to whole-directory items
+                crate::utils::calculate_dir_size(&path)
             } else {
                 std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
             };
@@ -790,7 +388,7 @@ fn convert_category_result(
             CleanableFile {
                 path,
                 size,
-                category,
+                category: category.clone(),
                 reason: reason.to_string(),
                 is_directory,
             }
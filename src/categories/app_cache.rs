@@ -1,3 +1,6 @@
+use crate::categories::locations::{
+    Base, KnownLocation, LocationResolver, Os, ResolvedLocation, Rule,
+};
 use crate::config::Config;
 use crate::output::{CategoryResult, OutputMode};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
@@ -6,146 +9,262 @@ use crate::utils;
 use anyhow::{Context, Result};
 use bytesize;
 use std::collections::HashSet;
-use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
-/// Application cache locations to scan
-/// Each tuple is (name, path_from_localappdata_or_appdata)
-const APP_CACHE_LOCATIONS: &[(&str, AppCacheLocation)] = &[
-    (
-        "Discord",
-        AppCacheLocation::LocalAppDataNested(&["discord", "Cache"]),
-    ),
-    (
-        "VS Code",
-        AppCacheLocation::LocalAppDataNested(&["Code", "Cache"]),
-    ),
-    (
-        "VS Code (User)",
-        AppCacheLocation::LocalAppDataNested(&["Code", "User", "CachedData"]),
-    ),
-    (
-        "Slack",
-        AppCacheLocation::LocalAppDataNested(&["slack", "Cache"]),
-    ),
-    (
-        "Spotify",
-        AppCacheLocation::LocalAppDataNested(&["Spotify", "Storage"]),
-    ),
-    (
-        "Steam",
-        AppCacheLocation::LocalAppDataNested(&["Steam", "htmlcache"]),
-    ),
-    (
-        "Telegram",
-        AppCacheLocation::LocalAppDataNested(&["Telegram Desktop", "tdata"]),
-    ),
-    (
-        "Zoom",
-        AppCacheLocation::LocalAppDataNested(&["Zoom", "Cache"]),
-    ),
-    (
-        "Teams",
-        AppCacheLocation::LocalAppDataNested(&["Microsoft", "Teams", "Cache"]),
-    ),
-    (
-        "Notion",
-        AppCacheLocation::LocalAppDataNested(&["Notion", "Cache"]),
-    ),
-    (
-        "Figma",
-        AppCacheLocation::LocalAppDataNested(&["Figma", "Cache"]),
-    ),
-    (
-        "Adobe",
-        AppCacheLocation::LocalAppDataNested(&["Adobe", "Common"]),
-    ),
-    (
-        "Adobe Acrobat",
-        AppCacheLocation::LocalAppDataNested(&["Adobe", "Acrobat", "Cache"]),
-    ),
-    (
-        "Dropbox",
-        AppCacheLocation::LocalAppDataNested(&["Dropbox", "Cache"]),
-    ),
-    (
-        "OneDrive",
-        AppCacheLocation::LocalAppDataNested(&["Microsoft", "OneDrive", "Cache"]),
-    ),
-    (
-        "GitHub Desktop",
-        AppCacheLocation::LocalAppDataNested(&["GitHub Desktop", "Cache"]),
-    ),
-    (
-        "Postman",
-        AppCacheLocation::LocalAppDataNested(&["Postman", "Cache"]),
-    ),
-    (
-        "Docker",
-        AppCacheLocation::LocalAppDataNested(&["Docker", "Cache"]),
-    ),
-    (
-        "DBeaver",
-        AppCacheLocation::LocalAppDataNested(&["DBeaver", "Cache"]),
-    ),
-    (
-        "JetBrains",
-        AppCacheLocation::LocalAppDataNested(&["JetBrains", "Cache"]),
-    ),
-    (
-        "IntelliJ IDEA",
-        AppCacheLocation::LocalAppDataNested(&["JetBrains", "IntelliJIdea", "cache"]),
-    ),
-    (
-        "PyCharm",
-        AppCacheLocation::LocalAppDataNested(&["JetBrains", "PyCharm", "cache"]),
-    ),
-    (
-        "WebStorm",
-        AppCacheLocation::LocalAppDataNested(&["JetBrains", "WebStorm", "cache"]),
-    ),
-    (
-        "Android Studio",
-        AppCacheLocation::LocalAppDataNested(&["Google", "AndroidStudio", "cache"]),
-    ),
-    (
-        "Unity",
-        AppCacheLocation::LocalAppDataNested(&["Unity", "cache"]),
-    ),
-    (
-        "Blender",
-        AppCacheLocation::LocalAppDataNested(&["Blender Foundation", "Blender", "cache"]),
-    ),
-    (
-        "OBS Studio",
-        AppCacheLocation::LocalAppDataNested(&["obs-studio", "Cache"]),
-    ),
-    (
-        "VLC",
-        AppCacheLocation::LocalAppDataNested(&["vlc", "cache"]),
-    ),
-    (
-        "WinRAR",
-        AppCacheLocation::LocalAppDataNested(&["WinRAR", "Cache"]),
-    ),
-    (
-        "7-Zip",
-        AppCacheLocation::LocalAppDataNested(&["7-Zip", "Cache"]),
-    ),
+/// Application cache locations to scan, per OS
+const APP_CACHE_LOCATIONS: &[KnownLocation] = &[
+    KnownLocation {
+        name: "Discord",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["discord", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserConfig, &["discord", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "VS Code",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["Code", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserConfig, &["Code", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "VS Code (User)",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["Code", "User", "CachedData"],
+            ),
+            Rule::Under(Os::Unix, Base::UserConfig, &["Code", "CachedData"]),
+        ],
+    },
+    KnownLocation {
+        name: "Slack",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["slack", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserConfig, &["Slack", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Spotify",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["Spotify", "Storage"]),
+            Rule::Under(Os::MacOs, Base::UserCache, &["com.spotify.client"]),
+            Rule::Under(Os::Linux, Base::UserCache, &["spotify"]),
+        ],
+    },
+    KnownLocation {
+        name: "Steam",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Steam", "htmlcache"],
+        )],
+    },
+    KnownLocation {
+        name: "Telegram",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Telegram Desktop", "tdata"],
+        )],
+    },
+    KnownLocation {
+        name: "Zoom",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Zoom", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "Teams",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["Microsoft", "Teams", "Cache"],
+            ),
+            Rule::Under(Os::Unix, Base::UserConfig, &["Microsoft", "Teams", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Notion",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["Notion", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserConfig, &["Notion", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Figma",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Figma", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "Adobe",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Adobe", "Common"],
+        )],
+    },
+    KnownLocation {
+        name: "Adobe Acrobat",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Adobe", "Acrobat", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "Dropbox",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Dropbox", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "OneDrive",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Microsoft", "OneDrive", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "GitHub Desktop",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["GitHub Desktop", "Cache"],
+            ),
+            Rule::Under(Os::Unix, Base::UserConfig, &["GitHub Desktop", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Postman",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["Postman", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserConfig, &["Postman", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Docker",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Docker", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "DBeaver",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["DBeaver", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "JetBrains",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["JetBrains", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["JetBrains"]),
+        ],
+    },
+    KnownLocation {
+        name: "IntelliJ IDEA",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["JetBrains", "IntelliJIdea", "cache"],
+        )],
+    },
+    KnownLocation {
+        name: "PyCharm",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["JetBrains", "PyCharm", "cache"],
+        )],
+    },
+    KnownLocation {
+        name: "WebStorm",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["JetBrains", "WebStorm", "cache"],
+        )],
+    },
+    KnownLocation {
+        name: "Android Studio",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Google", "AndroidStudio", "cache"],
+        )],
+    },
+    KnownLocation {
+        name: "Unity",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["Unity", "cache"]),
+            Rule::Under(Os::Linux, Base::UserConfig, &["unity3d", "cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Blender",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Blender Foundation", "Blender", "cache"],
+        )],
+    },
+    KnownLocation {
+        name: "OBS Studio",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["obs-studio", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserConfig, &["obs-studio", "Cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "VLC",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["vlc", "cache"],
+        )],
+    },
+    KnownLocation {
+        name: "WinRAR",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["WinRAR", "Cache"],
+        )],
+    },
+    KnownLocation {
+        name: "7-Zip",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["7-Zip", "Cache"],
+        )],
+    },
 ];
 
-enum AppCacheLocation {
-    LocalAppDataNested(&'static [&'static str]),
-}
-
 /// Common cache directory names used by applications
 const CACHE_DIR_NAMES: &[&str] = &["Cache", "cache", "Caches", ".cache", "Cache_Data"];
 
 /// Scan for app-specific cache directories
 ///
-/// Scans %LOCALAPPDATA% and %APPDATA% for app directories containing cache folders.
+/// Scans an application data root (%LOCALAPPDATA%, %APPDATA%, ~/.config, ...) for
+/// app directories containing cache folders.
 /// Looks for common cache directory names like "Cache", "cache", "Caches", etc.
 fn scan_app_caches(
     base_path: &Path,
@@ -158,7 +277,7 @@ fn scan_app_caches(
         return app_cache_paths;
     }
 
-    // Read the base directory (e.g., LOCALAPPDATA or APPDATA)
+    // Read the base directory (e.g., LOCALAPPDATA or ~/.config)
     let entries = match utils::safe_read_dir(base_path) {
         Ok(entries) => entries,
         Err(_) => return app_cache_paths,
@@ -216,10 +335,8 @@ fn scan_app_caches(
 
 /// Scan for application cache directories
 ///
-/// Scan for application cache directories
-///
-/// Checks well-known Windows cache locations for various applications.
-/// Also scans generically for app cache directories.
+/// Checks well-known cache locations for various applications on the current OS.
+/// Also scans the application data roots generically for app cache directories.
 ///
 /// Optimized to calculate directory sizes in parallel.
 pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<CategoryResult> {
@@ -227,8 +344,7 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     let mut known_paths = HashSet::new();
     let mut candidates = Vec::new();
 
-    let local_appdata = env::var("LOCALAPPDATA").ok().map(PathBuf::from);
-    let appdata = env::var("APPDATA").ok().map(PathBuf::from);
+    let resolver = LocationResolver::from_env();
 
     if output_mode != OutputMode::Quiet {
         println!(
@@ -240,37 +356,24 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     // 1. Collect all candidate paths first (fast IO check)
 
     // Scan known application caches
-    for (name, location) in APP_CACHE_LOCATIONS {
-        let cache_path = match location {
-            AppCacheLocation::LocalAppDataNested(subpaths) => local_appdata.as_ref().map(|p| {
-                let mut path = p.clone();
-                for subpath in *subpaths {
-                    path = path.join(subpath);
-                }
-                path
-            }),
-        };
-
-        if let Some(cache_path) = cache_path {
-            if cache_path.exists() && !config.is_excluded(&cache_path) {
-                known_paths.insert(cache_path.clone());
-                candidates.push(cache_path);
-                if output_mode != OutputMode::Quiet {
-                    println!("    {} Found {} cache", Theme::muted("•"), name);
-                }
+    for location in resolver.resolve_all(APP_CACHE_LOCATIONS) {
+        if !config.is_excluded(&location.path) {
+            if output_mode != OutputMode::Quiet {
+                println!(
+                    "    {} Found {} cache {}",
+                    Theme::muted("•"),
+                    location.name,
+                    Theme::muted(&format!("(via {})", location.source))
+                );
             }
+            known_paths.insert(location.path.clone());
+            candidates.push(location.path);
         }
     }
 
-    // Scan app-specific caches in LOCALAPPDATA
-    if let Some(ref local_appdata_path) = local_appdata {
-        let app_caches = scan_app_caches(local_appdata_path, &mut known_paths, config);
-        candidates.extend(app_caches);
-    }
-
-    // Scan app-specific caches in APPDATA
-    if let Some(ref appdata_path) = appdata {
-        let app_caches = scan_app_caches(appdata_path, &mut known_paths, config);
+    // Scan app-specific caches in each application data root
+    for (root, _source) in resolver.app_roots() {
+        let app_caches = scan_app_caches(&root, &mut known_paths, config);
         candidates.extend(app_caches);
     }
 
@@ -323,14 +426,13 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     }
 
     // Store paths
-    result.paths = paths_with_sizes.iter().map(|(p, _)| p.clone()).collect();
-    result.size_bytes = paths_with_sizes.iter().map(|(_, size)| *size).sum();
-    result.items = paths_with_sizes.len();
+    for (path, size) in paths_with_sizes {
+        result.push(path, size);
+    }
 
     Ok(result)
 }
 
-/// Scan with real-time progress events (for TUI).
 /// Scan with real-time progress events (for TUI).
 pub fn scan_with_progress(
     _root: &Path,
@@ -342,11 +444,16 @@ pub fn scan_with_progress(
     let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
     let mut known_paths = HashSet::new();
 
-    let local_appdata = env::var("LOCALAPPDATA").ok().map(PathBuf::from);
-    let appdata = env::var("APPDATA").ok().map(PathBuf::from);
+    let resolver = LocationResolver::from_env();
+    let locations: Vec<ResolvedLocation> = resolver
+        .resolve_all(APP_CACHE_LOCATIONS)
+        .into_iter()
+        .filter(|loc| !config.is_excluded(&loc.path))
+        .collect();
+    let app_roots = resolver.app_roots();
 
-    // Estimate total: known locations + app cache scanning (approximate)
-    let total = APP_CACHE_LOCATIONS.len() as u64 + 2; // +2 for LOCALAPPDATA and APPDATA app cache scans
+    // Known locations plus one unit per application data root
+    let total = (locations.len() + app_roots.len()) as u64;
     let mut completed = 0u64;
 
     let _ = tx.send(ScanProgressEvent::CategoryStarted {
@@ -359,79 +466,39 @@ pub fn scan_with_progress(
     let on_path = |path: &Path| reporter.emit_path(path);
 
     // Scan known application caches
-    for (idx, (_name, location)) in APP_CACHE_LOCATIONS.iter().enumerate() {
-        let cache_path = match location {
-            AppCacheLocation::LocalAppDataNested(subpaths) => local_appdata.as_ref().map(|p| {
-                let mut path = p.clone();
-                for subpath in *subpaths {
-                    path = path.join(subpath);
-                }
-                path
-            }),
-        };
-
-        if let Some(cache_path) = cache_path {
-            if cache_path.exists() && !config.is_excluded(&cache_path) {
-                let size = utils::calculate_dir_size_with_progress(&cache_path, &on_path);
-                if size > 0 {
-                    known_paths.insert(cache_path.clone());
-                    files_with_sizes.push((cache_path.clone(), size));
-                }
-            }
-
-            completed = (idx + 1) as u64;
-            let _ = tx.send(ScanProgressEvent::CategoryProgress {
-                category: CATEGORY.to_string(),
-                completed_units: completed,
-                total_units: Some(total),
-                current_path: Some(cache_path),
-            });
-        } else {
-            completed = (idx + 1) as u64;
-            let _ = tx.send(ScanProgressEvent::CategoryProgress {
-                category: CATEGORY.to_string(),
-                completed_units: completed,
-                total_units: Some(total),
-                current_path: None,
-            });
+    for location in locations {
+        let size = utils::calculate_dir_size_with_progress(&location.path, &on_path);
+        if size > 0 {
+            known_paths.insert(location.path.clone());
+            files_with_sizes.push((location.path.clone(), size));
         }
-    }
 
-    // Scan app-specific caches in LOCALAPPDATA
-    if let Some(ref local_appdata_path) = local_appdata {
+        completed += 1;
         let _ = tx.send(ScanProgressEvent::CategoryProgress {
             category: CATEGORY.to_string(),
-            completed_units: completed + 1,
+            completed_units: completed,
             total_units: Some(total),
-            current_path: Some(local_appdata_path.clone()),
+            current_path: Some(location.path),
         });
-
-        let app_caches = scan_app_caches(local_appdata_path, &mut known_paths, config);
-        for cache_path in app_caches {
-            let size = utils::calculate_dir_size_with_progress(&cache_path, &on_path);
-            if size > 0 {
-                files_with_sizes.push((cache_path, size));
-            }
-        }
-        completed += 1;
     }
 
-    // Scan app-specific caches in APPDATA
-    if let Some(ref appdata_path) = appdata {
+    // Scan app-specific caches in each application data root
+    for (root, _source) in app_roots {
         let _ = tx.send(ScanProgressEvent::CategoryProgress {
             category: CATEGORY.to_string(),
             completed_units: completed + 1,
             total_units: Some(total),
-            current_path: Some(appdata_path.clone()),
+            current_path: Some(root.clone()),
         });
 
-        let app_caches = scan_app_caches(appdata_path, &mut known_paths, config);
+        let app_caches = scan_app_caches(&root, &mut known_paths, config);
         for cache_path in app_caches {
             let size = utils::calculate_dir_size_with_progress(&cache_path, &on_path);
             if size > 0 {
                 files_with_sizes.push((cache_path, size));
             }
         }
+        completed += 1;
     }
 
     // Sort by size descending
@@ -439,9 +506,7 @@ pub fn scan_with_progress(
 
     // Build final result
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     let _ = tx.send(ScanProgressEvent::CategoryFinished {
@@ -320,6 +320,10 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
             .collect();
         let size_bytes: u64 = apps_with_sizes.iter().map(|e| e.size).sum();
         let items = apps_with_sizes.len();
+        let path_sizes = apps_with_sizes
+            .iter()
+            .map(|e| (e.install_location.clone(), e.size))
+            .collect();
 
         // Store display names + per-app sizes + uninstall commands (used by the TUI / cleaner)
         // Normalize paths for consistent lookup
@@ -353,6 +357,8 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
             paths,
             size_bytes,
             items,
+            path_sizes,
+            ..Default::default()
         };
 
         if output_mode != OutputMode::Quiet && !apps_with_sizes.is_empty() {
@@ -528,21 +534,11 @@ pub fn scan_with_progress(
         }
 
         // Build final result
-        let mut items = 0;
-        let mut size_bytes = 0;
-        let mut paths = Vec::new();
+        let mut result = CategoryResult::default();
         for entry in apps_with_sizes {
-            items += 1;
-            size_bytes += entry.size;
-            paths.push(entry.install_location);
+            result.push(entry.install_location, entry.size);
         }
 
-        let result = CategoryResult {
-            paths,
-            size_bytes,
-            items,
-        };
-
         let _ = tx.send(ScanProgressEvent::CategoryFinished {
             category: CATEGORY.to_string(),
             items: result.items,
@@ -1,274 +1,476 @@
+use crate::categories::locations::{Base, KnownLocation, LocationResolver, Os, Rule};
 use crate::config::Config;
 use crate::output::CategoryResult;
 use crate::utils;
 use anyhow::{Context, Result};
-use std::env;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-/// Browser cache locations to scan
-/// Each tuple is (name, path_from_localappdata)
-const BROWSER_CACHES: &[(&str, &[&str])] = &[
+/// Browser cache locations to scan, per OS
+const BROWSER_CACHES: &[KnownLocation] = &[
     // Chrome family
-    // Chrome family
-    (
-        "Chrome",
-        &[
-            "Google",
-            "Chrome",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    KnownLocation {
+        name: "Chrome",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "Google",
+                    "Chrome",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["google-chrome", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["Google", "Chrome", "Default", "Cache"],
+            ),
         ],
-    ),
-    (
-        "Chrome (Beta)",
-        &[
-            "Google",
-            "Chrome Beta",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    },
+    KnownLocation {
+        name: "Chrome (Beta)",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "Google",
+                    "Chrome Beta",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["google-chrome-beta", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["Google", "Chrome Beta", "Default", "Cache"],
+            ),
         ],
-    ),
-    (
-        "Chrome (Dev)",
-        &[
-            "Google",
-            "Chrome Dev",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    },
+    KnownLocation {
+        name: "Chrome (Dev)",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "Google",
+                    "Chrome Dev",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["google-chrome-unstable", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["Google", "Chrome Dev", "Default", "Cache"],
+            ),
         ],
-    ),
+    },
     // Edge family
-    (
-        "Edge",
-        &[
-            "Microsoft",
-            "Edge",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    KnownLocation {
+        name: "Edge",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "Microsoft",
+                    "Edge",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["microsoft-edge", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["Microsoft Edge", "Default", "Cache"],
+            ),
         ],
-    ),
-    (
-        "Edge (Beta)",
-        &[
-            "Microsoft",
-            "Edge Beta",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    },
+    KnownLocation {
+        name: "Edge (Beta)",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "Microsoft",
+                    "Edge Beta",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["microsoft-edge-beta", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["Microsoft Edge Beta", "Default", "Cache"],
+            ),
         ],
-    ),
-    (
-        "Edge (Dev)",
-        &[
-            "Microsoft",
-            "Edge Dev",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    },
+    KnownLocation {
+        name: "Edge (Dev)",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "Microsoft",
+                    "Edge Dev",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["microsoft-edge-dev", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["Microsoft Edge Dev", "Default", "Cache"],
+            ),
         ],
-    ),
+    },
     // Brave
-    (
-        "Brave",
-        &[
-            "BraveSoftware",
-            "Brave-Browser",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    KnownLocation {
+        name: "Brave",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "BraveSoftware",
+                    "Brave-Browser",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["BraveSoftware", "Brave-Browser", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["BraveSoftware", "Brave-Browser", "Default", "Cache"],
+            ),
         ],
-    ),
-    (
-        "Brave (Beta)",
-        &[
-            "BraveSoftware",
-            "Brave-Browser-Beta",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    },
+    KnownLocation {
+        name: "Brave (Beta)",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &[
+                    "BraveSoftware",
+                    "Brave-Browser-Beta",
+                    "User Data",
+                    "Default",
+                    "Cache",
+                    "Cache_Data",
+                ],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["BraveSoftware", "Brave-Browser-Beta", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["BraveSoftware", "Brave-Browser-Beta", "Default", "Cache"],
+            ),
         ],
-    ),
+    },
     // Opera
-    ("Opera", &["Opera Software", "Opera Stable", "Cache"]),
-    // Arc
-    (
-        "Arc",
-        &[
-            "The Browser Company",
-            "Arc",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    KnownLocation {
+        name: "Opera",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["Opera Software", "Opera Stable", "Cache"],
+            ),
+            Rule::Under(Os::Linux, Base::UserCache, &["opera"]),
+            Rule::Under(Os::MacOs, Base::UserCache, &["com.operasoftware.Opera"]),
         ],
-    ),
+    },
+    // Arc
+    KnownLocation {
+        name: "Arc",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &[
+                "The Browser Company",
+                "Arc",
+                "User Data",
+                "Default",
+                "Cache",
+                "Cache_Data",
+            ],
+        )],
+    },
     // Comet
-    (
-        "Perplexity",
-        &[
-            "Perplexity",
-            "Perplexity",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
-        ],
-    ),
+    KnownLocation {
+        name: "Perplexity",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &[
+                "Perplexity",
+                "Perplexity",
+                "User Data",
+                "Default",
+                "Cache",
+                "Cache_Data",
+            ],
+        )],
+    },
     // Atlast by OpenAI
-    (
-        "Atlas",
-        &[
-            "OpenAI",
-            "Atlast",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
-        ],
-    ),
+    KnownLocation {
+        name: "Atlas",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &[
+                "OpenAI",
+                "Atlast",
+                "User Data",
+                "Default",
+                "Cache",
+                "Cache_Data",
+            ],
+        )],
+    },
     // Vivaldi
-    (
-        "Vivaldi",
-        &["Vivaldi", "User Data", "Default", "Cache", "Cache_Data"],
-    ),
+    KnownLocation {
+        name: "Vivaldi",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["Vivaldi", "User Data", "Default", "Cache", "Cache_Data"],
+            ),
+            Rule::Under(Os::Linux, Base::UserCache, &["vivaldi", "Default", "Cache"]),
+            Rule::Under(Os::MacOs, Base::UserCache, &["Vivaldi", "Default", "Cache"]),
+        ],
+    },
     // Firefox (profile handled separately)
     // ("Firefox", profile-based, see scan impl)
     // Chromium (unbranded)
-    (
-        "Chromium",
-        &["Chromium", "User Data", "Default", "Cache", "Cache_Data"],
-    ),
-    // Sidekick
-    (
-        "Sidekick",
-        &[
-            "Redundant",
-            "Sidekick",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    KnownLocation {
+        name: "Chromium",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["Chromium", "User Data", "Default", "Cache", "Cache_Data"],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["chromium", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::MacOs,
+                Base::UserCache,
+                &["Chromium", "Default", "Cache"],
+            ),
         ],
-    ),
+    },
+    // Sidekick
+    KnownLocation {
+        name: "Sidekick",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &[
+                "Redundant",
+                "Sidekick",
+                "User Data",
+                "Default",
+                "Cache",
+                "Cache_Data",
+            ],
+        )],
+    },
     // Yandex Browser
-    (
-        "Yandex",
-        &["Yandex", "YandexBrowser", "User Data", "Default", "Cache"],
-    ),
-    // Avast Secure Browser
-    (
-        "Avast Secure Browser",
-        &[
-            "AVAST Software",
-            "Browser",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
+    KnownLocation {
+        name: "Yandex",
+        rules: &[
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["Yandex", "YandexBrowser", "User Data", "Default", "Cache"],
+            ),
+            Rule::Under(
+                Os::Linux,
+                Base::UserCache,
+                &["yandex-browser", "Default", "Cache"],
+            ),
         ],
-    ),
+    },
+    // Avast Secure Browser
+    KnownLocation {
+        name: "Avast Secure Browser",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &[
+                "AVAST Software",
+                "Browser",
+                "User Data",
+                "Default",
+                "Cache",
+                "Cache_Data",
+            ],
+        )],
+    },
     // CCleaner Browser
-    (
-        "CCleaner Browser",
-        &[
-            "CCleaner",
-            "CCleaner Browser",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
-        ],
-    ),
+    KnownLocation {
+        name: "CCleaner Browser",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &[
+                "CCleaner",
+                "CCleaner Browser",
+                "User Data",
+                "Default",
+                "Cache",
+                "Cache_Data",
+            ],
+        )],
+    },
     // Torch Browser
-    (
-        "Torch",
-        &["Torch", "User Data", "Default", "Cache", "Cache_Data"],
-    ),
+    KnownLocation {
+        name: "Torch",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Torch", "User Data", "Default", "Cache", "Cache_Data"],
+        )],
+    },
     // Epic Privacy Browser
-    (
-        "Epic",
-        &[
-            "Epic Privacy Browser",
-            "User Data",
-            "Default",
-            "Cache",
-            "Cache_Data",
-        ],
-    ),
+    KnownLocation {
+        name: "Epic",
+        rules: &[Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &[
+                "Epic Privacy Browser",
+                "User Data",
+                "Default",
+                "Cache",
+                "Cache_Data",
+            ],
+        )],
+    },
 ];
 
+/// Firefox keeps one cache per profile below this directory
+const FIREFOX_PROFILES: KnownLocation = KnownLocation {
+    name: "Firefox",
+    rules: &[
+        Rule::Under(
+            Os::Windows,
+            Base::LocalAppData,
+            &["Mozilla", "Firefox", "Profiles"],
+        ),
+        Rule::Under(Os::MacOs, Base::UserCache, &["Firefox", "Profiles"]),
+        Rule::Under(Os::Linux, Base::UserCache, &["mozilla", "firefox"]),
+    ],
+};
+
 /// Scan for browser cache directories
 ///
-/// Checks well-known Windows cache locations for Chrome, Edge, and Firefox.
+/// Checks well-known cache locations for Chromium-based browsers and Firefox
+/// on the current OS.
 pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
-    let mut paths = Vec::new();
+    let mut paths_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
 
-    let local_appdata = env::var("LOCALAPPDATA").ok().map(PathBuf::from);
+    let resolver = LocationResolver::from_env();
 
-    // Scan Chrome and Edge caches (fixed paths)
-    if let Some(ref local_appdata_path) = local_appdata {
-        for (_name, subpaths) in BROWSER_CACHES {
-            let mut cache_path = local_appdata_path.clone();
-            for subpath in *subpaths {
-                cache_path = cache_path.join(subpath);
-            }
-
-            if cache_path.exists() && !config.is_excluded(&cache_path) {
-                let size = utils::calculate_dir_size(&cache_path);
-                if size > 0 {
-                    result.items += 1;
-                    result.size_bytes += size;
-                    paths.push(cache_path);
-                }
+    // Scan Chromium-family and other fixed-path caches
+    for location in resolver.resolve_all(BROWSER_CACHES) {
+        if !config.is_excluded(&location.path) {
+            let size = utils::calculate_dir_size(&location.path);
+            if size > 0 {
+                paths_with_sizes.push((location.path, size));
             }
         }
     }
 
     // Scan Firefox profiles (need to glob for profile directories)
-    if let Some(ref local_appdata_path) = local_appdata {
-        let firefox_profiles = local_appdata_path
-            .join("Mozilla")
-            .join("Firefox")
-            .join("Profiles");
-        if firefox_profiles.exists() {
-            // Walk through profile directories
-            for entry in WalkDir::new(&firefox_profiles)
-                .max_depth(2)
-                .follow_links(false)
-                .into_iter()
-                .filter_map(|e| e.ok())
+    for firefox_profiles in resolver.resolve(&FIREFOX_PROFILES) {
+        // Walk through profile directories
+        for entry in WalkDir::new(&firefox_profiles.path)
+            .max_depth(2)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if path.is_dir()
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.contains(".default"))
+                    .unwrap_or(false)
             {
-                let path = entry.path();
-                if path.is_dir()
-                    && path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .map(|n| n.contains(".default"))
-                        .unwrap_or(false)
-                {
-                    // Found a Firefox profile directory, check for cache2
-                    let cache2_path = path.join("cache2");
-                    if cache2_path.exists() && !config.is_excluded(&cache2_path) {
-                        let size = utils::calculate_dir_size(&cache2_path);
-                        if size > 0 {
-                            result.items += 1;
-                            result.size_bytes += size;
-                            paths.push(cache2_path);
-                        }
+                // Found a Firefox profile directory, check for cache2
+                let cache2_path = path.join("cache2");
+                if cache2_path.exists() && !config.is_excluded(&cache2_path) {
+                    let size = utils::calculate_dir_size(&cache2_path);
+                    if size > 0 {
+                        paths_with_sizes.push((cache2_path, size));
                     }
                 }
             }
@@ -276,16 +478,11 @@ pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
     }
 
     // Sort by size descending
-    let mut paths_with_sizes: Vec<(PathBuf, u64)> = paths
-        .into_iter()
-        .map(|p| {
-            let size = utils::calculate_dir_size(&p);
-            (p, size)
-        })
-        .collect();
     paths_with_sizes.sort_by(|a, b| b.1.cmp(&a.1));
 
-    result.paths = paths_with_sizes.into_iter().map(|(p, _)| p).collect();
+    for (path, size) in paths_with_sizes {
+        result.push(path, size);
+    }
 
     Ok(result)
 }
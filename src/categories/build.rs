@@ -1,68 +1,230 @@
+use crate::categories::target_sweep::{self, SweepPlan, SweepReason};
 use crate::config::{CategoryConfig, Config};
+use crate::git;
 use crate::output::{CategoryResult, OutputMode};
-use crate::project;
+use crate::project::policy::{self, ProjectPolicy};
+use crate::project::{self, ProjectActivity, ProjectType};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::theme::Theme;
 use crate::utils;
+use crate::workspace::{self, ProjectGroup};
 use anyhow::{Context, Result};
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
-/// Default build artifact directories to detect
-const DEFAULT_BUILD_ARTIFACTS: &[&str] = &[
-    "node_modules",
-    "target",
-    "bin",
-    "obj",
-    "dist",
-    "build",
-    ".next",
-    ".nuxt",
-    ".output",
-    "__pycache__",
-    ".pytest_cache",
-    ".mypy_cache",
-    ".venv",
-    "venv",
-    ".gradle",
-    ".parcel-cache",
-    ".turbo",
-    ".angular",
-    ".svelte-kit",
-    "coverage",
-    ".nyc_output",
-];
-
-/// Get the list of build artifacts, merging defaults with custom artifacts from config
-fn get_build_artifacts(config: Option<&CategoryConfig>) -> Vec<String> {
-    let mut artifacts: Vec<String> = DEFAULT_BUILD_ARTIFACTS
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-    if let Some(cfg) = config {
-        // Add custom artifacts, avoiding duplicates
-        for custom in &cfg.custom_artifacts {
-            if !artifacts.contains(custom) {
-                artifacts.push(custom.clone());
-            }
-        }
+/// Build artifact directories each project type produces
+///
+/// CMake has none by name: its build trees are found by their `CMakeCache.txt`,
+/// so a `build/` folder in a CMake project only counts if CMake generated it.
+fn artifacts_for(project_type: ProjectType) -> &'static [&'static str] {
+    match project_type {
+        ProjectType::Node => &[
+            "node_modules",
+            "dist",
+            "build",
+            ".next",
+            ".nuxt",
+            ".output",
+            ".parcel-cache",
+            ".turbo",
+            ".angular",
+            ".svelte-kit",
+            "coverage",
+            ".nyc_output",
+        ],
+        ProjectType::Rust => &["target"],
+        ProjectType::DotNet => &["bin", "obj"],
+        ProjectType::Python => &[
+            "__pycache__",
+            ".pytest_cache",
+            ".mypy_cache",
+            ".venv",
+            "venv",
+            "build",
+            "dist",
+        ],
+        ProjectType::Java => &["target", "build", ".gradle"],
+        ProjectType::Go => &[],
+        ProjectType::Ruby => &["vendor/bundle", "coverage"],
+        ProjectType::Php => &["vendor"],
+        ProjectType::Elixir => &["_build", "deps"],
+        ProjectType::Dart => &[".dart_tool", "build"],
+        ProjectType::Swift => &[".build"],
+        ProjectType::CMake => &[],
+        ProjectType::Zig => &["zig-cache", ".zig-cache", "zig-out"],
+        ProjectType::Unity => &["Library", "Temp", "Obj", "obj"],
+        ProjectType::Unreal => &["Intermediate", "DerivedDataCache", "Saved"],
     }
+}
 
-    artifacts
+/// Custom build artifact folders from config, looked for in every project
+fn get_custom_artifacts(config: Option<&CategoryConfig>) -> Vec<String> {
+    config
+        .map(|cfg| cfg.custom_artifacts.clone())
+        .unwrap_or_default()
 }
 
 /// Project artifact information
 #[derive(Debug, Clone)]
 pub struct ProjectArtifact {
+    /// The project (or workspace member) the artifact sits in
     pub project_path: PathBuf,
     pub project_name: String,
     pub artifact_path: PathBuf,
     pub artifact_type: String,
+    /// Left at 0 by [`find_project_artifacts`]; filled in once an artifact is sized
     pub size_bytes: u64,
+    /// Activity of the whole workspace for workspace members
     pub is_active: bool,
+    /// Root of the workspace the project belongs to
+    pub workspace_root: Option<PathBuf>,
+    /// Workspace member the artifact belongs to (`None` for the workspace root's own)
+    pub member: Option<PathBuf>,
+    /// How the project's `.wole.toml` changed the decision for this artifact
+    pub policy: Option<PolicyDecision>,
+}
+
+/// Effect of a project's `.wole.toml` on one artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// Listed under `artifacts`, not found by project type
+    Listed,
+    /// Covered by `protect`, never cleaned
+    Protected,
+    /// The project sets `opt_out`, never cleaned
+    OptedOut,
+}
+
+impl PolicyDecision {
+    /// Whether the policy keeps the artifact
+    pub fn keeps(self) -> bool {
+        !matches!(self, Self::Listed)
+    }
+}
+
+impl std::fmt::Display for PolicyDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Listed => write!(f, "listed in {}", policy::FILE_NAME),
+            Self::Protected => write!(f, "protected by {}", policy::FILE_NAME),
+            Self::OptedOut => write!(f, "project opted out in {}", policy::FILE_NAME),
+        }
+    }
+}
+
+/// Find the build artifacts of every project below `root`
+///
+/// Workspaces (Cargo, pnpm, npm/yarn, Gradle, .NET solutions) count as one project:
+/// activity in any member keeps the artifacts of all members and of the root.
+/// `on_project` is called with each project or workspace, its activity and the
+/// member that made it active, if that wasn't the root.
+pub fn find_project_artifacts<F>(
+    root: &Path,
+    project_age_days: u64,
+    config: Option<&CategoryConfig>,
+    global_config: &Config,
+    on_project: &F,
+) -> Vec<ProjectArtifact>
+where
+    F: Fn(&ProjectGroup, &ProjectActivity, Option<&Path>) + Sync,
+{
+    // Custom artifact names from config (per-type names come from the detected project types)
+    let custom_artifacts = get_custom_artifacts(config);
+
+    // Walk to find projects (with exclusion filtering), including the root itself
+    let project_roots = project::find_project_roots(root, global_config);
+    let groups = workspace::group_projects(root, &project_roots);
+
+    groups
+        .par_iter()
+        .flat_map(|group| {
+            let (activity, decided_by) = group_activity(group, project_age_days);
+            on_project(group, &activity, decided_by.as_deref());
+
+            let members = group.workspace.iter().flat_map(|w| w.members.iter());
+            std::iter::once(&group.root)
+                .chain(members)
+                .flat_map(|project_path| {
+                    project_artifacts(project_path, group, activity.is_active, &custom_artifacts)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Artifacts of one project (or workspace member), with its `.wole.toml` applied
+fn project_artifacts(
+    project_path: &Path,
+    group: &ProjectGroup,
+    is_active: bool,
+    custom_artifacts: &[String],
+) -> Vec<ProjectArtifact> {
+    let policy = ProjectPolicy::for_project(project_path).unwrap_or_default();
+    let mut paths = find_build_artifacts(project_path, custom_artifacts);
+    for listed in policy.artifact_paths(project_path) {
+        if !paths.contains(&listed) {
+            paths.push(listed);
+        }
+    }
+
+    let name = |path: &Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    paths
+        .into_iter()
+        .map(|artifact_path| {
+            let policy_decision = if policy.opt_out {
+                Some(PolicyDecision::OptedOut)
+            } else if policy.is_protected(project_path, &artifact_path) {
+                Some(PolicyDecision::Protected)
+            } else if policy.lists_artifact(project_path, &artifact_path) {
+                Some(PolicyDecision::Listed)
+            } else {
+                None
+            };
+            ProjectArtifact {
+                project_path: project_path.to_path_buf(),
+                project_name: name(project_path),
+                artifact_type: name(&artifact_path),
+                artifact_path,
+                size_bytes: 0,
+                is_active,
+                workspace_root: group.workspace.as_ref().map(|w| w.root.clone()),
+                member: (project_path != group.root).then(|| project_path.to_path_buf()),
+                policy: policy_decision,
+            }
+        })
+        .collect()
+}
+
+/// Activity of a project, or of a workspace: the root's, unless a member is active
+fn group_activity(
+    group: &ProjectGroup,
+    project_age_days: u64,
+) -> (ProjectActivity, Option<PathBuf>) {
+    let activity = project::project_activity(&group.root, project_age_days);
+    if activity.is_active {
+        return (activity, None);
+    }
+    // Members follow the root's `.wole.toml` threshold unless they have their own;
+    // an opted-out member only keeps its own artifacts
+    let member_age_days = ProjectPolicy::for_project(&group.root)
+        .and_then(|p| p.inactive_after_days)
+        .unwrap_or(project_age_days);
+    group
+        .workspace
+        .iter()
+        .flat_map(|w| w.members.iter())
+        .map(|member| (project::project_activity(member, member_age_days), member))
+        .find(|(member_activity, _)| {
+            member_activity.is_active && !member_activity.signal.is_opt_out()
+        })
+        .map(|(member_activity, member)| (member_activity, Some(member.clone())))
+        .unwrap_or((activity, None))
 }
 
 /// Scan for build artifacts grouped by project
@@ -79,83 +241,128 @@ pub fn scan(
 ) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
 
-    // Get the list of artifacts to scan (defaults + custom from config)
-    let artifacts_to_scan = get_build_artifacts(config);
-
-    // Check if root itself is a project - if so, only scan that
-    let all_project_roots = if crate::project::detect_project_type(root).is_some() {
-        vec![root.to_path_buf()]
-    } else {
-        // Walk to find projects (with exclusion filtering)
-        project::find_project_roots(root, global_config)
-    };
-
-    // Show discovered projects
-    if output_mode != OutputMode::Quiet && !all_project_roots.is_empty() {
-        println!(
-            "  {} Found {} projects:",
-            Theme::muted("→"),
-            all_project_roots.len()
-        );
-    }
-
-    // Filter to only inactive projects (safety feature: don't delete from active projects)
-    let inactive_project_roots: Vec<PathBuf> = all_project_roots
-        .par_iter()
-        .filter_map(|project_root| {
-            // Check if project is inactive (not recently modified)
-            let is_active =
-                project::is_project_active(project_root, project_age_days).unwrap_or(true);
-
-            // Show project as it's being checked (always show in Normal+ mode)
-            if output_mode != OutputMode::Quiet {
-                let relative = utils::to_relative_path(project_root, root);
-                let status = if is_active {
-                    Theme::status_safe("active")
-                } else {
-                    Theme::status_review("inactive")
-                };
-                println!("    {} {} ({})", Theme::muted("•"), relative, status);
+    let header = std::sync::Once::new();
+    // Show each project (or workspace) and the signal that decided it (always show in Normal+ mode)
+    let show_project =
+        |group: &ProjectGroup, activity: &ProjectActivity, decided_by: Option<&Path>| {
+            if output_mode == OutputMode::Quiet {
+                return;
             }
-
-            if is_active {
-                None // Active - skip it
+            header.call_once(|| println!("  {} Projects:", Theme::muted("→")));
+            let status = if activity.is_active {
+                Theme::status_safe("active")
             } else {
-                Some(project_root.clone()) // Inactive - include it
+                Theme::status_review("inactive")
+            };
+            let signal = match decided_by {
+                Some(member) => format!(
+                    "{} in {}",
+                    activity,
+                    utils::to_relative_path(member, &group.root)
+                ),
+                None => activity.to_string(),
+            };
+            let workspace = group
+                .workspace
+                .as_ref()
+                .map(|w| {
+                    let kinds: Vec<String> = w.kinds.iter().map(|k| k.to_string()).collect();
+                    Theme::muted(&format!(
+                        " [{}, {} members]",
+                        kinds.join(" + "),
+                        w.members.len()
+                    ))
+                })
+                .unwrap_or_default();
+            println!(
+                "    {} {} ({}: {}){}",
+                Theme::muted("•"),
+                utils::to_relative_path(&group.root, root),
+                status,
+                signal,
+                workspace
+            );
+        };
+    let artifacts =
+        find_project_artifacts(root, project_age_days, config, global_config, &show_project);
+
+    // Only artifacts of inactive projects (safety feature: don't delete from active projects)
+    // that the project's .wole.toml doesn't keep
+    let (candidates, kept): (Vec<ProjectArtifact>, Vec<ProjectArtifact>) = artifacts
+        .into_iter()
+        .filter(|a| !a.is_active && a.artifact_path.exists())
+        .partition(|a| !a.policy.is_some_and(PolicyDecision::keeps));
+    if matches!(output_mode, OutputMode::Verbose | OutputMode::VeryVerbose) {
+        for artifact in &kept {
+            if let Some(decision) = artifact.policy {
+                println!(
+                    "      {} {} ({})",
+                    Theme::muted("→"),
+                    utils::to_relative_path(&artifact.artifact_path, root),
+                    Theme::muted(&format!("skipped: {}", decision))
+                );
             }
-        })
-        .collect();
+        }
+    }
 
-    // Collect all artifact paths from inactive projects only (fast check for existence)
-    let all_artifact_paths: Vec<PathBuf> = inactive_project_roots
-        .par_iter()
-        .flat_map(|project_root| find_build_artifacts(project_root, &artifacts_to_scan))
-        .filter(|p| p.exists())
-        .collect();
+    // Directories git tracks (e.g. a `bin/` of committed scripts) aren't build output
+    let (artifacts, skipped) = partition_generated(candidates);
+    if matches!(output_mode, OutputMode::Verbose | OutputMode::VeryVerbose) {
+        for (artifact, status) in &skipped {
+            println!(
+                "      {} {} ({})",
+                Theme::muted("→"),
+                utils::to_relative_path(&artifact.artifact_path, root),
+                Theme::muted(&format!("skipped: {}", status))
+            );
+        }
+    }
 
     // Show artifacts as they're found (after collection to avoid parallel counter issues)
-    if output_mode != OutputMode::Quiet && !all_artifact_paths.is_empty() {
+    if output_mode != OutputMode::Quiet && !artifacts.is_empty() {
         println!(
             "  {} Found {} build artifacts:",
             Theme::muted("→"),
-            all_artifact_paths.len()
+            artifacts.len()
         );
         let show_count = match output_mode {
-            OutputMode::VeryVerbose => all_artifact_paths.len(),
-            OutputMode::Verbose => all_artifact_paths.len(),
-            OutputMode::Normal => 10.min(all_artifact_paths.len()),
+            OutputMode::VeryVerbose => artifacts.len(),
+            OutputMode::Verbose => artifacts.len(),
+            OutputMode::Normal => 10.min(artifacts.len()),
             OutputMode::Quiet => 0,
         };
 
-        for (i, artifact_path) in all_artifact_paths.iter().take(show_count).enumerate() {
-            let relative = utils::to_relative_path(artifact_path, root);
-            println!("      {} {}", Theme::muted("→"), relative);
+        for (i, artifact) in artifacts.iter().take(show_count).enumerate() {
+            let relative = utils::to_relative_path(&artifact.artifact_path, root);
+            let mut notes = Vec::new();
+            if let (Some(workspace_root), Some(member)) =
+                (&artifact.workspace_root, &artifact.member)
+            {
+                notes.push(format!(
+                    "member {} of workspace {}",
+                    utils::to_relative_path(member, workspace_root),
+                    utils::to_relative_path(workspace_root, root)
+                ));
+            }
+            if let Some(decision) = artifact.policy {
+                notes.push(decision.to_string());
+            }
+            if notes.is_empty() {
+                println!("      {} {}", Theme::muted("→"), relative);
+            } else {
+                println!(
+                    "      {} {} {}",
+                    Theme::muted("→"),
+                    relative,
+                    Theme::muted(&format!("({})", notes.join("; ")))
+                );
+            }
 
-            if i == 9 && output_mode == OutputMode::Normal && all_artifact_paths.len() > 10 {
+            if i == 9 && output_mode == OutputMode::Normal && artifacts.len() > 10 {
                 println!(
                     "      {} ... and {} more (use -v to see all)",
                     Theme::muted("→"),
-                    all_artifact_paths.len() - 10
+                    artifacts.len() - 10
                 );
                 break;
             }
@@ -164,23 +371,33 @@ pub fn scan(
 
     // Calculate sizes sequentially per artifact to avoid disk thrashing
     // Individually, calculate_dir_size is still parallel
-    let mut artifacts_with_sizes: Vec<(PathBuf, u64)> = all_artifact_paths
+    let mut artifacts_with_sizes: Vec<(PathBuf, u64)> = artifacts
         .iter()
-        .map(|path| {
-            let size = utils::calculate_dir_size(path);
-            (path.clone(), size)
+        .map(|artifact| {
+            let size = utils::calculate_dir_size(&artifact.artifact_path);
+            (artifact.artifact_path.clone(), size)
         })
         .filter(|(_, size)| *size > 0)
         .collect();
 
+    // Offer only the parts sweeping each Rust target/ removes instead of the whole directory
+    if config.map(|c| c.sweep_rust_targets).unwrap_or(false) {
+        let sweep_age_days = config
+            .and_then(|c| c.sweep_age_days)
+            .unwrap_or(project_age_days);
+        let sweeps = plan_rust_target_sweeps(&artifacts_with_sizes, sweep_age_days, &|_| {});
+        if output_mode != OutputMode::Quiet && !sweeps.is_empty() {
+            print_sweeps(&sweeps, &artifacts_with_sizes, root);
+        }
+        artifacts_with_sizes = apply_sweeps(artifacts_with_sizes, sweeps);
+    }
+
     // Sort by size descending (biggest first)
     artifacts_with_sizes.par_sort_by(|a, b| b.1.cmp(&a.1));
 
     // Build result
     for (path, size) in artifacts_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     Ok(result)
@@ -198,72 +415,318 @@ pub fn scan_with_progress(
     let reporter = Arc::new(ScanPathReporter::new("Build Artifacts", tx.clone(), 75));
 
     let mut result = CategoryResult::default();
-    let artifacts_to_scan = get_build_artifacts(config);
-
-    let all_project_roots = if crate::project::detect_project_type(root).is_some() {
-        vec![root.to_path_buf()]
-    } else {
-        project::find_project_roots(root, global_config)
-    };
-
-    let inactive_project_roots: Vec<PathBuf> = all_project_roots
-        .par_iter()
-        .filter_map(|project_root| {
-            let is_active =
-                project::is_project_active(project_root, project_age_days).unwrap_or(true);
-            if is_active {
-                None
-            } else {
-                Some(project_root.clone())
-            }
-        })
-        .collect();
 
-    let all_artifact_paths: Vec<PathBuf> = inactive_project_roots
-        .par_iter()
-        .flat_map(|project_root| find_build_artifacts(project_root, &artifacts_to_scan))
-        .filter(|p| p.exists())
-        .collect();
+    let candidates: Vec<ProjectArtifact> =
+        find_project_artifacts(root, project_age_days, config, global_config, &|_, _, _| {})
+            .into_iter()
+            .filter(|a| !a.is_active && a.artifact_path.exists())
+            .filter(|a| !a.policy.is_some_and(PolicyDecision::keeps))
+            .collect();
+    let (artifacts, _) = partition_generated(candidates);
 
-    let mut artifacts_with_sizes: Vec<(PathBuf, u64)> = all_artifact_paths
+    let mut artifacts_with_sizes: Vec<(PathBuf, u64)> = artifacts
         .iter()
-        .map(|path| {
+        .map(|artifact| {
             let rep = Arc::clone(&reporter);
-            let size = utils::calculate_dir_size_with_progress(path, &|p| rep.emit_path(p));
-            (path.clone(), size)
+            let size = utils::calculate_dir_size_with_progress(&artifact.artifact_path, &|p| {
+                rep.emit_path(p)
+            });
+            (artifact.artifact_path.clone(), size)
         })
         .filter(|(_, size)| *size > 0)
         .collect();
 
+    if config.map(|c| c.sweep_rust_targets).unwrap_or(false) {
+        let sweep_age_days = config
+            .and_then(|c| c.sweep_age_days)
+            .unwrap_or(project_age_days);
+        let sweeps = plan_rust_target_sweeps(&artifacts_with_sizes, sweep_age_days, &|p| {
+            reporter.emit_path(p)
+        });
+        artifacts_with_sizes = apply_sweeps(artifacts_with_sizes, sweeps);
+    }
+
     artifacts_with_sizes.par_sort_by(|a, b| b.1.cmp(&a.1));
 
     for (path, size) in artifacts_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     let _ = output_mode;
     Ok(result)
 }
 
+/// How git sees a candidate artifact directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactGitStatus {
+    /// Not inside a git repository
+    NoRepository,
+    /// Untracked and matched by a `.gitignore` (or `.git/info/exclude`)
+    Ignored,
+    /// Untracked and not ignored
+    Untracked,
+    /// Contains files tracked by git
+    Tracked,
+    /// Tracked state couldn't be read and no ignore rule covers the directory
+    NotIgnored,
+}
+
+impl ArtifactGitStatus {
+    /// Whether the directory can be treated as generated output
+    pub fn is_generated(self) -> bool {
+        !matches!(self, Self::Tracked | Self::NotIgnored)
+    }
+}
+
+impl std::fmt::Display for ArtifactGitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NoRepository => "not in a git repository",
+            Self::Ignored => "ignored by git",
+            Self::Untracked => "untracked",
+            Self::Tracked => "tracked by git",
+            Self::NotIgnored => "not ignored by .gitignore",
+        })
+    }
+}
+
+/// Check whether an artifact directory is generated output as far as git is concerned
+///
+/// Tracked state comes from [`git::is_tracked`]; when that can't be read, the
+/// repository's ignore files decide instead.
+pub fn artifact_git_status(artifact: &Path) -> ArtifactGitStatus {
+    let Some(repo) = git::find_git_root_cached(artifact) else {
+        return ArtifactGitStatus::NoRepository;
+    };
+    let tracked = git::is_tracked(artifact);
+    if let Ok(true) = tracked {
+        return ArtifactGitStatus::Tracked;
+    }
+    match (tracked, git::ignore::is_ignored(&repo, artifact, true)) {
+        (_, true) => ArtifactGitStatus::Ignored,
+        (Ok(_), false) => ArtifactGitStatus::Untracked,
+        (Err(_), false) => ArtifactGitStatus::NotIgnored,
+    }
+}
+
+/// Split candidates into generated output and directories skipped because of git
+fn partition_generated(
+    artifacts: Vec<ProjectArtifact>,
+) -> (
+    Vec<ProjectArtifact>,
+    Vec<(ProjectArtifact, ArtifactGitStatus)>,
+) {
+    let (generated, skipped): (Vec<_>, Vec<_>) = artifacts
+        .into_par_iter()
+        .map(|artifact| {
+            let status = artifact_git_status(&artifact.artifact_path);
+            (artifact, status)
+        })
+        .partition(|(_, status)| status.is_generated());
+    (
+        generated
+            .into_iter()
+            .map(|(artifact, _)| artifact)
+            .collect(),
+        skipped,
+    )
+}
+
+/// Sweep plans for the Rust `target/` directories (those next to a `Cargo.toml`) among `artifacts`
+fn plan_rust_target_sweeps<F>(
+    artifacts: &[(PathBuf, u64)],
+    max_age_days: u64,
+    on_path: &F,
+) -> Vec<(PathBuf, SweepPlan)>
+where
+    F: Fn(&Path) + Sync,
+{
+    let targets: Vec<&PathBuf> = artifacts
+        .iter()
+        .map(|(path, _)| path)
+        .filter(|path| {
+            path.file_name().is_some_and(|n| n == "target")
+                && path
+                    .parent()
+                    .is_some_and(|project| project.join("Cargo.toml").is_file())
+        })
+        .collect();
+    if targets.is_empty() {
+        return Vec::new();
+    }
+
+    let installed = target_sweep::installed_rustc_versions();
+    targets
+        .into_iter()
+        .map(|target| {
+            let plan = target_sweep::plan(target, &installed, max_age_days, on_path);
+            (target.clone(), plan)
+        })
+        .collect()
+}
+
+/// Show the full and sweepable size of each Rust `target/`
+///
+/// Only the human-readable output has this breakdown; JSON and the TUI list the
+/// swept parts as the category's items.
+fn print_sweeps(sweeps: &[(PathBuf, SweepPlan)], artifacts: &[(PathBuf, u64)], root: &Path) {
+    let size = |bytes: u64| Theme::size(&bytesize::to_string(bytes, false));
+    println!(
+        "  {} {} Rust target directories (full target / sweepable):",
+        Theme::muted("→"),
+        sweeps.len()
+    );
+    for (target, plan) in sweeps {
+        let full = artifacts
+            .iter()
+            .find(|(path, _)| path == target)
+            .map(|(_, size)| *size)
+            .unwrap_or(0);
+        let breakdown: Vec<String> = [
+            (SweepReason::Incremental, "incremental"),
+            (SweepReason::UninstalledToolchain, "old toolchains"),
+            (SweepReason::StaleProfile, "stale profiles"),
+        ]
+        .iter()
+        .filter(|(reason, _)| plan.bytes(*reason) > 0)
+        .map(|(reason, label)| {
+            format!(
+                "{} {}",
+                label,
+                bytesize::to_string(plan.bytes(*reason), false)
+            )
+        })
+        .collect();
+        println!(
+            "      {} {}: {} / {}{}",
+            Theme::muted("→"),
+            utils::to_relative_path(target, root),
+            size(full),
+            size(plan.sweepable_bytes()),
+            if breakdown.is_empty() {
+                String::new()
+            } else {
+                Theme::muted(&format!(" ({})", breakdown.join(", ")))
+            }
+        );
+    }
+}
+
+/// Replace each swept `target/` with the parts its plan removes
+fn apply_sweeps(
+    artifacts: Vec<(PathBuf, u64)>,
+    sweeps: Vec<(PathBuf, SweepPlan)>,
+) -> Vec<(PathBuf, u64)> {
+    let mut plans: std::collections::HashMap<PathBuf, SweepPlan> = sweeps.into_iter().collect();
+    artifacts
+        .into_iter()
+        .flat_map(|(path, size)| match plans.remove(&path) {
+            Some(plan) => plan
+                .parts
+                .into_iter()
+                .map(|part| (part.path, part.size))
+                .collect(),
+            None => vec![(path, size)],
+        })
+        .collect()
+}
+
 /// Find build artifact directories in a project
-fn find_build_artifacts(project_path: &Path, artifacts_to_scan: &[String]) -> Vec<PathBuf> {
-    let mut artifacts = Vec::new();
+///
+/// Names come from every project type detected at `project_path`, plus the
+/// custom ones; CMake build trees are added wherever they are.
+fn find_build_artifacts(project_path: &Path, custom_artifacts: &[String]) -> Vec<PathBuf> {
+    let types = project::detect_project_types(project_path);
+    let names = types
+        .iter()
+        .flat_map(|t| artifacts_for(*t).iter().map(|name| name.to_string()))
+        .chain(custom_artifacts.iter().cloned());
+
+    let mut artifacts: Vec<PathBuf> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut push = |path: PathBuf| {
+        // Case-insensitive file systems resolve `Obj` and `obj` to the same folder
+        let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if seen.insert(key) {
+            artifacts.push(path);
+        }
+    };
 
-    for artifact_name in artifacts_to_scan {
-        let artifact_path = project_path.join(artifact_name);
-        if artifact_path.exists() && artifact_path.is_dir() {
-            artifacts.push(artifact_path);
+    for artifact_name in names {
+        let artifact_path = project_path.join(&artifact_name);
+        if artifact_path.is_dir() {
+            push(artifact_path);
+        }
+    }
+    if types.contains(&ProjectType::CMake) {
+        for build_tree in find_cmake_build_trees(project_path) {
+            push(build_tree);
         }
     }
 
     artifacts
 }
 
+/// Out-of-source CMake build trees (`build/`, `cmake-build-debug/`, `out/build/x64-Debug/`, ...)
+fn find_cmake_build_trees(project_path: &Path) -> Vec<PathBuf> {
+    // Deep enough for Visual Studio / preset layouts like `out/build/<preset>`
+    const MAX_DEPTH: usize = 3;
+
+    let mut trees: Vec<PathBuf> = walkdir::WalkDir::new(project_path)
+        .min_depth(1)
+        .max_depth(MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|e| {
+            e.file_type().is_dir()
+                && !e.file_name().to_string_lossy().starts_with('.')
+                && !e
+                    .path()
+                    .parent()
+                    .is_some_and(|p| p != project_path && p.join("CMakeCache.txt").is_file())
+        })
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|dir| dir.join("CMakeCache.txt").is_file())
+        .collect();
+    trees.sort();
+    trees
+}
+
 /// Clean (delete) a build artifact directory by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     crate::trash_ops::delete(path)
         .with_context(|| format!("Failed to delete build artifact: {}", path.display()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_artifacts_follow_project_types() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path();
+        fs::write(project.join("CMakeLists.txt"), "").unwrap();
+        fs::write(project.join("build.zig"), "").unwrap();
+        // A hand-written `build/` is not a CMake build tree
+        fs::create_dir_all(project.join("build/scripts")).unwrap();
+        fs::create_dir_all(project.join("out/build/x64-Debug")).unwrap();
+        fs::write(project.join("out/build/x64-Debug/CMakeCache.txt"), "").unwrap();
+        fs::create_dir_all(project.join(".zig-cache")).unwrap();
+        fs::create_dir_all(project.join("node_modules")).unwrap();
+
+        let found: Vec<PathBuf> = find_build_artifacts(project, &[])
+            .into_iter()
+            .map(|p| p.strip_prefix(project).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                PathBuf::from(".zig-cache"),
+                PathBuf::from("out").join("build").join("x64-Debug"),
+            ]
+        );
+    }
+}
@@ -1,64 +1,280 @@
+use crate::categories::cache_strategy::{self, CachePlan, Regeneration, Strategy};
+use crate::categories::locations::{
+    Base, KnownLocation, LocationResolver, Os, ResolvedLocation, Rule,
+};
+use crate::cleaner::{self, DeleteOutcome};
 use crate::config::Config;
-use crate::output::{CategoryResult, OutputMode};
+use crate::lockfiles::{LockfileFormat, RefCounts};
+use crate::output::{CategoryResult, OutputMode, ReclaimSplit};
+use crate::project;
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::theme::Theme;
 use crate::utils;
-use anyhow::{Context, Result};
+use anyhow::Result;
 use bytesize;
-use std::env;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 /// Package manager cache locations to scan
-/// Each tuple is (name, path_from_localappdata_or_userprofile)
-const CACHE_LOCATIONS: &[(&str, CacheLocation)] = &[
-    ("npm", CacheLocation::LocalAppData("npm-cache")),
-    ("pip", CacheLocation::LocalAppDataNested(&["pip", "cache"])),
-    (
-        "yarn",
-        CacheLocation::LocalAppDataNested(&["Yarn", "Cache"]),
-    ),
-    ("pnpm", CacheLocation::LocalAppData("pnpm-cache")),
-    ("pnpm-store", CacheLocation::LocalAppData("pnpm-store")),
-    (
-        "NuGet",
-        CacheLocation::LocalAppDataNested(&["NuGet", "v3-cache"]),
-    ),
-    (
-        "Cargo",
-        CacheLocation::UserProfileNested(&[".cargo", "registry"]),
-    ),
-    (
-        "Go",
-        CacheLocation::UserProfileNested(&["go", "pkg", "mod", "cache"]),
-    ),
-    (
-        "Maven",
-        CacheLocation::UserProfileNested(&[".m2", "repository"]),
-    ),
-    (
-        "Gradle",
-        CacheLocation::UserProfileNested(&[".gradle", "caches"]),
-    ),
+///
+/// Each tool's environment override is listed first, then its per-OS defaults.
+pub const CACHE_LOCATIONS: &[KnownLocation] = &[
+    KnownLocation {
+        name: "npm",
+        rules: &[
+            Rule::Env("npm_config_cache", &["_cacache"]),
+            Rule::Env("NPM_CONFIG_CACHE", &["_cacache"]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["npm-cache"]),
+            Rule::Under(Os::Unix, Base::Home, &[".npm", "_cacache"]),
+        ],
+    },
+    KnownLocation {
+        name: "pip",
+        rules: &[
+            Rule::Env("PIP_CACHE_DIR", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["pip", "cache"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["pip"]),
+        ],
+    },
+    KnownLocation {
+        name: "yarn",
+        rules: &[
+            Rule::Env("YARN_CACHE_FOLDER", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["Yarn", "Cache"]),
+            Rule::Under(Os::MacOs, Base::UserCache, &["Yarn"]),
+            Rule::Under(Os::Linux, Base::UserCache, &["yarn"]),
+        ],
+    },
+    KnownLocation {
+        name: "pnpm",
+        rules: &[
+            Rule::Under(Os::Windows, Base::LocalAppData, &["pnpm-cache"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["pnpm"]),
+        ],
+    },
+    KnownLocation {
+        name: "pnpm-store",
+        rules: &[
+            Rule::Env("npm_config_store_dir", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["pnpm-store"]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["pnpm", "store"]),
+            Rule::Under(Os::MacOs, Base::Home, &["Library", "pnpm", "store"]),
+            Rule::Under(Os::Linux, Base::UserData, &["pnpm", "store"]),
+        ],
+    },
+    KnownLocation {
+        name: "NuGet",
+        rules: &[
+            Rule::Env("NUGET_HTTP_CACHE_PATH", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["NuGet", "v3-cache"]),
+            Rule::Under(
+                Os::Unix,
+                Base::Home,
+                &[".local", "share", "NuGet", "v3-cache"],
+            ),
+            Rule::Under(
+                Os::Unix,
+                Base::Home,
+                &[".local", "share", "NuGet", "http-cache"],
+            ),
+        ],
+    },
+    KnownLocation {
+        name: "Cargo",
+        rules: &[
+            Rule::Env("CARGO_HOME", &["registry"]),
+            Rule::Under(Os::Any, Base::Home, &[".cargo", "registry"]),
+        ],
+    },
+    KnownLocation {
+        name: "Go",
+        rules: &[
+            Rule::Env("GOMODCACHE", &["cache"]),
+            Rule::EnvList("GOPATH", &["pkg", "mod", "cache"]),
+            Rule::Under(Os::Any, Base::Home, &["go", "pkg", "mod", "cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Maven",
+        rules: &[Rule::Under(Os::Any, Base::Home, &[".m2", "repository"])],
+    },
+    KnownLocation {
+        name: "Gradle",
+        rules: &[
+            Rule::Env("GRADLE_USER_HOME", &["caches"]),
+            Rule::Under(Os::Any, Base::Home, &[".gradle", "caches"]),
+        ],
+    },
+    KnownLocation {
+        name: "Go build",
+        rules: &[
+            Rule::Env("GOCACHE", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["go-build"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["go-build"]),
+        ],
+    },
+    KnownLocation {
+        name: "uv",
+        rules: &[
+            Rule::Env("UV_CACHE_DIR", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["uv", "cache"]),
+            Rule::Under(Os::Unix, Base::XdgCache, &["uv"]),
+        ],
+    },
+    KnownLocation {
+        name: "Poetry",
+        rules: &[
+            Rule::Env("POETRY_CACHE_DIR", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["pypoetry", "Cache"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["pypoetry"]),
+        ],
+    },
+    KnownLocation {
+        name: "conda",
+        rules: &[
+            Rule::EnvCsv("CONDA_PKGS_DIRS", &[]),
+            Rule::Under(Os::Any, Base::Home, &["miniconda3", "pkgs"]),
+            Rule::Under(Os::Any, Base::Home, &["anaconda3", "pkgs"]),
+            Rule::Under(Os::Any, Base::Home, &["miniforge3", "pkgs"]),
+            Rule::Under(Os::Any, Base::Home, &["mambaforge", "pkgs"]),
+            Rule::Under(Os::Any, Base::Home, &[".conda", "pkgs"]),
+        ],
+    },
+    KnownLocation {
+        name: "Bun",
+        rules: &[
+            Rule::Env("BUN_INSTALL_CACHE_DIR", &[]),
+            Rule::Env("BUN_INSTALL", &["install", "cache"]),
+            Rule::Under(Os::Any, Base::Home, &[".bun", "install", "cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Deno",
+        rules: &[
+            Rule::Env("DENO_DIR", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["deno"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["deno"]),
+        ],
+    },
+    KnownLocation {
+        name: "Composer",
+        rules: &[
+            Rule::Env("COMPOSER_CACHE_DIR", &[]),
+            Rule::Env("COMPOSER_HOME", &["cache"]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["Composer"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["composer"]),
+            Rule::Under(Os::Unix, Base::Home, &[".composer", "cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Bundler",
+        rules: &[
+            Rule::Env("BUNDLE_USER_CACHE", &[]),
+            Rule::Env("BUNDLE_USER_HOME", &["cache"]),
+            Rule::Under(Os::Any, Base::Home, &[".bundle", "cache"]),
+        ],
+    },
+    KnownLocation {
+        name: "CocoaPods",
+        rules: &[
+            Rule::Env("CP_CACHE_DIR", &[]),
+            Rule::Under(Os::MacOs, Base::UserCache, &["CocoaPods"]),
+        ],
+    },
+    KnownLocation {
+        name: "Hex",
+        rules: &[
+            Rule::Env("HEX_HOME", &["packages"]),
+            Rule::Under(Os::Any, Base::Home, &[".hex", "packages"]),
+        ],
+    },
+    KnownLocation {
+        name: "ccache",
+        rules: &[
+            Rule::Env("CCACHE_DIR", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["ccache"]),
+            Rule::Under(Os::MacOs, Base::UserCache, &["ccache"]),
+            Rule::Under(Os::Linux, Base::UserCache, &["ccache"]),
+            Rule::Under(Os::Unix, Base::Home, &[".ccache"]),
+        ],
+    },
+    KnownLocation {
+        name: "sccache",
+        rules: &[
+            Rule::Env("SCCACHE_DIR", &[]),
+            Rule::Under(
+                Os::Windows,
+                Base::LocalAppData,
+                &["Mozilla", "sccache", "cache"],
+            ),
+            Rule::Under(Os::MacOs, Base::UserCache, &["Mozilla.sccache"]),
+            Rule::Under(Os::Linux, Base::UserCache, &["sccache"]),
+        ],
+    },
+    KnownLocation {
+        name: "Bazel",
+        // The default output root ignores XDG; on macOS it lives in
+        // /private/var/tmp/_bazel_$USER, which isn't a per-user directory.
+        rules: &[Rule::Under(Os::Linux, Base::Home, &[".cache", "bazel"])],
+    },
+    KnownLocation {
+        name: "Bazelisk",
+        rules: &[
+            Rule::Env("BAZELISK_HOME", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["bazelisk"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["bazelisk"]),
+        ],
+    },
+    KnownLocation {
+        name: "vcpkg",
+        rules: &[
+            Rule::Env("VCPKG_DEFAULT_BINARY_CACHE", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["vcpkg", "archives"]),
+            Rule::Under(Os::Unix, Base::XdgCache, &["vcpkg", "archives"]),
+        ],
+    },
+    KnownLocation {
+        name: "Conan",
+        rules: &[
+            Rule::Env("CONAN_HOME", &["p"]),
+            Rule::Under(Os::Any, Base::Home, &[".conan2", "p"]),
+        ],
+    },
+    KnownLocation {
+        name: "Conan 1.x",
+        rules: &[
+            Rule::Env("CONAN_USER_HOME", &[".conan", "data"]),
+            Rule::Under(Os::Any, Base::Home, &[".conan", "data"]),
+        ],
+    },
+    KnownLocation {
+        name: "pre-commit",
+        rules: &[
+            Rule::Env("PRE_COMMIT_HOME", &[]),
+            Rule::Under(Os::Windows, Base::Home, &[".cache", "pre-commit"]),
+            Rule::Under(Os::Unix, Base::XdgCache, &["pre-commit"]),
+        ],
+    },
 ];
 
-enum CacheLocation {
-    LocalAppData(&'static str),
-    LocalAppDataNested(&'static [&'static str]),
-    UserProfileNested(&'static [&'static str]),
+/// Resolve every package manager cache that exists and isn't excluded
+pub fn resolve_locations(config: &Config) -> Vec<ResolvedLocation> {
+    LocationResolver::from_env()
+        .resolve_all(CACHE_LOCATIONS)
+        .into_iter()
+        .filter(|loc| !config.is_excluded(&loc.path))
+        .collect()
 }
 
 /// Scan for package manager cache directories
 ///
-/// Checks well-known Windows cache locations for various package managers.
-/// Uses shared calculate_dir_size for consistent size calculation.
-pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<CategoryResult> {
+/// Checks each package manager's cache location for the current OS, honoring
+/// environment overrides like `PIP_CACHE_DIR` and `$XDG_CACHE_HOME`. Caches with a
+/// [`cache_strategy::Strategy`] only offer their regenerable parts.
+pub fn scan(root: &Path, config: &Config, output_mode: OutputMode) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
-    let mut candidates = Vec::new();
-
-    let local_appdata = env::var("LOCALAPPDATA").ok().map(PathBuf::from);
-    let userprofile = env::var("USERPROFILE").ok().map(PathBuf::from);
 
     if output_mode != OutputMode::Quiet {
         println!(
@@ -69,44 +285,44 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     }
 
     // 1. Collect candidate paths
-    for (name, location) in CACHE_LOCATIONS {
-        let cache_path = match location {
-            CacheLocation::LocalAppData(subpath) => local_appdata.as_ref().map(|p| p.join(subpath)),
-            CacheLocation::LocalAppDataNested(subpaths) => local_appdata.as_ref().map(|p| {
-                let mut path = p.clone();
-                for subpath in *subpaths {
-                    path = path.join(subpath);
-                }
-                path
-            }),
-            CacheLocation::UserProfileNested(subpaths) => userprofile.as_ref().map(|p| {
-                let mut path = p.clone();
-                for subpath in *subpaths {
-                    path = path.join(subpath);
-                }
-                path
-            }),
-        };
+    let locations = resolve_locations(config);
 
-        if let Some(cache_path) = cache_path {
-            if cache_path.exists() && !config.is_excluded(&cache_path) {
-                candidates.push((name, cache_path));
-                if output_mode != OutputMode::Quiet {
-                    println!("    {} Found {} cache", Theme::muted("•"), name);
-                }
+    // 2. Plan each cache in turn (one parallel walk at a time)
+    let mut paths_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
+    let mut reclaim = ReclaimSplit::default();
+    for location in &locations {
+        let refs = lockfile_refs(location, root, config);
+        let plan = cache_strategy::plan(location, refs.as_ref(), &|_| {});
+        if output_mode != OutputMode::Quiet {
+            println!(
+                "    {} Found {} cache {}",
+                Theme::muted("•"),
+                location.name,
+                Theme::muted(&format!("(via {})", location.source))
+            );
+            println!("        {}", Theme::muted(&describe_plan(&plan)));
+            if let Some(refs) = &refs {
+                print_unreferenced(&plan, refs, output_mode);
+            } else if prune_format(location, config).is_some() {
+                println!(
+                    "        {}",
+                    Theme::muted(
+                        "Not pruning: the scan root doesn't cover your home directory and no lockfile_roots are set"
+                    )
+                );
             }
         }
+        take_parts(plan, config, &mut paths_with_sizes, &mut reclaim);
     }
 
-    // 2. Calculate sizes sequentially (one parallel walk at a time)
-    let mut paths_with_sizes: Vec<(PathBuf, u64)> = candidates
-        .iter()
-        .map(|(_name, p)| {
-            let size = utils::calculate_dir_size(p);
-            (p.clone(), size)
-        })
-        .filter(|(_, size)| *size > 0)
-        .collect();
+    if output_mode != OutputMode::Quiet && !locations.is_empty() {
+        println!(
+            "  {} {} reclaimable without network, {} requires re-download",
+            Theme::muted("→"),
+            Theme::size(&bytesize::to_string(reclaim.offline_bytes, false)),
+            Theme::size(&bytesize::to_string(reclaim.download_bytes, false))
+        );
+    }
 
     // Sort by size descending
     paths_with_sizes.sort_by(|a, b| b.1.cmp(&a.1));
@@ -114,7 +330,7 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     // Show found caches
     if output_mode != OutputMode::Quiet && !paths_with_sizes.is_empty() {
         println!(
-            "  {} Found {} package caches:",
+            "  {} Found {} package cache items:",
             Theme::muted("→"),
             paths_with_sizes.len()
         );
@@ -146,29 +362,28 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     }
 
     for (path, size) in paths_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
+    }
+    if !locations.is_empty() {
+        result.reclaim = Some(reclaim);
     }
 
     Ok(result)
 }
 
-/// Scan with real-time progress events (for TUI).
 /// Scan with real-time progress events (for TUI).
 pub fn scan_with_progress(
-    _root: &Path,
+    root: &Path,
     config: &Config,
     tx: &Sender<ScanProgressEvent>,
 ) -> Result<CategoryResult> {
     const CATEGORY: &str = "Package Cache";
-    let total = CACHE_LOCATIONS.len() as u64;
+    let locations = resolve_locations(config);
+    let total = locations.len() as u64;
 
     let mut result = CategoryResult::default();
     let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
-
-    let local_appdata = env::var("LOCALAPPDATA").ok().map(PathBuf::from);
-    let userprofile = env::var("USERPROFILE").ok().map(PathBuf::from);
+    let mut reclaim = ReclaimSplit::default();
 
     let _ = tx.send(ScanProgressEvent::CategoryStarted {
         category: CATEGORY.to_string(),
@@ -179,48 +394,18 @@ pub fn scan_with_progress(
     let reporter = Arc::new(ScanPathReporter::new(CATEGORY, tx.clone(), 10));
     let on_path = |path: &Path| reporter.emit_path(path);
 
-    // Scan known package manager caches
-    for (idx, (_name, location)) in CACHE_LOCATIONS.iter().enumerate() {
-        let cache_path = match location {
-            CacheLocation::LocalAppData(subpath) => local_appdata.as_ref().map(|p| p.join(subpath)),
-            CacheLocation::LocalAppDataNested(subpaths) => local_appdata.as_ref().map(|p| {
-                let mut path = p.clone();
-                for subpath in *subpaths {
-                    path = path.join(subpath);
-                }
-                path
-            }),
-            CacheLocation::UserProfileNested(subpaths) => userprofile.as_ref().map(|p| {
-                let mut path = p.clone();
-                for subpath in *subpaths {
-                    path = path.join(subpath);
-                }
-                path
-            }),
-        };
-
-        if let Some(cache_path) = cache_path {
-            if cache_path.exists() && !config.is_excluded(&cache_path) {
-                let size = utils::calculate_dir_size_with_progress(&cache_path, &on_path);
-                if size > 0 {
-                    files_with_sizes.push((cache_path.clone(), size));
-                }
-            }
+    // Plan each resolved package manager cache
+    for (idx, location) in locations.into_iter().enumerate() {
+        let refs = lockfile_refs(&location, root, config);
+        let plan = cache_strategy::plan(&location, refs.as_ref(), &on_path);
+        take_parts(plan, config, &mut files_with_sizes, &mut reclaim);
 
-            let _ = tx.send(ScanProgressEvent::CategoryProgress {
-                category: CATEGORY.to_string(),
-                completed_units: (idx + 1) as u64,
-                total_units: Some(total),
-                current_path: Some(cache_path),
-            });
-        } else {
-            let _ = tx.send(ScanProgressEvent::CategoryProgress {
-                category: CATEGORY.to_string(),
-                completed_units: (idx + 1) as u64,
-                total_units: Some(total),
-                current_path: None,
-            });
-        }
+        let _ = tx.send(ScanProgressEvent::CategoryProgress {
+            category: CATEGORY.to_string(),
+            completed_units: (idx + 1) as u64,
+            total_units: Some(total),
+            current_path: Some(location.path),
+        });
     }
 
     // Sort by size descending
@@ -228,9 +413,10 @@ pub fn scan_with_progress(
 
     // Build final result
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
+    }
+    if total > 0 {
+        result.reclaim = Some(reclaim);
     }
 
     let _ = tx.send(ScanProgressEvent::CategoryFinished {
@@ -242,16 +428,239 @@ pub fn scan_with_progress(
     Ok(result)
 }
 
-/// Clean (delete) a package cache directory by moving it to the Recycle Bin
-pub fn clean(path: &Path) -> Result<()> {
-    if !path.exists() {
-        return Ok(());
+/// Add a plan's parts that aren't excluded to the result, tallying how each comes back
+fn take_parts(
+    plan: CachePlan,
+    config: &Config,
+    paths_with_sizes: &mut Vec<(PathBuf, u64)>,
+    reclaim: &mut ReclaimSplit,
+) {
+    for part in plan.parts {
+        if config.is_excluded(&part.path) {
+            continue;
+        }
+        match part.regeneration {
+            Regeneration::Offline => reclaim.offline_bytes += part.size,
+            Regeneration::Download => reclaim.download_bytes += part.size,
+        }
+        paths_with_sizes.push((part.path, part.size));
+    }
+}
+
+/// Lockfile references for caches that can be pruned, when `prune_unreferenced` is on
+/// and every place projects live is searched (see [`lockfile_search_roots`])
+fn lockfile_refs(location: &ResolvedLocation, root: &Path, config: &Config) -> Option<RefCounts> {
+    let format = prune_format(location, config)?;
+    let search_roots = lockfile_search_roots(root, config)?;
+    Some(RefCounts::from_projects(format, &search_roots, config))
+}
+
+/// The lockfile format deciding what a cache can prune, if pruning is enabled
+fn prune_format(
+    location: &ResolvedLocation,
+    config: &Config,
+) -> Option<&'static dyn LockfileFormat> {
+    if !config.categories.cache.prune_unreferenced {
+        return None;
+    }
+    Strategy::for_location(location.name)?.lockfile_format()
+}
+
+/// Directories to look for project lockfiles in
+///
+/// The configured `lockfile_roots` plus the scan root, or the scan root alone
+/// when it covers the home directory (see [`project::search_roots`]). Returns
+/// `None` when neither is the case and pruning would be guesswork.
+fn lockfile_search_roots(root: &Path, config: &Config) -> Option<Vec<PathBuf>> {
+    project::search_roots(
+        root,
+        &config.categories.cache.lockfile_roots,
+        utils::home_dir().as_deref(),
+    )
+}
+
+/// List the package versions a pruning strategy offers, largest first
+fn print_unreferenced(plan: &CachePlan, refs: &RefCounts, output_mode: OutputMode) {
+    if refs.is_empty() {
+        println!(
+            "        {}",
+            Theme::muted("No lockfiles found; not pruning")
+        );
+        return;
+    }
+    if !refs.errors.is_empty() {
+        println!(
+            "        {}",
+            Theme::muted("Some lockfiles couldn't be read; not pruning")
+        );
+        for error in &refs.errors {
+            println!("          {} {}", Theme::warning("!"), error);
+        }
+        return;
+    }
+    println!(
+        "        {} unreferenced versions across {} lockfiles:",
+        plan.unreferenced.len(),
+        refs.lockfiles.len()
+    );
+    let show_count = match output_mode {
+        OutputMode::Normal => plan.unreferenced.len().min(10),
+        _ => plan.unreferenced.len(),
+    };
+    for package in plan.unreferenced.iter().take(show_count) {
+        println!(
+            "          {} {} {} ({})",
+            Theme::muted("→"),
+            package.name,
+            package.version,
+            Theme::size(&bytesize::to_string(package.size, false))
+        );
+    }
+    if plan.unreferenced.len() > show_count {
+        println!(
+            "          {} ... and {} more (use -v to see all)",
+            Theme::muted("→"),
+            plan.unreferenced.len() - show_count
+        );
+    }
+}
+
+/// One-line summary of what cleaning a cache removes and keeps
+fn describe_plan(plan: &CachePlan) -> String {
+    let size = |bytes: u64| bytesize::to_string(bytes, false);
+    if !plan.partial {
+        return format!(
+            "{} requires re-download (whole cache)",
+            size(plan.download_bytes())
+        );
+    }
+    let mut parts = vec![format!("{} without network", size(plan.offline_bytes()))];
+    if plan.download_bytes() > 0 {
+        parts.push(format!(
+            "{} requires re-download",
+            size(plan.download_bytes())
+        ));
     }
-    crate::trash_ops::delete(path).with_context(|| {
-        format!(
-            "Failed to delete package cache directory: {}",
+    parts.push(format!("{} kept", size(plan.kept_bytes)));
+    parts.join(", ")
+}
+
+/// Clean one item of a package cache scan
+///
+/// An npm `content-v2` item only loses the blobs no index entry refers to (see
+/// [`cache_strategy::deletion_paths`]); every other item is deleted as a whole.
+pub fn clean(path: &Path, permanent: bool) -> Result<DeleteOutcome> {
+    clean_parts(path, 0, permanent).0
+}
+
+/// [`clean`], also returning what was removed and its size, for the deletion history
+///
+/// That's the item itself (`size`), or the blobs of an npm `content-v2` item
+/// that were deleted, even when others couldn't be: restore looks for the
+/// paths that were moved to the trash, and the directory itself never is.
+pub fn clean_parts(
+    path: &Path,
+    size: u64,
+    permanent: bool,
+) -> (Result<DeleteOutcome>, Vec<(PathBuf, u64)>) {
+    if !cache_strategy::is_grouped(path) {
+        let outcome = cleaner::delete_with_precheck(path, permanent);
+        let removed = match outcome {
+            Ok(DeleteOutcome::Deleted) => vec![(path.to_path_buf(), size)],
+            _ => Vec::new(),
+        };
+        return (outcome, removed);
+    }
+    let blobs = cache_strategy::deletion_paths(path);
+    if blobs.is_empty() {
+        return (Ok(DeleteOutcome::SkippedMissing), Vec::new());
+    }
+    let sizes: HashMap<&PathBuf, u64> = blobs
+        .iter()
+        .map(|blob| (blob, std::fs::metadata(blob).map(|m| m.len()).unwrap_or(0)))
+        .collect();
+    let result = cleaner::clean_paths_batch(&blobs, permanent);
+    let removed: Vec<(PathBuf, u64)> = result
+        .deleted_paths
+        .iter()
+        .map(|blob| (blob.clone(), sizes.get(blob).copied().unwrap_or(0)))
+        .collect();
+    let outcome = if !result.locked_paths.is_empty() {
+        Ok(DeleteOutcome::SkippedLocked)
+    } else if !result.permission_denied_paths.is_empty() {
+        Ok(DeleteOutcome::SkippedPermission)
+    } else if result.error_count > 0 {
+        Err(anyhow::anyhow!(
+            "Failed to delete {} of {} unreferenced blobs in {}",
+            result.error_count,
+            blobs.len(),
             path.display()
-        )
-    })?;
-    Ok(())
+        ))
+    } else {
+        Ok(DeleteOutcome::Deleted)
+    };
+    (outcome, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockfile_search_roots_need_home_or_configured_roots() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scan_root = tmp.path().join("scan");
+        let projects = tmp.path().join("projects");
+        std::fs::create_dir_all(&scan_root).unwrap();
+        std::fs::create_dir_all(&projects).unwrap();
+        let canonical = |p: &Path| std::fs::canonicalize(p).unwrap();
+
+        // A directory outside the home directory doesn't see every project
+        let mut config = Config::default();
+        if utils::home_dir()
+            .is_some_and(|home| !canonical(&home).starts_with(canonical(tmp.path())))
+        {
+            assert_eq!(lockfile_search_roots(&scan_root, &config), None);
+        }
+
+        config.categories.cache.lockfile_roots = vec![projects.display().to_string()];
+        assert_eq!(
+            lockfile_search_roots(&scan_root, &config),
+            Some(vec![canonical(&projects), canonical(&scan_root)])
+        );
+        assert_eq!(
+            lockfile_search_roots(&projects.join("."), &config),
+            Some(vec![canonical(&projects)])
+        );
+    }
+
+    #[test]
+    fn test_grouped_npm_item_reports_each_blob_removed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cacache = tmp.path().join("_cacache");
+        // sha512 digest of 64 0x00 bytes, base64 and hex encoded
+        let bucket = format!(
+            "h1\t{{\"key\":\"a\",\"integrity\":\"sha512-{}==\"}}\n",
+            "A".repeat(86)
+        );
+        std::fs::create_dir_all(cacache.join("index-v5/00")).unwrap();
+        std::fs::write(cacache.join("index-v5/00/bucket"), bucket).unwrap();
+        let content = |hex: String| {
+            let path = cacache
+                .join("content-v2/sha512")
+                .join(&hex[..2])
+                .join(&hex[2..4])
+                .join(&hex[4..]);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            path
+        };
+        let (live, dead) = (content("00".repeat(64)), content("ff".repeat(64)));
+        std::fs::write(&live, [0u8; 10]).unwrap();
+        std::fs::write(&dead, [0u8; 7]).unwrap();
+
+        let (outcome, removed) = clean_parts(&cacache.join("content-v2"), 17, true);
+        assert!(matches!(outcome, Ok(DeleteOutcome::Deleted)));
+        assert_eq!(removed, vec![(dead.clone(), 7)]);
+        assert!(live.exists() && !dead.exists());
+    }
 }
@@ -0,0 +1,643 @@
+//! Per-manager strategies for partially cleaning package caches.
+//!
+//! Deleting a whole cache root such as `~/.cargo/registry` forces every
+//! dependency to be downloaded again. For the managers below, a strategy picks
+//! out only the parts that are safe to remove and says how each one comes back:
+//!
+//! - Cargo: extracted sources in `registry/src` (re-extracted from `registry/cache`)
+//! - Gradle: `caches/<version>` directories of every Gradle version but the newest
+//! - npm: `_cacache` content no index entry refers to (one item per cache), plus
+//!   `_cacache/tmp`
+//! - pip: the `http` download cache, keeping locally built `wheels`
+//!
+//! Caches without a strategy are offered whole, as before.
+//!
+//! With `prune_unreferenced` set under `[categories.cache]`, lockfile-based caches
+//! additionally offer package versions no lockfile on disk pins (see
+//! [`crate::lockfiles`]); for Cargo that's both the `.crate` and its `src` dir.
+
+use crate::categories::locations::ResolvedLocation;
+use crate::lockfiles::{self, CargoLock, LockfileFormat, RefCounts};
+use crate::utils;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// How a removed part of a cache comes back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regeneration {
+    /// Rebuilt from data that stays on disk (or never needed again)
+    Offline,
+    /// Downloaded again the next time it's needed
+    Download,
+}
+
+/// One removable piece of a cache
+#[derive(Debug, Clone)]
+pub struct CachePart {
+    pub path: PathBuf,
+    pub size: u64,
+    pub regeneration: Regeneration,
+}
+
+/// A cached package version no lockfile references, with its total size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnreferencedPackage {
+    pub name: String,
+    pub version: String,
+    pub size: u64,
+}
+
+/// What cleaning one cache location would remove and keep
+#[derive(Debug, Clone, Default)]
+pub struct CachePlan {
+    pub parts: Vec<CachePart>,
+    /// Package versions offered because no lockfile pins them, largest first
+    pub unreferenced: Vec<UnreferencedPackage>,
+    /// Bytes the strategy leaves in place
+    pub kept_bytes: u64,
+    /// False when the whole root is offered because no strategy applies
+    pub partial: bool,
+}
+
+impl CachePlan {
+    fn whole(root: &Path, size: u64) -> Self {
+        let mut plan = Self::default();
+        plan.push(root.to_path_buf(), size, Regeneration::Download);
+        plan
+    }
+
+    fn push(&mut self, path: PathBuf, size: u64, regeneration: Regeneration) {
+        if size > 0 {
+            self.parts.push(CachePart {
+                path,
+                size,
+                regeneration,
+            });
+        }
+    }
+
+    fn bytes(&self, regeneration: Regeneration) -> u64 {
+        self.parts
+            .iter()
+            .filter(|p| p.regeneration == regeneration)
+            .map(|p| p.size)
+            .sum()
+    }
+
+    /// Bytes reclaimable without touching the network
+    pub fn offline_bytes(&self) -> u64 {
+        self.bytes(Regeneration::Offline)
+    }
+
+    /// Bytes that have to be downloaded again once needed
+    pub fn download_bytes(&self) -> u64 {
+        self.bytes(Regeneration::Download)
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.parts.iter().map(|p| p.size).sum()
+    }
+}
+
+/// Cleaning strategies for caches wole understands the layout of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    CargoRegistry,
+    GradleCaches,
+    NpmCacache,
+    PipHttp,
+}
+
+impl Strategy {
+    /// The strategy for a [`crate::categories::cache::CACHE_LOCATIONS`] entry, if any
+    pub fn for_location(name: &str) -> Option<Self> {
+        match name {
+            "Cargo" => Some(Strategy::CargoRegistry),
+            "Gradle" => Some(Strategy::GradleCaches),
+            "npm" => Some(Strategy::NpmCacache),
+            "pip" => Some(Strategy::PipHttp),
+            _ => None,
+        }
+    }
+
+    /// Lockfile format whose references decide what this cache can prune
+    pub fn lockfile_format(self) -> Option<&'static dyn LockfileFormat> {
+        match self {
+            Strategy::CargoRegistry => Some(&CargoLock),
+            _ => None,
+        }
+    }
+}
+
+/// Work out what to remove from a resolved cache location
+///
+/// `refs` enables pruning for strategies with a [`Strategy::lockfile_format`]; it's
+/// ignored when no lockfile was found, since then everything would look unused,
+/// and when a lockfile couldn't be read, since its pins would look unused.
+pub fn plan<F>(location: &ResolvedLocation, refs: Option<&RefCounts>, on_path: &F) -> CachePlan
+where
+    F: Fn(&Path) + Sync,
+{
+    let root = location.path.as_path();
+    let total = utils::calculate_dir_size_with_progress(root, on_path);
+
+    let Some(strategy) = Strategy::for_location(location.name) else {
+        return CachePlan::whole(root, total);
+    };
+
+    let mut plan = CachePlan {
+        partial: true,
+        ..CachePlan::default()
+    };
+    match strategy {
+        Strategy::CargoRegistry => match refs.filter(|r| !r.is_empty() && r.errors.is_empty()) {
+            Some(refs) => prune_cargo(root, refs, &mut plan, on_path),
+            None => plan_cargo(root, &mut plan, on_path),
+        },
+        Strategy::GradleCaches => plan_gradle(root, &mut plan, on_path),
+        Strategy::NpmCacache => plan_npm(root, &mut plan, on_path),
+        Strategy::PipHttp => plan_pip(root, &mut plan, on_path),
+    }
+    plan.kept_bytes = total.saturating_sub(plan.total_bytes());
+    plan
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `registry/src/<index>` holds sources unpacked from `registry/cache/<index>/*.crate`
+fn plan_cargo<F>(root: &Path, plan: &mut CachePlan, on_path: &F)
+where
+    F: Fn(&Path) + Sync,
+{
+    for index_dir in subdirs(&root.join("src")) {
+        let size = utils::calculate_dir_size_with_progress(&index_dir, on_path);
+        plan.push(index_dir, size, Regeneration::Offline);
+    }
+}
+
+/// Per-crate variant of [`plan_cargo`]: unpinned versions lose both their `.crate`
+/// in `registry/cache` and their `registry/src` dir; pinned ones only the latter.
+fn prune_cargo<F>(root: &Path, refs: &RefCounts, plan: &mut CachePlan, on_path: &F)
+where
+    F: Fn(&Path) + Sync,
+{
+    let mut unreferenced: BTreeMap<(String, String), u64> = BTreeMap::new();
+
+    for index_dir in subdirs(&root.join("cache")) {
+        let Ok(entries) = fs::read_dir(&index_dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("crate") {
+                continue;
+            }
+            let Some((name, version)) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(lockfiles::split_name_version)
+            else {
+                continue;
+            };
+            if refs.is_referenced(name, version) {
+                continue;
+            }
+            on_path(&path);
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            *unreferenced
+                .entry((name.to_string(), version.to_string()))
+                .or_insert(0) += size;
+            plan.push(path, size, Regeneration::Download);
+        }
+    }
+
+    for index_dir in subdirs(&root.join("src")) {
+        for crate_dir in subdirs(&index_dir) {
+            let Some((name, version)) = crate_dir
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(lockfiles::split_name_version)
+                .map(|(n, v)| (n.to_string(), v.to_string()))
+            else {
+                continue;
+            };
+            let size = utils::calculate_dir_size_with_progress(&crate_dir, on_path);
+            if refs.is_referenced(&name, &version) {
+                plan.push(crate_dir, size, Regeneration::Offline);
+            } else {
+                *unreferenced.entry((name, version)).or_insert(0) += size;
+                plan.push(crate_dir, size, Regeneration::Download);
+            }
+        }
+    }
+
+    plan.unreferenced = unreferenced
+        .into_iter()
+        .filter(|(_, size)| *size > 0)
+        .map(|((name, version), size)| UnreferencedPackage {
+            name,
+            version,
+            size,
+        })
+        .collect();
+    plan.unreferenced.sort_by_key(|p| std::cmp::Reverse(p.size));
+}
+
+/// Parse a Gradle version directory name like `8.5` or `8.0-rc-1`
+fn gradle_version(name: &str) -> Option<Vec<u64>> {
+    if !name.contains('.') {
+        return None;
+    }
+    let release = name.split('-').next()?;
+    release
+        .split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect()
+}
+
+/// `caches/<version>` holds per-version generated state (Kotlin DSL accessors,
+/// file hashes, ...). Only the newest version's directory is kept.
+fn plan_gradle<F>(root: &Path, plan: &mut CachePlan, on_path: &F)
+where
+    F: Fn(&Path) + Sync,
+{
+    let mut versions: Vec<(Vec<u64>, PathBuf)> = subdirs(root)
+        .into_iter()
+        .filter_map(|dir| {
+            let version = gradle_version(dir.file_name()?.to_str()?)?;
+            Some((version, dir))
+        })
+        .collect();
+    versions.sort();
+    versions.pop();
+
+    for (_, dir) in versions {
+        let size = utils::calculate_dir_size_with_progress(&dir, on_path);
+        plan.push(dir, size, Regeneration::Offline);
+    }
+}
+
+/// cacache stores content under `content-v2/<algo>/<hex[..2]>/<hex[2..4]>/<hex[4..]>`
+/// and an append-only index of `<hash>\t<json>` lines under `index-v5`. Content no
+/// live index entry points at is garbage `npm cache verify` would also remove.
+///
+/// The garbage is offered as a single part, the `content-v2` directory, rather than
+/// one item per blob; [`deletion_paths`] expands it when cleaning.
+fn plan_npm<F>(root: &Path, plan: &mut CachePlan, on_path: &F)
+where
+    F: Fn(&Path) + Sync,
+{
+    let cacache = if root.join("index-v5").is_dir() {
+        root.to_path_buf()
+    } else {
+        root.join("_cacache")
+    };
+    if !cacache.join("index-v5").is_dir() {
+        return;
+    }
+
+    let garbage = npm_garbage(&cacache, on_path);
+    let size = garbage.iter().map(|(_, size)| size).sum();
+    plan.push(cacache.join("content-v2"), size, Regeneration::Offline);
+
+    let tmp = cacache.join("tmp");
+    if tmp.is_dir() {
+        let size = utils::calculate_dir_size_with_progress(&tmp, on_path);
+        plan.push(tmp, size, Regeneration::Offline);
+    }
+}
+
+/// Unreferenced content blobs of a cacache directory, with their sizes
+fn npm_garbage<F>(cacache: &Path, on_path: &F) -> Vec<(PathBuf, u64)>
+where
+    F: Fn(&Path) + Sync,
+{
+    // Later lines for a key replace earlier ones; a null integrity deletes the key.
+    let mut live: HashMap<String, Option<String>> = HashMap::new();
+    for entry in WalkDir::new(cacache.join("index-v5"))
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        on_path(entry.path());
+        let Ok(contents) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        for line in contents.lines() {
+            let Some((_, json)) = line.split_once('\t') else {
+                continue;
+            };
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+                continue;
+            };
+            let Some(key) = value.get("key").and_then(|k| k.as_str()) else {
+                continue;
+            };
+            let integrity = value
+                .get("integrity")
+                .and_then(|i| i.as_str())
+                .map(str::to_string);
+            live.insert(key.to_string(), integrity);
+        }
+    }
+
+    let referenced: HashSet<(String, String)> = live
+        .values()
+        .flatten()
+        .flat_map(|integrity| integrity.split_whitespace())
+        .filter_map(|sri| {
+            let (algo, digest) = sri.split_once('-')?;
+            let digest = digest.split('?').next()?;
+            Some((algo.to_string(), to_hex(&decode_base64(digest)?)))
+        })
+        .collect();
+
+    let mut garbage = Vec::new();
+    for algo_dir in subdirs(&cacache.join("content-v2")) {
+        let Some(algo) = algo_dir.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        for entry in WalkDir::new(&algo_dir)
+            .min_depth(3)
+            .max_depth(3)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let Ok(relative) = entry.path().strip_prefix(&algo_dir) else {
+                continue;
+            };
+            let hex: String = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            if !referenced.contains(&(algo.to_string(), hex)) {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                garbage.push((entry.into_path(), size));
+            }
+        }
+    }
+    garbage
+}
+
+/// The paths to actually delete for a part of a cache plan
+///
+/// A part is normally removed as a whole. An npm `content-v2` directory stands
+/// for its unreferenced blobs, which are worked out again from the index so
+/// content cached since the scan is left alone.
+pub fn deletion_paths(path: &Path) -> Vec<PathBuf> {
+    match npm_cacache_of(path) {
+        Some(cacache) => npm_garbage(cacache, &|_| {})
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect(),
+        None => vec![path.to_path_buf()],
+    }
+}
+
+/// Whether a part of a cache plan stands for a set of files inside it
+pub fn is_grouped(path: &Path) -> bool {
+    npm_cacache_of(path).is_some()
+}
+
+fn npm_cacache_of(path: &Path) -> Option<&Path> {
+    if path.file_name()? != "content-v2" {
+        return None;
+    }
+    let cacache = path.parent()?;
+    cacache.join("index-v5").is_dir().then_some(cacache)
+}
+
+/// pip's `http` (and newer `http-v2`) directories are cached downloads; `wheels`
+/// holds wheels built locally from sdists and is kept.
+fn plan_pip<F>(root: &Path, plan: &mut CachePlan, on_path: &F)
+where
+    F: Fn(&Path) + Sync,
+{
+    for name in ["http", "http-v2"] {
+        let dir = root.join(name);
+        if dir.is_dir() {
+            let size = utils::calculate_dir_size_with_progress(&dir, on_path);
+            plan.push(dir, size, Regeneration::Download);
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode standard (padded or unpadded) base64, as used by SRI integrity strings
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a' + 26) as u32),
+            b'0'..=b'9' => Some((c - b'0' + 52) as u32),
+            b'+' | b'-' => Some(62),
+            b'/' | b'_' => Some(63),
+            _ => None,
+        }
+    }
+
+    let input = input.trim_end_matches('=');
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in input.bytes() {
+        buffer = (buffer << 6) | value(c)?;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::categories::locations::LocationSource;
+
+    fn write(path: &Path, len: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![b'x'; len]).unwrap();
+    }
+
+    fn location(name: &'static str, path: &Path) -> ResolvedLocation {
+        ResolvedLocation {
+            name,
+            path: path.to_path_buf(),
+            source: LocationSource::Home,
+        }
+    }
+
+    #[test]
+    fn test_gradle_keeps_newest_version() {
+        let tmp = tempfile::tempdir().unwrap();
+        let caches = tmp.path();
+        write(&caches.join("7.6").join("a"), 10);
+        write(&caches.join("8.10").join("a"), 20);
+        write(&caches.join("8.9").join("a"), 30);
+        write(&caches.join("modules-2").join("a"), 40);
+
+        let plan = plan(&location("Gradle", caches), None, &|_| {});
+        let mut removed: Vec<_> = plan.parts.iter().map(|p| p.path.clone()).collect();
+        removed.sort();
+        assert_eq!(removed, vec![caches.join("7.6"), caches.join("8.9")]);
+        assert_eq!(plan.offline_bytes(), 40);
+        assert_eq!(plan.kept_bytes, 60);
+    }
+
+    #[test]
+    fn test_npm_offers_only_unreferenced_content() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cacache = tmp.path().join("_cacache");
+        // sha512 digests of 64 0x00 and 0xff bytes, base64 and hex encoded
+        let live_b64 = format!("{}==", "A".repeat(86));
+        let live_hex = "00".repeat(64);
+        let dead_hex = "ff".repeat(64);
+        let deleted_b64 = format!("{}w==", "/".repeat(85));
+        let bucket = format!(
+            "h1\t{{\"key\":\"a\",\"integrity\":\"sha512-{}\"}}\nh2\t{{\"key\":\"b\",\"integrity\":\"sha512-{}\"}}\nh3\t{{\"key\":\"b\",\"integrity\":null}}\n",
+            live_b64, deleted_b64
+        );
+        fs::create_dir_all(cacache.join("index-v5").join("00")).unwrap();
+        fs::write(cacache.join("index-v5").join("00").join("bucket"), bucket).unwrap();
+        let content = |hex: &str| {
+            cacache
+                .join("content-v2")
+                .join("sha512")
+                .join(&hex[..2])
+                .join(&hex[2..4])
+                .join(&hex[4..])
+        };
+        write(&content(&live_hex), 100);
+        write(&content(&dead_hex), 7);
+
+        write(&content(&"ee".repeat(64)), 3);
+
+        let plan = plan(&location("npm", tmp.path()), None, &|_| {});
+        assert_eq!(plan.parts.len(), 1);
+        assert_eq!(plan.parts[0].path, cacache.join("content-v2"));
+        assert_eq!(plan.offline_bytes(), 10);
+        assert!(is_grouped(&plan.parts[0].path));
+
+        let mut blobs = deletion_paths(&plan.parts[0].path);
+        blobs.sort();
+        assert_eq!(blobs, vec![content(&"ee".repeat(64)), content(&dead_hex)]);
+        assert_eq!(
+            deletion_paths(&cacache.join("tmp")),
+            vec![cacache.join("tmp")]
+        );
+    }
+
+    #[test]
+    fn test_pip_keeps_wheels_and_unknown_caches_are_whole() {
+        let tmp = tempfile::tempdir().unwrap();
+        write(&tmp.path().join("http").join("a"), 5);
+        write(&tmp.path().join("wheels").join("a"), 9);
+
+        let pip = plan(&location("pip", tmp.path()), None, &|_| {});
+        assert!(pip.partial);
+        assert_eq!(pip.download_bytes(), 5);
+        assert_eq!(pip.kept_bytes, 9);
+
+        let other = plan(&location("Maven", tmp.path()), None, &|_| {});
+        assert!(!other.partial);
+        assert_eq!(other.parts.len(), 1);
+        assert_eq!(other.download_bytes(), 14);
+    }
+
+    #[test]
+    fn test_cargo_prunes_versions_no_lockfile_pins() {
+        let tmp = tempfile::tempdir().unwrap();
+        let registry = tmp.path().join("registry");
+        let index = "index.crates.io-6f17d22bba15001f";
+        for crate_name in ["serde-1.0.200", "serde-1.0.100"] {
+            write(
+                &registry
+                    .join("cache")
+                    .join(index)
+                    .join(format!("{}.crate", crate_name)),
+                10,
+            );
+            write(
+                &registry
+                    .join("src")
+                    .join(index)
+                    .join(crate_name)
+                    .join("lib.rs"),
+                3,
+            );
+        }
+        write(&registry.join("index").join(index).join("config.json"), 1);
+
+        let project = tmp.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            project.join("Cargo.lock"),
+            "[[package]]\nname = \"serde\"\nversion = \"1.0.200\"\nsource = \"sparse+https://index.crates.io/\"\n",
+        )
+        .unwrap();
+        let refs = RefCounts::from_roots(&CargoLock, &[project]);
+
+        let plan = plan(&location("Cargo", &registry), Some(&refs), &|_| {});
+        assert_eq!(
+            plan.unreferenced,
+            vec![UnreferencedPackage {
+                name: "serde".to_string(),
+                version: "1.0.100".to_string(),
+                size: 13,
+            }]
+        );
+        assert_eq!(plan.download_bytes(), 13);
+        // The pinned version's sources can still be re-extracted offline
+        assert_eq!(plan.offline_bytes(), 3);
+        assert_eq!(plan.kept_bytes, 11);
+
+        // No lockfiles at all: fall back to the per-index src strategy
+        let plan = plan_fn_without_refs(&registry);
+        assert!(plan.unreferenced.is_empty());
+        assert_eq!(plan.offline_bytes(), 6);
+
+        // A lockfile that can't be parsed may pin 1.0.100: don't prune
+        let broken = tmp.path().join("broken");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(
+            broken.join("Cargo.lock"),
+            "[[package]]\nname = \"serde\"\nversion = \"1.0.100\n",
+        )
+        .unwrap();
+        let refs = RefCounts::from_roots(&CargoLock, &[tmp.path().join("project"), broken]);
+        assert_eq!(refs.errors.len(), 1);
+        let plan = super::plan(&location("Cargo", &registry), Some(&refs), &|_| {});
+        assert!(plan.unreferenced.is_empty());
+        assert_eq!(plan.download_bytes(), 0);
+        assert_eq!(plan.offline_bytes(), 6);
+    }
+
+    fn plan_fn_without_refs(registry: &Path) -> CachePlan {
+        plan(
+            &location("Cargo", registry),
+            Some(&RefCounts::default()),
+            &|_| {},
+        )
+    }
+}
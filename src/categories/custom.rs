@@ -0,0 +1,286 @@
+//! User-defined cleanup categories declared in config.toml.
+//!
+//! Each `[[categories.custom]]` entry names a set of root directories and glob
+//! rules; matching directories (or files) are reported like any built-in category.
+
+use crate::config::{Config, CustomCategoryConfig, CustomGranularity};
+use crate::output::{CategoryResult, OutputMode};
+use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
+use crate::theme::Theme;
+use crate::utils;
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Maximum traversal depth below each root
+const MAX_DEPTH: usize = 12;
+
+/// Compiled include/exclude rules for one category.
+///
+/// Patterns containing a `/` match the path relative to the root; all other
+/// patterns match the entry's file name only (like .gitignore).
+struct RuleSet {
+    by_path: GlobSet,
+    by_name: GlobSet,
+    is_empty: bool,
+}
+
+impl RuleSet {
+    fn compile(patterns: &[String], category: &str) -> Result<Self> {
+        let mut by_path = GlobSetBuilder::new();
+        let mut by_name = GlobSetBuilder::new();
+        for pattern in patterns {
+            let normalized = pattern.replace('\\', "/");
+            let glob = Glob::new(normalized.trim_start_matches('/')).with_context(|| {
+                format!(
+                    "Invalid glob pattern '{}' in custom category '{}'",
+                    pattern, category
+                )
+            })?;
+            if normalized.contains('/') {
+                by_path.add(glob);
+            } else {
+                by_name.add(glob);
+            }
+        }
+        Ok(Self {
+            by_path: by_path.build()?,
+            by_name: by_name.build()?,
+            is_empty: patterns.is_empty(),
+        })
+    }
+
+    fn matches(&self, relative: &Path) -> bool {
+        let rel = relative.to_string_lossy().replace('\\', "/");
+        if self.by_path.is_match(&rel) {
+            return true;
+        }
+        relative
+            .file_name()
+            .map(|name| self.by_name.is_match(name))
+            .unwrap_or(false)
+    }
+}
+
+/// Expand and de-duplicate the configured roots, keeping only existing directories
+pub fn resolve_roots(def: &CustomCategoryConfig) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for raw in &def.roots {
+        if let Some(path) = utils::expand_path(raw) {
+            if path.is_dir() && !roots.contains(&path) {
+                roots.push(path);
+            }
+        }
+    }
+    roots
+}
+
+/// Scan one user-defined category
+pub fn scan(
+    def: &CustomCategoryConfig,
+    config: &Config,
+    output_mode: OutputMode,
+) -> Result<CategoryResult> {
+    let roots = resolve_roots(def);
+
+    if output_mode != OutputMode::Quiet {
+        println!(
+            "  {} Checking {} of {} configured roots for {}...",
+            Theme::muted("→"),
+            roots.len(),
+            def.roots.len(),
+            def.name
+        );
+    }
+
+    let found = collect(def, &roots, config, &|_| {})?;
+
+    if output_mode != OutputMode::Quiet && !found.is_empty() {
+        println!(
+            "  {} Found {} matching {}:",
+            Theme::muted("→"),
+            found.len(),
+            match def.granularity {
+                CustomGranularity::Directory => "directories",
+                CustomGranularity::File => "files",
+            }
+        );
+        let show_count = match output_mode {
+            OutputMode::Normal => found.len().min(10),
+            _ => found.len(),
+        };
+        for (path, size) in found.iter().take(show_count) {
+            println!(
+                "      {} {} ({})",
+                Theme::muted("→"),
+                path.display(),
+                Theme::size(&bytesize::to_string(*size, false))
+            );
+        }
+        if found.len() > show_count {
+            println!(
+                "      {} ... and {} more (use -v to see all)",
+                Theme::muted("→"),
+                found.len() - show_count
+            );
+        }
+    }
+
+    Ok(into_result(found))
+}
+
+/// Scan one user-defined category with real-time progress events (for TUI)
+pub fn scan_with_progress(
+    def: &CustomCategoryConfig,
+    config: &Config,
+    tx: &Sender<ScanProgressEvent>,
+) -> Result<CategoryResult> {
+    let roots = resolve_roots(def);
+
+    let _ = tx.send(ScanProgressEvent::CategoryStarted {
+        category: def.name.clone(),
+        total_units: Some(roots.len() as u64),
+        current_path: None,
+    });
+
+    let reporter = Arc::new(ScanPathReporter::new(&def.name, tx.clone(), 10));
+    let on_path = |path: &Path| reporter.emit_path(path);
+    let result = collect(def, &roots, config, &on_path).map(into_result);
+
+    let (items, size_bytes) = result
+        .as_ref()
+        .map(|r| (r.items, r.size_bytes))
+        .unwrap_or((0, 0));
+    let _ = tx.send(ScanProgressEvent::CategoryFinished {
+        category: def.name.clone(),
+        items,
+        size_bytes,
+    });
+
+    result
+}
+
+fn into_result(found: Vec<(PathBuf, u64)>) -> CategoryResult {
+    let mut result = CategoryResult::default();
+    for (path, size) in found {
+        result.push(path, size);
+    }
+    result
+}
+
+/// Walk every root and return matching entries with their sizes, largest first
+fn collect<F>(
+    def: &CustomCategoryConfig,
+    roots: &[PathBuf],
+    config: &Config,
+    on_path: &F,
+) -> Result<Vec<(PathBuf, u64)>>
+where
+    F: Fn(&Path) + Sync,
+{
+    let include = RuleSet::compile(&def.include, &def.name)?;
+    let exclude = RuleSet::compile(&def.exclude, &def.name)?;
+    let min_size = def.min_size_bytes();
+    let cutoff = def
+        .min_age_days
+        .filter(|days| *days > 0)
+        .and_then(|days| SystemTime::now().checked_sub(Duration::from_secs(days * 24 * 60 * 60)));
+
+    let mut found: Vec<(PathBuf, u64)> = Vec::new();
+
+    for root in roots {
+        let mut walker = WalkDir::new(root)
+            .min_depth(1)
+            .max_depth(MAX_DEPTH)
+            .follow_links(false)
+            .into_iter();
+
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let path = entry.path();
+            let is_dir = entry.file_type().is_dir();
+            let relative = path.strip_prefix(root).unwrap_or(path);
+
+            if entry.file_type().is_symlink()
+                || (is_dir && utils::is_windows_reparse_point(path))
+                || config.is_excluded(path)
+                || exclude.matches(relative)
+            {
+                if is_dir {
+                    walker.skip_current_dir();
+                }
+                continue;
+            }
+
+            match def.granularity {
+                CustomGranularity::Directory => {
+                    if !is_dir {
+                        continue;
+                    }
+                    // Without include rules, every direct child of the root is a candidate.
+                    let candidate = if include.is_empty {
+                        entry.depth() == 1
+                    } else {
+                        include.matches(relative)
+                    };
+                    if !candidate {
+                        continue;
+                    }
+                    // The whole directory is one item; don't report anything nested inside it.
+                    walker.skip_current_dir();
+
+                    if let Some(cutoff) = cutoff {
+                        if newest_mtime(path).map(|t| t > cutoff).unwrap_or(true) {
+                            continue;
+                        }
+                    }
+                    let size = utils::calculate_dir_size_with_progress(path, on_path);
+                    if size > 0 && size >= min_size {
+                        found.push((path.to_path_buf(), size));
+                    }
+                }
+                CustomGranularity::File => {
+                    if !entry.file_type().is_file() {
+                        continue;
+                    }
+                    if !include.is_empty && !include.matches(relative) {
+                        continue;
+                    }
+                    on_path(path);
+                    let Ok(meta) = entry.metadata() else {
+                        continue;
+                    };
+                    if let Some(cutoff) = cutoff {
+                        if meta.modified().map(|t| t > cutoff).unwrap_or(true) {
+                            continue;
+                        }
+                    }
+                    if meta.len() >= min_size {
+                        found.push((path.to_path_buf(), meta.len()));
+                    }
+                }
+            }
+        }
+    }
+
+    found.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    Ok(found)
+}
+
+/// Most recent modification time of anything inside a directory (including itself)
+fn newest_mtime(dir: &Path) -> Option<SystemTime> {
+    WalkDir::new(dir)
+        .max_depth(MAX_DEPTH)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter_map(|m| m.modified().ok())
+        .max()
+}
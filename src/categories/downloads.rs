@@ -153,9 +153,7 @@ pub fn scan(
 
     // Build result
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     Ok(result)
@@ -216,9 +214,7 @@ pub fn scan_with_progress(
     files_with_sizes.sort_by(|a, b| b.1.cmp(&a.1));
     files_with_sizes.truncate(MAX_RESULTS);
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     let _ = root;
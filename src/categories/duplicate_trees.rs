@@ -0,0 +1,214 @@
+//! Duplicate directory trees for the duplicates category.
+//!
+//! Whole copied folders (extracted SDKs, photo imports, project snapshots)
+//! would otherwise show up as one duplicate group per file. Each folder gets a
+//! bottom-up Merkle hash from the names and content hashes of its entries, so
+//! identical trees hash the same, and only the largest identical trees are
+//! reported: a copy inside a folder that's itself a copy is left out (the file
+//! groups still cover copies of it elsewhere).
+//!
+//! A folder only gets a hash when everything in it was compared. Entries the
+//! walk skipped (hidden files, excluded or build folders, unreadable folders)
+//! would be deleted along with the tree, so a folder holding any of them, or a
+//! file with a unique size or content, never matches another folder.
+
+use blake3::Hasher;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Content hash used for empty files (they're never hashed)
+const EMPTY_FILE_HASH: &str = "empty";
+
+/// What the duplicates walk saw of the scanned folders
+#[derive(Debug, Default)]
+pub struct TreeListing {
+    pub dirs: Vec<PathBuf>,
+    /// Every file the walk kept, with its size
+    pub files: Vec<(PathBuf, u64)>,
+    /// Folders with entries that weren't compared
+    pub incomplete: HashSet<PathBuf>,
+}
+
+/// Identical directory trees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeGroup {
+    pub hash: String,
+    /// Total size of the files in one tree
+    pub size: u64,
+    /// Number of files in one tree
+    pub file_count: usize,
+    pub paths: Vec<PathBuf>,
+}
+
+/// Hash, total size and file count of a folder
+struct DirSummary {
+    hash: Option<String>,
+    size: u64,
+    file_count: usize,
+}
+
+/// Find the largest identical directory trees
+///
+/// `file_hashes` holds the full content hash of every file that has one; files
+/// without a hash have unique contents.
+pub fn find_duplicate_trees(
+    listing: &TreeListing,
+    file_hashes: &HashMap<PathBuf, String>,
+) -> Vec<TreeGroup> {
+    let known_dirs: HashSet<&Path> = listing.dirs.iter().map(PathBuf::as_path).collect();
+
+    // Entries of each folder: (name, is_dir, path, size for files)
+    let mut children: HashMap<&Path, Vec<(String, bool, &Path, u64)>> = HashMap::new();
+    for (path, size) in &listing.files {
+        if let Some(parent) = path.parent() {
+            children
+                .entry(parent)
+                .or_default()
+                .push((entry_name(path), false, path, *size));
+        }
+    }
+    for dir in &listing.dirs {
+        if let Some(parent) = dir.parent().filter(|p| known_dirs.contains(p)) {
+            children
+                .entry(parent)
+                .or_default()
+                .push((entry_name(dir), true, dir, 0));
+        }
+    }
+
+    // Deepest folders first, so every subfolder is summarized before its parent
+    let mut dirs: Vec<&Path> = known_dirs.iter().copied().collect();
+    dirs.sort_by_key(|d| std::cmp::Reverse(d.components().count()));
+
+    let mut summaries: HashMap<&Path, DirSummary> = HashMap::new();
+    for dir in dirs {
+        let mut entries = children.remove(dir).unwrap_or_default();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut complete = !listing.incomplete.contains(dir);
+        let mut size = 0;
+        let mut file_count = 0;
+        let mut hasher = Hasher::new();
+        for (name, is_dir, path, file_size) in entries {
+            let hash = if is_dir {
+                let summary = &summaries[path];
+                size += summary.size;
+                file_count += summary.file_count;
+                summary.hash.as_deref()
+            } else {
+                size += file_size;
+                file_count += 1;
+                if file_size == 0 {
+                    Some(EMPTY_FILE_HASH)
+                } else {
+                    file_hashes.get(path).map(String::as_str)
+                }
+            };
+            match hash {
+                Some(hash) => {
+                    let kind = if is_dir { "d" } else { "f" };
+                    hasher.update(format!("{}\0{}\0{}\n", kind, name, hash).as_bytes());
+                }
+                None => complete = false,
+            }
+        }
+
+        let hash = complete.then(|| hasher.finalize().to_hex().to_string());
+        summaries.insert(
+            dir,
+            DirSummary {
+                hash,
+                size,
+                file_count,
+            },
+        );
+    }
+
+    let mut by_hash: HashMap<&str, Vec<&Path>> = HashMap::new();
+    for (dir, summary) in &summaries {
+        if let Some(hash) = summary.hash.as_deref().filter(|_| summary.size > 0) {
+            by_hash.entry(hash).or_default().push(dir);
+        }
+    }
+    let is_duplicated = |dir: &Path| match summaries.get(dir).and_then(|s| s.hash.as_deref()) {
+        Some(hash) => by_hash.get(hash).is_some_and(|dirs| dirs.len() > 1),
+        None => false,
+    };
+
+    let mut groups = Vec::new();
+    for (hash, dirs) in &by_hash {
+        // Copies inside a copied folder are covered by the folder's group
+        let mut paths: Vec<PathBuf> = dirs
+            .iter()
+            .filter(|dir| !dir.parent().is_some_and(is_duplicated))
+            .map(|dir| dir.to_path_buf())
+            .collect();
+        if paths.len() < 2 {
+            continue;
+        }
+        paths.sort();
+        let summary = &summaries[paths[0].as_path()];
+        groups.push(TreeGroup {
+            hash: hash.to_string(),
+            size: summary.size,
+            file_count: summary.file_count,
+            paths,
+        });
+    }
+    groups
+}
+
+fn entry_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_only_the_largest_identical_trees() {
+        let p = |s: &str| PathBuf::from(s);
+        let dirs = [
+            "/home",
+            "/home/a",
+            "/home/a/sdk",
+            "/home/b",
+            "/home/b/sdk",
+            "/home/c",
+            "/home/c/sdk",
+        ];
+        let files = [
+            ("/home/a/readme.txt", "r"),
+            ("/home/a/sdk/lib.so", "l"),
+            ("/home/b/readme.txt", "r"),
+            ("/home/b/sdk/lib.so", "l"),
+            ("/home/c/notes.txt", "n"),
+            ("/home/c/sdk/lib.so", "l"),
+        ];
+        let mut listing = TreeListing {
+            dirs: dirs.iter().map(|d| p(d)).collect(),
+            files: files.iter().map(|(f, _)| (p(f), 10)).collect(),
+            incomplete: HashSet::new(),
+        };
+        let hashes: HashMap<PathBuf, String> = files
+            .iter()
+            .filter(|(f, _)| *f != "/home/c/notes.txt")
+            .map(|(f, h)| (p(f), h.to_string()))
+            .collect();
+
+        // a ≡ b, which covers a/sdk ≡ b/sdk; c/sdk alone is left to the file groups
+        let groups = find_duplicate_trees(&listing, &hashes);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths, vec![p("/home/a"), p("/home/b")]);
+        assert_eq!((groups[0].size, groups[0].file_count), (20, 2));
+
+        // Something in b wasn't compared: only the sdk folders match
+        listing.incomplete.insert(p("/home/b"));
+        let groups = find_duplicate_trees(&listing, &hashes);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths.len(), 3);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::config::{Config, DuplicatesConfig};
+use super::duplicate_trees::{self, TreeListing};
+use super::similar_images::{self, ImageInfo};
+use crate::config::{Config, DuplicatesConfig, KeepRule};
+use crate::dedupe::{self, DedupeMode};
 use crate::output::CategoryResult;
+use crate::scan_cache::context::HashCache;
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::utils;
 use anyhow::{Context, Result};
@@ -7,7 +11,8 @@ use blake3::Hasher;
 use jwalk::WalkDir;
 use memmap2::MmapOptions;
 use rayon::prelude::*;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::{Path, PathBuf};
@@ -45,77 +50,276 @@ fn is_duplicate_filename(filename: &str) -> bool {
         || filename.contains("_Copy")
 }
 
+/// A file of a duplicate group that's kept, and the keep rule that chose it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeptFile {
+    pub path: PathBuf,
+    pub rule: String,
+}
+
+/// Rule reported when no rule chose a file and the first one by name is kept
+pub const FALLBACK_RULE: &str = "first-alphabetically";
+
+/// What the paths of a duplicate group are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateKind {
+    File,
+    /// Identical directory trees (see [`duplicate_trees`])
+    Directory,
+    /// Images that look the same but differ in size or format (see [`similar_images`])
+    #[serde(rename = "similar-images")]
+    SimilarImages,
+}
+
 /// Duplicate file group
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DuplicateGroup {
+    pub kind: DuplicateKind,
     pub hash: String,
+    /// Size of one copy (all the files in one tree for directories, the kept
+    /// image for similar images)
     pub size: u64,
+    /// Files in one copy (1 for file groups)
+    pub file_count: usize,
     pub paths: Vec<PathBuf>,
+    /// The files that are kept (the one redundant copies are linked to first)
+    pub kept: Vec<KeptFile>,
+    /// How the redundant copies can be removed (hardlinks need one volume,
+    /// reflinks a filesystem that supports them)
+    pub dedupe_modes: Vec<DedupeMode>,
+    /// Resolution and size of each image (similar images only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImageInfo>,
 }
 
-/// Result for duplicate file detection
-#[derive(Debug, Clone, Default)]
-pub struct DuplicatesResult {
-    pub groups: Vec<DuplicateGroup>,
-    pub total_wasted: u64, // Size of all duplicates minus one copy each
+impl DuplicateGroup {
+    /// Split the group into the files that are kept and the redundant copies
+    ///
+    /// Redundant copies with a lower suffix number come first (" (1)" before " (2)").
+    pub fn split(&self) -> (Vec<&PathBuf>, Vec<&PathBuf>) {
+        let kept: Vec<&PathBuf> = self.kept.iter().map(|k| &k.path).collect();
+        let mut redundant: Vec<&PathBuf> =
+            self.paths.iter().filter(|p| !kept.contains(p)).collect();
+        redundant.sort_by_key(|p| extract_suffix_number(&file_name(p)));
+        (kept, redundant)
+    }
+
+    /// What's kept instead of the redundant copies, e.g. "keeps photo.jpg (keep-newest)"
+    pub fn keep_note(&self) -> Option<String> {
+        self.kept
+            .first()
+            .map(|k| format!("keeps {} ({})", file_name(&k.path), k.rule))
+    }
+
+    /// Size of one of the group's paths (similar images differ in size)
+    pub fn copy_size(&self, path: &Path) -> u64 {
+        self.image(path).map_or(self.size, |image| image.size)
+    }
+
+    /// The resolution and size of one of the group's images
+    pub fn image(&self, path: &Path) -> Option<&ImageInfo> {
+        self.images.iter().find(|image| image.path == path)
+    }
+
+    /// Space taken by the redundant copies
+    pub fn wasted(&self) -> u64 {
+        self.split().1.into_iter().map(|p| self.copy_size(p)).sum()
+    }
+
+    /// Whether the redundant copies can be removed with `mode`
+    pub fn supports(&self, mode: DedupeMode) -> bool {
+        self.dedupe_modes.contains(&mode)
+    }
 }
 
-impl DuplicatesResult {
-    /// Convert to CategoryResult for compatibility with existing output system
-    /// Only includes duplicate files, not the original (keeps files without duplicate patterns like "(1)")
-    pub fn to_category_result(&self) -> CategoryResult {
-        let mut paths = Vec::new();
-        for group in &self.groups {
-            // Separate files into those with duplicate patterns and those without
-            let mut originals: Vec<&PathBuf> = Vec::new();
-            let mut duplicates: Vec<&PathBuf> = Vec::new();
-
-            for path in &group.paths {
-                let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                if is_duplicate_filename(filename) {
-                    duplicates.push(path);
-                } else {
-                    originals.push(path);
-                }
-            }
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string()
+}
 
-            // Sort duplicates by suffix number (lower numbers first, to delete higher copies first)
-            duplicates.sort_by(|a, b| {
-                let a_name = a.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                let b_name = b.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                let a_num = extract_suffix_number(a_name);
-                let b_num = extract_suffix_number(b_name);
-                a_num.cmp(&b_num)
+/// Choose the files of a duplicate group to keep by evaluating `policy` in order
+///
+/// At least one file is always kept: when no rule kept one, the first file by
+/// name is ([`FALLBACK_RULE`]), preferring names that don't look like copies.
+/// The file that narrowing rules settle on comes first.
+pub fn choose_kept(paths: &[PathBuf], policy: &[KeepRule]) -> Vec<KeptFile> {
+    let mut kept: Vec<KeptFile> = Vec::new();
+    let keep = |kept: &mut Vec<KeptFile>, path: &PathBuf, rule: &str| {
+        if !kept.iter().any(|k| &k.path == path) {
+            kept.push(KeptFile {
+                path: path.clone(),
+                rule: rule.to_string(),
             });
+        }
+    };
+    // Names that don't look like copies come first, then the lowest copy number
+    let by_name = |a: &&PathBuf, b: &&PathBuf| {
+        let (a_name, b_name) = (file_name(a), file_name(b));
+        is_duplicate_filename(&a_name)
+            .cmp(&is_duplicate_filename(&b_name))
+            .then_with(|| extract_suffix_number(&a_name).cmp(&extract_suffix_number(&b_name)))
+            .then_with(|| a_name.cmp(&b_name))
+            .then_with(|| a.cmp(b))
+    };
 
-            // If there are files with duplicate patterns, ONLY flag those as duplicates
-            // (never flag files without patterns like "lto_meeting_isa.ogg")
-            if !duplicates.is_empty() {
-                // Add all files with duplicate patterns
-                for path in &duplicates {
-                    paths.push((*path).clone());
+    // Candidates left by the narrowing rules, and the last rule that narrowed them
+    let mut candidates: Vec<&PathBuf> = paths.iter().collect();
+    let mut narrowed_by: Option<&str> = None;
+
+    for rule in policy {
+        match rule {
+            KeepRule::KeepOriginals => {
+                let (originals, copies): (Vec<&PathBuf>, Vec<&PathBuf>) = paths
+                    .iter()
+                    .partition(|p| !is_duplicate_filename(&file_name(p)));
+                // Without anything named like a copy, every file is an "original"
+                if !copies.is_empty() {
+                    for path in originals {
+                        keep(&mut kept, path, rule.name());
+                    }
                 }
-            } else {
-                // No files have duplicate patterns - fall back to keeping one and flagging the rest
-                // Sort originals alphabetically and keep the first one
-                originals.sort_by(|a, b| {
-                    let a_name = a.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    let b_name = b.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    a_name.cmp(b_name)
-                });
-
-                // Add all but the first one
-                for path in originals.iter().skip(1) {
-                    paths.push((*path).clone());
+            }
+            KeepRule::NeverDeleteUnder { roots } => {
+                let roots: Vec<PathBuf> =
+                    roots.iter().filter_map(|r| utils::expand_path(r)).collect();
+                // A duplicate folder that contains a root would take it along when deleted
+                for path in paths
+                    .iter()
+                    .filter(|p| roots.iter().any(|r| p.starts_with(r) || r.starts_with(p)))
+                {
+                    keep(&mut kept, path, rule.name());
+                }
+            }
+            KeepRule::KeepOnePerDirectory => {
+                let mut by_dir: HashMap<Option<&Path>, Vec<&PathBuf>> = HashMap::new();
+                for path in paths {
+                    by_dir.entry(path.parent()).or_default().push(path);
+                }
+                let mut dirs: Vec<_> = by_dir.into_values().collect();
+                dirs.sort();
+                for mut files in dirs {
+                    if !files.iter().any(|p| kept.iter().any(|k| &k.path == *p)) {
+                        files.sort_by(by_name);
+                        keep(&mut kept, files[0], rule.name());
+                    }
                 }
             }
+            _ if candidates.len() > 1 => {
+                candidates = narrow(candidates, rule);
+                narrowed_by = Some(rule.name());
+            }
+            _ => {}
+        }
+    }
+
+    let rule = match narrowed_by {
+        Some(rule) => rule,
+        None if kept.is_empty() => FALLBACK_RULE,
+        None => return kept,
+    };
+    candidates.sort_by(by_name);
+    if let Some(&chosen) = candidates.first() {
+        match kept.iter().position(|k| &k.path == chosen) {
+            Some(idx) => {
+                let file = kept.remove(idx);
+                kept.insert(0, file);
+            }
+            None => kept.insert(
+                0,
+                KeptFile {
+                    path: chosen.clone(),
+                    rule: rule.to_string(),
+                },
+            ),
         }
+    }
+    kept
+}
+
+/// The candidates a narrowing rule prefers (all of them when it has no preference)
+fn narrow<'a>(candidates: Vec<&'a PathBuf>, rule: &KeepRule) -> Vec<&'a PathBuf> {
+    let modified = |p: &PathBuf| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let best_by = |key: &dyn Fn(&PathBuf) -> Option<i128>| {
+        let keys: Vec<Option<i128>> = candidates.iter().map(|p| key(p)).collect();
+        match keys.iter().flatten().max() {
+            Some(best) => candidates
+                .iter()
+                .zip(&keys)
+                .filter(|(_, k)| k.as_ref() == Some(best))
+                .map(|(p, _)| *p)
+                .collect(),
+            None => candidates.clone(),
+        }
+    };
+    let nanos = |t: std::time::SystemTime| match t.duration_since(std::time::UNIX_EPOCH) {
+        Ok(d) => d.as_nanos() as i128,
+        Err(e) => -(e.duration().as_nanos() as i128),
+    };
+
+    match rule {
+        KeepRule::KeepNewest => best_by(&|p| modified(p).map(nanos)),
+        KeepRule::KeepOldest => best_by(&|p| modified(p).map(|t| -nanos(t))),
+        KeepRule::KeepShortestPath => best_by(&|p| Some(-(p.as_os_str().len() as i128))),
+        KeepRule::KeepHighestResolution => best_by(&|p| {
+            image::image_dimensions(p)
+                .ok()
+                .map(|(w, h)| w as i128 * h as i128)
+        }),
+        KeepRule::KeepLargest => best_by(&|p| std::fs::metadata(p).ok().map(|m| m.len() as i128)),
+        KeepRule::KeepInPreferredDirectories { dirs } => dirs
+            .iter()
+            .filter_map(|d| utils::expand_path(d))
+            .map(|dir| {
+                candidates
+                    .iter()
+                    .filter(|p| p.starts_with(&dir))
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .find(|preferred| !preferred.is_empty())
+            .unwrap_or(candidates),
+        _ => candidates,
+    }
+}
+
+/// Result for duplicate file detection
+#[derive(Debug, Clone, Default)]
+pub struct DuplicatesResult {
+    pub groups: Vec<DuplicateGroup>,
+    pub total_wasted: u64, // Size of all duplicates minus the kept files
+}
+
+impl DuplicatesResult {
+    /// Convert to CategoryResult for compatibility with existing output system
+    /// Only includes the redundant copies, not the files the keep policy keeps
+    ///
+    /// Copies of similar images are left out too: they aren't identical, so
+    /// they're only cleaned once their group is confirmed on its own.
+    pub fn to_category_result(&self) -> CategoryResult {
+        let identical = || {
+            self.groups
+                .iter()
+                .filter(|group| group.kind != DuplicateKind::SimilarImages)
+        };
+        let path_sizes: Vec<(PathBuf, u64)> = identical()
+            .flat_map(|group| {
+                group
+                    .split()
+                    .1
+                    .into_iter()
+                    .map(|path| (path.clone(), group.copy_size(path)))
+            })
+            .collect();
 
         CategoryResult {
-            items: paths.len(),
-            size_bytes: self.total_wasted,
-            paths,
+            items: path_sizes.len(),
+            size_bytes: identical().map(DuplicateGroup::wasted).sum(),
+            paths: path_sizes.iter().map(|(path, _)| path.clone()).collect(),
+            path_sizes: path_sizes.into_iter().collect(),
+            ..Default::default()
         }
     }
 
@@ -138,16 +342,20 @@ impl DuplicatesResult {
 /// Note: This function uses default config. For custom configuration, use `scan_with_config`.
 pub fn scan(root: &Path) -> Result<DuplicatesResult> {
     let default_config = Config::default();
-    scan_with_config(root, None, &default_config)
+    scan_with_config(root, None, &default_config, None)
 }
 
 /// Scan for duplicate files with configuration
+///
+/// With `hashes`, partial and full hashes of files unchanged since an earlier
+/// scan are taken from the scan cache instead of reading the files again.
 pub fn scan_with_config(
     root: &Path,
     config: Option<&DuplicatesConfig>,
     global_config: &Config,
+    hashes: Option<&HashCache>,
 ) -> Result<DuplicatesResult> {
-    scan_with_config_internal(root, config, global_config, None)
+    scan_with_config_internal(root, config, global_config, hashes, None)
 }
 
 /// Scan for duplicate files with configuration + TUI progress updates (current file path).
@@ -155,16 +363,18 @@ pub fn scan_with_config_with_progress(
     root: &Path,
     config: Option<&DuplicatesConfig>,
     global_config: &Config,
+    hashes: Option<&HashCache>,
     tx: &Sender<ScanProgressEvent>,
 ) -> Result<DuplicatesResult> {
     let reporter = Arc::new(ScanPathReporter::new("Duplicates", tx.clone(), 75));
-    scan_with_config_internal(root, config, global_config, Some(reporter))
+    scan_with_config_internal(root, config, global_config, hashes, Some(reporter))
 }
 
 fn scan_with_config_internal(
     root: &Path,
     config: Option<&DuplicatesConfig>,
     global_config: &Config,
+    hashes: Option<&HashCache>,
     reporter: Option<Arc<ScanPathReporter>>,
 ) -> Result<DuplicatesResult> {
     let mut result = DuplicatesResult::default();
@@ -206,11 +416,19 @@ fn scan_with_config_internal(
     let buffer_size = config
         .map(|c| c.buffer_size_bytes)
         .unwrap_or(8 * 1024 * 1024); // Default 8MB
+    let keep_policy = config
+        .map(|c| c.keep.clone())
+        .unwrap_or_else(|| DuplicatesConfig::default().keep);
+
+    // Folders and files seen by the walk, for finding duplicate directory trees
+    let mut listing = TreeListing::default();
 
     // Step 1: Group files by size (using parallel directory traversal)
     let size_groups: HashMap<u64, Vec<PathBuf>> = {
         use std::sync::Mutex;
         let groups: Mutex<HashMap<u64, Vec<PathBuf>>> = Mutex::new(HashMap::new());
+        // Folders with entries the walk skipped (filled from the walker's threads)
+        let incomplete: Arc<Mutex<HashSet<PathBuf>>> = Arc::default();
 
         // Clone config for thread-safe access (jwalk requires 'static)
         let config_arc = Arc::new(global_config.clone());
@@ -225,19 +443,27 @@ fn scan_with_config_internal(
             let config_clone = Arc::clone(&config_arc);
 
             let reporter_for_walk = reporter.as_ref().map(Arc::clone);
+            let incomplete_for_walk = Arc::clone(&incomplete);
 
             WalkDir::new(&dir)
                 .max_depth(MAX_DEPTH)
                 .follow_links(false) // CRITICAL: Prevents infinite loops on Windows junctions/reparse points
+                // Dot entries are skipped below instead, so their folder can be marked incomplete
+                .skip_hidden(false)
                 .parallelism(jwalk::Parallelism::RayonDefaultPool {
                     busy_timeout: std::time::Duration::from_secs(1),
                 })
-                .process_read_dir(move |_depth, _path, _read_dir_state, children| {
+                .process_read_dir(move |depth, parent, _read_dir_state, children| {
+                    let before = children.len();
                     // Filter out directories we don't want to descend into
                     children.retain(|entry| {
                         if let Ok(ref e) = entry {
                             let path = e.path();
 
+                            if depth.is_some() && e.file_name().to_string_lossy().starts_with('.') {
+                                return false;
+                            }
+
                             // Skip symlinks and Windows reparse points (junctions, OneDrive placeholders)
                             // This prevents infinite loops on Windows systems with OneDrive folders
                             if utils::should_skip_entry(&path) {
@@ -278,6 +504,14 @@ fn scan_with_config_internal(
                         }
                         true
                     });
+                    // depth is None for the walk root itself
+                    let skipped = children.len() < before || children.iter().any(|e| e.is_err());
+                    if depth.is_some() && skipped {
+                        incomplete_for_walk
+                            .lock()
+                            .unwrap()
+                            .insert(parent.to_path_buf());
+                    }
                 })
                 .into_iter()
                 .filter_map(|e| e.ok())
@@ -287,32 +521,47 @@ fn scan_with_config_internal(
                         reporter.emit_path(&path);
                     }
 
-                    // Only process files
-                    if !entry.file_type().is_file() {
-                        return;
-                    }
-
-                    // Skip hidden files
-                    if utils::is_hidden(&path) {
-                        return;
-                    }
-
-                    // Skip system paths
-                    if utils::is_system_path(&path) {
+                    if entry.file_type().is_dir() {
+                        // Unreadable, or too deep to be read
+                        if entry.read_children_error.is_some() || entry.depth >= MAX_DEPTH {
+                            listing.incomplete.insert(path.clone());
+                        }
+                        listing.dirs.push(path);
                         return;
                     }
 
-                    // Get file size from cached metadata
-                    if let Ok(metadata) = entry.metadata() {
-                        let size = metadata.len();
-                        if size > 0 {
-                            let mut groups = groups.lock().unwrap();
-                            groups.entry(size).or_default().push(path);
+                    // Only process files, skipping hidden files and system paths
+                    let size = if entry.file_type().is_file()
+                        && !utils::is_hidden(&path)
+                        && !utils::is_system_path(&path)
+                    {
+                        // Get file size from cached metadata
+                        entry.metadata().ok().map(|m| m.len())
+                    } else {
+                        None
+                    };
+
+                    match size {
+                        Some(size) => {
+                            listing.files.push((path.clone(), size));
+                            if size > 0 {
+                                let mut groups = groups.lock().unwrap();
+                                groups.entry(size).or_default().push(path);
+                            }
+                        }
+                        // Not compared, so its folder can't be compared as a whole
+                        None => {
+                            if let Some(parent) = path.parent() {
+                                listing.incomplete.insert(parent.to_path_buf());
+                            }
                         }
                     }
                 });
         }
 
+        listing
+            .incomplete
+            .extend(incomplete.lock().unwrap().drain());
         groups.into_inner().unwrap()
     };
 
@@ -336,9 +585,13 @@ fn scan_with_config_internal(
                     if let Some(ref reporter) = reporter_for_partial {
                         reporter.emit_path(path);
                     }
-                    compute_partial_hash(path, buffer_size)
-                        .ok()
-                        .map(|hash| (hash, path.clone()))
+                    let compute = || compute_partial_hash(path, buffer_size);
+                    match hashes {
+                        Some(hashes) => hashes.partial_hash(path, compute),
+                        None => compute(),
+                    }
+                    .ok()
+                    .map(|hash| (hash, path.clone()))
                 })
                 .collect::<Vec<_>>()
         })
@@ -375,21 +628,55 @@ fn scan_with_config_internal(
                     if let Some(ref reporter) = reporter_for_full {
                         reporter.emit_path(path);
                     }
-                    compute_full_hash(path, memmap_threshold_clone, buffer_size_clone)
-                        .ok()
-                        .map(|hash| (hash, path.clone()))
+                    let compute =
+                        || compute_full_hash(path, memmap_threshold_clone, buffer_size_clone);
+                    match hashes {
+                        Some(hashes) => hashes.full_hash(path, compute),
+                        None => compute(),
+                    }
+                    .ok()
+                    .map(|hash| (hash, path.clone()))
                 })
                 .collect::<Vec<_>>()
         })
         .collect();
 
     // Group by full hash
+    let file_hashes: HashMap<PathBuf, String> = full_hash_results
+        .iter()
+        .map(|(hash, path)| (path.clone(), hash.clone()))
+        .collect();
     for (full_hash, path) in full_hash_results {
         full_hash_groups.entry(full_hash).or_default().push(path);
     }
 
+    // Identical directory trees are reported as one group each
+    let mut redundant_dirs: HashSet<PathBuf> = HashSet::new();
+    for tree in duplicate_trees::find_duplicate_trees(&listing, &file_hashes) {
+        let group = DuplicateGroup {
+            kind: DuplicateKind::Directory,
+            kept: choose_kept(&tree.paths, &keep_policy),
+            hash: tree.hash,
+            size: tree.size,
+            file_count: tree.file_count,
+            paths: tree.paths,
+            // Folders can't be linked
+            dedupe_modes: vec![DedupeMode::Delete],
+            images: Vec::new(),
+        };
+        redundant_dirs.extend(group.split().1.into_iter().cloned());
+        result.total_wasted += group.wasted();
+        result.groups.push(group);
+    }
+
     // Build duplicate groups
     for (hash, paths) in full_hash_groups {
+        // Paths that are already hardlinks of each other are one file, and
+        // copies inside a redundant folder go with the folder
+        let paths: Vec<PathBuf> = distinct_files(paths)
+            .into_iter()
+            .filter(|p| !p.ancestors().any(|a| redundant_dirs.contains(a)))
+            .collect();
         // Only include groups with duplicates (2+ files)
         if paths.len() < 2 {
             continue;
@@ -402,20 +689,46 @@ fn scan_with_config_internal(
             .map(|m| m.len())
             .unwrap_or(0);
 
-        // Calculate wasted space: (n-1) * size (keep one copy)
-        let wasted = (paths.len() - 1) as u64 * size;
+        let kept = choose_kept(&paths, &keep_policy);
+        let dedupe_modes = dedupe::eligible_modes(&paths);
+        let group = DuplicateGroup {
+            kind: DuplicateKind::File,
+            hash,
+            size,
+            file_count: 1,
+            paths,
+            kept,
+            dedupe_modes,
+            images: Vec::new(),
+        };
 
-        result.groups.push(DuplicateGroup { hash, size, paths });
+        result.total_wasted += group.wasted();
+        result.groups.push(group);
+    }
 
-        result.total_wasted += wasted;
+    // Optionally, images that look the same without being identical
+    if let Some(max_distance) = config
+        .filter(|c| c.similar_images)
+        .map(|c| c.similar_image_distance)
+    {
+        let image_groups = similar_image_groups(
+            &listing,
+            &result.groups,
+            &redundant_dirs,
+            &keep_policy,
+            max_distance,
+            reporter.as_deref(),
+        );
+        for group in image_groups {
+            result.total_wasted += group.wasted();
+            result.groups.push(group);
+        }
     }
 
     // Sort groups by wasted space descending
-    result.groups.sort_by(|a, b| {
-        let wasted_a = (a.paths.len() - 1) as u64 * a.size;
-        let wasted_b = (b.paths.len() - 1) as u64 * b.size;
-        wasted_b.cmp(&wasted_a)
-    });
+    result
+        .groups
+        .sort_by_key(|group| std::cmp::Reverse(group.wasted()));
 
     // Limit to top groups
     result.groups.truncate(MAX_GROUPS);
@@ -423,6 +736,78 @@ fn scan_with_config_internal(
     Ok(result)
 }
 
+/// Group the images the walk found that look the same
+///
+/// Redundant copies of identical files and folders are already reported, so
+/// only the kept file of an identical group takes part. The kept image is
+/// chosen by the keep policy, with the highest resolution and then the biggest
+/// file breaking its ties.
+fn similar_image_groups(
+    listing: &TreeListing,
+    groups: &[DuplicateGroup],
+    redundant_dirs: &HashSet<PathBuf>,
+    keep_policy: &[KeepRule],
+    max_distance: u32,
+    reporter: Option<&ScanPathReporter>,
+) -> Vec<DuplicateGroup> {
+    let reported: HashSet<&PathBuf> = groups.iter().flat_map(|g| g.split().1).collect();
+    let candidates: Vec<&(PathBuf, u64)> = listing
+        .files
+        .iter()
+        .filter(|(path, size)| {
+            *size > 0
+                && similar_images::is_image(path)
+                && !reported.contains(path)
+                && !path.ancestors().any(|a| redundant_dirs.contains(a))
+        })
+        .collect();
+
+    // Images that can't be decoded or are near-uniform are left out
+    let images: Vec<ImageInfo> = candidates
+        .par_iter()
+        .filter_map(|(path, size)| {
+            if let Some(reporter) = reporter {
+                reporter.emit_path(path);
+            }
+            similar_images::image_info(path, *size).ok().flatten()
+        })
+        .collect();
+
+    let mut policy = keep_policy.to_vec();
+    policy.extend([KeepRule::KeepHighestResolution, KeepRule::KeepLargest]);
+
+    let mut image_groups = Vec::new();
+    for cluster in similar_images::cluster(&images, max_distance) {
+        // Hardlinks of one image look the same too, but are one file
+        let paths = distinct_files(cluster.iter().map(|&i| images[i].path.clone()).collect());
+        if paths.len() < 2 {
+            continue;
+        }
+        let mut members: Vec<ImageInfo> = cluster
+            .iter()
+            .map(|&i| images[i].clone())
+            .filter(|image| paths.contains(&image.path))
+            .collect();
+        members.sort_by(|a, b| a.path.cmp(&b.path));
+        let paths: Vec<PathBuf> = members.iter().map(|image| image.path.clone()).collect();
+
+        let kept = choose_kept(&paths, &policy);
+        let kept_image = members.iter().find(|image| image.path == kept[0].path);
+        image_groups.push(DuplicateGroup {
+            kind: DuplicateKind::SimilarImages,
+            hash: format!("{:016x}", members[0].hash),
+            size: kept_image.map_or(0, |image| image.size),
+            file_count: 1,
+            paths,
+            kept,
+            // The files differ, so they can't be linked
+            dedupe_modes: vec![DedupeMode::Delete],
+            images: members,
+        });
+    }
+    image_groups
+}
+
 /// Compute partial hash (first 4KB) of a file
 fn compute_partial_hash(path: &Path, _buffer_size: usize) -> Result<String> {
     let file =
@@ -502,12 +887,29 @@ fn compute_full_hash_memmap(path: &Path, _file_size: u64) -> Result<String> {
     Ok(format!("{}", hash.to_hex()))
 }
 
+/// The file the redundant copies of a group are linked to, and those copies
+pub fn link_target(group: &DuplicateGroup) -> Option<(&PathBuf, Vec<&PathBuf>)> {
+    let (kept, redundant) = group.split();
+    kept.first().map(|target| (*target, redundant))
+}
+
+/// Drop paths that point at a file already in the list (hardlinks)
+fn distinct_files(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    paths
+        .into_iter()
+        .filter(|path| match same_file::Handle::from_path(path) {
+            Ok(handle) => seen.insert(handle),
+            Err(_) => true,
+        })
+        .collect()
+}
+
 /// Clean (delete) duplicate files by moving them to the Recycle Bin
-/// Keeps the first file in each group, deletes the rest
+/// Keeps the files the keep policy chose, deletes the rest
 pub fn clean(groups: &[DuplicateGroup], permanent: bool) -> Result<()> {
     for group in groups {
-        // Keep the first file, delete the rest
-        for path in group.paths.iter().skip(1) {
+        for path in group.split().1 {
             if permanent {
                 std::fs::remove_file(path)
                     .with_context(|| format!("Failed to permanently delete: {}", path.display()))?;
@@ -519,3 +921,140 @@ pub fn clean(groups: &[DuplicateGroup], permanent: bool) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_keep_policy_rules_are_evaluated_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |p: &str| dir.path().join(p);
+        std::fs::create_dir_all(path("music")).unwrap();
+        std::fs::create_dir_all(path("backup")).unwrap();
+        let files = [
+            path("backup/song.mp3"),
+            path("music/song.mp3"),
+            path("music/song (1).mp3"),
+        ];
+        for (age, file) in files.iter().enumerate() {
+            std::fs::write(file, b"la la").unwrap();
+            let modified = SystemTime::now() - Duration::from_secs(3600 * age as u64);
+            File::options()
+                .write(true)
+                .open(file)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+        let kept = |policy: &[KeepRule]| -> Vec<(PathBuf, String)> {
+            choose_kept(&files, policy)
+                .into_iter()
+                .map(|k| (k.path, k.rule))
+                .collect()
+        };
+
+        // The default keeps every file not named like a copy
+        assert_eq!(
+            kept(&DuplicatesConfig::default().keep),
+            vec![
+                (files[0].clone(), "keep-originals".to_string()),
+                (files[1].clone(), "keep-originals".to_string()),
+            ]
+        );
+        // Ties are left to the next rule, then to the file name
+        let preferred = KeepRule::KeepInPreferredDirectories {
+            dirs: vec![path("music").display().to_string()],
+        };
+        assert_eq!(
+            kept(&[preferred.clone(), KeepRule::KeepOldest]),
+            vec![(files[2].clone(), "keep-oldest".to_string())]
+        );
+        assert_eq!(
+            kept(&[preferred, KeepRule::KeepShortestPath]),
+            vec![(files[1].clone(), "keep-shortest-path".to_string())]
+        );
+        assert_eq!(
+            kept(&[
+                KeepRule::NeverDeleteUnder {
+                    roots: vec![path("backup").display().to_string()],
+                },
+                KeepRule::KeepNewest,
+            ]),
+            vec![(files[0].clone(), "never-delete-under".to_string())]
+        );
+        assert_eq!(
+            kept(&[KeepRule::KeepOnePerDirectory]),
+            vec![
+                (files[0].clone(), "keep-one-per-directory".to_string()),
+                (files[1].clone(), "keep-one-per-directory".to_string()),
+            ]
+        );
+        assert_eq!(kept(&[])[0].1, FALLBACK_RULE);
+        // A duplicate folder holding a protected root is protected too
+        let trees = [path("a"), path("b")];
+        assert_eq!(
+            choose_kept(
+                &trees,
+                &[KeepRule::NeverDeleteUnder {
+                    roots: vec![path("b/Originals").display().to_string()],
+                }]
+            ),
+            vec![KeptFile {
+                path: trees[1].clone(),
+                rule: "never-delete-under".to_string(),
+            }]
+        );
+        // No file named like a copy: the originals rule has nothing to keep
+        assert_eq!(
+            choose_kept(&files[..2], &DuplicatesConfig::default().keep),
+            vec![KeptFile {
+                path: files[0].clone(),
+                rule: FALLBACK_RULE.to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_similar_image_groups_dont_merge_chains() {
+        let dir = tempfile::tempdir().unwrap();
+        // A 9×8 image whose difference hash is `hash`: each bit says whether
+        // brightness goes up between two neighbours
+        let save = |name: &str, hash: u64| {
+            let image = image::GrayImage::from_fn(9, 8, |x, y| {
+                let level = (0..x).fold(128i32, |level, i| {
+                    let bit = 63 - (y * 8 + i);
+                    level + if hash >> bit & 1 == 1 { 10 } else { -10 }
+                });
+                image::Luma([level as u8])
+            });
+            let path = dir.path().join(name);
+            image.save(&path).unwrap();
+            assert_eq!(similar_images::dhash(&image), hash);
+            let size = std::fs::metadata(&path).unwrap().len();
+            (path, size)
+        };
+        // a looks like b and b like c, but a and c are 8 bits apart
+        let listing = TreeListing {
+            dirs: vec![dir.path().to_path_buf()],
+            files: vec![save("a.png", 0), save("b.png", 0x0f), save("c.png", 0xff)],
+            incomplete: HashSet::new(),
+        };
+
+        let groups = similar_image_groups(&listing, &[], &HashSet::new(), &[], 5, None);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].paths,
+            vec![listing.files[0].0.clone(), listing.files[1].0.clone()]
+        );
+
+        // Look-alikes aren't cleaned along with identical copies
+        let result = DuplicatesResult {
+            total_wasted: groups[0].wasted(),
+            groups,
+        }
+        .to_category_result();
+        assert_eq!((result.items, result.size_bytes), (0, 0));
+    }
+}
@@ -1,13 +1,16 @@
 use crate::config::Config;
 use crate::git;
-use crate::output::{CategoryResult, OutputMode};
+use crate::output::{CacheCandidates, CategoryResult, OutputMode};
 use crate::project;
+use crate::scan_cache::{CacheContext, DirSignature};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::theme::Theme;
 use crate::utils;
 use anyhow::{Context, Result};
 use bytesize;
 use jwalk::WalkDir;
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
@@ -16,6 +19,9 @@ use std::sync::{Arc, Mutex};
 /// Maximum number of results to return
 const MAX_RESULTS: usize = 100;
 
+/// Category the directory signatures are recorded under in the scan cache
+const CACHE_CATEGORY: &str = "large";
+
 /// Scan for large files in user directories
 ///
 /// Optimizations:
@@ -25,11 +31,16 @@ const MAX_RESULTS: usize = 100;
 /// - Sorts by size descending (biggest first)
 /// - Limits to top 100 results
 /// - Detects file types (video, archive, disk image, etc.)
+/// - With a scan cache, skips directories unchanged since the last session and
+///   reuses the files over the threshold found in them, reported or not (a
+///   smaller file that grows past the threshold in such a directory is only
+///   found once the directory is walked again)
 pub fn scan(
     _root: &Path,
     min_size_bytes: u64,
     config: &Config,
     output_mode: OutputMode,
+    cache: Option<&CacheContext>,
 ) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
 
@@ -44,8 +55,8 @@ pub fn scan(
         );
     }
 
-    // Collect files with sizes for sorting
-    let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
+    // Collect every file over the threshold, reported or not
+    let mut candidates: Vec<(PathBuf, u64)> = Vec::new();
 
     for dir in &user_dirs {
         if output_mode != OutputMode::Quiet {
@@ -54,18 +65,16 @@ pub fn scan(
         scan_directory(
             dir,
             min_size_bytes,
-            &mut files_with_sizes,
+            &mut candidates,
             config,
             output_mode,
             None,
+            cache,
         )?;
     }
 
-    // Sort by size descending (biggest first)
-    files_with_sizes.sort_by(|a, b| b.1.cmp(&a.1));
-
-    // Limit results
-    files_with_sizes.truncate(MAX_RESULTS);
+    // Biggest reportable files first, limited to MAX_RESULTS
+    let files_with_sizes = top_reportable(&candidates, config);
 
     // Show found files
     if output_mode != OutputMode::Quiet && !files_with_sizes.is_empty() {
@@ -103,10 +112,9 @@ pub fn scan(
 
     // Build result
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
+    result.cache_candidates = Some(cache_candidates(candidates, min_size_bytes));
 
     Ok(result)
 }
@@ -118,31 +126,30 @@ pub fn scan_with_progress(
     config: &Config,
     output_mode: OutputMode,
     tx: &Sender<ScanProgressEvent>,
+    cache: Option<&CacheContext>,
 ) -> Result<CategoryResult> {
     let reporter = Arc::new(ScanPathReporter::new("Large Files", tx.clone(), 75));
 
     let mut result = CategoryResult::default();
     let user_dirs = get_user_directories()?;
-    let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
+    let mut candidates: Vec<(PathBuf, u64)> = Vec::new();
 
     for dir in &user_dirs {
         scan_directory(
             dir,
             min_size_bytes,
-            &mut files_with_sizes,
+            &mut candidates,
             config,
             output_mode,
             Some(Arc::clone(&reporter)),
+            cache,
         )?;
     }
 
-    files_with_sizes.sort_by(|a, b| b.1.cmp(&a.1));
-    files_with_sizes.truncate(MAX_RESULTS);
-    for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+    for (path, size) in top_reportable(&candidates, config) {
+        result.push(path, size);
     }
+    result.cache_candidates = Some(cache_candidates(candidates, min_size_bytes));
 
     let _ = root;
     Ok(result)
@@ -165,7 +172,29 @@ fn get_user_directories() -> Result<Vec<PathBuf>> {
     Ok(dirs)
 }
 
-/// Scan a directory for large files with parallel traversal
+/// The biggest of `candidates` that should be reported, at most [`MAX_RESULTS`]
+fn top_reportable(candidates: &[(PathBuf, u64)], config: &Config) -> Vec<(PathBuf, u64)> {
+    let mut sorted = candidates.to_vec();
+    sorted.sort_by_key(|(_, size)| Reverse(*size));
+    sorted
+        .into_iter()
+        .filter(|(path, _)| is_reportable(path, config))
+        .take(MAX_RESULTS)
+        .collect()
+}
+
+/// Every file over the threshold, stored so skipped directories can replay them
+fn cache_candidates(candidates: Vec<(PathBuf, u64)>, min_size_bytes: u64) -> CacheCandidates {
+    CacheCandidates {
+        min_size: min_size_bytes,
+        paths: candidates.into_iter().map(|(path, _)| path).collect(),
+    }
+}
+
+/// Collect the files over the threshold in a directory with parallel traversal
+///
+/// Files aren't filtered with [`is_reportable`] yet: the cache stores all of
+/// them, so a file hidden today can be reported once its project goes quiet.
 fn scan_directory(
     dir: &Path,
     min_size_bytes: u64,
@@ -173,17 +202,41 @@ fn scan_directory(
     config: &Config,
     _output_mode: OutputMode,
     reporter: Option<Arc<ScanPathReporter>>,
+    cache: Option<&CacheContext>,
 ) -> Result<()> {
     if !dir.exists() {
         return Ok(());
     }
 
+    // Last session's files only cover this threshold if they were found with
+    // the same or a lower one
+    let can_skip = cache
+        .and_then(|c| c.cached_min_size)
+        .is_some_and(|size| size <= min_size_bytes);
+
+    // Nothing changed in the whole directory since the last session
+    if let Some(cache) = cache.filter(|_| can_skip) {
+        if cache.unchanged_dir(dir).is_some() {
+            files.extend(cached_large_files(cache, dir, min_size_bytes));
+            return Ok(());
+        }
+    }
+
     const MAX_DEPTH: usize = 20;
 
+    // For the scan cache: directories skipped as unchanged, entry counts of the
+    // directories walked and the size of the files directly in each of them
+    let pruner = cache.filter(|_| can_skip).map(CacheContext::dir_pruner);
+    let skipped_dirs: Arc<Mutex<Vec<DirSignature>>> = Arc::new(Mutex::new(Vec::new()));
+    let skipped_dirs_clone = Arc::clone(&skipped_dirs);
+    let entry_counts: Arc<Mutex<HashMap<PathBuf, u64>>> = Arc::new(Mutex::new(HashMap::new()));
+    let entry_counts_clone = Arc::clone(&entry_counts);
+    let dir_sizes: Mutex<HashMap<PathBuf, u64>> = Mutex::new(HashMap::new());
+    let dir_sizes_ref = &dir_sizes;
+    let record_dirs = cache.is_some();
+
     // Clone config for thread-safe access (jwalk requires 'static)
     let config_clone = Arc::new(config.clone());
-    // Clone again for the second closure
-    let config_clone_for_each = Arc::clone(&config_clone);
 
     // Use Arc<Mutex<>> for thread-safe collection that can be shared
     let found_files: Arc<Mutex<Vec<(PathBuf, u64)>>> = Arc::new(Mutex::new(Vec::new()));
@@ -197,7 +250,15 @@ fn scan_directory(
         .parallelism(jwalk::Parallelism::RayonDefaultPool {
             busy_timeout: std::time::Duration::from_secs(1),
         })
-        .process_read_dir(move |_depth, _path, _read_dir_state, children| {
+        .process_read_dir(move |depth, path, _read_dir_state, children| {
+            // jwalk reads the root's parent first (depth None) to yield the root itself
+            if record_dirs && depth.is_some() {
+                entry_counts_clone
+                    .lock()
+                    .unwrap()
+                    .insert(path.to_path_buf(), children.len() as u64);
+            }
+
             // Filter out directories we don't want to descend into
             children.retain(|entry| {
                 if let Ok(ref e) = entry {
@@ -241,6 +302,12 @@ fn scan_directory(
                         if config_clone.is_excluded(&path) {
                             return false;
                         }
+
+                        // Unchanged since the last session: its results come from the cache
+                        if let Some(sig) = pruner.as_ref().and_then(|p| p.unchanged(&path)) {
+                            skipped_dirs_clone.lock().unwrap().push(sig);
+                            return false;
+                        }
                     }
                 }
                 true
@@ -265,28 +332,20 @@ fn scan_directory(
                 Err(_) => return,
             };
 
-            // Check size threshold first (fast)
-            if metadata.len() < min_size_bytes {
-                return;
+            if record_dirs {
+                if let Some(parent) = path.parent() {
+                    *dir_sizes_ref
+                        .lock()
+                        .unwrap()
+                        .entry(parent.to_path_buf())
+                        .or_default() += metadata.len();
+                }
             }
 
-            // Skip hidden files
-            if utils::is_hidden(&path) {
+            if metadata.len() < min_size_bytes {
                 return;
             }
 
-            // Skip files in active projects (using CACHED git lookup for performance)
-            // This is a critical safety check to prevent deletion of files from projects
-            // the user is actively working on
-            // PERFORMANCE: Both find_git_root_cached and is_project_active are now cached
-            if let Some(project_root) = git::find_git_root_cached(&path) {
-                // Use project_age_days from config (defaults to 14 if not set)
-                let project_age_days = config_clone_for_each.thresholds.project_age_days;
-                if let Ok(true) = project::is_project_active(&project_root, project_age_days) {
-                    return; // Skip files from active projects
-                }
-            }
-
             let mut files_guard = found_files_clone.lock().unwrap();
             files_guard.push((path, metadata.len()));
         });
@@ -296,9 +355,100 @@ fn scan_directory(
     let mut collected = Arc::try_unwrap(found_files).unwrap().into_inner().unwrap();
     files.append(&mut collected);
 
+    if let Some(cache) = cache {
+        let skipped = std::mem::take(&mut *skipped_dirs.lock().unwrap());
+        for sig in &skipped {
+            files.extend(cached_large_files(cache, &sig.path, min_size_bytes));
+        }
+        let entry_counts = std::mem::take(&mut *entry_counts.lock().unwrap());
+        record_dir_signatures(
+            cache,
+            entry_counts,
+            dir_sizes.into_inner().unwrap(),
+            &skipped,
+        );
+    }
+
     Ok(())
 }
 
+/// Whether a file over the size threshold should be reported
+fn is_reportable(path: &Path, config: &Config) -> bool {
+    // Skip hidden files
+    if utils::is_hidden(path) {
+        return false;
+    }
+
+    // Skip files in active projects (using CACHED git lookup for performance)
+    // This is a critical safety check to prevent deletion of files from projects
+    // the user is actively working on
+    // PERFORMANCE: Both find_git_root_cached and is_project_active are now cached
+    if let Some(project_root) = git::find_git_root_cached(path) {
+        // Use project_age_days from config (defaults to 14 if not set)
+        let project_age_days = config.thresholds.project_age_days;
+        if let Ok(true) = project::is_project_active(&project_root, project_age_days) {
+            return false; // Skip files from active projects
+        }
+    }
+
+    true
+}
+
+/// Last session's files over the threshold inside a directory that was skipped as unchanged
+///
+/// Sizes are read again and the threshold re-applied, since a file may have
+/// been rewritten or the threshold raised. A file that was below the threshold
+/// last time isn't among them even if it has grown since: it's found once its
+/// directory is walked again, or with `--force-full`.
+fn cached_large_files(
+    cache: &CacheContext,
+    dir: &Path,
+    min_size_bytes: u64,
+) -> Vec<(PathBuf, u64)> {
+    cache
+        .cached_paths_under(dir)
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(path).ok()?;
+            (metadata.is_file() && metadata.len() >= min_size_bytes)
+                .then(|| (path.clone(), metadata.len()))
+        })
+        .collect()
+}
+
+/// Record the signature of every directory walked, with the size of everything below it
+fn record_dir_signatures(
+    cache: &CacheContext,
+    entry_counts: HashMap<PathBuf, u64>,
+    mut sizes: HashMap<PathBuf, u64>,
+    skipped: &[DirSignature],
+) {
+    for sig in skipped {
+        if let Some(parent) = sig.path.parent() {
+            *sizes.entry(parent.to_path_buf()).or_default() += sig.total_size;
+        }
+    }
+
+    // Deepest first, so a directory's total is complete before it's added to its parent
+    let mut dirs: Vec<(PathBuf, u64)> = entry_counts.into_iter().collect();
+    dirs.sort_by_key(|(dir, _)| Reverse(dir.components().count()));
+    for (dir, entry_count) in dirs {
+        let total_size = sizes.get(&dir).copied().unwrap_or(0);
+        if let Some(parent) = dir.parent() {
+            *sizes.entry(parent.to_path_buf()).or_default() += total_size;
+        }
+        let Ok(mtime) = std::fs::metadata(&dir).and_then(|m| m.modified()) else {
+            continue;
+        };
+        let sig = DirSignature {
+            path: dir,
+            mtime,
+            entry_count,
+            total_size,
+        };
+        let _ = cache.record_dir(&sig, CACHE_CATEGORY);
+    }
+}
+
 /// Get file type for a large file (for display purposes)
 pub fn get_file_type(path: &Path) -> utils::FileType {
     utils::detect_file_type(path)
@@ -310,3 +460,198 @@ pub fn clean(path: &Path) -> Result<()> {
         .with_context(|| format!("Failed to delete large file: {}", path.display()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan_cache::context::CacheContextBuilder;
+    use std::collections::HashSet;
+    use std::fs;
+
+    fn walk(dir: &Path, cache: &CacheContext) -> Vec<(PathBuf, u64)> {
+        let mut files = Vec::new();
+        let config = Config::default();
+        scan_directory(
+            dir,
+            100,
+            &mut files,
+            &config,
+            OutputMode::Quiet,
+            None,
+            Some(cache),
+        )
+        .unwrap();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_skips_directories_unchanged_since_last_session() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("Videos");
+        fs::create_dir_all(root.join("trip/raw")).unwrap();
+        fs::create_dir_all(root.join("clips")).unwrap();
+        fs::write(root.join("trip/raw/day1.mov"), [0u8; 300]).unwrap();
+        fs::write(root.join("clips/small.mp4"), [0u8; 20]).unwrap();
+
+        let recorded: Arc<Mutex<HashMap<PathBuf, DirSignature>>> = Arc::default();
+        let sink = Arc::clone(&recorded);
+        let first = CacheContextBuilder::new()
+            .with_record_dir_fn(Box::new(move |sig, _| {
+                sink.lock().unwrap().insert(sig.path.clone(), sig.clone());
+                Ok(())
+            }))
+            .build();
+        let found = walk(&root, &first);
+        assert_eq!(found, vec![(root.join("trip/raw/day1.mov"), 300)]);
+        let recorded = recorded.lock().unwrap().clone();
+        assert_eq!(recorded[&root].total_size, 320);
+        assert_eq!(recorded[&root.join("trip")].total_size, 300);
+
+        let next_session = || {
+            CacheContextBuilder::new()
+                .with_cached_paths(HashSet::from([root.join("trip/raw/day1.mov")]))
+                .with_cached_min_size(Some(100))
+                .with_cached_dirs(recorded.clone())
+                .build()
+        };
+        let second = next_session();
+        assert_eq!(walk(&root, &second), found);
+        assert_eq!(second.skipped_dirs(), 1);
+
+        // A new file at the top: only the top level is walked again
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs::write(root.join("new.iso"), [0u8; 200]).unwrap();
+        let third = next_session();
+        assert_eq!(walk(&root, &third).len(), 2);
+        assert_eq!(third.skipped_dirs(), 2);
+    }
+
+    #[test]
+    fn test_finds_large_file_added_in_nested_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("Videos");
+        fs::create_dir_all(root.join("trip/raw")).unwrap();
+        fs::write(root.join("trip/raw/day1.mov"), [0u8; 300]).unwrap();
+
+        let recorded: Arc<Mutex<HashMap<PathBuf, DirSignature>>> = Arc::default();
+        let sink = Arc::clone(&recorded);
+        let first = CacheContextBuilder::new()
+            .with_record_dir_fn(Box::new(move |sig, _| {
+                sink.lock().unwrap().insert(sig.path.clone(), sig.clone());
+                Ok(())
+            }))
+            .build();
+        walk(&root, &first);
+
+        // Only trip/raw changes: Videos and trip keep their listings
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs::write(root.join("trip/raw/day2.mov"), [0u8; 400]).unwrap();
+        let second = CacheContextBuilder::new()
+            .with_cached_paths(HashSet::from([root.join("trip/raw/day1.mov")]))
+            .with_cached_min_size(Some(100))
+            .with_cached_dirs(recorded.lock().unwrap().clone())
+            .build();
+        assert_eq!(
+            walk(&root, &second),
+            vec![
+                (root.join("trip/raw/day1.mov"), 300),
+                (root.join("trip/raw/day2.mov"), 400)
+            ]
+        );
+        assert_eq!(second.skipped_dirs(), 0);
+    }
+
+    #[test]
+    fn test_rewritten_file_in_skipped_directory_keeps_its_new_size() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("Videos");
+        fs::create_dir_all(root.join("trip")).unwrap();
+        fs::write(root.join("trip/day1.mov"), [0u8; 300]).unwrap();
+
+        let recorded: Arc<Mutex<HashMap<PathBuf, DirSignature>>> = Arc::default();
+        let sink = Arc::clone(&recorded);
+        let first = CacheContextBuilder::new()
+            .with_record_dir_fn(Box::new(move |sig, _| {
+                sink.lock().unwrap().insert(sig.path.clone(), sig.clone());
+                Ok(())
+            }))
+            .build();
+        walk(&root, &first);
+
+        // Rewriting a file leaves its directory's listing alone
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs::write(root.join("trip/day1.mov"), [0u8; 500]).unwrap();
+        let second = CacheContextBuilder::new()
+            .with_cached_paths(HashSet::from([root.join("trip/day1.mov")]))
+            .with_cached_min_size(Some(100))
+            .with_cached_dirs(recorded.lock().unwrap().clone())
+            .build();
+        assert_eq!(
+            walk(&root, &second),
+            vec![(root.join("trip/day1.mov"), 500)]
+        );
+        assert_eq!(second.skipped_dirs(), 1);
+    }
+
+    #[test]
+    fn test_skipped_directories_refill_results_past_the_limit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("Videos");
+        fs::create_dir_all(root.join("big")).unwrap();
+        fs::create_dir_all(root.join("smaller")).unwrap();
+        for i in 0..MAX_RESULTS {
+            fs::write(root.join(format!("big/{}.mov", i)), vec![0u8; 1000 + i]).unwrap();
+        }
+        for i in 0..50 {
+            fs::write(root.join(format!("smaller/{}.mov", i)), vec![0u8; 200 + i]).unwrap();
+        }
+
+        let recorded: Arc<Mutex<HashMap<PathBuf, DirSignature>>> = Arc::default();
+        let sink = Arc::clone(&recorded);
+        let first = CacheContextBuilder::new()
+            .with_record_dir_fn(Box::new(move |sig, _| {
+                sink.lock().unwrap().insert(sig.path.clone(), sig.clone());
+                Ok(())
+            }))
+            .build();
+        let candidates = walk(&root, &first);
+        assert_eq!(candidates.len(), MAX_RESULTS + 50);
+        // Only the big files are reported, but every candidate is stored
+        let reported = top_reportable(&candidates, &Config::default());
+        assert!(reported
+            .iter()
+            .all(|(path, _)| path.starts_with(root.join("big"))));
+        let stored = cache_candidates(candidates, 100).paths;
+
+        // Cleaning the top ten only changes the folder they were in
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        for (path, _) in &reported[..10] {
+            fs::remove_file(path).unwrap();
+        }
+        let next_session = |min_size| {
+            CacheContextBuilder::new()
+                .with_cached_paths(stored.iter().cloned().collect())
+                .with_cached_min_size(Some(min_size))
+                .with_cached_dirs(recorded.lock().unwrap().clone())
+                .build()
+        };
+        let second = next_session(100);
+        let candidates = walk(&root, &second);
+        assert_eq!(second.skipped_dirs(), 1);
+        let reported = top_reportable(&candidates, &Config::default());
+        assert_eq!(reported.len(), MAX_RESULTS);
+        assert_eq!(
+            reported
+                .iter()
+                .filter(|(path, _)| path.starts_with(root.join("smaller")))
+                .count(),
+            10
+        );
+
+        // Stored with a higher threshold: nothing is skipped
+        let third = next_session(150);
+        assert_eq!(walk(&root, &third).len(), MAX_RESULTS + 40);
+        assert_eq!(third.skipped_dirs(), 0);
+    }
+}
@@ -0,0 +1,509 @@
+//! Platform-aware resolution of well-known per-user directories.
+//!
+//! The cache, app_cache and browser categories describe where things live as a
+//! list of [`Rule`]s per tool: environment overrides first, then per-OS defaults
+//! relative to a [`Base`] directory (`%LOCALAPPDATA%`, `$XDG_CACHE_HOME`,
+//! `~/Library/Caches`, ...). [`LocationResolver`] turns those rules into concrete
+//! paths and remembers which [`LocationSource`] produced each one.
+
+use crate::utils;
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Operating system family the resolver targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OsFamily {
+    Windows,
+    MacOs,
+    /// Linux and the other Unix-likes that follow the XDG base directory spec
+    Linux,
+}
+
+impl OsFamily {
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            OsFamily::Windows
+        } else if cfg!(target_os = "macos") {
+            OsFamily::MacOs
+        } else {
+            OsFamily::Linux
+        }
+    }
+}
+
+/// Which operating systems a [`Rule`] applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Any,
+    Windows,
+    MacOs,
+    Linux,
+    /// Everything except Windows
+    Unix,
+}
+
+impl Os {
+    fn matches(self, family: OsFamily) -> bool {
+        match self {
+            Os::Any => true,
+            Os::Windows => family == OsFamily::Windows,
+            Os::MacOs => family == OsFamily::MacOs,
+            Os::Linux => family == OsFamily::Linux,
+            Os::Unix => family != OsFamily::Windows,
+        }
+    }
+}
+
+/// Per-user base directories that default locations are relative to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    /// `%USERPROFILE%` / `$HOME`
+    Home,
+    /// `%LOCALAPPDATA%`, `~/Library/Caches`, or `$XDG_CACHE_HOME` (default `~/.cache`)
+    UserCache,
+    /// `%APPDATA%`, `~/Library/Application Support`, or `$XDG_CONFIG_HOME` (default `~/.config`)
+    UserConfig,
+    /// `%LOCALAPPDATA%`, `~/Library/Application Support`, or `$XDG_DATA_HOME` (default `~/.local/share`)
+    UserData,
+    /// `$XDG_CACHE_HOME` (default `~/.cache`) on every Unix, for tools that ignore
+    /// `~/Library/Caches` on macOS; nothing on Windows
+    XdgCache,
+    /// `%LOCALAPPDATA%` (Windows only)
+    LocalAppData,
+    /// `%APPDATA%` (Windows only)
+    AppData,
+}
+
+/// One way of locating a tool's directory, tried in declaration order
+#[derive(Debug, Clone, Copy)]
+pub enum Rule {
+    /// `$VAR` joined with a sub-path, if the variable is set
+    Env(&'static str, &'static [&'static str]),
+    /// Each entry of a path-list variable (like `GOPATH`) joined with a sub-path
+    EnvList(&'static str, &'static [&'static str]),
+    /// Each entry of a comma-separated variable (like conda's `CONDA_PKGS_DIRS`)
+    /// joined with a sub-path
+    EnvCsv(&'static str, &'static [&'static str]),
+    /// A sub-path of a base directory, on matching operating systems only
+    Under(Os, Base, &'static [&'static str]),
+}
+
+/// A named directory and the rules that locate it
+#[derive(Debug, Clone, Copy)]
+pub struct KnownLocation {
+    pub name: &'static str,
+    pub rules: &'static [Rule],
+}
+
+/// What produced a resolved path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocationSource {
+    /// A tool-specific environment override such as `PIP_CACHE_DIR`
+    Env(&'static str),
+    XdgCacheHome,
+    XdgConfigHome,
+    XdgDataHome,
+    LocalAppData,
+    AppData,
+    /// The built-in default below the home directory
+    Home,
+}
+
+impl fmt::Display for LocationSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocationSource::Env(var) => write!(f, "${}", var),
+            LocationSource::XdgCacheHome => f.write_str("$XDG_CACHE_HOME"),
+            LocationSource::XdgConfigHome => f.write_str("$XDG_CONFIG_HOME"),
+            LocationSource::XdgDataHome => f.write_str("$XDG_DATA_HOME"),
+            LocationSource::LocalAppData => f.write_str("%LOCALAPPDATA%"),
+            LocationSource::AppData => f.write_str("%APPDATA%"),
+            LocationSource::Home => f.write_str("default location"),
+        }
+    }
+}
+
+/// A [`KnownLocation`] resolved to a concrete path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedLocation {
+    pub name: &'static str,
+    pub path: PathBuf,
+    pub source: LocationSource,
+}
+
+/// Resolves [`KnownLocation`]s against a snapshot of the environment
+#[derive(Debug, Clone)]
+pub struct LocationResolver {
+    family: OsFamily,
+    vars: HashMap<String, OsString>,
+    home: Option<PathBuf>,
+}
+
+impl LocationResolver {
+    /// Resolver for the current OS and process environment
+    pub fn from_env() -> Self {
+        let mut resolver = Self::new(OsFamily::current(), env::vars_os());
+        if resolver.home.is_none() {
+            resolver.home = utils::home_dir();
+        }
+        resolver
+    }
+
+    /// Resolver for an explicit OS family and set of variables
+    pub fn new<I, K, V>(family: OsFamily, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<OsString>,
+        V: Into<OsString>,
+    {
+        let vars: HashMap<String, OsString> = vars
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let value: OsString = v.into();
+                let key = k.into().into_string().ok()?;
+                (!value.is_empty()).then_some((key, value))
+            })
+            .collect();
+        let home_vars: &[&str] = match family {
+            OsFamily::Windows => &["USERPROFILE", "HOME"],
+            _ => &["HOME"],
+        };
+        let home = home_vars
+            .iter()
+            .find_map(|name| vars.get(*name))
+            .map(PathBuf::from);
+        Self { family, vars, home }
+    }
+
+    pub fn family(&self) -> OsFamily {
+        self.family
+    }
+
+    /// The home directory the locations are resolved against
+    pub fn home(&self) -> Option<&Path> {
+        self.home.as_deref()
+    }
+
+    /// A variable of the environment snapshot
+    pub fn env_var(&self, name: &str) -> Option<&OsStr> {
+        self.vars.get(name).map(OsString::as_os_str)
+    }
+
+    fn var(&self, name: &str) -> Option<PathBuf> {
+        self.vars.get(name).map(PathBuf::from)
+    }
+
+    /// An XDG variable, which the spec says to ignore unless it's absolute
+    fn xdg_var(&self, name: &str) -> Option<PathBuf> {
+        self.var(name).filter(|p| p.is_absolute())
+    }
+
+    fn under_home(&self, parts: &[&str]) -> Option<(PathBuf, LocationSource)> {
+        self.home
+            .as_ref()
+            .map(|home| (join_all(home, parts), LocationSource::Home))
+    }
+
+    /// Resolve a base directory for the resolver's OS
+    pub fn base(&self, base: Base) -> Option<(PathBuf, LocationSource)> {
+        let local_appdata = || {
+            self.var("LOCALAPPDATA")
+                .map(|p| (p, LocationSource::LocalAppData))
+        };
+        let appdata = || self.var("APPDATA").map(|p| (p, LocationSource::AppData));
+        let xdg = |var: &str, source: LocationSource, default: &[&str]| {
+            self.xdg_var(var)
+                .map(|p| (p, source))
+                .or_else(|| self.under_home(default))
+        };
+
+        match (base, self.family) {
+            (Base::Home, _) => self.under_home(&[]),
+            (Base::XdgCache, OsFamily::MacOs | OsFamily::Linux) => {
+                xdg("XDG_CACHE_HOME", LocationSource::XdgCacheHome, &[".cache"])
+            }
+            (Base::LocalAppData, OsFamily::Windows) => local_appdata(),
+            (Base::AppData, OsFamily::Windows) => appdata(),
+            (Base::LocalAppData | Base::AppData | Base::XdgCache, _) => None,
+
+            (Base::UserCache | Base::UserData, OsFamily::Windows) => local_appdata(),
+            (Base::UserConfig, OsFamily::Windows) => appdata(),
+
+            (Base::UserCache, OsFamily::MacOs) => self.under_home(&["Library", "Caches"]),
+            (Base::UserConfig | Base::UserData, OsFamily::MacOs) => {
+                self.under_home(&["Library", "Application Support"])
+            }
+
+            (Base::UserCache, OsFamily::Linux) => {
+                xdg("XDG_CACHE_HOME", LocationSource::XdgCacheHome, &[".cache"])
+            }
+            (Base::UserConfig, OsFamily::Linux) => xdg(
+                "XDG_CONFIG_HOME",
+                LocationSource::XdgConfigHome,
+                &[".config"],
+            ),
+            (Base::UserData, OsFamily::Linux) => xdg(
+                "XDG_DATA_HOME",
+                LocationSource::XdgDataHome,
+                &[".local", "share"],
+            ),
+        }
+    }
+
+    /// Every path the location's rules produce on this OS, in rule order
+    pub fn candidates(&self, location: &KnownLocation) -> Vec<ResolvedLocation> {
+        let mut out: Vec<ResolvedLocation> = Vec::new();
+        let mut push = |path: PathBuf, source: LocationSource| {
+            if !out.iter().any(|r| r.path == path) {
+                out.push(ResolvedLocation {
+                    name: location.name,
+                    path,
+                    source,
+                });
+            }
+        };
+
+        for rule in location.rules {
+            match *rule {
+                Rule::Env(var, parts) => {
+                    if let Some(value) = self.var(var) {
+                        push(join_all(&value, parts), LocationSource::Env(var));
+                    }
+                }
+                Rule::EnvList(var, parts) => {
+                    if let Some(value) = self.vars.get(var) {
+                        for entry in env::split_paths(value).filter(|p| !p.as_os_str().is_empty()) {
+                            push(join_all(&entry, parts), LocationSource::Env(var));
+                        }
+                    }
+                }
+                Rule::EnvCsv(var, parts) => {
+                    if let Some(value) = self.vars.get(var) {
+                        let value = value.to_string_lossy();
+                        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                            push(join_all(Path::new(entry), parts), LocationSource::Env(var));
+                        }
+                    }
+                }
+                Rule::Under(os, base, parts) => {
+                    if !os.matches(self.family) {
+                        continue;
+                    }
+                    if let Some((dir, source)) = self.base(base) {
+                        push(join_all(&dir, parts), source);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Every candidate that exists on disk, in rule order
+    ///
+    /// A tool can have data in more than one place, e.g. a `PIP_CACHE_DIR`
+    /// override set after pip had already filled `~/.cache/pip`.
+    pub fn resolve(&self, location: &KnownLocation) -> Vec<ResolvedLocation> {
+        self.candidates(location)
+            .into_iter()
+            .filter(|r| r.path.exists())
+            .collect()
+    }
+
+    /// Resolve a table of locations, dropping missing ones and repeated paths
+    pub fn resolve_all(&self, locations: &[KnownLocation]) -> Vec<ResolvedLocation> {
+        let mut out: Vec<ResolvedLocation> = Vec::new();
+        for location in locations {
+            for resolved in self.resolve(location) {
+                if !out.iter().any(|r| r.path == resolved.path) {
+                    out.push(resolved);
+                }
+            }
+        }
+        out
+    }
+
+    /// Directories applications keep their per-user state in
+    ///
+    /// `%LOCALAPPDATA%` and `%APPDATA%` on Windows, the config directory elsewhere.
+    pub fn app_roots(&self) -> Vec<(PathBuf, LocationSource)> {
+        let mut roots: Vec<(PathBuf, LocationSource)> = Vec::new();
+        for base in [Base::LocalAppData, Base::UserConfig] {
+            if let Some((path, source)) = self.base(base) {
+                if !roots.iter().any(|(p, _)| *p == path) {
+                    roots.push((path, source));
+                }
+            }
+        }
+        roots
+    }
+}
+
+fn join_all(base: &Path, parts: &[&str]) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for part in parts {
+        path.push(part);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIP: KnownLocation = KnownLocation {
+        name: "pip",
+        rules: &[
+            Rule::Env("PIP_CACHE_DIR", &[]),
+            Rule::Under(Os::Windows, Base::LocalAppData, &["pip", "cache"]),
+            Rule::Under(Os::Unix, Base::UserCache, &["pip"]),
+        ],
+    };
+
+    #[test]
+    fn test_linux_prefers_env_then_xdg_then_home() {
+        let resolver = LocationResolver::new(
+            OsFamily::Linux,
+            [
+                ("HOME", "/home/dev"),
+                ("XDG_CACHE_HOME", "/var/cache/dev"),
+                ("PIP_CACHE_DIR", "/scratch/pip"),
+            ],
+        );
+        let candidates = resolver.candidates(&PIP);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].path, PathBuf::from("/scratch/pip"));
+        assert_eq!(candidates[0].source, LocationSource::Env("PIP_CACHE_DIR"));
+        assert_eq!(
+            candidates[1].path,
+            PathBuf::from("/var/cache/dev").join("pip")
+        );
+        assert_eq!(candidates[1].source, LocationSource::XdgCacheHome);
+
+        // Relative XDG values are ignored, falling back to ~/.cache
+        let resolver = LocationResolver::new(
+            OsFamily::Linux,
+            [("HOME", "/home/dev"), ("XDG_CACHE_HOME", "relative/cache")],
+        );
+        let candidates = resolver.candidates(&PIP);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(
+            candidates[0].path,
+            PathBuf::from("/home/dev/.cache").join("pip")
+        );
+        assert_eq!(candidates[0].source, LocationSource::Home);
+    }
+
+    #[test]
+    fn test_windows_and_macos_defaults() {
+        let windows = LocationResolver::new(
+            OsFamily::Windows,
+            [
+                ("USERPROFILE", "C:/Users/dev"),
+                ("LOCALAPPDATA", "C:/Users/dev/AppData/Local"),
+                ("XDG_CACHE_HOME", "/ignored"),
+            ],
+        );
+        let candidates = windows.candidates(&PIP);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(
+            candidates[0].path,
+            PathBuf::from("C:/Users/dev/AppData/Local")
+                .join("pip")
+                .join("cache")
+        );
+        assert_eq!(candidates[0].source, LocationSource::LocalAppData);
+
+        let macos = LocationResolver::new(OsFamily::MacOs, [("HOME", "/Users/dev")]);
+        let candidates = macos.candidates(&PIP);
+        assert_eq!(
+            candidates[0].path,
+            PathBuf::from("/Users/dev")
+                .join("Library")
+                .join("Caches")
+                .join("pip")
+        );
+        assert!(macos.base(Base::LocalAppData).is_none());
+    }
+
+    #[test]
+    fn test_resolve_returns_every_existing_candidate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let home = tmp.path().join("home");
+        std::fs::create_dir_all(home.join(".cache").join("pip")).unwrap();
+        let gopath_a = tmp.path().join("go-a");
+        let gopath_b = tmp.path().join("go-b");
+        let gopath_c = tmp.path().join("go-c");
+        std::fs::create_dir_all(gopath_b.join("pkg").join("mod")).unwrap();
+        std::fs::create_dir_all(gopath_c.join("pkg").join("mod")).unwrap();
+
+        let vars: Vec<(&str, OsString)> = vec![
+            ("HOME", home.clone().into_os_string()),
+            ("PIP_CACHE_DIR", tmp.path().join("missing").into_os_string()),
+            (
+                "GOPATH",
+                env::join_paths([&gopath_a, &gopath_b, &gopath_c]).unwrap(),
+            ),
+        ];
+        let resolver = LocationResolver::new(OsFamily::Linux, vars);
+
+        let pip = resolver.resolve(&PIP);
+        assert_eq!(pip.len(), 1);
+        assert_eq!(pip[0].path, home.join(".cache").join("pip"));
+        assert_eq!(pip[0].source, LocationSource::Home);
+
+        // An override doesn't hide data left at the default location
+        std::fs::create_dir_all(tmp.path().join("missing")).unwrap();
+        let paths: Vec<PathBuf> = resolver.resolve(&PIP).into_iter().map(|r| r.path).collect();
+        assert_eq!(
+            paths,
+            vec![tmp.path().join("missing"), home.join(".cache").join("pip")]
+        );
+
+        let go = KnownLocation {
+            name: "Go",
+            rules: &[Rule::EnvList("GOPATH", &["pkg", "mod"])],
+        };
+        let resolved = resolver.resolve(&go);
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].path, gopath_b.join("pkg").join("mod"));
+        assert_eq!(resolved[1].path, gopath_c.join("pkg").join("mod"));
+        assert_eq!(resolved[1].source, LocationSource::Env("GOPATH"));
+    }
+
+    #[test]
+    fn test_xdg_cache_base_and_comma_lists() {
+        let conda = KnownLocation {
+            name: "conda",
+            rules: &[
+                Rule::EnvCsv("CONDA_PKGS_DIRS", &[]),
+                Rule::Under(Os::Unix, Base::XdgCache, &["conda"]),
+            ],
+        };
+        let macos = LocationResolver::new(
+            OsFamily::MacOs,
+            [
+                ("HOME", "/Users/dev"),
+                ("CONDA_PKGS_DIRS", "/opt/pkgs, /scratch/pkgs"),
+            ],
+        );
+        let paths: Vec<PathBuf> = macos
+            .candidates(&conda)
+            .into_iter()
+            .map(|r| r.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/opt/pkgs"),
+                PathBuf::from("/scratch/pkgs"),
+                PathBuf::from("/Users/dev/.cache").join("conda"),
+            ]
+        );
+
+        let windows = LocationResolver::new(OsFamily::Windows, [("USERPROFILE", "C:/Users/dev")]);
+        assert!(windows.base(Base::XdgCache).is_none());
+    }
+}
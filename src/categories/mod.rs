@@ -3,14 +3,22 @@ pub mod applications;
 pub mod browser;
 pub mod build;
 pub mod cache;
+pub mod cache_strategy;
+pub mod custom;
 pub mod downloads;
+pub mod duplicate_trees;
 pub mod duplicates;
 pub mod empty;
 pub mod event_logs;
 pub mod large;
+pub mod locations;
 pub mod old;
+pub mod registry;
+pub mod similar_images;
 pub mod startup;
 pub mod system;
+pub mod target_sweep;
 pub mod temp;
+pub mod toolchains;
 pub mod trash;
 pub mod windows_update;
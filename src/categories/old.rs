@@ -109,9 +109,7 @@ pub fn scan(
 
     // Build result
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     Ok(result)
@@ -164,9 +162,7 @@ pub fn scan_with_progress(
 
     let mut result = CategoryResult::default();
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     let _ = root;
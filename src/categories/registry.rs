@@ -0,0 +1,855 @@
+//! Registry of cleanup categories.
+//!
+//! Every category is described once by a [`CategoryDescriptor`]: its stable id,
+//! display name, emoji, risk, platform and the [`Scanner`] that finds and cleans
+//! its files. The scanner, output, cleaner and TUI iterate the registry instead of
+//! naming categories one by one, and library users can add their own scanners with
+//! [`CategoryRegistry::register`].
+
+use crate::analyzer::Scanner;
+use crate::categories;
+use crate::categories::duplicates::DuplicateGroup;
+use crate::cleaner::DeleteOutcome;
+use crate::cli::ScanOptions;
+use crate::config::{Config, CustomCategoryConfig, RiskLevel};
+use crate::output::{CategoryResult, OutputMode};
+use crate::scan_cache::CacheContext;
+use crate::scan_events::ScanProgressEvent;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Operating systems a category is available on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Any,
+    Windows,
+}
+
+impl Platform {
+    /// Whether the category can run on the current OS
+    pub fn is_current(self) -> bool {
+        match self {
+            Platform::Any => true,
+            Platform::Windows => cfg!(windows),
+        }
+    }
+}
+
+/// How a category's paths are removed by `cleaner::clean_all`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanMode {
+    /// Delete all paths in one batch (10-50x faster than one-by-one)
+    Batch,
+    /// Delete paths one at a time through [`Scanner::clean`]
+    PerPath,
+    /// Empty the Recycle Bin as a whole
+    RecycleBin,
+    /// Run each application's uninstaller before removing its leftovers
+    Uninstall,
+}
+
+/// Side outputs a scanner can attach to the aggregated results
+#[derive(Debug, Default)]
+pub struct ScanExtras {
+    /// Duplicate groups for enhanced display (filled by the duplicates scanner)
+    pub duplicate_groups: Mutex<Option<Vec<DuplicateGroup>>>,
+}
+
+/// Everything a scanner needs to run
+#[derive(Clone, Copy)]
+pub struct ScanContext<'a> {
+    pub root: &'a Path,
+    pub options: &'a ScanOptions,
+    pub config: &'a Config,
+    pub mode: OutputMode,
+    pub extras: &'a ScanExtras,
+    /// Scan cache state for this category, when the cache is enabled
+    pub cache: Option<&'a CacheContext>,
+}
+
+impl<'a> ScanContext<'a> {
+    pub fn new(
+        root: &'a Path,
+        options: &'a ScanOptions,
+        config: &'a Config,
+        mode: OutputMode,
+        extras: &'a ScanExtras,
+    ) -> Self {
+        Self {
+            root,
+            options,
+            config,
+            mode,
+            extras,
+            cache: None,
+        }
+    }
+
+    /// Same context with the scan cache of the category about to be scanned
+    pub fn with_cache(&self, cache: Option<&'a CacheContext>) -> Self {
+        Self { cache, ..*self }
+    }
+
+    /// Same context with console output suppressed (used while the TUI owns the screen)
+    pub fn quiet(&self) -> Self {
+        Self {
+            mode: OutputMode::Quiet,
+            ..*self
+        }
+    }
+}
+
+/// Metadata and behaviour of one cleanup category
+#[derive(Clone)]
+pub struct CategoryDescriptor {
+    /// Stable identifier used for JSON keys, the scan cache and deletion history
+    pub id: String,
+    /// Name shown in the TUI and CLI tables
+    pub display_name: String,
+    pub emoji: &'static str,
+    /// Short description for the TUI dashboard
+    pub description: String,
+    /// Safe categories are auto-selected for cleaning in the TUI
+    pub risk: RiskLevel,
+    /// Enabled on the TUI dashboard when config.toml doesn't say otherwise
+    pub default_enabled: bool,
+    pub requires_admin: bool,
+    /// Status shown in the scan summary table instead of the one derived from
+    /// the risk level, e.g. "[OK] Inactive projects"
+    pub status: Option<&'static str>,
+    pub platform: Platform,
+    pub clean_mode: CleanMode,
+    /// False for the built-in categories, true for config.toml and library additions
+    pub custom: bool,
+    pub scanner: Arc<dyn Scanner>,
+}
+
+impl fmt::Debug for CategoryDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CategoryDescriptor")
+            .field("id", &self.id)
+            .field("display_name", &self.display_name)
+            .field("risk", &self.risk)
+            .field("requires_admin", &self.requires_admin)
+            .field("platform", &self.platform)
+            .field("clean_mode", &self.clean_mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CategoryDescriptor {
+    /// Describe a new (non built-in) category with conservative defaults:
+    /// review required, batch cleaning, available everywhere.
+    pub fn new(id: &str, display_name: &str, scanner: Arc<dyn Scanner>) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: display_name.to_string(),
+            emoji: "📁",
+            description: String::new(),
+            risk: RiskLevel::Review,
+            default_enabled: false,
+            requires_admin: false,
+            status: None,
+            platform: Platform::Any,
+            clean_mode: CleanMode::Batch,
+            custom: true,
+            scanner,
+        }
+    }
+
+    /// Descriptor for a `[[categories.custom]]` entry from config.toml
+    pub fn from_custom(def: &CustomCategoryConfig) -> Self {
+        let mut descriptor = Self::new(
+            &def.name,
+            &def.name,
+            Arc::new(CustomScanner { def: def.clone() }),
+        );
+        descriptor.description = def
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("Custom: {} root(s)", def.roots.len()));
+        descriptor.risk = def.risk;
+        descriptor
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.platform.is_current()
+    }
+
+    pub fn is_safe(&self) -> bool {
+        self.risk == RiskLevel::Safe
+    }
+
+    /// Status label used in the scan summary table
+    pub fn status_label(&self) -> &'static str {
+        if let Some(status) = self.status {
+            status
+        } else if self.requires_admin {
+            "[!] Requires admin"
+        } else if self.is_safe() {
+            "[OK] Safe to clean"
+        } else {
+            "[!] Review suggested"
+        }
+    }
+
+    pub fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        self.scanner.scan(ctx)
+    }
+
+    /// Scan with TUI progress events, sending started/finished on behalf of
+    /// scanners that don't report progress themselves
+    pub fn scan_with_progress(
+        &self,
+        ctx: &ScanContext,
+        tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        if self.scanner.reports_progress() {
+            return self.scanner.scan_with_progress(ctx, tx);
+        }
+
+        let _ = tx.send(ScanProgressEvent::CategoryStarted {
+            category: self.display_name.clone(),
+            total_units: None,
+            current_path: None,
+        });
+        let result = self.scanner.scan_with_progress(&ctx.quiet(), tx);
+        let (items, size_bytes) = result
+            .as_ref()
+            .map(|r| (r.items, r.size_bytes))
+            .unwrap_or((0, 0));
+        let _ = tx.send(ScanProgressEvent::CategoryFinished {
+            category: self.display_name.clone(),
+            items,
+            size_bytes,
+        });
+        result
+    }
+
+    pub fn clean(&self, path: &Path, permanent: bool) -> Result<DeleteOutcome> {
+        self.scanner.clean(path, permanent)
+    }
+}
+
+/// Ordered set of category descriptors
+///
+/// Order matters: it is the dashboard order (Quick Clean -> Developer Cleanup ->
+/// Space Hunters -> Advanced -> custom) and the order results are reported in.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryRegistry {
+    descriptors: Vec<Arc<CategoryDescriptor>>,
+}
+
+impl CategoryRegistry {
+    /// Registry containing only the built-in categories
+    pub fn builtin() -> Self {
+        Self {
+            descriptors: builtin_descriptors().into_iter().map(Arc::new).collect(),
+        }
+    }
+
+    /// Built-in categories followed by the user-defined ones from config.toml
+    ///
+    /// Custom categories whose name clashes with an existing category are skipped
+    /// with a warning (printed once per process, as the registry is built often).
+    pub fn with_config(config: &Config) -> Self {
+        static WARNED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+        let mut registry = Self::builtin();
+        for def in &config.categories.custom {
+            if let Err(e) = registry.register(CategoryDescriptor::from_custom(def)) {
+                let mut warned = WARNED
+                    .get_or_init(Default::default)
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                if warned.insert(def.name.clone()) {
+                    eprintln!(
+                        "Warning: Custom category '{}' in config.toml is ignored: {}",
+                        def.name, e
+                    );
+                }
+            }
+        }
+        registry
+    }
+
+    /// Add a category; ids and display names must be unique (case-insensitive)
+    pub fn register(&mut self, descriptor: CategoryDescriptor) -> Result<()> {
+        if let Some(existing) = self.descriptors.iter().find(|d| {
+            d.id.eq_ignore_ascii_case(&descriptor.id)
+                || d.display_name
+                    .eq_ignore_ascii_case(&descriptor.display_name)
+        }) {
+            anyhow::bail!(
+                "Category '{}' conflicts with existing category '{}'",
+                descriptor.id,
+                existing.id
+            );
+        }
+        self.descriptors.push(Arc::new(descriptor));
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<CategoryDescriptor>> {
+        self.descriptors.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.descriptors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.descriptors.is_empty()
+    }
+
+    /// Look up a category by id (case-insensitive)
+    pub fn get(&self, id: &str) -> Option<&Arc<CategoryDescriptor>> {
+        self.descriptors
+            .iter()
+            .find(|d| d.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Look up a category by its display name
+    pub fn find_by_name(&self, display_name: &str) -> Option<&Arc<CategoryDescriptor>> {
+        self.descriptors
+            .iter()
+            .find(|d| d.display_name == display_name)
+    }
+
+    /// Position of a category in registry order
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.descriptors
+            .iter()
+            .position(|d| d.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Categories selected by `options` that can run on this platform, in registry order
+    pub fn enabled(&self, options: &ScanOptions) -> Vec<Arc<CategoryDescriptor>> {
+        self.descriptors
+            .iter()
+            .filter(|d| d.is_available() && options.is_enabled(&d.id))
+            .cloned()
+            .collect()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn builtin(
+    id: &str,
+    display_name: &str,
+    emoji: &'static str,
+    description: &str,
+    risk: RiskLevel,
+    default_enabled: bool,
+    clean_mode: CleanMode,
+    scanner: Arc<dyn Scanner>,
+) -> CategoryDescriptor {
+    CategoryDescriptor {
+        id: id.to_string(),
+        display_name: display_name.to_string(),
+        emoji,
+        description: description.to_string(),
+        risk,
+        default_enabled,
+        requires_admin: false,
+        status: None,
+        platform: Platform::Any,
+        clean_mode,
+        custom: false,
+        scanner,
+    }
+}
+
+/// Central category definitions, in dashboard order
+fn builtin_descriptors() -> Vec<CategoryDescriptor> {
+    use CleanMode::*;
+    use RiskLevel::*;
+
+    let mut applications = builtin(
+        "applications",
+        "Installed Applications",
+        "📱",
+        "Uninstallable programs",
+        Review,
+        false,
+        Uninstall,
+        Arc::new(ApplicationsScanner),
+    );
+    applications.platform = Platform::Windows;
+
+    let mut build = builtin(
+        "build",
+        "Build Artifacts",
+        "🔨",
+        "node_modules, target, .next",
+        Safe,
+        true,
+        Batch,
+        Arc::new(BuildScanner),
+    );
+    build.status = Some("[OK] Inactive projects");
+
+    let mut downloads = builtin(
+        "downloads",
+        "Old Downloads",
+        "⬇️",
+        "Unused download files",
+        Review,
+        false,
+        Batch,
+        Arc::new(DownloadsScanner),
+    );
+    downloads.status = Some("[OK] Old files");
+
+    let mut windows_update = builtin(
+        "windows_update",
+        "Windows Update",
+        "🔄",
+        "Update installation files (requires admin)",
+        Review,
+        false,
+        PerPath,
+        Arc::new(WindowsUpdateScanner),
+    );
+    windows_update.requires_admin = true;
+    windows_update.platform = Platform::Windows;
+
+    let mut event_logs = builtin(
+        "event_logs",
+        "Event Logs",
+        "📋",
+        "System event logs (requires admin)",
+        Review,
+        false,
+        PerPath,
+        Arc::new(EventLogsScanner),
+    );
+    event_logs.requires_admin = true;
+    event_logs.platform = Platform::Windows;
+
+    vec![
+        // A. Quick Clean (safe, minimal side effects)
+        builtin(
+            "trash",
+            "Trash",
+            "🗑️",
+            "Recycle Bin contents",
+            Safe,
+            true,
+            RecycleBin,
+            Arc::new(TrashScanner),
+        ),
+        builtin(
+            "temp",
+            "Temp Files",
+            "🗑️",
+            "Temporary system files",
+            Safe,
+            true,
+            Batch,
+            Arc::new(TempScanner),
+        ),
+        builtin(
+            "browser",
+            "Browser Cache",
+            "🌐",
+            "Web browser data cache",
+            Safe,
+            true,
+            PerPath,
+            Arc::new(BrowserScanner),
+        ),
+        builtin(
+            "app_cache",
+            "Application Cache",
+            "💾",
+            "App data cache (Notion, VS Code, Slack, etc.)",
+            Safe,
+            true,
+            Batch,
+            Arc::new(AppCacheScanner),
+        ),
+        builtin(
+            "system",
+            "System Cache",
+            "⚙️",
+            "Windows system cache files",
+            Safe,
+            true,
+            PerPath,
+            Arc::new(SystemScanner),
+        ),
+        builtin(
+            "empty",
+            "Empty Folders",
+            "📁",
+            "Directories with no files",
+            Safe,
+            true,
+            PerPath,
+            Arc::new(EmptyScanner),
+        ),
+        // B. Developer Cleanup (safe, but may trigger rebuilds / re-downloads)
+        build,
+        builtin(
+            "cache",
+            "Package Cache",
+            "📚",
+            "Package manager cache (npm, pip, nuget, etc.)",
+            Safe,
+            false,
+            PerPath,
+            Arc::new(CacheScanner),
+        ),
+        // C. Space Hunters (review required, biggest wins)
+        applications,
+        builtin(
+            "toolchains",
+            "Stale Toolchains",
+            "🧰",
+            "Unpinned rustup, nvm, pyenv, SDKMAN! and Gradle versions",
+            Review,
+            false,
+            Batch,
+            Arc::new(ToolchainsScanner),
+        ),
+        downloads,
+        builtin(
+            "large",
+            "Large Files",
+            "📦",
+            "Files over size threshold",
+            Review,
+            false,
+            Batch,
+            Arc::new(LargeScanner),
+        ),
+        builtin(
+            "old",
+            "Old Files",
+            "📅",
+            "Files not accessed in X days",
+            Review,
+            false,
+            Batch,
+            Arc::new(OldScanner),
+        ),
+        builtin(
+            "duplicates",
+            "Duplicates",
+            "📋",
+            "Identical file copies",
+            Review,
+            false,
+            Batch,
+            Arc::new(DuplicatesScanner),
+        ),
+        // D. Advanced (admin / system)
+        windows_update,
+        event_logs,
+    ]
+}
+
+// Built-in scanners wrapping the category modules
+
+struct TrashScanner;
+impl Scanner for TrashScanner {
+    fn scan(&self, _ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::trash::scan()
+    }
+}
+
+struct TempScanner;
+impl Scanner for TempScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::temp::scan(ctx.root, ctx.config)
+    }
+
+    fn scan_with_progress(
+        &self,
+        ctx: &ScanContext,
+        tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        categories::temp::scan_with_progress(ctx.root, ctx.config, tx)
+    }
+
+    fn reports_progress(&self) -> bool {
+        true
+    }
+}
+
+struct BrowserScanner;
+impl Scanner for BrowserScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::browser::scan(ctx.root, ctx.config)
+    }
+}
+
+struct AppCacheScanner;
+impl Scanner for AppCacheScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::app_cache::scan(ctx.root, ctx.config, ctx.mode)
+    }
+
+    fn scan_with_progress(
+        &self,
+        ctx: &ScanContext,
+        tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        categories::app_cache::scan_with_progress(ctx.root, ctx.config, tx)
+    }
+
+    fn reports_progress(&self) -> bool {
+        true
+    }
+}
+
+struct SystemScanner;
+impl Scanner for SystemScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::system::scan(ctx.root, ctx.config)
+    }
+}
+
+struct EmptyScanner;
+impl Scanner for EmptyScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::empty::scan(ctx.root, ctx.config)
+    }
+
+    fn scan_with_progress(
+        &self,
+        ctx: &ScanContext,
+        tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        // empty::scan_with_progress only reports the start; finish it here
+        let result = categories::empty::scan_with_progress(ctx.root, ctx.config, tx);
+        let (items, size_bytes) = result
+            .as_ref()
+            .map(|r| (r.items, r.size_bytes))
+            .unwrap_or((0, 0));
+        let _ = tx.send(ScanProgressEvent::CategoryFinished {
+            category: "Empty Folders".to_string(),
+            items,
+            size_bytes,
+        });
+        result
+    }
+
+    fn reports_progress(&self) -> bool {
+        true
+    }
+}
+
+struct BuildScanner;
+impl Scanner for BuildScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::build::scan(
+            ctx.root,
+            ctx.options.project_age_days,
+            Some(&ctx.config.categories.build),
+            ctx.config,
+            ctx.mode,
+        )
+    }
+}
+
+struct CacheScanner;
+impl Scanner for CacheScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::cache::scan(ctx.root, ctx.config, ctx.mode)
+    }
+
+    fn scan_with_progress(
+        &self,
+        ctx: &ScanContext,
+        tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        categories::cache::scan_with_progress(ctx.root, ctx.config, tx)
+    }
+
+    fn reports_progress(&self) -> bool {
+        true
+    }
+
+    fn clean(&self, path: &Path, permanent: bool) -> Result<DeleteOutcome> {
+        categories::cache::clean(path, permanent)
+    }
+}
+
+struct ApplicationsScanner;
+impl Scanner for ApplicationsScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::applications::scan(ctx.root, ctx.config, ctx.mode)
+    }
+
+    fn scan_with_progress(
+        &self,
+        ctx: &ScanContext,
+        tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        categories::applications::scan_with_progress(ctx.root, ctx.config, tx)
+    }
+
+    fn reports_progress(&self) -> bool {
+        true
+    }
+}
+
+struct ToolchainsScanner;
+impl Scanner for ToolchainsScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::toolchains::scan(ctx.root, ctx.config, ctx.mode)
+    }
+}
+
+struct DownloadsScanner;
+impl Scanner for DownloadsScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::downloads::scan(ctx.root, ctx.options.min_age_days, ctx.config, ctx.mode)
+    }
+}
+
+struct LargeScanner;
+impl Scanner for LargeScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::large::scan(
+            ctx.root,
+            ctx.options.min_size_bytes,
+            ctx.config,
+            ctx.mode,
+            ctx.cache,
+        )
+    }
+}
+
+struct OldScanner;
+impl Scanner for OldScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::old::scan(ctx.root, ctx.options.min_age_days, ctx.config, ctx.mode)
+    }
+}
+
+struct DuplicatesScanner;
+impl Scanner for DuplicatesScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        let dup_result = categories::duplicates::scan_with_config(
+            ctx.root,
+            Some(&ctx.config.categories.duplicates),
+            ctx.config,
+            ctx.cache.map(|cache| cache.hash_cache()),
+        )?;
+        // Store groups for enhanced display
+        if let Ok(mut groups) = ctx.extras.duplicate_groups.lock() {
+            *groups = Some(dup_result.groups.clone());
+        }
+        Ok(dup_result.to_category_result())
+    }
+}
+
+struct WindowsUpdateScanner;
+impl Scanner for WindowsUpdateScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::windows_update::scan(ctx.root, ctx.config)
+    }
+
+    fn clean(&self, path: &Path, _permanent: bool) -> Result<DeleteOutcome> {
+        categories::windows_update::clean(path)?;
+        Ok(DeleteOutcome::Deleted)
+    }
+}
+
+struct EventLogsScanner;
+impl Scanner for EventLogsScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::event_logs::scan(ctx.root, ctx.config)
+    }
+
+    fn clean(&self, path: &Path, _permanent: bool) -> Result<DeleteOutcome> {
+        categories::event_logs::clean(path)?;
+        Ok(DeleteOutcome::Deleted)
+    }
+}
+
+struct CustomScanner {
+    def: CustomCategoryConfig,
+}
+impl Scanner for CustomScanner {
+    fn scan(&self, ctx: &ScanContext) -> Result<CategoryResult> {
+        categories::custom::scan(&self.def, ctx.config, ctx.mode)
+    }
+
+    fn scan_with_progress(
+        &self,
+        ctx: &ScanContext,
+        tx: &Sender<ScanProgressEvent>,
+    ) -> Result<CategoryResult> {
+        categories::custom::scan_with_progress(&self.def, ctx.config, tx)
+    }
+
+    fn reports_progress(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_ids_are_unique() {
+        let registry = CategoryRegistry::builtin();
+        assert_eq!(registry.len(), 16);
+        for (index, descriptor) in registry.iter().enumerate() {
+            assert_eq!(registry.position(&descriptor.id), Some(index));
+            assert_eq!(
+                registry
+                    .find_by_name(&descriptor.display_name)
+                    .map(|d| d.id.as_str()),
+                Some(descriptor.id.as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn test_register_rejects_conflicts() {
+        let mut registry = CategoryRegistry::builtin();
+        let scanner: Arc<dyn Scanner> = Arc::new(TrashScanner);
+        assert!(registry
+            .register(CategoryDescriptor::new("Trash", "Other", scanner.clone()))
+            .is_err());
+        assert!(registry
+            .register(CategoryDescriptor::new("mine", "Mine", scanner))
+            .is_ok());
+        assert_eq!(registry.position("mine"), Some(16));
+    }
+
+    #[test]
+    fn test_status_labels() {
+        let registry = CategoryRegistry::builtin();
+        let status = |id: &str| registry.get(id).unwrap().status_label();
+        assert_eq!(status("build"), "[OK] Inactive projects");
+        assert_eq!(status("downloads"), "[OK] Old files");
+        assert_eq!(status("large"), "[!] Review suggested");
+        assert_eq!(status("temp"), "[OK] Safe to clean");
+    }
+
+    #[test]
+    fn test_enabled_follows_options() {
+        let registry = CategoryRegistry::builtin();
+        let options = ScanOptions {
+            categories: vec!["build".to_string(), "temp".to_string()],
+            ..Default::default()
+        };
+        let ids: Vec<String> = registry
+            .enabled(&options)
+            .iter()
+            .map(|d| d.id.clone())
+            .collect();
+        assert_eq!(ids, vec!["temp", "build"]);
+    }
+}
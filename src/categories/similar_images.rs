@@ -0,0 +1,172 @@
+//! Near-duplicate images for the duplicates category.
+//!
+//! The same picture exported at another size or in another format has
+//! different bytes, so the exact (blake3) comparison misses it. Each image is
+//! decoded and reduced to a 64-bit difference hash (dHash): shrunk to 9×8
+//! grayscale pixels, with one bit per pair of horizontal neighbours saying
+//! whether brightness goes up. Resizing and re-encoding barely change those
+//! gradients, so images whose hashes differ in at most a few bits (the Hamming
+//! distance) look the same. Near-uniform images (blank pages, solid fills) have
+//! no gradients to speak of and are left out.
+
+use anyhow::{Context, Result};
+use image::imageops::{self, FilterType};
+use image::{GrayImage, ImageReader};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the image formats that are decoded
+pub const IMAGE_EXTENSIONS: &[&str] = &["bmp", "gif", "jpeg", "jpg", "png", "tif", "tiff", "webp"];
+
+/// Brightness range (of 255) of the shrunk image below which it counts as uniform
+const UNIFORM_RANGE: u8 = 8;
+
+/// An image of a similar-images group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImageInfo {
+    pub path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    /// File size
+    pub size: u64,
+    /// Difference hash
+    #[serde(skip)]
+    pub hash: u64,
+}
+
+impl ImageInfo {
+    pub fn pixels(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+}
+
+/// Whether `path` has the extension of a format that's decoded
+pub fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Decode an image and compute its difference hash
+///
+/// Returns `None` for a near-uniform image: its hash says nothing about what
+/// it shows, so it would look like every other one.
+pub fn image_info(path: &Path, size: u64) -> Result<Option<ImageInfo>> {
+    let image = ImageReader::open(path)
+        .with_context(|| format!("Failed to open image: {}", path.display()))?
+        .with_guessed_format()?
+        .decode()
+        .with_context(|| format!("Failed to decode image: {}", path.display()))?;
+    let small = shrink(&image.to_luma8());
+    if is_near_uniform(&small) {
+        return Ok(None);
+    }
+    Ok(Some(ImageInfo {
+        path: path.to_path_buf(),
+        width: image.width(),
+        height: image.height(),
+        size,
+        hash: hash_gradients(&small),
+    }))
+}
+
+/// 64-bit difference hash of a grayscale image
+pub fn dhash(image: &GrayImage) -> u64 {
+    hash_gradients(&shrink(image))
+}
+
+/// The 9×8 image the hash is taken from
+fn shrink(image: &GrayImage) -> GrayImage {
+    imageops::resize(image, 9, 8, FilterType::Triangle)
+}
+
+fn is_near_uniform(small: &GrayImage) -> bool {
+    let (min, max) = small.pixels().fold((u8::MAX, u8::MIN), |(min, max), p| {
+        (min.min(p[0]), max.max(p[0]))
+    });
+    max.saturating_sub(min) < UNIFORM_RANGE
+}
+
+fn hash_gradients(small: &GrayImage) -> u64 {
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = small.get_pixel(x, y)[0];
+            let right = small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left < right);
+        }
+    }
+    hash
+}
+
+/// Group images whose hashes are at most `max_distance` bits apart
+///
+/// An image only joins a cluster when it's close to every image already in it,
+/// so a chain of small differences (A like B, B like C) doesn't pull in images
+/// that don't look alike (A and C). Images are taken in order and join the
+/// first cluster that fits. Returns clusters of two or more, as indices into
+/// `images`.
+pub fn cluster(images: &[ImageInfo], max_distance: u32) -> Vec<Vec<usize>> {
+    // Every pair may be compared (images are few compared to files, and a
+    // comparison is one popcount)
+    let distance = |a: usize, b: usize| (images[a].hash ^ images[b].hash).count_ones();
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for i in 0..images.len() {
+        match clusters
+            .iter_mut()
+            .find(|cluster| cluster.iter().all(|&j| distance(i, j) <= max_distance))
+        {
+            Some(cluster) => cluster.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+    clusters.retain(|c| c.len() >= 2);
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Luma, RgbImage};
+
+    #[test]
+    fn test_resized_and_converted_copies_cluster_together() {
+        let dir = tempfile::tempdir().unwrap();
+        let gradient = RgbImage::from_fn(320, 240, |x, y| {
+            let v = ((x * 255 / 320) as u8).wrapping_add((y / 30) as u8 * 7);
+            image::Rgb([v, v / 2, 255 - v])
+        });
+        let big = dir.path().join("banner.png");
+        let small = dir.path().join("banner-small.jpg");
+        gradient.save_with_format(&big, ImageFormat::Png).unwrap();
+        imageops::resize(&gradient, 160, 120, FilterType::Triangle)
+            .save_with_format(&small, ImageFormat::Jpeg)
+            .unwrap();
+        let other = GrayImage::from_fn(320, 240, |x, _| Luma([255 - (x * 255 / 320) as u8]));
+
+        let mut images = vec![
+            image_info(&big, 1).unwrap().unwrap(),
+            image_info(&small, 1).unwrap().unwrap(),
+        ];
+        images.push(ImageInfo {
+            path: dir.path().join("other.png"),
+            width: 320,
+            height: 240,
+            size: 1,
+            hash: dhash(&other),
+        });
+        assert!(is_image(&small) && !is_image(Path::new("notes.txt")));
+        assert_eq!((images[1].width, images[1].height), (160, 120));
+        assert_eq!(cluster(&images, 10), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_near_uniform_images_are_left_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let blank = dir.path().join("blank.png");
+        GrayImage::from_fn(64, 64, |x, _| Luma([250 - (x % 3) as u8]))
+            .save(&blank)
+            .unwrap();
+        assert_eq!(image_info(&blank, 1).unwrap(), None);
+    }
+}
@@ -76,6 +76,7 @@ pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
         .collect();
     paths_with_sizes.sort_by(|a, b| b.1.cmp(&a.1));
 
+    result.path_sizes = paths_with_sizes.iter().cloned().collect();
     result.paths = paths_with_sizes.into_iter().map(|(p, _)| p).collect();
 
     Ok(result)
@@ -0,0 +1,428 @@
+//! Partial cleaning of Cargo `target/` directories, similar to cargo-sweep.
+//!
+//! Deleting a whole `target/` means a full rebuild the next time the project is
+//! touched. A sweep removes only the parts unlikely to be reused:
+//!
+//! - `incremental/` directories, which only speed up rebuilds of workspace crates
+//! - fingerprints, `deps` and `build` outputs compiled by a rustc that's no longer installed
+//! - whole profile directories (`debug`, `release`, `<triple>/debug`, ...) not built in N days
+
+use crate::categories::toolchains;
+use crate::utils;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Why a part of `target/` can be swept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepReason {
+    /// An `incremental/` directory
+    Incremental,
+    /// Compiled by a rustc that's no longer installed
+    UninstalledToolchain,
+    /// A profile directory nothing was built into recently
+    StaleProfile,
+}
+
+/// One removable piece of a `target/` directory
+#[derive(Debug, Clone)]
+pub struct SweepPart {
+    pub path: PathBuf,
+    pub size: u64,
+    pub reason: SweepReason,
+}
+
+/// What a sweep would remove from one `target/` directory
+#[derive(Debug, Clone, Default)]
+pub struct SweepPlan {
+    pub parts: Vec<SweepPart>,
+}
+
+impl SweepPlan {
+    pub fn bytes(&self, reason: SweepReason) -> u64 {
+        self.parts
+            .iter()
+            .filter(|p| p.reason == reason)
+            .map(|p| p.size)
+            .sum()
+    }
+
+    pub fn sweepable_bytes(&self) -> u64 {
+        self.parts.iter().map(|p| p.size).sum()
+    }
+
+    fn push(&mut self, path: PathBuf, size: u64, reason: SweepReason) {
+        if size > 0 {
+            self.parts.push(SweepPart { path, size, reason });
+        }
+    }
+}
+
+/// Version strings (`rustc 1.75.0 (82e1608df 2023-12-21)`) of every rustc on this machine
+///
+/// Covers each rustup toolchain, read from its channel manifest, plus a `rustc`
+/// on `PATH` that isn't a rustup proxy (running a proxy may install a toolchain).
+/// An empty set means no version was found, in which case nothing is swept for
+/// being built by an old rustc.
+pub fn installed_rustc_versions() -> HashSet<String> {
+    toolchains::rustup_toolchain_dirs()
+        .iter()
+        .filter_map(|dir| toolchain_rustc_version(dir))
+        .chain(standalone_rustc_version())
+        .collect()
+}
+
+/// The rustc version in a rustup toolchain's `multirust-channel-manifest.toml`
+///
+/// Toolchains linked with `rustup toolchain link` have no manifest.
+fn toolchain_rustc_version(toolchain: &Path) -> Option<String> {
+    let manifest =
+        fs::read_to_string(toolchain.join("lib/rustlib/multirust-channel-manifest.toml")).ok()?;
+    // The manifest lists every package and target, so look for the one line
+    // instead of parsing all of it
+    let mut lines = manifest
+        .lines()
+        .skip_while(|line| line.trim() != "[pkg.rustc]");
+    lines.next()?;
+    let version = lines
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| line.trim().strip_prefix("version"))?
+        .trim()
+        .strip_prefix('=')?
+        .trim()
+        .trim_matches('"');
+    normalize_rustc_version(&format!("rustc {}", version))
+}
+
+/// `rustc -V` of the first `rustc` on `PATH`, unless it's a rustup proxy
+fn standalone_rustc_version() -> Option<String> {
+    let (rustc, rustup) = if cfg!(windows) {
+        ("rustc.exe", "rustup.exe")
+    } else {
+        ("rustc", "rustup")
+    };
+    let rustc = std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(rustc))
+        .find(|path| path.is_file())?;
+    // rustup installs its proxies next to itself
+    if rustc.with_file_name(rustup).exists() {
+        return None;
+    }
+    let output = Command::new(rustc).arg("-V").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    normalize_rustc_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Cut a rustc version string down to `rustc X.Y.Z (hash date)`
+///
+/// Distro and Homebrew builds append their own suffix to `rustc -V`, like
+/// `(Fedora 1.79.0-1.fc40)`, which the version embedded in metadata doesn't have.
+fn normalize_rustc_version(version: &str) -> Option<String> {
+    let version = version.trim_start().strip_prefix("rustc ")?;
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let end = version.find(')').map_or(version.len(), |i| i + 1);
+    Some(format!("rustc {}", version[..end].trim_end()))
+}
+
+/// Work out what a sweep would remove from `target`
+///
+/// Profiles whose last build is older than `max_age_days` are swept whole; in the
+/// others only `incremental/` and units built by a rustc missing from `installed`.
+pub fn plan<F>(
+    target: &Path,
+    installed: &HashSet<String>,
+    max_age_days: u64,
+    on_path: &F,
+) -> SweepPlan
+where
+    F: Fn(&Path) + Sync,
+{
+    let cutoff = SystemTime::now() - Duration::from_secs(max_age_days * 24 * 60 * 60);
+    let mut plan = SweepPlan::default();
+
+    for profile in profile_dirs(target) {
+        if last_build(&profile).map(|t| t < cutoff).unwrap_or(false) {
+            let size = utils::calculate_dir_size_with_progress(&profile, on_path);
+            plan.push(profile, size, SweepReason::StaleProfile);
+            continue;
+        }
+
+        let incremental = profile.join("incremental");
+        if incremental.is_dir() {
+            let size = utils::calculate_dir_size_with_progress(&incremental, on_path);
+            plan.push(incremental, size, SweepReason::Incremental);
+        }
+
+        if !installed.is_empty() {
+            sweep_old_toolchain_units(&profile, installed, &mut plan, on_path);
+        }
+    }
+    plan
+}
+
+/// Profile directories: `target/<profile>` and `target/<triple>/<profile>`
+fn profile_dirs(target: &Path) -> Vec<PathBuf> {
+    let is_profile = |dir: &Path| dir.join(".fingerprint").is_dir();
+    let mut profiles = Vec::new();
+    for dir in subdirs(target) {
+        if is_profile(&dir) {
+            profiles.push(dir);
+        } else {
+            profiles.extend(subdirs(&dir).into_iter().filter(|d| is_profile(d)));
+        }
+    }
+    profiles
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+/// When cargo last built into a profile: its `.cargo-lock` or newest fingerprint file
+fn last_build(profile: &Path) -> Option<SystemTime> {
+    let fingerprints = WalkDir::new(profile.join(".fingerprint"))
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path());
+    std::iter::once(profile.join(".cargo-lock"))
+        .chain(fingerprints)
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+/// Sweep every unit (`.fingerprint/<crate>-<hash>`) whose `deps` outputs were built by
+/// a rustc that isn't installed anymore, along with those outputs and its `build/` dir
+fn sweep_old_toolchain_units<F>(
+    profile: &Path,
+    installed: &HashSet<String>,
+    plan: &mut SweepPlan,
+    on_path: &F,
+) where
+    F: Fn(&Path) + Sync,
+{
+    let mut deps: Vec<PathBuf> = fs::read_dir(profile.join("deps"))
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default();
+    deps.sort();
+
+    for unit in subdirs(&profile.join(".fingerprint")) {
+        let Some(unit_name) = unit.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some((_, hash)) = unit_name.rsplit_once('-') else {
+            continue;
+        };
+        let suffix = format!("-{}", hash);
+        let outputs: Vec<&PathBuf> = deps
+            .iter()
+            .filter(|p| {
+                p.file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.ends_with(&suffix))
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let built_by = outputs
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.extension().and_then(|e| e.to_str()),
+                    Some("rmeta") | Some("rlib")
+                )
+            })
+            .find_map(|p| rustc_version(p));
+        match built_by {
+            Some(version) if !installed.contains(&version) => {}
+            _ => continue,
+        }
+
+        let size = utils::calculate_dir_size_with_progress(&unit, on_path);
+        plan.push(unit.clone(), size, SweepReason::UninstalledToolchain);
+        for output in outputs {
+            on_path(output);
+            let size = fs::metadata(output).map(|m| m.len()).unwrap_or(0);
+            plan.push(output.clone(), size, SweepReason::UninstalledToolchain);
+        }
+        let build = profile.join("build").join(unit_name);
+        if build.is_dir() {
+            let size = utils::calculate_dir_size_with_progress(&build, on_path);
+            plan.push(build, size, SweepReason::UninstalledToolchain);
+        }
+    }
+}
+
+/// The `rustc X.Y.Z (hash date)` string embedded in a crate's metadata
+fn rustc_version(path: &Path) -> Option<String> {
+    const HEADER_BYTES: u64 = 256 * 1024;
+    let mut bytes = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(HEADER_BYTES)
+        .read_to_end(&mut bytes)
+        .ok()?;
+
+    let needle = b"rustc ";
+    let start = bytes.windows(needle.len()).enumerate().find_map(|(i, w)| {
+        (w == needle && bytes.get(i + needle.len()).is_some_and(u8::is_ascii_digit)).then_some(i)
+    })?;
+    let len = bytes[start..].iter().take(128).position(|&b| b == b')')?;
+    normalize_rustc_version(std::str::from_utf8(&bytes[start..=start + len]).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn backdate(path: &Path, days: u64) {
+        let time = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sweeps_incremental_and_stale_profiles() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("target");
+        write(
+            &target.join("debug/.fingerprint/app-1/invoked.timestamp"),
+            b"",
+        );
+        write(
+            &target.join("debug/incremental/app-1/s-abc/query-cache.bin"),
+            &[0; 100],
+        );
+        write(&target.join("debug/deps/app-1"), &[0; 50]);
+        let release_stamp =
+            target.join("x86_64-unknown-linux-gnu/release/.fingerprint/app-2/invoked.timestamp");
+        write(&release_stamp, b"");
+        write(
+            &target.join("x86_64-unknown-linux-gnu/release/deps/app-2"),
+            &[0; 70],
+        );
+        backdate(&release_stamp, 60);
+
+        let plan = plan(&target, &HashSet::new(), 30, &|_| {});
+        assert_eq!(plan.bytes(SweepReason::Incremental), 100);
+        assert_eq!(plan.bytes(SweepReason::StaleProfile), 70);
+        assert_eq!(plan.sweepable_bytes(), 170);
+    }
+
+    #[test]
+    fn test_sweeps_units_built_by_uninstalled_rustc() {
+        let tmp = tempfile::tempdir().unwrap();
+        let debug = tmp.path().join("target").join("debug");
+        let header =
+            |version: &str| format!("rust\0\0\0\x09\0\0\x10{} (abc 2023-01-01)\0", version);
+        write(&debug.join(".fingerprint/serde-aaa/lib-serde"), b"x");
+        write(
+            &debug.join("deps/libserde-aaa.rmeta"),
+            header("rustc 1.70.0").as_bytes(),
+        );
+        write(&debug.join("deps/serde-aaa.d"), b"deps");
+        write(&debug.join("build/serde-aaa/output"), b"out");
+        write(&debug.join(".fingerprint/log-bbb/lib-log"), b"x");
+        write(
+            &debug.join("deps/liblog-bbb.rmeta"),
+            header("rustc 1.75.0").as_bytes(),
+        );
+
+        let installed: HashSet<String> = ["rustc 1.75.0 (abc 2023-01-01)".to_string()].into();
+        let plan = plan(debug.parent().unwrap(), &installed, 30, &|_| {});
+        let swept: Vec<String> = plan
+            .parts
+            .iter()
+            .map(|p| {
+                p.path
+                    .strip_prefix(&debug)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        assert_eq!(
+            swept,
+            vec![
+                ".fingerprint/serde-aaa",
+                "deps/libserde-aaa.rmeta",
+                "deps/serde-aaa.d",
+                "build/serde-aaa"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reads_rustc_version_from_toolchain_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let toolchain = tmp.path().join("nightly-x");
+        write(
+            &toolchain.join("lib/rustlib/multirust-channel-manifest.toml"),
+            b"[pkg.cargo]\nversion = \"0.77.0-nightly (abc 2023-12-20)\"\n\n\
+              [pkg.rustc]\nversion = \"1.77.0-nightly (def 2023-12-20)\"\n\n\
+              [pkg.rustc.target.x86_64-unknown-linux-gnu]\navailable = true\n",
+        );
+        assert_eq!(
+            toolchain_rustc_version(&toolchain).as_deref(),
+            Some("rustc 1.77.0-nightly (def 2023-12-20)")
+        );
+        assert_eq!(toolchain_rustc_version(&tmp.path().join("linked")), None);
+    }
+
+    #[test]
+    fn test_normalizes_suffixed_rustc_versions() {
+        assert_eq!(
+            normalize_rustc_version("rustc 1.79.0 (129f3b996 2024-06-10) (Fedora 1.79.0-1.fc40)\n")
+                .as_deref(),
+            Some("rustc 1.79.0 (129f3b996 2024-06-10)")
+        );
+        assert_eq!(
+            normalize_rustc_version("rustc 1.79.0 (129f3b996 2024-06-10) (Homebrew)").as_deref(),
+            Some("rustc 1.79.0 (129f3b996 2024-06-10)")
+        );
+        assert_eq!(normalize_rustc_version("rustc-wrapper 1.0"), None);
+
+        // A unit built by the distro compiler is kept
+        let tmp = tempfile::tempdir().unwrap();
+        let debug = tmp.path().join("target").join("debug");
+        write(&debug.join(".fingerprint/serde-aaa/lib-serde"), b"x");
+        write(
+            &debug.join("deps/libserde-aaa.rmeta"),
+            b"rust\0\0\0\x09\0\0\x10rustc 1.79.0 (129f3b996 2024-06-10)\0",
+        );
+        let installed: HashSet<String> =
+            normalize_rustc_version("rustc 1.79.0 (129f3b996 2024-06-10) (Fedora 1.79.0-1.fc40)")
+                .into_iter()
+                .collect();
+        let plan = plan(debug.parent().unwrap(), &installed, 30, &|_| {});
+        assert!(plan.parts.is_empty());
+    }
+}
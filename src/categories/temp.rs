@@ -54,9 +54,7 @@ pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
 
     // Build result
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     Ok(result)
@@ -128,9 +126,7 @@ pub fn scan_with_progress(
     files_with_sizes.truncate(MAX_RESULTS);
 
     for (path, size) in files_with_sizes {
-        result.items += 1;
-        result.size_bytes += size;
-        result.paths.push(path);
+        result.push(path, size);
     }
 
     let _ = tx.send(ScanProgressEvent::CategoryFinished {
@@ -0,0 +1,649 @@
+//! Installed toolchain versions (rustup, nvm, pyenv, SDKMAN!, Gradle wrapper).
+//!
+//! Every installed version is listed with its size, whether it's the tool's
+//! configured default, and which projects pin it (`rust-toolchain.toml`, `.nvmrc`,
+//! `.python-version`, `.sdkmanrc`, `gradle-wrapper.properties`). Only versions that
+//! are neither the default nor pinned by a project under the scan root are offered.
+//! For rustup, directory overrides (`rustup override set`) count as pins and the
+//! `RUSTUP_TOOLCHAIN` of the environment as a default.
+
+use crate::categories::locations::{Base, KnownLocation, LocationResolver, Os, Rule};
+use crate::config::Config;
+use crate::output::{CategoryResult, OutputMode};
+use crate::project;
+use crate::theme::Theme;
+use crate::utils;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Toolchain managers wole knows the on-disk layout of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Rustup,
+    Nvm,
+    Pyenv,
+    Sdkman,
+    GradleWrapper,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 5] = [
+        Tool::Rustup,
+        Tool::Nvm,
+        Tool::Pyenv,
+        Tool::Sdkman,
+        Tool::GradleWrapper,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Tool::Rustup => "rustup",
+            Tool::Nvm => "nvm",
+            Tool::Pyenv => "pyenv",
+            Tool::Sdkman => "SDKMAN!",
+            Tool::GradleWrapper => "Gradle wrapper",
+        }
+    }
+
+    /// Where the tool keeps its state
+    fn location(self) -> KnownLocation {
+        const RUSTUP: &[Rule] = &[
+            Rule::Env("RUSTUP_HOME", &[]),
+            Rule::Under(Os::Any, Base::Home, &[".rustup"]),
+        ];
+        const NVM: &[Rule] = &[
+            Rule::Env("NVM_DIR", &[]),
+            Rule::Under(Os::Unix, Base::Home, &[".nvm"]),
+        ];
+        const PYENV: &[Rule] = &[
+            Rule::Env("PYENV_ROOT", &[]),
+            Rule::Under(Os::Unix, Base::Home, &[".pyenv"]),
+        ];
+        const SDKMAN: &[Rule] = &[
+            Rule::Env("SDKMAN_DIR", &[]),
+            Rule::Under(Os::Unix, Base::Home, &[".sdkman"]),
+        ];
+        const GRADLE: &[Rule] = &[
+            Rule::Env("GRADLE_USER_HOME", &[]),
+            Rule::Under(Os::Any, Base::Home, &[".gradle"]),
+        ];
+        let rules = match self {
+            Tool::Rustup => RUSTUP,
+            Tool::Nvm => NVM,
+            Tool::Pyenv => PYENV,
+            Tool::Sdkman => SDKMAN,
+            Tool::GradleWrapper => GRADLE,
+        };
+        KnownLocation {
+            name: self.name(),
+            rules,
+        }
+    }
+}
+
+/// One installed toolchain version
+#[derive(Debug, Clone)]
+pub struct InstalledVersion {
+    pub tool: Tool,
+    /// Version as the tool names it, e.g. `1.75.0-x86_64-unknown-linux-gnu` or `java/17.0.2-tem`
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub is_default: bool,
+    /// Project roots whose pin file selects this version
+    pub pinned_by: Vec<PathBuf>,
+}
+
+impl InstalledVersion {
+    /// Neither the default nor pinned by any project
+    pub fn is_cleanable(&self) -> bool {
+        !self.is_default && self.pinned_by.is_empty()
+    }
+}
+
+/// A version requested by a project's pin file
+#[derive(Debug, Clone)]
+struct Pin {
+    tool: Tool,
+    spec: String,
+    project: PathBuf,
+}
+
+/// Find every installed toolchain version and work out which ones are still needed
+///
+/// Pins are read from the projects under the scan root and `paths.scan_roots`.
+/// Returns `None` when those don't cover every project (see
+/// [`project::search_roots`]): a pin elsewhere could make a version look unused.
+pub fn find_installed(root: &Path, config: &Config) -> Option<Vec<InstalledVersion>> {
+    find_installed_with(&LocationResolver::from_env(), root, config)
+}
+
+/// [`find_installed`] with the tools' homes resolved against an explicit environment
+fn find_installed_with(
+    resolver: &LocationResolver,
+    root: &Path,
+    config: &Config,
+) -> Option<Vec<InstalledVersion>> {
+    let search_roots = project::search_roots(root, &config.paths.scan_roots, resolver.home())?;
+    let homes: Vec<(Tool, PathBuf)> = Tool::ALL
+        .iter()
+        .flat_map(|tool| {
+            resolver
+                .resolve(&tool.location())
+                .into_iter()
+                .map(|home| (*tool, home.path))
+        })
+        .collect();
+    if homes.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let project_roots: Vec<PathBuf> = search_roots
+        .iter()
+        .flat_map(|root| project::find_project_roots(root, config))
+        .collect();
+    let pins = collect_pins(&project_roots);
+
+    let mut installed = Vec::new();
+    for (tool, home) in homes {
+        let mut versions = list_versions(tool, &home);
+        let mut defaults = default_specs(tool, &home);
+        let mut overrides = Vec::new();
+        if tool == Tool::Rustup {
+            defaults.extend(
+                resolver
+                    .env_var("RUSTUP_TOOLCHAIN")
+                    .map(|v| v.to_string_lossy().into_owned()),
+            );
+            overrides = rustup_overrides(&home);
+        }
+        let newest = newest_name(&versions);
+
+        for version in &mut versions {
+            version.is_default = defaults
+                .iter()
+                .any(|spec| matches(tool, &home, &version.name, spec, newest.as_deref()));
+            version.pinned_by = pins
+                .iter()
+                .chain(&overrides)
+                .filter(|pin| pin.tool == tool)
+                .filter(|pin| matches(tool, &home, &version.name, &pin.spec, newest.as_deref()))
+                .map(|pin| pin.project.clone())
+                .collect();
+        }
+        installed.extend(
+            versions
+                .into_iter()
+                .filter(|version| !config.is_excluded(&version.path)),
+        );
+    }
+    Some(installed)
+}
+
+/// Directories of the toolchains rustup has installed (`<RUSTUP_HOME>/toolchains/*`)
+pub fn rustup_toolchain_dirs() -> Vec<PathBuf> {
+    LocationResolver::from_env()
+        .resolve(&Tool::Rustup.location())
+        .into_iter()
+        .flat_map(|home| subdirs(&home.path.join("toolchains")))
+        .collect()
+}
+
+/// Scan for installed toolchain versions no project or default needs
+pub fn scan(root: &Path, config: &Config, output_mode: OutputMode) -> Result<CategoryResult> {
+    let mut result = CategoryResult::default();
+
+    if output_mode != OutputMode::Quiet {
+        println!(
+            "  {} Checking rustup, nvm, pyenv, SDKMAN! and Gradle wrapper installs...",
+            Theme::muted("→")
+        );
+    }
+
+    let Some(installed) = find_installed(root, config) else {
+        if output_mode != OutputMode::Quiet {
+            println!(
+                "  {} {}",
+                Theme::muted("→"),
+                Theme::muted(
+                    "Not checking: the scan root doesn't cover your home directory and no paths.scan_roots are set"
+                )
+            );
+        }
+        return Ok(result);
+    };
+
+    if output_mode != OutputMode::Quiet {
+        for tool in Tool::ALL {
+            let versions: Vec<&InstalledVersion> =
+                installed.iter().filter(|v| v.tool == tool).collect();
+            if versions.is_empty() {
+                continue;
+            }
+            println!(
+                "  {} {} ({} installed):",
+                Theme::muted("→"),
+                tool.name(),
+                versions.len()
+            );
+            for version in versions {
+                let status = if version.is_default {
+                    "default".to_string()
+                } else if !version.pinned_by.is_empty() {
+                    format!("pinned by {} project(s)", version.pinned_by.len())
+                } else {
+                    "unused".to_string()
+                };
+                println!(
+                    "      {} {} ({}) {}",
+                    Theme::muted("•"),
+                    version.name,
+                    Theme::size(&bytesize::to_string(version.size, false)),
+                    Theme::muted(&status)
+                );
+                if output_mode == OutputMode::VeryVerbose {
+                    for project in &version.pinned_by {
+                        println!("          {} {}", Theme::muted("←"), project.display());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut cleanable: Vec<(PathBuf, u64)> = installed
+        .into_iter()
+        .filter(|v| v.is_cleanable() && v.size > 0)
+        .map(|v| (v.path, v.size))
+        .collect();
+    cleanable.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    for (path, size) in cleanable {
+        result.push(path, size);
+    }
+
+    Ok(result)
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name().map(|n| n.to_string_lossy().into_owned())
+}
+
+fn list_versions(tool: Tool, home: &Path) -> Vec<InstalledVersion> {
+    let named: Vec<(String, PathBuf)> = match tool {
+        Tool::Rustup => named_subdirs(&home.join("toolchains")),
+        Tool::Nvm => named_subdirs(&home.join("versions").join("node")),
+        Tool::Pyenv => named_subdirs(&home.join("versions")),
+        Tool::GradleWrapper => named_subdirs(&home.join("wrapper").join("dists")),
+        Tool::Sdkman => subdirs(&home.join("candidates"))
+            .into_iter()
+            .flat_map(|candidate| {
+                let candidate_name = file_name(&candidate).unwrap_or_default();
+                named_subdirs(&candidate)
+                    .into_iter()
+                    .filter(|(name, path)| name != "current" && !path.is_symlink())
+                    .map(move |(name, path)| (format!("{}/{}", candidate_name, name), path))
+                    .collect::<Vec<_>>()
+            })
+            .collect(),
+    };
+
+    named
+        .into_iter()
+        .map(|(name, path)| InstalledVersion {
+            tool,
+            size: utils::calculate_dir_size(&path),
+            name,
+            path,
+            is_default: false,
+            pinned_by: Vec::new(),
+        })
+        .collect()
+}
+
+fn named_subdirs(dir: &Path) -> Vec<(String, PathBuf)> {
+    subdirs(dir)
+        .into_iter()
+        .filter(|path| !path.is_symlink())
+        .filter_map(|path| Some((file_name(&path)?, path)))
+        .collect()
+}
+
+/// Specs naming the tool's configured default version(s)
+fn default_specs(tool: Tool, home: &Path) -> Vec<String> {
+    match tool {
+        Tool::Rustup => fs::read_to_string(home.join("settings.toml"))
+            .ok()
+            .and_then(|s| s.parse::<toml::Table>().ok())
+            .and_then(|t| {
+                t.get("default_toolchain")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+            .into_iter()
+            .collect(),
+        Tool::Nvm => read_first_line(&home.join("alias").join("default"))
+            .into_iter()
+            .collect(),
+        Tool::Pyenv => fs::read_to_string(home.join("version"))
+            .map(|s| spec_lines(&s))
+            .unwrap_or_default(),
+        Tool::Sdkman => subdirs(&home.join("candidates"))
+            .into_iter()
+            .filter_map(|candidate| {
+                let target = fs::read_link(candidate.join("current")).ok()?;
+                Some(format!(
+                    "{}/{}",
+                    file_name(&candidate)?,
+                    file_name(&target)?
+                ))
+            })
+            .collect(),
+        Tool::GradleWrapper => Vec::new(),
+    }
+}
+
+/// Directory overrides from rustup's `settings.toml`, as pins of those directories
+fn rustup_overrides(home: &Path) -> Vec<Pin> {
+    let Some(settings) = fs::read_to_string(home.join("settings.toml"))
+        .ok()
+        .and_then(|s| s.parse::<toml::Table>().ok())
+    else {
+        return Vec::new();
+    };
+    let Some(overrides) = settings.get("overrides").and_then(|v| v.as_table()) else {
+        return Vec::new();
+    };
+    overrides
+        .iter()
+        .filter_map(|(dir, toolchain)| {
+            Some(Pin {
+                tool: Tool::Rustup,
+                spec: toolchain.as_str()?.to_string(),
+                project: PathBuf::from(dir),
+            })
+        })
+        .collect()
+}
+
+fn read_first_line(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()?
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+}
+
+fn spec_lines(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Read every pin file in the given project roots
+fn collect_pins(project_roots: &[PathBuf]) -> Vec<Pin> {
+    let mut pins = Vec::new();
+    for project in project_roots {
+        let mut pin = |tool: Tool, spec: String| {
+            pins.push(Pin {
+                tool,
+                spec,
+                project: project.clone(),
+            })
+        };
+
+        if let Some(channel) = rust_toolchain_channel(project) {
+            pin(Tool::Rustup, channel);
+        }
+        if let Some(spec) = read_first_line(&project.join(".nvmrc")) {
+            pin(Tool::Nvm, spec);
+        }
+        if let Ok(contents) = fs::read_to_string(project.join(".python-version")) {
+            for spec in spec_lines(&contents) {
+                pin(Tool::Pyenv, spec);
+            }
+        }
+        if let Ok(contents) = fs::read_to_string(project.join(".sdkmanrc")) {
+            for line in spec_lines(&contents) {
+                if let Some((candidate, version)) = line.split_once('=') {
+                    pin(
+                        Tool::Sdkman,
+                        format!("{}/{}", candidate.trim(), version.trim()),
+                    );
+                }
+            }
+        }
+        if let Some(dist) = gradle_wrapper_dist(project) {
+            pin(Tool::GradleWrapper, dist);
+        }
+    }
+    pins
+}
+
+/// `[toolchain] channel` from `rust-toolchain.toml`, or the legacy one-line `rust-toolchain`
+fn rust_toolchain_channel(project: &Path) -> Option<String> {
+    for name in ["rust-toolchain.toml", "rust-toolchain"] {
+        let Ok(contents) = fs::read_to_string(project.join(name)) else {
+            continue;
+        };
+        if let Ok(table) = contents.parse::<toml::Table>() {
+            if let Some(channel) = table
+                .get("toolchain")
+                .and_then(|t| t.get("channel"))
+                .and_then(|c| c.as_str())
+            {
+                return Some(channel.to_string());
+            }
+        }
+        if let Some(line) = spec_lines(&contents).into_iter().next() {
+            if !line.contains('=') && !line.starts_with('[') {
+                return Some(line);
+            }
+        }
+    }
+    None
+}
+
+/// `gradle-8.5-bin` from the wrapper's `distributionUrl`
+fn gradle_wrapper_dist(project: &Path) -> Option<String> {
+    let properties = project
+        .join("gradle")
+        .join("wrapper")
+        .join("gradle-wrapper.properties");
+    let contents = fs::read_to_string(properties).ok()?;
+    let url = contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "distributionUrl").then(|| value.trim().to_string())
+    })?;
+    let file = url.rsplit('/').next()?;
+    Some(file.trim_end_matches(".zip").to_string())
+}
+
+/// Numeric sort key for version-like names (`v18.17.0`, `3.11.4`)
+fn version_key(name: &str) -> Vec<u64> {
+    name.trim_start_matches('v')
+        .split(['.', '-'])
+        .map_while(|part| part.parse::<u64>().ok())
+        .collect()
+}
+
+fn newest_name(versions: &[InstalledVersion]) -> Option<String> {
+    versions
+        .iter()
+        .max_by_key(|v| version_key(&v.name))
+        .map(|v| v.name.clone())
+}
+
+/// Follow nvm aliases (`default` → `lts/*` → `lts/iron` → `v20.11.0`)
+fn resolve_nvm_alias(home: &Path, spec: &str) -> Option<String> {
+    let mut spec = spec.to_string();
+    for _ in 0..8 {
+        let bare = spec.trim_start_matches('v');
+        if bare.starts_with(|c: char| c.is_ascii_digit()) {
+            return Some(bare.to_string());
+        }
+        spec = read_first_line(&home.join("alias").join(&spec))?;
+    }
+    None
+}
+
+/// Whether a `MAJOR[.MINOR[.PATCH]]` spec selects `version` (`18` matches `18.17.0`)
+fn version_prefix_matches(version: &str, spec: &str) -> bool {
+    version == spec || version.starts_with(&format!("{}.", spec))
+}
+
+/// Whether an installed version satisfies a pin or default spec
+///
+/// Specs that can't be resolved offline (nvm's `node`, an unknown alias) select the
+/// newest installed version, so something always stays protected.
+fn matches(tool: Tool, home: &Path, installed: &str, spec: &str, newest: Option<&str>) -> bool {
+    let spec = spec.trim();
+    match tool {
+        Tool::Rustup => installed == spec || installed.starts_with(&format!("{}-", spec)),
+        Tool::Nvm => match resolve_nvm_alias(home, spec) {
+            Some(version) => version_prefix_matches(installed.trim_start_matches('v'), &version),
+            None => newest == Some(installed),
+        },
+        Tool::Pyenv => spec != "system" && version_prefix_matches(installed, spec),
+        Tool::Sdkman | Tool::GradleWrapper => installed == spec,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_reads_pin_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().to_path_buf();
+        write(
+            &project.join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.75.0\"\n",
+        );
+        write(&project.join(".nvmrc"), "lts/iron\n");
+        write(&project.join(".python-version"), "3.11\n3.12.1\n");
+        write(
+            &project.join("gradle/wrapper/gradle-wrapper.properties"),
+            "distributionUrl=https\\://services.gradle.org/distributions/gradle-8.5-bin.zip\n",
+        );
+
+        let pins = collect_pins(&[project]);
+        let specs: Vec<(Tool, &str)> = pins.iter().map(|p| (p.tool, p.spec.as_str())).collect();
+        assert_eq!(
+            specs,
+            vec![
+                (Tool::Rustup, "1.75.0"),
+                (Tool::Nvm, "lts/iron"),
+                (Tool::Pyenv, "3.11"),
+                (Tool::Pyenv, "3.12.1"),
+                (Tool::GradleWrapper, "gradle-8.5-bin"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_specs_per_tool() {
+        let tmp = tempfile::tempdir().unwrap();
+        let nvm = tmp.path();
+        write(&nvm.join("alias/default"), "lts/*\n");
+        write(&nvm.join("alias/lts/*"), "lts/iron\n");
+        write(&nvm.join("alias/lts/iron"), "v20.11.0\n");
+
+        let toolchain = "1.75.0-x86_64-unknown-linux-gnu";
+        assert!(matches(Tool::Rustup, nvm, toolchain, "1.75.0", None));
+        assert!(!matches(Tool::Rustup, nvm, toolchain, "1.75", None));
+        assert!(matches(
+            Tool::Rustup,
+            nvm,
+            "stable-x86_64-unknown-linux-gnu",
+            "stable",
+            None
+        ));
+
+        assert!(matches(Tool::Nvm, nvm, "v20.11.0", "default", None));
+        assert!(matches(Tool::Nvm, nvm, "v18.17.0", "18", None));
+        assert!(!matches(Tool::Nvm, nvm, "v18.17.0", "18.1", None));
+        assert!(matches(Tool::Nvm, nvm, "v21.0.0", "node", Some("v21.0.0")));
+
+        assert!(matches(Tool::Pyenv, nvm, "3.11.4", "3.11", None));
+        assert!(!matches(Tool::Pyenv, nvm, "3.11.4", "system", None));
+    }
+
+    #[test]
+    fn test_offers_only_unpinned_non_default_versions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let home = tmp.path().join("home");
+        let rustup = tmp.path().join("rustup-home");
+        for toolchain in ["stable-x", "nightly-x", "1.60.0-x", "1.70.0-x", "1.75.0-x"] {
+            write(
+                &rustup.join("toolchains").join(toolchain).join("bin/rustc"),
+                "x",
+            );
+        }
+        let overridden = tmp.path().join("elsewhere").join("legacy");
+        write(
+            &rustup.join("settings.toml"),
+            &format!(
+                "default_toolchain = \"stable-x\"\n\n[overrides]\n{:?} = \"1.60.0-x\"\n",
+                overridden.display().to_string()
+            ),
+        );
+        let root = tmp.path().join("work");
+        let project = root.join("app");
+        write(&project.join("Cargo.toml"), "[package]\n");
+        write(&project.join("rust-toolchain"), "1.75.0\n");
+
+        let resolver = LocationResolver::new(
+            crate::categories::locations::OsFamily::Linux,
+            [
+                ("HOME", home.as_os_str()),
+                ("RUSTUP_HOME", rustup.as_os_str()),
+                ("RUSTUP_TOOLCHAIN", "nightly".as_ref()),
+            ],
+        );
+        // The scan root doesn't cover the home directory: nothing can be trusted
+        let mut config = Config::default();
+        assert!(find_installed_with(&resolver, &root, &config).is_none());
+
+        // Scanning another folder still sees the pins of every configured root
+        config.paths.scan_roots = vec![root.display().to_string()];
+        let elsewhere = tmp.path().join("downloads");
+        fs::create_dir_all(&elsewhere).unwrap();
+        let versions = find_installed_with(&resolver, &elsewhere, &config).unwrap();
+        let cleanable: Vec<&str> = versions
+            .iter()
+            .filter(|v| v.is_cleanable())
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(cleanable, vec!["1.70.0-x"]);
+
+        let version = |name: &str| versions.iter().find(|v| v.name == name).unwrap();
+        assert!(version("stable-x").is_default && version("nightly-x").is_default);
+        assert_eq!(version("1.60.0-x").pinned_by, vec![overridden]);
+        assert_eq!(version("1.75.0-x").pinned_by, vec![project]);
+    }
+}
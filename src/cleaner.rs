@@ -6,5 +6,5 @@ mod path_precheck;
 mod single_deletion;
 
 pub use batch_deletion::{clean_paths_batch, BatchDeleteResult};
-pub use category_cleaning::clean_all;
+pub use category_cleaning::{clean_all, clean_all_with_dedupe};
 pub use single_deletion::{clean_path, delete_with_precheck, DeleteOutcome};
@@ -5,13 +5,16 @@
 use super::batch_deletion::{clean_paths_batch, BatchDeleteResult};
 use super::single_deletion::{delete_with_precheck, DeleteOutcome};
 use crate::categories;
+use crate::categories::duplicates::{self, DuplicateGroup, DuplicateKind};
+use crate::categories::registry::{CategoryDescriptor, CleanMode};
+use crate::dedupe::{self as dedupe_ops, DedupeMode};
 use crate::history::DeletionLog;
-use crate::output::{OutputMode, ScanResults};
+use crate::output::{CategoryResult, OutputMode, ScanResults};
 use crate::progress;
 use crate::theme::Theme;
 use crate::utils;
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
@@ -155,6 +158,454 @@ fn batch_clean_category_internal(
     (success_count as u64, error_count as u64)
 }
 
+/// Helper function to clean a category one path at a time through its scanner
+///
+/// Used for categories that need per-path handling (browser/system caches that may
+/// be locked, empty folders, admin-only Windows Update and Event Logs files).
+fn per_path_clean_category_internal(
+    descriptor: &CategoryDescriptor,
+    paths: &[PathBuf],
+    permanent: bool,
+    dry_run: bool,
+    progress: Option<&indicatif::ProgressBar>,
+    mut history: Option<&mut DeletionLog>,
+    mode: OutputMode,
+) -> (u64, u64) {
+    if let Some(pb) = progress {
+        pb.set_message(format!("Cleaning {}...", descriptor.display_name));
+    }
+
+    let category = descriptor.id.as_str();
+    let mut cleaned = 0u64;
+    let mut errors = 0u64;
+
+    for path in paths {
+        if dry_run {
+            cleaned += 1;
+            if let Some(pb) = progress {
+                pb.inc(1);
+            }
+            continue;
+        }
+
+        let size = if path.is_dir() {
+            utils::calculate_dir_size(path)
+        } else {
+            utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
+        };
+
+        // A grouped package cache item stands for files inside it, which are
+        // logged one by one so restore can find them
+        let (outcome, removed) = if categories::cache_strategy::is_grouped(path) {
+            categories::cache::clean_parts(path, size, permanent)
+        } else {
+            let outcome = descriptor.clean(path, permanent);
+            let removed = match outcome {
+                Ok(DeleteOutcome::Deleted) => vec![(path.clone(), size)],
+                _ => Vec::new(),
+            };
+            (outcome, removed)
+        };
+        if let Some(log) = history.as_deref_mut() {
+            for (part, size) in &removed {
+                log.log_success(part, *size, category, permanent);
+            }
+        }
+
+        let failure = match outcome {
+            Ok(DeleteOutcome::Deleted) => {
+                cleaned += 1;
+                if let Some(pb) = progress {
+                    pb.inc(1);
+                }
+                None
+            }
+            Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => None,
+            Ok(DeleteOutcome::SkippedLocked) => {
+                Some("Path is locked by another process".to_string())
+            }
+            Ok(DeleteOutcome::SkippedPermission) => Some("Permission denied".to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+
+        if let Some(reason) = failure {
+            errors += 1;
+            if let Some(log) = history.as_deref_mut() {
+                log.log_failure(path, size, category, permanent, &reason);
+            }
+            if mode != OutputMode::Quiet {
+                eprintln!(
+                    "[WARNING] Failed to clean {}: {}",
+                    Theme::secondary(&path.display().to_string()),
+                    Theme::error(&reason)
+                );
+            }
+        }
+    }
+
+    (cleaned, errors)
+}
+
+/// Helper function to empty the Recycle Bin as a whole
+///
+/// Returns (cleaned, errors, cleaned_bytes).
+fn empty_recycle_bin_internal(
+    result: &CategoryResult,
+    dry_run: bool,
+    progress: Option<&indicatif::ProgressBar>,
+    history: Option<&mut DeletionLog>,
+    mode: OutputMode,
+) -> (u64, u64, u64) {
+    if let Some(pb) = progress {
+        pb.set_message("Emptying Recycle Bin...");
+    }
+    if dry_run {
+        if let Some(pb) = progress {
+            pb.inc(result.items as u64);
+        }
+        return (result.items as u64, 0, result.size_bytes);
+    }
+
+    match categories::trash::clean() {
+        Ok(()) => {
+            if let Some(pb) = progress {
+                pb.inc(result.items as u64);
+            }
+            if let Some(log) = history {
+                log.log_success(Path::new("Recycle Bin"), result.size_bytes, "trash", true);
+            }
+            (result.items as u64, 0, result.size_bytes)
+        }
+        Err(e) => {
+            if let Some(log) = history {
+                log.log_failure(
+                    Path::new("Recycle Bin"),
+                    result.size_bytes,
+                    "trash",
+                    true,
+                    &e.to_string(),
+                );
+            }
+            if mode != OutputMode::Quiet {
+                eprintln!(
+                    "[WARNING] Failed to empty Recycle Bin: {}",
+                    Theme::error(&e.to_string())
+                );
+            }
+            (0, 1, 0)
+        }
+    }
+}
+
+/// Helper function to uninstall applications and remove their leftovers
+///
+/// Returns (cleaned, errors, cleaned_bytes).
+fn uninstall_applications_internal(
+    paths: &[PathBuf],
+    permanent: bool,
+    dry_run: bool,
+    progress: Option<&indicatif::ProgressBar>,
+    mut history: Option<&mut DeletionLog>,
+    mode: OutputMode,
+) -> (u64, u64, u64) {
+    if let Some(pb) = progress {
+        pb.set_message("Uninstalling applications...");
+    }
+
+    let mut cleaned = 0u64;
+    let mut errors = 0u64;
+    let mut cleaned_bytes = 0u64;
+
+    // IMPORTANT: uninstalling applications is not safely restorable, even if permanent=false.
+    // We still honor `permanent` for leftover file deletion (Recycle Bin vs permanent),
+    // but we always log these as permanent to avoid offering restore.
+    let log_as_permanent = true;
+
+    for path in paths {
+        let size = categories::applications::get_app_size(path).unwrap_or_else(|| {
+            if path.is_dir() {
+                utils::calculate_dir_size(path)
+            } else {
+                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
+            }
+        });
+
+        if dry_run {
+            cleaned += 1;
+            if let Some(pb) = progress {
+                pb.inc(1);
+            }
+            cleaned_bytes += size;
+            continue;
+        }
+
+        let display = categories::applications::get_app_display_name(path)
+            .unwrap_or_else(|| path.display().to_string());
+
+        // Tighten: uninstall must succeed before we delete any install/artifact paths.
+        // This avoids leaving the app "installed" but with missing files.
+        let mut had_error = false;
+        let Some(_uninstall_cmd) = categories::applications::get_app_uninstall_string(path) else {
+            // No uninstall command - skip (had_error not set here since we continue)
+            if mode != OutputMode::Quiet {
+                eprintln!(
+                    "[WARNING] Cannot uninstall {}: {}",
+                    Theme::secondary(&display),
+                    Theme::error("No uninstall command in registry")
+                );
+            }
+            // Do not delete any files for this app.
+            // It would leave a broken, still-installed entry.
+            if let Some(log) = history.as_deref_mut() {
+                log.log_failure(
+                    path,
+                    size,
+                    "applications",
+                    log_as_permanent,
+                    "No uninstall command in registry; skipped to avoid breaking installed app",
+                );
+            }
+            errors += 1;
+            continue;
+        };
+
+        if let Err(e) = categories::applications::uninstall(path) {
+            had_error = true;
+            if mode != OutputMode::Quiet {
+                eprintln!(
+                    "[WARNING] Uninstall failed for {}: {}",
+                    Theme::secondary(&display),
+                    Theme::error(&e.to_string())
+                );
+            }
+        }
+
+        // Post-check: if it's still installed, don't delete artifacts (tight/safe).
+        if !had_error && categories::applications::is_still_installed(path) {
+            had_error = true;
+            if mode != OutputMode::Quiet {
+                eprintln!(
+                    "[WARNING] {} still appears installed after uninstall (may require reboot). Skipping artifact deletion.",
+                    Theme::secondary(&display)
+                );
+            }
+        }
+
+        if !had_error {
+            // Only after uninstall succeeds and entry disappears: delete app-specific leftovers.
+            let artifacts = categories::applications::get_app_artifact_paths(path);
+            for artifact in artifacts {
+                match delete_with_precheck(&artifact, permanent) {
+                    Ok(DeleteOutcome::Deleted) => {}
+                    Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
+                    Ok(DeleteOutcome::SkippedLocked | DeleteOutcome::SkippedPermission) => {
+                        had_error = true;
+                    }
+                    Err(_) => had_error = true,
+                }
+            }
+        }
+
+        // Update counters/logs.
+        if had_error {
+            errors += 1;
+            if let Some(log) = history.as_deref_mut() {
+                log.log_failure(
+                    path,
+                    size,
+                    "applications",
+                    log_as_permanent,
+                    "Application uninstall and/or cleanup did not complete",
+                );
+            }
+        } else {
+            cleaned += 1;
+            if let Some(pb) = progress {
+                pb.inc(1);
+            }
+            cleaned_bytes += size;
+            if let Some(log) = history.as_deref_mut() {
+                log.log_success(path, size, "applications", log_as_permanent);
+            }
+        }
+    }
+
+    (cleaned, errors, cleaned_bytes)
+}
+
+/// Helper function to replace duplicate copies with hardlinks or reflinks
+///
+/// Only the copies in `paths` (the scan's duplicates) are touched, and only in
+/// groups that are eligible for `dedupe`. Returns (cleaned, errors, cleaned_bytes).
+fn dedupe_duplicates_internal(
+    groups: &[DuplicateGroup],
+    paths: &[PathBuf],
+    dedupe: DedupeMode,
+    dry_run: bool,
+    progress: Option<&indicatif::ProgressBar>,
+    mut history: Option<&mut DeletionLog>,
+    mode: OutputMode,
+) -> (u64, u64, u64) {
+    if let Some(pb) = progress {
+        pb.set_message(format!("Replacing duplicates ({})...", dedupe.name()));
+    }
+
+    let selected: HashSet<&PathBuf> = paths.iter().collect();
+    let mut cleaned = 0u64;
+    let mut errors = 0u64;
+    let mut cleaned_bytes = 0u64;
+
+    for group in groups {
+        let Some((target, copies)) = duplicates::link_target(group) else {
+            continue;
+        };
+        for copy in copies.into_iter().filter(|copy| selected.contains(copy)) {
+            if let Some(pb) = progress {
+                pb.inc(1);
+            }
+            let result = if group.kind == DuplicateKind::Directory {
+                Err(anyhow::anyhow!(
+                    "Duplicate folders can't be replaced with links"
+                ))
+            } else if group.kind == DuplicateKind::SimilarImages {
+                Err(anyhow::anyhow!(
+                    "Similar images aren't identical, so they can't be replaced with links"
+                ))
+            } else if !group.supports(dedupe) {
+                Err(anyhow::anyhow!(
+                    "Can't {} duplicates on different volumes or without reflink support",
+                    dedupe.name()
+                ))
+            } else if dry_run {
+                Ok(())
+            } else {
+                dedupe_ops::replace_with_link(target, copy, dedupe)
+            };
+
+            let size = group.copy_size(copy);
+            match result {
+                Ok(()) => {
+                    cleaned += 1;
+                    cleaned_bytes += size;
+                    if let Some(log) = history.as_deref_mut() {
+                        log.log_linked(copy, size, "duplicates", dedupe.name());
+                    }
+                }
+                Err(e) => {
+                    errors += 1;
+                    if let Some(log) = history.as_deref_mut() {
+                        log.log_failure(copy, size, "duplicates", false, &e.to_string());
+                    }
+                    if mode != OutputMode::Quiet {
+                        eprintln!(
+                            "[WARNING] Failed to {} {}: {}",
+                            dedupe.name(),
+                            Theme::secondary(&copy.display().to_string()),
+                            Theme::error(&e.to_string())
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    (cleaned, errors, cleaned_bytes)
+}
+
+/// Ask about each group of similar images on its own
+///
+/// Look-alike images aren't identical, so the scan leaves their copies out of
+/// the duplicates to clean and the overall prompt doesn't cover them: each
+/// group's copies need their own yes. Without prompts (`--yes`, dry runs, link
+/// modes) they're all left alone, with a note saying so. Returns the results
+/// with the confirmed copies added, or `None` when none were.
+fn confirm_similar_images(
+    results: &ScanResults,
+    skip_confirm: bool,
+    dry_run: bool,
+    dedupe: DedupeMode,
+    mode: OutputMode,
+) -> Result<Option<ScanResults>> {
+    let groups: Vec<&DuplicateGroup> = results
+        .duplicates_groups
+        .iter()
+        .flatten()
+        .filter(|group| group.kind == DuplicateKind::SimilarImages)
+        .collect();
+    if groups.is_empty() || results.get("duplicates").is_none() {
+        return Ok(None);
+    }
+
+    let skipped_because = if dedupe != DedupeMode::Delete {
+        Some(format!("they can't be replaced with {}s", dedupe.name()))
+    } else if dry_run {
+        Some("a real clean asks about each group before deleting it".to_string())
+    } else if skip_confirm {
+        Some(
+            "--yes doesn't cover them, they're only deleted after confirming each group"
+                .to_string(),
+        )
+    } else {
+        None
+    };
+    if let Some(reason) = skipped_because {
+        if mode != OutputMode::Quiet {
+            println!(
+                "{}",
+                Theme::muted(&format!(
+                    "Leaving {} similar-image {} alone: {}",
+                    groups.len(),
+                    if groups.len() == 1 { "group" } else { "groups" },
+                    reason
+                ))
+            );
+        }
+        return Ok(None);
+    }
+
+    let mut confirmed: Vec<(PathBuf, u64)> = Vec::new();
+    for group in groups {
+        let (kept, copies) = group.split();
+        if copies.is_empty() {
+            continue;
+        }
+        println!();
+        if let Some(kept) = kept.first() {
+            println!(
+                "Similar images (not identical), keeping {}:",
+                Theme::secondary(&kept.display().to_string())
+            );
+        }
+        for copy in &copies {
+            println!(
+                "  {} ({})",
+                copy.display(),
+                bytesize::to_string(group.copy_size(copy), false)
+            );
+        }
+        print!(
+            "Delete {} look-alike {}? [yes/no]: ",
+            copies.len(),
+            if copies.len() == 1 { "copy" } else { "copies" }
+        );
+        let input = read_line_from_stdin()?;
+        if matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            confirmed.extend(copies.into_iter().map(|p| (p.clone(), group.copy_size(p))));
+        }
+    }
+    if confirmed.is_empty() {
+        return Ok(None);
+    }
+
+    let mut with_confirmed = results.clone();
+    if let Some(result) = with_confirmed.get_mut("duplicates") {
+        for (path, size) in confirmed {
+            result.push(path, size);
+        }
+    }
+    Ok(Some(with_confirmed))
+}
+
 /// Clean all categories based on scan results
 ///
 /// Handles confirmation prompts, error tracking, and provides progress feedback
@@ -165,34 +616,40 @@ pub fn clean_all(
     permanent: bool,
     dry_run: bool,
 ) -> Result<()> {
-    let total_items = results.cache.items
-        + results.app_cache.items
-        + results.temp.items
-        + results.trash.items
-        + results.build.items
-        + results.downloads.items
-        + results.large.items
-        + results.old.items
-        + results.browser.items
-        + results.system.items
-        + results.empty.items
-        + results.duplicates.items
-        + results.windows_update.items
-        + results.event_logs.items;
-    let total_bytes = results.cache.size_bytes
-        + results.app_cache.size_bytes
-        + results.temp.size_bytes
-        + results.trash.size_bytes
-        + results.build.size_bytes
-        + results.downloads.size_bytes
-        + results.large.size_bytes
-        + results.old.size_bytes
-        + results.browser.size_bytes
-        + results.system.size_bytes
-        + results.empty.size_bytes
-        + results.duplicates.size_bytes
-        + results.windows_update.size_bytes
-        + results.event_logs.size_bytes;
+    clean_all_with_dedupe(
+        results,
+        skip_confirm,
+        mode,
+        permanent,
+        dry_run,
+        DedupeMode::Delete,
+    )
+}
+
+/// Clean all categories, handling duplicate copies according to `dedupe`
+///
+/// With [`DedupeMode::Link`] or [`DedupeMode::Reflink`], duplicate copies are
+/// replaced with links to the file that's kept instead of being deleted.
+pub fn clean_all_with_dedupe(
+    results: &ScanResults,
+    skip_confirm: bool,
+    mode: OutputMode,
+    permanent: bool,
+    dry_run: bool,
+    dedupe: DedupeMode,
+) -> Result<()> {
+    // Similar images are only deleted group by group, once confirmed
+    let confirmed;
+    let results = match confirm_similar_images(results, skip_confirm, dry_run, dedupe, mode)? {
+        Some(filtered) => {
+            confirmed = filtered;
+            &confirmed
+        }
+        None => results,
+    };
+
+    let total_items = results.total_items();
+    let total_bytes = results.total_bytes();
 
     if total_items == 0 {
         if mode != OutputMode::Quiet {
@@ -216,10 +673,46 @@ pub fn clean_all(
         );
     }
 
+    if dedupe != DedupeMode::Delete && mode != OutputMode::Quiet {
+        println!(
+            "{}",
+            Theme::warning_msg(&format!(
+                "{} MODE - Duplicate copies will be replaced with {}s to the kept file",
+                dedupe.name().to_uppercase(),
+                dedupe.name()
+            ))
+        );
+    }
+
     if !skip_confirm && !dry_run {
+        // Duplicate copies are replaced with links rather than deleted in link modes
+        let linked_items = if dedupe == DedupeMode::Delete {
+            0
+        } else {
+            results
+                .iter()
+                .filter(|scanned| scanned.descriptor.id == "duplicates")
+                .map(|scanned| scanned.result.items)
+                .sum()
+        };
+        let deleted_items = total_items - linked_items;
+        let action = match (deleted_items, linked_items) {
+            (_, 0) => format!("Delete {} items", Theme::value(&total_items.to_string())),
+            (0, _) => format!(
+                "Replace {} duplicates with {}s",
+                Theme::value(&linked_items.to_string()),
+                dedupe.name()
+            ),
+            _ => format!(
+                "Delete {} items and replace {} duplicates with {}s",
+                Theme::value(&deleted_items.to_string()),
+                Theme::value(&linked_items.to_string()),
+                dedupe.name()
+            ),
+        };
         print!(
-            "Delete {} items ({})? [yes/no]: ",
-            Theme::value(&total_items.to_string()),
+            "{} ({})? [yes/no]: ",
+            action,
             Theme::warning(&bytesize::to_string(total_bytes, false))
         );
 
@@ -256,634 +749,75 @@ pub fn clean_all(
     let mut cleaned_bytes = 0u64;
     let mut errors = 0;
 
-    // Clean cache (batch)
-    if results.cache.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.cache.paths,
-            "cache",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.cache.size_bytes;
-    }
-
-    // Clean application cache (batch)
-    if results.app_cache.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.app_cache.paths,
-            "application cache",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.app_cache.size_bytes;
-    }
-
-    // Clean temp (batch)
-    if results.temp.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.temp.paths,
-            "temp files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.temp.size_bytes;
-    }
-
-    // Clean trash
-    if results.trash.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Emptying Recycle Bin...");
-        }
-        if dry_run {
-            cleaned += results.trash.items as u64;
-            if let Some(ref pb) = progress {
-                pb.inc(results.trash.items as u64);
-            }
-            cleaned_bytes += results.trash.size_bytes;
-        } else {
-            match categories::trash::clean() {
-                Ok(()) => {
-                    cleaned += results.trash.items as u64;
-                    if let Some(ref pb) = progress {
-                        pb.inc(results.trash.items as u64);
-                    }
-                    cleaned_bytes += results.trash.size_bytes;
-                    if let Some(ref mut log) = history {
-                        log.log_success(
-                            Path::new("Recycle Bin"),
-                            results.trash.size_bytes,
-                            "trash",
-                            true,
-                        );
-                    }
-                }
-                Err(e) => {
-                    errors += 1;
-                    if let Some(ref mut log) = history {
-                        log.log_failure(
-                            Path::new("Recycle Bin"),
-                            results.trash.size_bytes,
-                            "trash",
-                            true,
-                            &e.to_string(),
-                        );
-                    }
-                    if mode != OutputMode::Quiet {
-                        eprintln!(
-                            "[WARNING] Failed to empty Recycle Bin: {}",
-                            Theme::error(&e.to_string())
-                        );
-                    }
-                }
-            }
+    // Each category is removed the way its descriptor asks for
+    for scanned in results.iter() {
+        let descriptor = &scanned.descriptor;
+        let result = &scanned.result;
+        if result.items == 0 {
+            continue;
         }
-    }
 
-    // Clean build artifacts (batch)
-    if results.build.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.build.paths,
-            "build artifacts",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.build.size_bytes;
-    }
-
-    // Clean downloads (batch)
-    if results.downloads.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.downloads.paths,
-            "old downloads",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.downloads.size_bytes;
-    }
-
-    // Clean large files (batch)
-    if results.large.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.large.paths,
-            "large files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.large.size_bytes;
-    }
-
-    // Clean old files (batch)
-    if results.old.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.old.paths,
-            "old files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.old.size_bytes;
-    }
-
-    // Clean browser caches
-    if results.browser.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning browser caches...");
-        }
-        for path in &results.browser.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match delete_with_precheck(path, permanent) {
-                    Ok(DeleteOutcome::Deleted) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "browser", permanent);
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                    Ok(DeleteOutcome::SkippedLocked) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                size,
-                                "browser",
-                                permanent,
-                                "Path is locked by another process",
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Path is locked by another process")
-                            );
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedPermission) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "browser", permanent, "Permission denied");
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Permission denied")
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "browser", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
+        if descriptor.id == "duplicates" && dedupe != DedupeMode::Delete {
+            let groups = results.duplicates_groups.as_deref().unwrap_or_default();
+            let (success, errs, bytes) = dedupe_duplicates_internal(
+                groups,
+                &result.paths,
+                dedupe,
+                dry_run,
+                progress.as_ref(),
+                history.as_mut(),
+                mode,
+            );
+            cleaned += success;
+            errors += errs;
+            cleaned_bytes += bytes;
+            continue;
         }
-        cleaned_bytes += results.browser.size_bytes;
-    }
 
-    // Clean system caches
-    if results.system.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning system caches...");
-        }
-        for path in &results.system.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match delete_with_precheck(path, permanent) {
-                    Ok(DeleteOutcome::Deleted) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "system", permanent);
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                    Ok(DeleteOutcome::SkippedLocked) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                size,
-                                "system",
-                                permanent,
-                                "Path is locked by another process",
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Path is locked by another process")
-                            );
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedPermission) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "system", permanent, "Permission denied");
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Permission denied")
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "system", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
+        let (success, errs, bytes) = match descriptor.clean_mode {
+            CleanMode::Batch => {
+                let (success, errs) = batch_clean_category_internal(
+                    &result.paths,
+                    &descriptor.display_name,
+                    permanent,
+                    dry_run,
+                    progress.as_ref(),
+                    history.as_mut(),
+                    mode,
+                );
+                (success, errs, result.size_bytes)
             }
-        }
-        cleaned_bytes += results.system.size_bytes;
-    }
-
-    // Clean empty folders
-    if results.empty.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning empty folders...");
-        }
-        for path in &results.empty.paths {
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match delete_with_precheck(path, permanent) {
-                    Ok(DeleteOutcome::Deleted) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, 0, "empty", permanent);
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                    Ok(DeleteOutcome::SkippedLocked) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                0,
-                                "empty",
-                                permanent,
-                                "Path is locked by another process",
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Path is locked by another process")
-                            );
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedPermission) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, 0, "empty", permanent, "Permission denied");
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Permission denied")
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, 0, "empty", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
+            CleanMode::PerPath => {
+                let (success, errs) = per_path_clean_category_internal(
+                    descriptor,
+                    &result.paths,
+                    permanent,
+                    dry_run,
+                    progress.as_ref(),
+                    history.as_mut(),
+                    mode,
+                );
+                (success, errs, result.size_bytes)
             }
-        }
-        cleaned_bytes += results.empty.size_bytes;
-    }
-
-    // Clean duplicate files (batch)
-    if results.duplicates.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.duplicates.paths,
-            "duplicate files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
+            CleanMode::RecycleBin => empty_recycle_bin_internal(
+                result,
+                dry_run,
+                progress.as_ref(),
+                history.as_mut(),
+                mode,
+            ),
+            CleanMode::Uninstall => uninstall_applications_internal(
+                &result.paths,
+                permanent,
+                dry_run,
+                progress.as_ref(),
+                history.as_mut(),
+                mode,
+            ),
+        };
         cleaned += success;
         errors += errs;
-        cleaned_bytes += results.duplicates.size_bytes;
-    }
-
-    // Clean installed applications (batch)
-    if results.applications.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Uninstalling applications...");
-        }
-
-        // IMPORTANT: uninstalling applications is not safely restorable, even if permanent=false.
-        // We still honor `permanent` for leftover file deletion (Recycle Bin vs permanent),
-        // but we always log these as permanent to avoid offering restore.
-        let log_as_permanent = true;
-
-        for path in &results.applications.paths {
-            let size = categories::applications::get_app_size(path).unwrap_or_else(|| {
-                if path.is_dir() {
-                    utils::calculate_dir_size(path)
-                } else {
-                    utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-                }
-            });
-
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-                cleaned_bytes += size;
-                continue;
-            }
-
-            let display = categories::applications::get_app_display_name(path)
-                .unwrap_or_else(|| path.display().to_string());
-
-            // Tighten: uninstall must succeed before we delete any install/artifact paths.
-            // This avoids leaving the app "installed" but with missing files.
-            let mut had_error = false;
-            let Some(_uninstall_cmd) = categories::applications::get_app_uninstall_string(path)
-            else {
-                // No uninstall command - skip (had_error not set here since we continue)
-                if mode != OutputMode::Quiet {
-                    eprintln!(
-                        "[WARNING] Cannot uninstall {}: {}",
-                        Theme::secondary(&display),
-                        Theme::error("No uninstall command in registry")
-                    );
-                }
-                // Do not delete any files for this app.
-                // It would leave a broken, still-installed entry.
-                if let Some(ref mut log) = history {
-                    log.log_failure(
-                        path,
-                        size,
-                        "applications",
-                        log_as_permanent,
-                        "No uninstall command in registry; skipped to avoid breaking installed app",
-                    );
-                }
-                errors += 1;
-                continue;
-            };
-
-            if let Err(e) = categories::applications::uninstall(path) {
-                had_error = true;
-                if mode != OutputMode::Quiet {
-                    eprintln!(
-                        "[WARNING] Uninstall failed for {}: {}",
-                        Theme::secondary(&display),
-                        Theme::error(&e.to_string())
-                    );
-                }
-            }
-
-            // Post-check: if it's still installed, don't delete artifacts (tight/safe).
-            if !had_error && categories::applications::is_still_installed(path) {
-                had_error = true;
-                if mode != OutputMode::Quiet {
-                    eprintln!(
-                        "[WARNING] {} still appears installed after uninstall (may require reboot). Skipping artifact deletion.",
-                        Theme::secondary(&display)
-                    );
-                }
-            }
-
-            if !had_error {
-                // Only after uninstall succeeds and entry disappears: delete app-specific leftovers.
-                let artifacts = categories::applications::get_app_artifact_paths(path);
-                for artifact in artifacts {
-                    match delete_with_precheck(&artifact, permanent) {
-                        Ok(DeleteOutcome::Deleted) => {}
-                        Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                        Ok(DeleteOutcome::SkippedLocked | DeleteOutcome::SkippedPermission) => {
-                            had_error = true;
-                        }
-                        Err(_) => had_error = true,
-                    }
-                }
-            }
-
-            // Update counters/logs.
-            if had_error {
-                errors += 1;
-                if let Some(ref mut log) = history {
-                    log.log_failure(
-                        path,
-                        size,
-                        "applications",
-                        log_as_permanent,
-                        "Application uninstall and/or cleanup did not complete",
-                    );
-                }
-            } else {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-                cleaned_bytes += size;
-                if let Some(ref mut log) = history {
-                    log.log_success(path, size, "applications", log_as_permanent);
-                }
-            }
-        }
-    }
-
-    // Clean Windows Update files
-    if results.windows_update.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning Windows Update files...");
-        }
-        for path in &results.windows_update.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match categories::windows_update::clean(path) {
-                    Ok(()) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "windows_update", permanent);
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                size,
-                                "windows_update",
-                                permanent,
-                                &e.to_string(),
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
-        }
-        cleaned_bytes += results.windows_update.size_bytes;
-    }
-
-    // Clean Event Logs
-    if results.event_logs.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning Event Logs...");
-        }
-        for path in &results.event_logs.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match categories::event_logs::clean(path) {
-                    Ok(()) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "event_logs", permanent);
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "event_logs", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
-        }
-        cleaned_bytes += results.event_logs.size_bytes;
+        cleaned_bytes += bytes;
     }
 
     // Finish progress bar
@@ -946,6 +880,7 @@ pub fn clean_all(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::categories::registry::CategoryRegistry;
     use crate::output::ScanResults;
     use std::fs;
     use tempfile::TempDir;
@@ -971,9 +906,16 @@ mod tests {
         fs::write(&file, "test content").unwrap();
 
         let mut results = ScanResults::default();
-        results.cache.paths.push(file.clone());
-        results.cache.items = 1;
-        results.cache.size_bytes = 12;
+        let cache = CategoryRegistry::builtin().get("cache").unwrap().clone();
+        results.insert(
+            cache,
+            CategoryResult {
+                items: 1,
+                size_bytes: 12,
+                paths: vec![file.clone()],
+                ..Default::default()
+            },
+        );
 
         // Dry run should not delete the file
         // Use Quiet mode in tests to avoid spinner thread issues
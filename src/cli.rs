@@ -1,7 +1,10 @@
-use clap::{ArgAction, Parser, Subcommand};
+use clap::{Arg, ArgAction, ArgMatches, Args, Command, FromArgMatches, Parser, Subcommand};
 use std::path::PathBuf;
 
+use crate::categories::registry::CategoryRegistry;
+use crate::dedupe::DedupeMode;
 use crate::output::OutputMode;
+use crate::projects::ProjectSort;
 
 pub mod commands;
 mod interactive_menu;
@@ -45,49 +48,12 @@ pub enum Commands {
         #[arg(short = 'a', long)]
         all: bool,
 
-        /// Scan package manager cache directories (npm, pip, nuget, etc.)
-        #[arg(long)]
-        cache: bool,
-
-        /// Scan application cache directories (Discord, VS Code, Slack, etc.)
-        #[arg(long)]
-        app_cache: bool,
-
-        /// Scan temporary files (system temp folders)
-        #[arg(long)]
-        temp: bool,
-
-        /// Scan Recycle Bin contents
-        #[arg(long)]
-        trash: bool,
-
-        /// Scan build artifacts from inactive projects (node_modules, target, etc.)
-        #[arg(long)]
-        build: bool,
-
-        /// Scan Downloads folder for old files
-        #[arg(long)]
-        downloads: bool,
-
-        /// Scan for files over size threshold
-        #[arg(long)]
-        large: bool,
-
-        /// Scan for files not accessed in N days
-        #[arg(long)]
-        old: bool,
-
-        /// Scan for installed applications (Windows only)
-        #[arg(long)]
-        applications: bool,
-
-        /// Scan Windows Update files (download cache, logs) - requires admin
-        #[arg(long)]
-        windows_update: bool,
+        #[command(flatten)]
+        categories: CategoryFlags,
 
-        /// Scan Windows Event Log files (old .evtx files) - requires admin
-        #[arg(long)]
-        event_logs: bool,
+        /// Scan a user-defined category from config.toml by name (repeatable)
+        #[arg(long, value_name = "NAME")]
+        custom: Vec<String>,
 
         /// Root path to scan (default: home directory)
         #[arg(long, value_name = "PATH")]
@@ -124,6 +90,10 @@ pub enum Commands {
         /// Clear scan cache before running
         #[arg(long)]
         clear_cache: bool,
+
+        /// Show what changed since the last scan, or since scan session SESSION
+        #[arg(long, value_name = "SESSION", num_args = 0..=1)]
+        diff: Option<Option<i64>>,
     },
 
     /// Delete files found by scan (with confirmation)
@@ -133,65 +103,17 @@ pub enum Commands {
         #[arg(short = 'a', long)]
         all: bool,
 
-        /// Clean package manager cache directories (npm, pip, nuget, etc.)
-        #[arg(long)]
-        cache: bool,
-
-        /// Clean application cache directories (Discord, VS Code, Slack, etc.)
-        #[arg(long)]
-        app_cache: bool,
-
-        /// Clean temporary files (system temp folders)
-        #[arg(long)]
-        temp: bool,
-
-        /// Empty Recycle Bin
-        #[arg(long)]
-        trash: bool,
-
-        /// Clean build artifacts from inactive projects (node_modules, target, etc.)
-        #[arg(long)]
-        build: bool,
-
-        /// Clean old files in Downloads folder
-        #[arg(long)]
-        downloads: bool,
-
-        /// Clean files over size threshold
-        #[arg(long)]
-        large: bool,
-
-        /// Clean files not accessed in N days
-        #[arg(long)]
-        old: bool,
-
-        /// Clean browser caches (Chrome, Edge, Firefox)
-        #[arg(long)]
-        browser: bool,
-
-        /// Clean Windows system caches (thumbnails, updates, icons)
-        #[arg(long)]
-        system: bool,
-
-        /// Clean empty folders
-        #[arg(long)]
-        empty: bool,
-
-        /// Clean duplicate files (keeps one copy)
-        #[arg(long)]
-        duplicates: bool,
-
-        /// Clean installed applications (Windows only)
-        #[arg(long)]
-        applications: bool,
+        #[command(flatten)]
+        categories: CategoryFlags,
 
-        /// Clean Windows Update files (download cache, logs) - requires admin
+        /// With --duplicates, also group images that look the same (resized,
+        /// re-encoded or converted copies)
         #[arg(long)]
-        windows_update: bool,
+        similar_images: bool,
 
-        /// Clean Windows Event Log files (old .evtx files) - requires admin
-        #[arg(long)]
-        event_logs: bool,
+        /// Clean a user-defined category from config.toml by name (repeatable)
+        #[arg(long, value_name = "NAME")]
+        custom: Vec<String>,
 
         /// Root path to scan (default: home directory)
         #[arg(long, value_name = "PATH")]
@@ -228,6 +150,12 @@ pub enum Commands {
         /// Preview only, don't delete
         #[arg(long)]
         dry_run: bool,
+
+        /// What to do with duplicate copies: delete, link (hardlink to the kept
+        /// file, same volume only; the copy then shows the kept file's timestamps)
+        /// or reflink (copy-on-write clone keeping the copy's timestamps, btrfs/XFS)
+        #[arg(long, default_value = "delete", value_name = "MODE")]
+        dedupe_mode: DedupeMode,
     },
 
     /// Show detailed analysis with file lists
@@ -257,61 +185,25 @@ pub enum Commands {
         #[arg(long, value_name = "SORT")]
         sort: Option<String>,
 
+        /// Show how fast folders grew across earlier disk scans (per day and week)
+        #[arg(long)]
+        trend: bool,
+
         /// Enable all scan categories (legacy cleanable file analysis)
         #[arg(short = 'a', long)]
         all: bool,
 
-        /// Scan package manager cache directories (npm, pip, nuget, etc.)
-        #[arg(long)]
-        cache: bool,
-
-        /// Scan application cache directories (Discord, VS Code, Slack, etc.)
-        #[arg(long)]
-        app_cache: bool,
-
-        /// Scan temporary files (system temp folders)
-        #[arg(long)]
-        temp: bool,
-
-        /// Scan Recycle Bin contents
-        #[arg(long)]
-        trash: bool,
-
-        /// Scan build artifacts from inactive projects (node_modules, target, etc.)
-        #[arg(long)]
-        build: bool,
-
-        /// Scan Downloads folder for old files
-        #[arg(long)]
-        downloads: bool,
-
-        /// Scan for files over size threshold
-        #[arg(long)]
-        large: bool,
+        #[command(flatten)]
+        categories: CategoryFlags,
 
-        /// Scan for files not accessed in N days
+        /// With --duplicates, also group images that look the same (resized,
+        /// re-encoded or converted copies)
         #[arg(long)]
-        old: bool,
+        similar_images: bool,
 
-        /// Scan browser caches (Chrome, Edge, Firefox)
-        #[arg(long)]
-        browser: bool,
-
-        /// Scan Windows system caches (thumbnails, updates, icons)
-        #[arg(long)]
-        system: bool,
-
-        /// Scan for empty folders
-        #[arg(long)]
-        empty: bool,
-
-        /// Scan for duplicate files
-        #[arg(long)]
-        duplicates: bool,
-
-        /// Scan for installed applications (Windows only)
-        #[arg(long)]
-        applications: bool,
+        /// Scan a user-defined category from config.toml by name (repeatable)
+        #[arg(long, value_name = "NAME")]
+        custom: Vec<String>,
 
         /// Root path to scan (default: user profile)
         #[arg(long, value_name = "PATH")]
@@ -475,6 +367,62 @@ pub enum Commands {
         new: bool,
     },
 
+    /// List discovered projects with their activity and build artifact sizes
+    #[command(visible_alias = "p")]
+    Projects {
+        /// Root path to search (default: current directory)
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Sort by size, activity, name or path [default: size]
+        #[arg(long, default_value = "size", value_name = "ORDER")]
+        sort: ProjectSort,
+
+        /// Only show projects of this type, e.g. rust, node, dotnet (repeatable)
+        #[arg(long = "type", value_name = "TYPE")]
+        types: Vec<String>,
+
+        /// Only show inactive projects
+        #[arg(long)]
+        inactive: bool,
+
+        /// Project inactivity threshold in days [default: 14]
+        #[arg(long, default_value = "14", value_name = "DAYS")]
+        project_age: u64,
+
+        /// Exclude paths matching pattern (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        exclude: Vec<String>,
+    },
+
+    /// Serve scan, clean, restore, status and disk insights over a local JSON-RPC socket
+    ///
+    /// For GUIs and editor integrations: newline-delimited JSON-RPC 2.0 with
+    /// progress notifications and cancellation. Runs until stopped.
+    Serve {
+        /// Socket path, or pipe name on Windows [default: wole.sock in the wole data folder, pipe "wole" on Windows]
+        #[arg(long, value_name = "NAME")]
+        socket: Option<String>,
+    },
+
+    /// Keep the scan cache warm by watching folders for changes
+    ///
+    /// Runs until stopped. Changes are applied to the scan cache as they
+    /// happen, so the next scan only has to walk the folders that changed.
+    Watch {
+        /// Folder to watch (repeatable; default: paths.scan_roots from the config, else home)
+        #[arg(long = "path", value_name = "PATH")]
+        paths: Vec<PathBuf>,
+
+        /// Skip the scan that warms up the cache when watching starts
+        #[arg(long)]
+        no_initial_scan: bool,
+    },
+
     /// Manage Windows startup programs
     #[command(visible_alias = "su")]
     Startup {
@@ -521,17 +469,8 @@ impl Cli {
             Some(command) => match command {
                 Commands::Scan {
                     all,
-                    cache,
-                    app_cache,
-                    temp,
-                    trash,
-                    build,
-                    downloads,
-                    large,
-                    old,
-                    applications,
-                    windows_update,
-                    event_logs,
+                    categories,
+                    custom,
                     path,
                     json,
                     project_age,
@@ -541,19 +480,11 @@ impl Cli {
                     force_full,
                     no_cache,
                     clear_cache,
+                    diff,
                 } => commands::scan_command::handle_scan(
                     all,
-                    cache,
-                    app_cache,
-                    temp,
-                    trash,
-                    build,
-                    downloads,
-                    large,
-                    old,
-                    applications,
-                    windows_update,
-                    event_logs,
+                    categories,
+                    custom,
                     path,
                     json,
                     project_age,
@@ -563,25 +494,14 @@ impl Cli {
                     force_full,
                     no_cache,
                     clear_cache,
+                    diff,
                     output_mode,
                 ),
                 Commands::Clean {
                     all,
-                    cache,
-                    app_cache,
-                    temp,
-                    trash,
-                    build,
-                    downloads,
-                    large,
-                    old,
-                    browser,
-                    system,
-                    empty,
-                    duplicates,
-                    applications,
-                    windows_update,
-                    event_logs,
+                    categories,
+                    similar_images,
+                    custom,
                     path,
                     json,
                     yes,
@@ -591,23 +511,12 @@ impl Cli {
                     exclude,
                     permanent,
                     dry_run,
+                    dedupe_mode,
                 } => commands::clean_command::handle_clean(
                     all,
-                    cache,
-                    app_cache,
-                    temp,
-                    trash,
-                    build,
-                    downloads,
-                    large,
-                    old,
-                    browser,
-                    system,
-                    empty,
-                    duplicates,
-                    applications,
-                    windows_update,
-                    event_logs,
+                    categories,
+                    similar_images,
+                    custom,
                     path,
                     json,
                     yes,
@@ -617,6 +526,7 @@ impl Cli {
                     exclude,
                     permanent,
                     dry_run,
+                    dedupe_mode,
                     output_mode,
                 ),
                 Commands::Analyze {
@@ -626,20 +536,11 @@ impl Cli {
                     depth,
                     top,
                     sort,
+                    trend,
                     all,
-                    cache,
-                    app_cache,
-                    temp,
-                    trash,
-                    build,
-                    downloads,
-                    large,
-                    old,
-                    browser,
-                    system,
-                    empty,
-                    duplicates,
-                    applications,
+                    categories,
+                    similar_images,
+                    custom,
                     path,
                     project_age,
                     min_age,
@@ -652,20 +553,11 @@ impl Cli {
                     depth,
                     top,
                     sort,
+                    trend,
                     all,
-                    cache,
-                    app_cache,
-                    temp,
-                    trash,
-                    build,
-                    downloads,
-                    large,
-                    old,
-                    browser,
-                    system,
-                    empty,
-                    duplicates,
-                    applications,
+                    categories,
+                    similar_images,
+                    custom,
                     path,
                     project_age,
                     min_age,
@@ -772,6 +664,31 @@ impl Cli {
                 Commands::Status { json, watch, new } => {
                     commands::status_command::handle_status(json, watch, new)
                 }
+                Commands::Projects {
+                    path,
+                    json,
+                    sort,
+                    types,
+                    inactive,
+                    project_age,
+                    exclude,
+                } => commands::projects_command::handle_projects(
+                    path,
+                    json,
+                    sort,
+                    types,
+                    inactive,
+                    project_age,
+                    exclude,
+                    output_mode,
+                ),
+                Commands::Serve { socket } => {
+                    commands::serve_command::handle_serve(socket, output_mode)
+                }
+                Commands::Watch {
+                    paths,
+                    no_initial_scan,
+                } => commands::watch_command::handle_watch(paths, no_initial_scan, output_mode),
                 Commands::Startup {
                     list,
                     disable,
@@ -783,24 +700,111 @@ impl Cli {
     }
 }
 
-#[derive(Clone)]
+/// One `--<id>` flag per built-in category, generated from the registry
+///
+/// `--app-cache` selects `app_cache` and so on, so `scan`, `clean` and `analyze`
+/// pick up a new built-in category without a field of their own.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryFlags {
+    /// Registry ids of the categories whose flag was given, in registry order
+    pub ids: Vec<String>,
+}
+
+impl CategoryFlags {
+    /// Ids of the selected built-in categories: all of them with `--all`
+    pub fn selected(&self, all: bool) -> Vec<String> {
+        if all {
+            CategoryRegistry::builtin()
+                .iter()
+                .map(|d| d.id.clone())
+                .collect()
+        } else {
+            self.ids.clone()
+        }
+    }
+}
+
+impl FromArgMatches for CategoryFlags {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        let mut flags = Self::default();
+        flags.update_from_arg_matches(matches)?;
+        Ok(flags)
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        for descriptor in CategoryRegistry::builtin().iter() {
+            if matches.get_flag(&descriptor.id) && !self.ids.contains(&descriptor.id) {
+                self.ids.push(descriptor.id.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Args for CategoryFlags {
+    fn augment_args(cmd: Command) -> Command {
+        CategoryRegistry::builtin()
+            .iter()
+            .fold(cmd, |cmd, descriptor| {
+                cmd.arg(
+                    Arg::new(descriptor.id.clone())
+                        .long(descriptor.id.replace('_', "-"))
+                        .action(ArgAction::SetTrue)
+                        .help(format!(
+                            "{}: {}",
+                            descriptor.display_name, descriptor.description
+                        )),
+                )
+            })
+    }
+
+    fn augment_args_for_update(cmd: Command) -> Command {
+        Self::augment_args(cmd)
+    }
+}
+
+#[derive(Clone, Default)]
 pub struct ScanOptions {
-    pub cache: bool,
-    pub app_cache: bool,
-    pub temp: bool,
-    pub trash: bool,
-    pub build: bool,
-    pub downloads: bool,
-    pub large: bool,
-    pub old: bool,
-    pub applications: bool,
-    pub browser: bool,
-    pub system: bool,
-    pub empty: bool,
-    pub duplicates: bool,
-    pub windows_update: bool,
-    pub event_logs: bool,
+    /// Registry ids of the categories to scan: built-in ids and the names of
+    /// user-defined categories from config.toml
+    pub categories: Vec<String>,
     pub project_age_days: u64,
     pub min_age_days: u64,
     pub min_size_bytes: u64,
 }
+
+impl ScanOptions {
+    /// Whether the category with the given registry id is selected
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.categories.iter().any(|c| c.eq_ignore_ascii_case(id))
+    }
+
+    /// Select or deselect the category with the given registry id
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) {
+        self.categories.retain(|c| !c.eq_ignore_ascii_case(id));
+        if enabled {
+            self.categories.push(id.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_flags_come_from_the_registry() {
+        let cli = Cli::try_parse_from(["wole", "scan", "--toolchains", "--app-cache"]).unwrap();
+        let Some(Commands::Scan { categories, .. }) = cli.command else {
+            panic!("expected the scan command");
+        };
+        assert_eq!(categories.ids, vec!["app_cache", "toolchains"]);
+
+        for descriptor in CategoryRegistry::builtin().iter() {
+            let flag = format!("--{}", descriptor.id.replace('_', "-"));
+            for command in ["scan", "clean", "analyze"] {
+                assert!(Cli::try_parse_from(["wole", command, flag.as_str()]).is_ok());
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module owns and handles the "wole analyze" command behavior.
 
-use crate::cli::ScanOptions;
+use crate::cli::{CategoryFlags, ScanOptions};
 use crate::config::Config;
 use crate::output::{self, OutputMode};
 use crate::scanner;
@@ -17,20 +17,11 @@ pub(crate) fn handle_analyze(
     depth: u8,
     top: Option<usize>,
     sort: Option<String>,
+    trend: bool,
     all: bool,
-    cache: bool,
-    app_cache: bool,
-    temp: bool,
-    trash: bool,
-    build: bool,
-    downloads: bool,
-    large: bool,
-    old: bool,
-    browser: bool,
-    system: bool,
-    empty: bool,
-    duplicates: bool,
-    applications: bool,
+    categories: CategoryFlags,
+    similar_images: bool,
+    custom: Vec<String>,
     path: Option<PathBuf>,
     project_age: u64,
     min_age: u64,
@@ -42,21 +33,8 @@ pub(crate) fn handle_analyze(
     let config = Config::load();
 
     // Determine if we're in disk insights mode or legacy cleanable file mode
-    let has_category_flags = cache
-        || app_cache
-        || temp
-        || trash
-        || build
-        || downloads
-        || large
-        || old
-        || browser
-        || system
-        || empty
-        || duplicates
-        || applications
-        || all;
-    let disk_mode = disk || (!has_category_flags); // Default to disk mode if no category flags
+    let has_category_flags = all || !categories.ids.is_empty() || !custom.is_empty();
+    let disk_mode = disk || trend || (!has_category_flags); // Default to disk mode if no category flags
 
     if disk_mode {
         // Disk insights mode
@@ -134,8 +112,13 @@ pub(crate) fn handle_analyze(
                 cursor: 0,
                 sort_by,
                 selected_paths: std::collections::HashSet::new(),
+                trends: crate::disk_trend::load_trends(&scan_path).unwrap_or_default(),
             };
             tui::run(Some(app_state))?;
+        } else if trend {
+            // The scan above recorded today's sizes (unless it came from the cache)
+            let trends = crate::disk_trend::load_trends(&scan_path)?;
+            output::print_folder_trends(&trends, &scan_path, top.unwrap_or(10), output_mode);
         } else {
             // CLI output mode
             output::print_disk_insights(
@@ -150,42 +133,6 @@ pub(crate) fn handle_analyze(
         Ok(())
     } else {
         // Legacy cleanable file analysis mode
-        let (
-            cache,
-            app_cache,
-            temp,
-            trash,
-            build,
-            downloads,
-            large,
-            old,
-            applications,
-            browser,
-            system,
-            empty,
-            duplicates,
-        ) = if all {
-            (
-                true, true, true, true, true, true, true, true, true, true, true, true, true,
-            )
-        } else {
-            (
-                cache,
-                app_cache,
-                temp,
-                trash,
-                build,
-                downloads,
-                large,
-                old,
-                applications,
-                browser,
-                system,
-                empty,
-                duplicates,
-            )
-        };
-
         let scan_path = path.unwrap_or_else(|| {
             directories::UserDirs::new()
                 .expect("Failed to get user directory")
@@ -209,6 +156,10 @@ pub(crate) fn handle_analyze(
 
         // Merge CLI exclusions
         config.exclusions.patterns.extend(exclude.iter().cloned());
+        config.categories.duplicates.similar_images |= similar_images;
+
+        // Resolve user-defined categories (--all selects every configured one)
+        let custom = config.select_custom_categories(all, &custom)?;
 
         // Use config values (after CLI overrides) for scan options
         let min_size_bytes = config.thresholds.min_size_mb * 1024 * 1024;
@@ -235,21 +186,8 @@ pub(crate) fn handle_analyze(
         let results = scanner::scan_all(
             &scan_path,
             ScanOptions {
-                cache,
-                app_cache,
-                temp,
-                trash,
-                build,
-                downloads,
-                large,
-                old,
-                applications,
-                browser,
-                system,
-                empty,
-                duplicates,
-                windows_update: false,
-                event_logs: false,
+                // --all enables all categories
+                categories: categories.selected(all).into_iter().chain(custom).collect(),
                 project_age_days: config.thresholds.project_age_days,
                 min_age_days: config.thresholds.min_age_days,
                 min_size_bytes,
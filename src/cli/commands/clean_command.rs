@@ -3,8 +3,9 @@
 //! This module owns and handles the "wole clean" command behavior.
 
 use crate::cleaner;
-use crate::cli::ScanOptions;
+use crate::cli::{CategoryFlags, ScanOptions};
 use crate::config::Config;
+use crate::dedupe::DedupeMode;
 use crate::output::{self, OutputMode};
 use crate::scanner;
 use crate::size;
@@ -50,21 +51,9 @@ fn get_disk_space_for_path(path: &Path) -> Option<(u64, u64)> {
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_clean(
     all: bool,
-    cache: bool,
-    app_cache: bool,
-    temp: bool,
-    trash: bool,
-    build: bool,
-    downloads: bool,
-    large: bool,
-    old: bool,
-    browser: bool,
-    system: bool,
-    empty: bool,
-    duplicates: bool,
-    applications: bool,
-    windows_update: bool,
-    event_logs: bool,
+    categories: CategoryFlags,
+    similar_images: bool,
+    custom: Vec<String>,
     path: Option<PathBuf>,
     json: bool,
     yes: bool,
@@ -74,69 +63,15 @@ pub(crate) fn handle_clean(
     exclude: Vec<String>,
     permanent: bool,
     dry_run: bool,
+    dedupe_mode: DedupeMode,
     output_mode: OutputMode,
 ) -> anyhow::Result<()> {
-    // --all enables all categories
-    let (
-        cache,
-        app_cache,
-        temp,
-        trash,
-        build,
-        downloads,
-        large,
-        old,
-        applications,
-        browser,
-        system,
-        empty,
-        duplicates,
-        windows_update,
-        event_logs,
-    ) = if all {
-        (
-            true, true, true, true, true, true, true, true, true, true, true, true, true, true,
-            true,
-        )
-    } else if !cache
-        && !app_cache
-        && !temp
-        && !trash
-        && !build
-        && !downloads
-        && !large
-        && !old
-        && !browser
-        && !system
-        && !empty
-        && !duplicates
-        && !applications
-        && !windows_update
-        && !event_logs
-    {
+    if !all && categories.ids.is_empty() && custom.is_empty() {
         // No categories specified - show help message
         eprintln!("No categories specified. Use --all or specify categories like --cache, --app-cache, --temp, --build");
         eprintln!("Run 'wole clean --help' for more information.");
         return Ok(());
-    } else {
-        (
-            cache,
-            app_cache,
-            temp,
-            trash,
-            build,
-            downloads,
-            large,
-            old,
-            applications,
-            browser,
-            system,
-            empty,
-            duplicates,
-            windows_update,
-            event_logs,
-        )
-    };
+    }
 
     let scan_path = path.unwrap_or_else(|| {
         directories::UserDirs::new()
@@ -161,6 +96,10 @@ pub(crate) fn handle_clean(
 
     // Merge CLI exclusions
     config.exclusions.patterns.extend(exclude.iter().cloned());
+    config.categories.duplicates.similar_images |= similar_images;
+
+    // Resolve user-defined categories (--all selects every configured one)
+    let custom = config.select_custom_categories(all, &custom)?;
 
     let mut scan_cache = if config.cache.enabled {
         match crate::scan_cache::ScanCache::open() {
@@ -238,22 +177,9 @@ pub(crate) fn handle_clean(
     // Use config values (after CLI overrides) for scan options
     let min_size_bytes = config.thresholds.min_size_mb * 1024 * 1024;
 
+    // --all enables all categories
     let scan_options = ScanOptions {
-        cache,
-        app_cache,
-        temp,
-        trash,
-        build,
-        downloads,
-        large,
-        old,
-        applications,
-        browser,
-        system,
-        empty,
-        duplicates,
-        windows_update,
-        event_logs,
+        categories: categories.selected(all).into_iter().chain(custom).collect(),
         project_age_days: config.thresholds.project_age_days,
         min_age_days: config.thresholds.min_age_days,
         min_size_bytes,
@@ -317,7 +243,7 @@ pub(crate) fn handle_clean(
         }
     }
 
-    cleaner::clean_all(&results, yes, output_mode, permanent, dry_run)?;
+    cleaner::clean_all_with_dedupe(&results, yes, output_mode, permanent, dry_run, dedupe_mode)?;
 
     Ok(())
 }
@@ -6,9 +6,12 @@ pub mod analyze_command;
 pub mod clean_command;
 pub mod config_command;
 pub mod optimize_command;
+pub mod projects_command;
 pub mod remove_command;
 pub mod restore_command;
 pub mod scan_command;
+pub mod serve_command;
 pub mod startup_command;
 pub mod status_command;
 pub mod update_command;
+pub mod watch_command;
@@ -0,0 +1,170 @@
+//! Projects command feature.
+//!
+//! This module owns and handles the "wole projects" command behavior.
+
+use crate::config::Config;
+use crate::output::OutputMode;
+use crate::project::ProjectType;
+use crate::projects::{self, ProjectInfo, ProjectSort};
+use crate::theme::Theme;
+use crate::utils;
+use std::path::PathBuf;
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_projects(
+    path: Option<PathBuf>,
+    json: bool,
+    sort: ProjectSort,
+    types: Vec<String>,
+    inactive: bool,
+    project_age: u64,
+    exclude: Vec<String>,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let types: Vec<ProjectType> = types
+        .iter()
+        .map(|name| {
+            ProjectType::from_name(name)
+                .ok_or_else(|| anyhow::anyhow!("Unknown project type '{}'", name))
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    // Same default as scan: the current directory
+    let scan_path =
+        path.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+    let mut config = Config::load();
+    config.apply_cli_overrides(Some(project_age), None, None);
+    config.exclusions.patterns.extend(exclude.iter().cloned());
+
+    let spinner = if !json && output_mode != OutputMode::Quiet {
+        Some(crate::progress::create_spinner(&format!(
+            "Finding projects in {}...",
+            scan_path.display()
+        )))
+    } else {
+        None
+    };
+    let mut found = projects::discover(&scan_path, project_age, &config, &|_| {});
+    if let Some(sp) = spinner {
+        crate::progress::finish_and_clear(&sp);
+    }
+
+    found.retain(|p| !inactive || !p.is_active);
+    found.retain(|p| types.is_empty() || types.iter().any(|t| p.has_type(*t)));
+    projects::sort_projects(&mut found, sort);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&found)?);
+        return Ok(());
+    }
+    print_projects(&found, &scan_path, output_mode);
+    Ok(())
+}
+
+fn print_projects(found: &[ProjectInfo], scan_path: &std::path::Path, output_mode: OutputMode) {
+    println!();
+    println!("{}", Theme::header("Projects"));
+    println!("{}", Theme::divider_bold(60));
+    println!();
+
+    if found.is_empty() {
+        println!("{}", Theme::muted("No projects found."));
+        println!();
+        return;
+    }
+
+    let size = |bytes: u64| bytesize::to_string(bytes, false);
+    for project in found {
+        let status = if project.is_active {
+            Theme::status_safe("active")
+        } else {
+            Theme::status_review("inactive")
+        };
+        let signal = match &project.signal_member {
+            Some(member) => format!(
+                "{} in {}",
+                project.signal,
+                utils::to_relative_path(member, &project.path)
+            ),
+            None => project.signal.clone(),
+        };
+        let last_activity = project
+            .last_activity
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "-".to_string());
+
+        println!(
+            "{} {} {} ({}: {})",
+            Theme::value(&utils::to_relative_path(&project.path, scan_path)),
+            Theme::muted(&format!("[{}]", project.types.join(", "))),
+            Theme::size(&size(project.total_bytes)),
+            status,
+            signal
+        );
+        let mut details = vec![format!("last activity {}", last_activity)];
+        if let Some(workspace) = &project.workspace {
+            details.push(workspace.clone());
+        }
+        match &project.git_root {
+            Some(git_root) if git_root != &project.path => {
+                details.push(format!("git root {}", git_root.display()))
+            }
+            Some(_) => details.push("git repository".to_string()),
+            None => details.push("no git repository".to_string()),
+        }
+        println!("    {}", Theme::muted(&details.join(" · ")));
+
+        let show_count = match output_mode {
+            OutputMode::Verbose | OutputMode::VeryVerbose => project.artifacts.len(),
+            _ => 5.min(project.artifacts.len()),
+        };
+        for artifact in project.artifacts.iter().take(show_count) {
+            let note = artifact
+                .policy
+                .as_ref()
+                .map(|policy| Theme::muted(&format!(" ({})", policy)))
+                .unwrap_or_default();
+            println!(
+                "    {} {} {}{}",
+                Theme::muted("→"),
+                utils::to_relative_path(&artifact.path, &project.path),
+                Theme::size(&size(artifact.size_bytes)),
+                note
+            );
+        }
+        if project.artifacts.len() > show_count {
+            println!(
+                "    {} ... and {} more (use -v to see all)",
+                Theme::muted("→"),
+                project.artifacts.len() - show_count
+            );
+        }
+    }
+
+    let inactive: Vec<&ProjectInfo> = found.iter().filter(|p| !p.is_active).collect();
+    let total: u64 = found.iter().map(|p| p.total_bytes).sum();
+    let reclaimable: u64 = inactive
+        .iter()
+        .flat_map(|p| p.artifacts.iter())
+        .filter(|a| !a.kept_by_policy)
+        .map(|a| a.size_bytes)
+        .sum();
+    println!();
+    println!("{}", Theme::divider(60));
+    println!(
+        "{} projects ({} inactive) · {} in build artifacts · {} reclaimable",
+        found.len(),
+        inactive.len(),
+        Theme::size(&size(total)),
+        Theme::size(&size(reclaimable))
+    );
+    if reclaimable > 0 {
+        println!(
+            "{} Run {} to remove artifacts of inactive projects",
+            Theme::muted("→"),
+            Theme::command("wole clean --build")
+        );
+    }
+    println!();
+}
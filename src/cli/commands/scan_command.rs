@@ -2,9 +2,12 @@
 //!
 //! This module owns and handles the "wole scan" command behavior.
 
-use crate::cli::ScanOptions;
+use crate::cli::{CategoryFlags, ScanOptions};
 use crate::config::Config;
 use crate::output::{self, OutputMode};
+use crate::scan_cache::database::normalize_path;
+use crate::scan_cache::{ScanCache, ScanSession, SessionItem};
+use crate::scan_diff;
 use crate::scanner;
 use crate::size;
 use crate::theme::Theme;
@@ -46,20 +49,68 @@ fn get_disk_space_for_path(path: &Path) -> Option<(u64, u64)> {
     best.map(|(_, total, avail)| (total, avail))
 }
 
+/// The scan session `--diff` compares with and its stored results
+///
+/// Without a session ID, the most recent session with stored results of the
+/// same scan root; `None` when there is none yet. A session of another scan
+/// root is refused: every item would show up as added or removed.
+fn diff_baseline(
+    scan_cache: Option<&ScanCache>,
+    session_id: Option<i64>,
+    scan_path: &Path,
+) -> anyhow::Result<Option<(ScanSession, Vec<SessionItem>)>> {
+    let opened;
+    let cache = match scan_cache {
+        Some(cache) => cache,
+        None => {
+            opened = ScanCache::open()?;
+            &opened
+        }
+    };
+
+    // Stored canonicalized, like the scanner records it
+    let scan_root = scan_path
+        .canonicalize()
+        .unwrap_or_else(|_| scan_path.to_path_buf());
+    let session_id = match session_id {
+        Some(id) => id,
+        None => match cache.get_previous_stored_session(None, Some(&scan_root))? {
+            Some(id) => id,
+            None => return Ok(None),
+        },
+    };
+    let stored = cache.get_stored_sessions()?;
+    let Some(session) = stored.iter().find(|s| s.id == session_id).cloned() else {
+        let available: Vec<String> = stored.iter().map(|s| s.id.to_string()).collect();
+        return Err(anyhow::anyhow!(
+            "Scan session {} has no stored results (sessions available: {})",
+            session_id,
+            if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            }
+        ));
+    };
+    if let Some(root) = &session.scan_root {
+        if normalize_path(root) != normalize_path(&scan_root) {
+            anyhow::bail!(
+                "Scan session {} scanned {}, not {}: compare with a session of the same folder",
+                session_id,
+                root.display(),
+                scan_root.display()
+            );
+        }
+    }
+    let items = cache.get_session_items(session_id)?;
+    Ok(Some((session, items)))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_scan(
     all: bool,
-    cache: bool,
-    app_cache: bool,
-    temp: bool,
-    trash: bool,
-    build: bool,
-    downloads: bool,
-    large: bool,
-    old: bool,
-    applications: bool,
-    windows_update: bool,
-    event_logs: bool,
+    categories: CategoryFlags,
+    custom: Vec<String>,
     path: Option<PathBuf>,
     json: bool,
     project_age: u64,
@@ -69,66 +120,17 @@ pub(crate) fn handle_scan(
     force_full: bool,
     no_cache: bool,
     clear_cache: bool,
+    diff: Option<Option<i64>>,
     output_mode: OutputMode,
 ) -> anyhow::Result<()> {
-    // --all enables all categories
-    let (
-        cache,
-        app_cache,
-        temp,
-        trash,
-        build,
-        downloads,
-        large,
-        old,
-        applications,
-        browser,
-        system,
-        empty,
-        duplicates,
-        windows_update,
-        event_logs,
-    ) = if all {
-        (
-            true, true, true, true, true, true, true, true, true, true, true, true, true, true,
-            true,
-        )
-    } else if !cache
-        && !app_cache
-        && !temp
-        && !trash
-        && !build
-        && !downloads
-        && !large
-        && !old
-        && !applications
-        && !windows_update
-        && !event_logs
-    {
+    if !all && categories.ids.is_empty() && custom.is_empty() {
         // No categories specified - show help message
         eprintln!("No categories specified. Use --all or specify categories like --cache, --app-cache, --temp, --build");
         eprintln!("Run 'wole scan --help' for more information.");
         return Ok(());
-    } else {
-        // Scan command doesn't support browser, system, empty, duplicates
-        (
-            cache,
-            app_cache,
-            temp,
-            trash,
-            build,
-            downloads,
-            large,
-            old,
-            applications,
-            false,
-            false,
-            false,
-            false,
-            windows_update,
-            event_logs,
-        )
-    };
+    }
+    // --all enables all categories
+    let builtin = categories.selected(all);
 
     // Default to current directory to avoid stack overflow from OneDrive/UserDirs
     // PERFORMANCE FIX: Avoid OneDrive paths which are very slow to scan on Windows
@@ -155,63 +157,16 @@ pub(crate) fn handle_scan(
     // Merge CLI exclusions
     config.exclusions.patterns.extend(exclude.iter().cloned());
 
+    // Resolve user-defined categories (--all selects every configured one)
+    let custom = config.select_custom_categories(all, &custom)?;
+
     // Handle cache flags
     let use_cache = !no_cache && config.cache.enabled && !force_full;
 
     if clear_cache {
         if let Ok(mut scan_cache) = crate::scan_cache::ScanCache::open() {
             // Get categories to clear
-            let categories: Vec<&str> = if all {
-                vec![
-                    "cache",
-                    "app_cache",
-                    "temp",
-                    "trash",
-                    "build",
-                    "downloads",
-                    "large",
-                    "old",
-                    "applications",
-                    "windows_update",
-                    "event_logs",
-                ]
-            } else {
-                let mut cats = Vec::new();
-                if cache {
-                    cats.push("cache");
-                }
-                if app_cache {
-                    cats.push("app_cache");
-                }
-                if temp {
-                    cats.push("temp");
-                }
-                if trash {
-                    cats.push("trash");
-                }
-                if build {
-                    cats.push("build");
-                }
-                if downloads {
-                    cats.push("downloads");
-                }
-                if large {
-                    cats.push("large");
-                }
-                if old {
-                    cats.push("old");
-                }
-                if applications {
-                    cats.push("applications");
-                }
-                if windows_update {
-                    cats.push("windows_update");
-                }
-                if event_logs {
-                    cats.push("event_logs");
-                }
-                cats
-            };
+            let categories: Vec<&str> = builtin.iter().map(String::as_str).collect();
 
             if categories.is_empty() {
                 // Full reset: clear cache + scan history so first-scan detection triggers again.
@@ -230,21 +185,7 @@ pub(crate) fn handle_scan(
     let min_size_bytes = config.thresholds.min_size_mb * 1024 * 1024;
 
     let scan_options = ScanOptions {
-        cache,
-        app_cache,
-        temp,
-        trash,
-        build,
-        downloads,
-        large,
-        old,
-        applications,
-        browser,
-        system,
-        empty,
-        duplicates,
-        windows_update,
-        event_logs,
+        categories: builtin.into_iter().chain(custom).collect(),
         project_age_days: config.thresholds.project_age_days,
         min_age_days: config.thresholds.min_age_days,
         min_size_bytes,
@@ -268,6 +209,21 @@ pub(crate) fn handle_scan(
         None
     };
 
+    // Resolve the session to compare with before this scan adds a new one
+    let baseline = match diff {
+        Some(session_id) => {
+            let baseline = diff_baseline(scan_cache.as_ref(), session_id, &scan_path)?;
+            if baseline.is_none() {
+                eprintln!(
+                    "No previous scan of {} to compare with yet; this scan will be the baseline.",
+                    scan_path.display()
+                );
+            }
+            baseline
+        }
+        None => None,
+    };
+
     let mut first_scan_detected = false;
     if let Some(cache) = scan_cache.as_ref() {
         match cache.get_previous_scan_id() {
@@ -334,10 +290,25 @@ pub(crate) fn handle_scan(
         scan_cache.as_mut(),
     )?;
 
+    let scan_diff = baseline.map(|(session, items)| {
+        // The scanner stored the sizes of this scan's items with its session
+        let current = scan_cache
+            .as_ref()
+            .and_then(|cache| cache.get_last_scan().ok().flatten())
+            .filter(|last| last.id > session.id)
+            .and_then(|last| scan_cache.as_ref()?.get_session_items(last.id).ok())
+            .filter(|current| !current.is_empty() || results.total_items() == 0)
+            .unwrap_or_else(|| scan_diff::item_sizes(&results));
+        scan_diff::diff(&session, &items, &results, &current)
+    });
+
     if json {
-        output::print_json(&results)?;
+        output::print_json_with_diff(&results, scan_diff.as_ref())?;
     } else {
         output::print_human_with_options(&results, output_mode, Some(&scan_options));
+        if let Some(scan_diff) = &scan_diff {
+            output::print_diff(scan_diff, output_mode);
+        }
     }
 
     // After first scan, show cache statistics
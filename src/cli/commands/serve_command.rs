@@ -0,0 +1,26 @@
+//! Serve command feature.
+//!
+//! This module owns and handles the "wole serve" command behavior.
+
+use crate::output::OutputMode;
+use crate::serve::{self, Server};
+use crate::theme::Theme;
+
+pub(crate) fn handle_serve(socket: Option<String>, output_mode: OutputMode) -> anyhow::Result<()> {
+    let socket = socket.unwrap_or_else(serve::default_socket);
+    let server = Server::bind(&socket)?;
+
+    if output_mode != OutputMode::Quiet {
+        println!();
+        println!("{}", Theme::header("Serving JSON-RPC"));
+        println!("{}", Theme::divider_bold(60));
+        println!("  {}", Theme::value(&socket));
+        println!(
+            "{}",
+            Theme::muted("Methods: scan, clean, restore, status, disk_insights, cancel")
+        );
+        println!("{}", Theme::muted("Press Ctrl+C to stop."));
+        println!();
+    }
+    server.run()
+}
@@ -4,20 +4,9 @@
 
 pub(crate) fn handle_status(json: bool, _watch: bool, new_format: bool) -> anyhow::Result<()> {
     if json {
-        // JSON output mode - use text output
-        use sysinfo::System;
-
-        let mut system = System::new();
-        system.refresh_all();
-
-        match crate::status::gather_status(&mut system) {
-            Ok(status) => {
-                let json_output = serde_json::to_string_pretty(&status)?;
-                println!("{}", json_output);
-                Ok(())
-            }
-            Err(e) => Err(anyhow::anyhow!("Failed to gather system status: {}", e)),
-        }
+        let status = crate::status::snapshot()?;
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        Ok(())
     } else if new_format {
         // New experimental CLI format
         use sysinfo::System;
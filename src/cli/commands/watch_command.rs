@@ -0,0 +1,70 @@
+//! Watch command feature.
+//!
+//! This module owns and handles the "wole watch" command behavior.
+
+use crate::config::Config;
+use crate::output::OutputMode;
+use crate::scan_cache::ScanCache;
+use crate::theme::Theme;
+use crate::watch::{self, Batch};
+use std::path::PathBuf;
+
+pub(crate) fn handle_watch(
+    paths: Vec<PathBuf>,
+    no_initial_scan: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let config = Config::load();
+    if !config.cache.enabled {
+        anyhow::bail!("The scan cache is disabled in the config; there is nothing to keep warm");
+    }
+    let roots: Vec<PathBuf> = watch::watch_roots(paths, &config)
+        .into_iter()
+        .filter(|root| root.is_dir())
+        .collect();
+    if roots.is_empty() {
+        anyhow::bail!("No folders to watch: pass --path or set paths.scan_roots in the config");
+    }
+    let mut cache = ScanCache::open()?;
+    let quiet = output_mode == OutputMode::Quiet;
+
+    if !quiet {
+        println!();
+        println!("{}", Theme::header("Watching"));
+        println!("{}", Theme::divider_bold(60));
+        for root in &roots {
+            println!("  {}", Theme::value(&root.display().to_string()));
+        }
+        if !no_initial_scan {
+            println!("{}", Theme::muted("Warming up the scan cache..."));
+        }
+        println!("{}", Theme::muted("Press Ctrl+C to stop."));
+        println!();
+    }
+
+    watch::run(
+        &roots,
+        &config,
+        &mut cache,
+        !no_initial_scan,
+        |batch, result| {
+            let time = chrono::Local::now().format("%H:%M:%S");
+            match (batch, result) {
+                (_, Err(e)) => eprintln!("Warning: Failed to update the scan cache: {}", e),
+                (Batch::Rescan, Ok(_)) if !quiet => println!(
+                    "{} Missed some changes; rescanned the watched folders",
+                    Theme::muted(&time.to_string())
+                ),
+                (Batch::Changes(paths), Ok(Some(stats))) if !quiet => println!(
+                    "{} {} changed paths: {} files updated, {} removed, {} folders to rescan",
+                    Theme::muted(&time.to_string()),
+                    paths.len(),
+                    stats.updated_files,
+                    stats.removed_files,
+                    stats.invalidated_dirs
+                ),
+                _ => {}
+            }
+        },
+    )
+}
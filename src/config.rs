@@ -209,6 +209,10 @@ pub struct CategorySettings {
 
     #[serde(default)]
     pub duplicates: DuplicatesConfig,
+
+    /// User-defined categories declared as `[[categories.custom]]` tables
+    #[serde(default)]
+    pub custom: Vec<CustomCategoryConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -225,6 +229,27 @@ pub struct CategoryConfig {
     /// Merged with default artifacts
     #[serde(default)]
     pub custom_artifacts: Vec<String>,
+
+    /// Offer registry entries no project's lockfile references
+    /// (for cache category only; currently Cargo's registry via `Cargo.lock`)
+    #[serde(default)]
+    pub prune_unreferenced: bool,
+
+    /// Directories whose projects' lockfiles count for `prune_unreferenced`, in
+    /// addition to the scan root; `~` and environment variables are expanded.
+    /// When empty, pruning only happens if the scan root covers the home directory.
+    #[serde(default)]
+    pub lockfile_roots: Vec<String>,
+
+    /// Offer only stale parts of Rust `target/` directories instead of the whole
+    /// directory (for build category only; see `categories::target_sweep`)
+    #[serde(default)]
+    pub sweep_rust_targets: bool,
+
+    /// Age in days after which an unbuilt `target/` profile counts as stale
+    /// (for build category only; defaults to the project age threshold)
+    #[serde(default)]
+    pub sweep_age_days: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -244,6 +269,169 @@ pub struct DuplicatesConfig {
     /// Default: 8MB for optimal performance on modern NVMe SSDs
     #[serde(default = "default_duplicate_buffer_size")]
     pub buffer_size_bytes: usize,
+
+    /// Rules choosing which files of a duplicate group are kept, evaluated in order
+    /// Default: keep every file not named like a copy, else the first by name
+    #[serde(default = "default_keep_policy")]
+    pub keep: Vec<KeepRule>,
+
+    /// Also group images that look the same (resized, re-encoded or converted copies)
+    /// Default: false (only identical files are grouped)
+    #[serde(default)]
+    pub similar_images: bool,
+
+    /// How many of the 64 bits of two images' perceptual hashes may differ for
+    /// them to count as similar (every image of a group is this close to every other)
+    /// Default: 5
+    #[serde(default = "default_similar_image_distance")]
+    pub similar_image_distance: u32,
+}
+
+/// A rule choosing which files of a duplicate group are kept
+///
+/// `keep-originals`, `never-delete-under` and `keep-one-per-directory` keep
+/// every file they match. The other rules narrow the group down to the one
+/// file that's kept: each later rule breaks the ties of the one before, and
+/// the first file by name is kept when a tie is left over, preferring names
+/// that don't look like copies.
+///
+/// Example:
+/// ```toml
+/// [[categories.duplicates.keep]]
+/// rule = "never-delete-under"
+/// roots = ["~/Pictures/Originals"]
+///
+/// [[categories.duplicates.keep]]
+/// rule = "keep-in-preferred-directories"
+/// dirs = ["~/Music", "~/Documents"]
+///
+/// [[categories.duplicates.keep]]
+/// rule = "keep-newest"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "kebab-case")]
+pub enum KeepRule {
+    /// Keep every file not named like a copy ("report (1).pdf", "notes - Copy.txt"),
+    /// in groups that have a file named like one
+    KeepOriginals,
+    /// Keep the most recently modified file
+    KeepNewest,
+    /// Keep the least recently modified file
+    KeepOldest,
+    /// Keep the file with the shortest path
+    KeepShortestPath,
+    /// Keep a file in the first of these folders that has one
+    KeepInPreferredDirectories { dirs: Vec<String> },
+    /// Never delete files below these folders
+    NeverDeleteUnder { roots: Vec<String> },
+    /// Keep one file in every folder; only copies within a folder are removed
+    KeepOnePerDirectory,
+    /// Keep the image with the most pixels (groups of similar images)
+    KeepHighestResolution,
+    /// Keep the biggest file (groups of similar images)
+    KeepLargest,
+}
+
+impl KeepRule {
+    /// The rule's name as written in the config
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeepRule::KeepOriginals => "keep-originals",
+            KeepRule::KeepNewest => "keep-newest",
+            KeepRule::KeepOldest => "keep-oldest",
+            KeepRule::KeepShortestPath => "keep-shortest-path",
+            KeepRule::KeepInPreferredDirectories { .. } => "keep-in-preferred-directories",
+            KeepRule::NeverDeleteUnder { .. } => "never-delete-under",
+            KeepRule::KeepOnePerDirectory => "keep-one-per-directory",
+            KeepRule::KeepHighestResolution => "keep-highest-resolution",
+            KeepRule::KeepLargest => "keep-largest",
+        }
+    }
+}
+
+/// A user-defined cleanup category
+///
+/// Example:
+/// ```toml
+/// [[categories.custom]]
+/// name = "SDK Scratch"
+/// roots = ["~/.acme-sdk/scratch", "%LOCALAPPDATA%\\AcmeIDE\\tmp"]
+/// include = ["*.tmp", "session-*"]
+/// exclude = ["keep-*"]
+/// min_age_days = 7
+/// min_size = "1MB"
+/// granularity = "directory"
+/// risk = "safe"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCategoryConfig {
+    /// Display name (must not collide with a built-in category)
+    pub name: String,
+
+    /// Short description shown on the TUI dashboard
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Root directories to scan; `~`, `$VAR`, `${VAR}` and `%VAR%` are expanded
+    #[serde(default)]
+    pub roots: Vec<String>,
+
+    /// Glob patterns an entry must match (relative to its root). Empty = everything.
+    /// Patterns without a `/` are matched against the entry name only.
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Glob patterns that exclude an entry (same matching rules as `include`)
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Only report entries not modified in this many days
+    #[serde(default)]
+    pub min_age_days: Option<u64>,
+
+    /// Only report entries at least this large (e.g. "10MB")
+    #[serde(default)]
+    pub min_size: Option<String>,
+
+    /// Report whole directories or individual files
+    #[serde(default)]
+    pub granularity: CustomGranularity,
+
+    /// Whether matches are safe to auto-select or need review
+    #[serde(default)]
+    pub risk: RiskLevel,
+}
+
+/// What a custom category reports as a single cleanable item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomGranularity {
+    /// Matching directories are reported (and removed) as a whole
+    #[default]
+    Directory,
+    /// Matching files are reported individually
+    File,
+}
+
+/// Risk level of a category's results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    /// Safe to clean; auto-selected in the TUI
+    Safe,
+    /// Needs user review before cleaning
+    #[default]
+    Review,
+}
+
+impl CustomCategoryConfig {
+    /// Minimum size in bytes (`min_size` parsed, 0 when unset or invalid)
+    pub fn min_size_bytes(&self) -> u64 {
+        self.min_size
+            .as_deref()
+            .and_then(|s| crate::size::parse_size(s).ok())
+            .unwrap_or(0)
+    }
 }
 
 impl Default for Thresholds {
@@ -347,6 +535,9 @@ impl Default for DuplicatesConfig {
             scan_paths: Vec::new(),
             memmap_threshold_bytes: default_memmap_threshold(),
             buffer_size_bytes: default_duplicate_buffer_size(),
+            keep: default_keep_policy(),
+            similar_images: false,
+            similar_image_distance: default_similar_image_distance(),
         }
     }
 }
@@ -398,6 +589,12 @@ fn default_memmap_threshold() -> u64 {
 fn default_duplicate_buffer_size() -> usize {
     8 * 1024 * 1024
 } // 8MB
+fn default_keep_policy() -> Vec<KeepRule> {
+    vec![KeepRule::KeepOriginals]
+}
+fn default_similar_image_distance() -> u32 {
+    5
+}
 fn default_scan_depth_user() -> u8 {
     8
 }
@@ -515,6 +712,54 @@ impl Config {
         }
     }
 
+    /// Find a user-defined category by name (case-insensitive)
+    pub fn custom_category(&self, name: &str) -> Option<&CustomCategoryConfig> {
+        self.categories
+            .custom
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Resolve the custom categories selected on the command line.
+    ///
+    /// `--all` selects every configured category; otherwise each requested name
+    /// must match a `[[categories.custom]]` entry.
+    pub fn select_custom_categories(&self, all: bool, requested: &[String]) -> Result<Vec<String>> {
+        if all {
+            return Ok(self
+                .categories
+                .custom
+                .iter()
+                .map(|c| c.name.clone())
+                .collect());
+        }
+
+        let mut selected: Vec<String> = Vec::new();
+        for name in requested {
+            let def = self.custom_category(name).ok_or_else(|| {
+                let known: Vec<&str> = self
+                    .categories
+                    .custom
+                    .iter()
+                    .map(|c| c.name.as_str())
+                    .collect();
+                anyhow::anyhow!(
+                    "Unknown custom category '{}'. Defined in config.toml: {}",
+                    name,
+                    if known.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                )
+            })?;
+            if !selected.contains(&def.name) {
+                selected.push(def.name.clone());
+            }
+        }
+        Ok(selected)
+    }
+
     /// Check if a path matches any exclusion pattern
     ///
     /// Uses pre-compiled glob patterns for O(1) matching instead of O(patterns)
@@ -712,4 +957,39 @@ mod tests {
         assert_eq!(config.thresholds.min_age_days, original_age);
         assert_eq!(config.thresholds.min_size_mb, 100); // Default
     }
+
+    #[test]
+    fn test_custom_categories_parse() {
+        let config: Config = toml::from_str(
+            r#"
+            [[categories.custom]]
+            name = "Unity Library"
+            roots = ["~/UnityProjects"]
+            include = ["Library"]
+            min_size = "50MB"
+            risk = "safe"
+
+            [[categories.custom]]
+            name = "Old Logs"
+            roots = ["%TEMP%/logs"]
+            granularity = "file"
+            "#,
+        )
+        .unwrap();
+
+        let unity = config.custom_category("unity library").unwrap();
+        assert_eq!(unity.risk, RiskLevel::Safe);
+        assert_eq!(unity.granularity, CustomGranularity::Directory);
+        assert_eq!(unity.min_size_bytes(), 50 * 1024 * 1024);
+
+        let logs = config.custom_category("Old Logs").unwrap();
+        assert_eq!(logs.risk, RiskLevel::Review);
+        assert_eq!(logs.granularity, CustomGranularity::File);
+
+        let all = config.select_custom_categories(true, &[]).unwrap();
+        assert_eq!(all, vec!["Unity Library", "Old Logs"]);
+        assert!(config
+            .select_custom_categories(false, &["missing".to_string()])
+            .is_err());
+    }
 }
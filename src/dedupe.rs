@@ -0,0 +1,355 @@
+//! Replacing duplicate copies with links (`wole clean --duplicates --dedupe-mode`).
+//!
+//! Deleting the redundant copies of a duplicate group frees the most space, but
+//! breaks anything that expects the file at each of its paths. Instead, each
+//! copy can be swapped for a hardlink to the kept file (one file, several
+//! names; same volume only) or a copy-on-write reflink (a separate file sharing
+//! its data blocks; btrfs and XFS on Linux). Contents are compared byte for
+//! byte right before each swap, and the new file is renamed over the copy so
+//! the path never goes missing.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, FileTimes};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const COMPARE_BUFFER_SIZE: usize = 256 * 1024;
+
+/// What to do with the redundant copies of a duplicate group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupeMode {
+    /// Delete them (to the Recycle Bin unless `--permanent`)
+    #[default]
+    Delete,
+    /// Replace them with hardlinks to the kept file
+    Link,
+    /// Replace them with copy-on-write clones of the kept file
+    Reflink,
+}
+
+impl DedupeMode {
+    pub const ALL: [DedupeMode; 3] = [DedupeMode::Delete, DedupeMode::Link, DedupeMode::Reflink];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DedupeMode::Delete => "delete",
+            DedupeMode::Link => "link",
+            DedupeMode::Reflink => "reflink",
+        }
+    }
+}
+
+impl FromStr for DedupeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("expected one of: link, reflink, delete (got '{}')", s))
+    }
+}
+
+/// Modes that can be used for a group of identical files
+///
+/// Links need every file on the same volume; reflinks also need a filesystem
+/// that supports them. Deleting always works.
+pub fn eligible_modes(paths: &[PathBuf]) -> Vec<DedupeMode> {
+    let mut modes = vec![DedupeMode::Delete];
+    let volumes: Option<Vec<String>> = paths.iter().map(|p| volume_id(p)).collect();
+    let same_volume = volumes.is_some_and(|v| v.windows(2).all(|pair| pair[0] == pair[1]));
+    if same_volume {
+        modes.push(DedupeMode::Link);
+        if paths.first().is_some_and(|p| supports_reflink(p)) {
+            modes.push(DedupeMode::Reflink);
+        }
+    }
+    modes
+}
+
+/// The device a file is on
+#[cfg(unix)]
+fn volume_id(path: &Path) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|m| m.dev().to_string())
+}
+
+/// The serial number of the volume a file is on
+///
+/// Taken from the file itself rather than its drive letter: a folder can be a
+/// mount point or junction leading to another volume.
+#[cfg(windows)]
+fn volume_id(path: &Path) -> Option<String> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, FILE_FLAG_BACKUP_SEMANTICS,
+    };
+
+    // Directories (duplicate trees) can only be opened with backup semantics
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0)
+        .open(path)
+        .ok()?;
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    // Safety: the handle stays open for the duration of the call
+    unsafe { GetFileInformationByHandle(HANDLE(file.as_raw_handle()), &mut info) }.ok()?;
+    Some(format!("{:08x}", info.dwVolumeSerialNumber))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn volume_id(_path: &Path) -> Option<String> {
+    None
+}
+
+/// Whether the file is on btrfs or XFS
+#[cfg(target_os = "linux")]
+fn supports_reflink(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    const BTRFS_SUPER_MAGIC: u32 = 0x9123_683E;
+    const XFS_SUPER_MAGIC: u32 = 0x5846_5342;
+
+    let Ok(path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // Safety: statfs only writes to the zeroed struct we pass it
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return false;
+    }
+    matches!(stat.f_type as u32, BTRFS_SUPER_MAGIC | XFS_SUPER_MAGIC)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn supports_reflink(_path: &Path) -> bool {
+    false
+}
+
+/// Whether two files have exactly the same contents
+pub fn same_contents(a: &Path, b: &Path) -> Result<bool> {
+    let open = |path: &Path| {
+        File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))
+    };
+    let (file_a, file_b) = (open(a)?, open(b)?);
+    if file_a.metadata()?.len() != file_b.metadata()?.len() {
+        return Ok(false);
+    }
+
+    let mut reader_a = BufReader::with_capacity(COMPARE_BUFFER_SIZE, file_a);
+    let mut reader_b = BufReader::with_capacity(COMPARE_BUFFER_SIZE, file_b);
+    loop {
+        let chunk_a = reader_a.fill_buf()?;
+        let chunk_b = reader_b.fill_buf()?;
+        let len = chunk_a.len().min(chunk_b.len());
+        if len == 0 {
+            return Ok(chunk_a.is_empty() && chunk_b.is_empty());
+        }
+        if chunk_a[..len] != chunk_b[..len] {
+            return Ok(false);
+        }
+        reader_a.consume(len);
+        reader_b.consume(len);
+    }
+}
+
+/// Replace `copy` with a hardlink or reflink to `keep`
+///
+/// Fails without touching `copy` when the contents differ. A reflink gets the
+/// permissions and timestamps of the copy it replaces; a hardlink is the kept
+/// file itself, so it's only made when the permissions already match, and the
+/// copy's path then shows the kept file's timestamps. A copy that's already a
+/// hardlink of `keep` is left as it is.
+pub fn replace_with_link(keep: &Path, copy: &Path, mode: DedupeMode) -> Result<()> {
+    // Renaming a hardlink over another link of the same file does nothing, which
+    // would leave the temporary link behind
+    if same_file::is_same_file(keep, copy).with_context(|| {
+        format!(
+            "Failed to compare {} with {}",
+            copy.display(),
+            keep.display()
+        )
+    })? {
+        return Ok(());
+    }
+    if !same_contents(keep, copy)? {
+        anyhow::bail!(
+            "{} no longer has the same contents as {}",
+            copy.display(),
+            keep.display()
+        );
+    }
+    let metadata = fs::metadata(copy)
+        .with_context(|| format!("Failed to get metadata: {}", copy.display()))?;
+    if mode == DedupeMode::Link && fs::metadata(keep)?.permissions() != metadata.permissions() {
+        anyhow::bail!(
+            "{} has different permissions than {}",
+            copy.display(),
+            keep.display()
+        );
+    }
+    let temp = create_temp_link(keep, copy, mode)?;
+
+    let result = (|| -> Result<()> {
+        if mode == DedupeMode::Reflink {
+            // Timestamps first: the copy's permissions may make it read-only
+            File::options().write(true).open(&temp)?.set_times(
+                FileTimes::new()
+                    .set_accessed(metadata.accessed()?)
+                    .set_modified(metadata.modified()?),
+            )?;
+            fs::set_permissions(&temp, metadata.permissions())?;
+        }
+        fs::rename(&temp, copy).with_context(|| format!("Failed to replace {}", copy.display()))
+    })();
+
+    // Only ever the file made above: the name was free when it was created
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Make the hardlink or reflink to `keep` under a temporary name next to `copy`
+///
+/// A name that's already taken is left alone and another one is tried.
+fn create_temp_link(keep: &Path, copy: &Path, mode: DedupeMode) -> Result<PathBuf> {
+    const ATTEMPTS: usize = 8;
+    for _ in 0..ATTEMPTS {
+        let temp = temp_path(copy);
+        let created = match mode {
+            DedupeMode::Link => fs::hard_link(keep, &temp),
+            DedupeMode::Reflink => reflink(keep, &temp),
+            DedupeMode::Delete => anyhow::bail!("Deleting doesn't replace files"),
+        };
+        match created {
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to create {} of {}", mode.name(), keep.display())
+                })
+            }
+            Ok(()) => return Ok(temp),
+        }
+    }
+    anyhow::bail!(
+        "Failed to find a free temporary name next to {}",
+        copy.display()
+    )
+}
+
+/// A random name in the copy's folder for the link before it's renamed over the copy
+fn temp_path(copy: &Path) -> PathBuf {
+    use std::hash::{BuildHasher, RandomState};
+    let name = copy
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // Every RandomState is seeded differently
+    let suffix = RandomState::new().hash_one(copy);
+    copy.with_file_name(format!(".{}.{:016x}.wole-link", name, suffix))
+}
+
+/// Create `dest` as a copy-on-write clone of `src` (FICLONE)
+///
+/// Fails with `AlreadyExists` when `dest` exists; removes `dest` again when
+/// the clone itself fails.
+#[cfg(target_os = "linux")]
+fn reflink(src: &Path, dest: &Path) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    const FICLONE: libc::c_ulong = 0x4004_9409;
+
+    let src_file = File::open(src)?;
+    let dest_file = File::options().write(true).create_new(true).open(dest)?;
+    // Safety: both descriptors stay open for the duration of the call
+    let ret = unsafe { libc::ioctl(dest_file.as_raw_fd(), FICLONE as _, src_file.as_raw_fd()) };
+    if ret != 0 {
+        let err = std::io::Error::last_os_error();
+        drop(dest_file);
+        let _ = fs::remove_file(dest);
+        return Err(err);
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reflink(_src: &Path, _dest: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "reflinks are only supported on Linux (btrfs, XFS)",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replaces_identical_copies_with_hardlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let keep = dir.path().join("photo.jpg");
+        let copy = dir.path().join("photo (1).jpg");
+        let other = dir.path().join("other.jpg");
+        fs::write(&keep, b"same bytes").unwrap();
+        fs::write(&copy, b"same bytes").unwrap();
+        fs::write(&other, b"diff bytes").unwrap();
+
+        assert!(same_contents(&keep, &copy).unwrap());
+        assert!(!same_contents(&keep, &other).unwrap());
+        let modes = eligible_modes(&[keep.clone(), copy.clone()]);
+        assert_eq!(modes[..2], [DedupeMode::Delete, DedupeMode::Link]);
+
+        // Different contents: left alone
+        assert!(replace_with_link(&keep, &other, DedupeMode::Link).is_err());
+        assert_eq!(fs::read(&other).unwrap(), b"diff bytes");
+
+        replace_with_link(&keep, &copy, DedupeMode::Link).unwrap();
+        assert_eq!(fs::read(&copy).unwrap(), b"same bytes");
+        assert_eq!(leftovers(dir.path()), Vec::<String>::new());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let ino = |p: &Path| fs::metadata(p).unwrap().ino();
+            assert_eq!(ino(&keep), ino(&copy));
+        }
+
+        // Already linked: nothing to do, and nothing left behind
+        replace_with_link(&keep, &copy, DedupeMode::Link).unwrap();
+        assert_eq!(leftovers(dir.path()), Vec::<String>::new());
+        assert_eq!("Reflink".parse::<DedupeMode>(), Ok(DedupeMode::Reflink));
+    }
+
+    fn leftovers(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".wole-link"))
+            .collect()
+    }
+
+    #[test]
+    fn test_failed_swap_leaves_files_it_didnt_create() {
+        let dir = tempfile::tempdir().unwrap();
+        let keep = dir.path().join("song.mp3");
+        let copy = dir.path().join("song (1).mp3");
+        fs::write(&keep, b"la la").unwrap();
+        fs::write(&copy, b"la la").unwrap();
+        // Named like an old-style temporary link, but the user's own file
+        let unrelated = dir.path().join(".song (1).mp3.wole-link");
+        fs::write(&unrelated, b"mine").unwrap();
+
+        assert_ne!(temp_path(&copy), temp_path(&copy));
+        // Reflinks fail on most filesystems; either way the user's file stays
+        let _ = replace_with_link(&keep, &copy, DedupeMode::Reflink);
+        replace_with_link(&keep, &copy, DedupeMode::Link).unwrap();
+        assert_eq!(fs::read(&unrelated).unwrap(), b"mine");
+        assert_eq!(
+            leftovers(dir.path()),
+            vec![".song (1).mp3.wole-link".to_string()]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Folder size trends for `wole analyze --trend`.
+//!
+//! Every disk usage scan records the sizes of the biggest folders at each depth
+//! in the scan cache. Comparing those samples over days and weeks shows which
+//! folders keep growing, not just which ones are big today.
+
+use crate::config::Config;
+use crate::disk_usage::{DiskInsights, FolderNode};
+use crate::scan_cache::{FolderSample, ScanCache};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Folders recorded per depth level on each scan
+pub const TOP_FOLDERS_PER_DEPTH: usize = 10;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Size history of one folder
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderTrend {
+    pub path: PathBuf,
+    pub depth: u8,
+    /// Recorded sizes, oldest first
+    pub samples: Vec<(DateTime<Utc>, u64)>,
+    pub current_bytes: u64,
+    /// Growth in bytes per day (least-squares slope over the samples)
+    pub growth_per_day: f64,
+}
+
+impl FolderTrend {
+    pub fn growth_per_week(&self) -> f64 {
+        self.growth_per_day * 7.0
+    }
+
+    /// Days between the first and the last sample
+    pub fn days_tracked(&self) -> f64 {
+        match (self.samples.first(), self.samples.last()) {
+            (Some((first, _)), Some((last, _))) => {
+                (*last - *first).num_seconds() as f64 / SECONDS_PER_DAY
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Sizes above the smallest sample, so small changes in a big folder show
+    pub fn sparkline(&self) -> Vec<u64> {
+        let min = self.samples.iter().map(|(_, s)| *s).min().unwrap_or(0);
+        self.samples.iter().map(|(_, s)| s - min).collect()
+    }
+}
+
+/// The biggest folders at each depth of a scan, as recorded in the cache
+pub fn folder_samples(insights: &DiskInsights) -> Vec<FolderSample> {
+    let mut by_depth: Vec<Vec<&FolderNode>> = Vec::new();
+    let mut level = vec![&insights.root];
+    while !level.is_empty() {
+        let next: Vec<&FolderNode> = level.iter().flat_map(|n| n.children.iter()).collect();
+        by_depth.push(level);
+        level = next;
+    }
+
+    by_depth
+        .into_iter()
+        .enumerate()
+        .flat_map(|(depth, mut nodes)| {
+            nodes.sort_by_key(|node| Reverse(node.size));
+            nodes
+                .into_iter()
+                .take(TOP_FOLDERS_PER_DEPTH)
+                .map(move |node| FolderSample {
+                    path: node.path.clone(),
+                    depth: depth.min(u8::MAX as usize) as u8,
+                    size_bytes: node.size,
+                })
+        })
+        .collect()
+}
+
+/// Record the folder sizes of a fresh scan (skipped when the cache is disabled)
+pub fn record(insights: &DiskInsights) -> Result<()> {
+    if !Config::load().cache.enabled {
+        return Ok(());
+    }
+    let mut cache = ScanCache::open()?;
+    cache.record_folder_sizes(Utc::now(), &folder_samples(insights))
+}
+
+/// Group recorded samples into per-folder trends, fastest growing first
+pub fn trends(history: Vec<(DateTime<Utc>, FolderSample)>) -> Vec<FolderTrend> {
+    let mut by_path: HashMap<PathBuf, FolderTrend> = HashMap::new();
+    for (recorded_at, sample) in history {
+        let trend = by_path
+            .entry(sample.path.clone())
+            .or_insert_with(|| FolderTrend {
+                path: sample.path,
+                depth: sample.depth,
+                samples: Vec::new(),
+                current_bytes: 0,
+                growth_per_day: 0.0,
+            });
+        trend.samples.push((recorded_at, sample.size_bytes));
+    }
+
+    let mut trends: Vec<FolderTrend> = by_path
+        .into_values()
+        .map(|mut trend| {
+            trend.samples.sort_by_key(|(at, _)| *at);
+            trend.current_bytes = trend.samples.last().map(|(_, s)| *s).unwrap_or(0);
+            trend.growth_per_day = slope_per_day(&trend.samples);
+            trend
+        })
+        .collect();
+    trends.sort_by(|a, b| {
+        b.growth_per_day
+            .total_cmp(&a.growth_per_day)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    trends
+}
+
+/// Trends for `root` and the folders below it
+pub fn load_trends(root: &Path) -> Result<Vec<FolderTrend>> {
+    let cache = ScanCache::open()?;
+    Ok(trends(cache.get_folder_history(root)?))
+}
+
+/// Least-squares growth in bytes per day (0 with fewer than two points in time)
+pub(crate) fn slope_per_day(samples: &[(DateTime<Utc>, u64)]) -> f64 {
+    let Some((start, _)) = samples.first() else {
+        return 0.0;
+    };
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|(at, size)| {
+            let days = (*at - *start).num_seconds() as f64 / SECONDS_PER_DAY;
+            (days, *size as f64)
+        })
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut num, mut den) = (0.0, 0.0);
+    for (x, y) in &points {
+        num += (x - mean_x) * (y - mean_y);
+        den += (x - mean_x) * (x - mean_x);
+    }
+    if den == 0.0 {
+        0.0
+    } else {
+        num / den
+    }
+}
+
+/// Text sparkline of the values, e.g. "▁▂▄█"
+pub fn sparkline_text(values: &[u64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|v| {
+            if max == 0 {
+                SPARK_CHARS[0]
+            } else {
+                SPARK_CHARS[(*v as f64 / max as f64 * 7.0).round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Signed human-readable growth, e.g. "+1.2 GB"
+pub fn format_growth(bytes: f64) -> String {
+    crate::scan_diff::format_delta(bytes.round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(path: &str, depth: u8, size_bytes: u64) -> FolderSample {
+        FolderSample {
+            path: PathBuf::from(path),
+            depth,
+            size_bytes,
+        }
+    }
+
+    #[test]
+    fn test_computes_growth_per_folder() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let day = |n| start + chrono::Duration::days(n);
+        let history = vec![
+            (day(0), sample("/home/videos", 1, 1_000)),
+            (day(0), sample("/home/docs", 1, 500)),
+            (day(1), sample("/home/videos", 1, 2_000)),
+            (day(2), sample("/home/videos", 1, 3_000)),
+            (day(2), sample("/home/docs", 1, 400)),
+        ];
+
+        let trends = trends(history);
+        assert_eq!(trends[0].path, PathBuf::from("/home/videos"));
+        assert_eq!(trends[0].growth_per_day, 1_000.0);
+        assert_eq!(trends[0].growth_per_week(), 7_000.0);
+        assert_eq!(trends[0].current_bytes, 3_000);
+        assert_eq!(trends[0].days_tracked(), 2.0);
+        assert_eq!(trends[0].sparkline(), vec![0, 1_000, 2_000]);
+        assert_eq!(trends[1].growth_per_day, -50.0);
+
+        assert_eq!(sparkline_text(&[0, 1_000, 2_000]), "▁▅█");
+        assert_eq!(format_growth(-50.0), "-50 B");
+    }
+}
@@ -81,7 +81,8 @@ pub fn scan_directory(path: &Path, max_depth: u8) -> Result<DiskInsights> {
 }
 
 /// Scan a directory with progress reporting
-/// Checks cache first, and saves results to cache after scanning
+/// Checks cache first, and saves results to cache after scanning.
+/// Fresh scans also record folder sizes in the scan cache for growth trends.
 pub fn scan_directory_with_progress(
     path: &Path,
     max_depth: u8,
@@ -265,6 +266,8 @@ pub fn scan_directory_with_progress(
 
     // Save to cache (ignore errors - cache is optional)
     let _ = crate::disk_usage_cache::save_cached_insights(path, max_depth, &insights);
+    // Record the biggest folders for `wole analyze --trend` (also optional)
+    let _ = crate::disk_trend::record(&insights);
 
     Ok(insights)
 }
@@ -0,0 +1,327 @@
+//! Free-space forecast per volume.
+//!
+//! Free-space readings (taken by `wole status`, the TUI and every cached scan)
+//! give the rate at which each volume fills up, and so roughly when it will be
+//! full. The results stored with scan sessions show which categories grow on
+//! that volume, to say what's driving it: "C:\ full in ~23 days, mostly build
+//! artifacts".
+
+use crate::categories::registry::CategoryRegistry;
+use crate::config::Config;
+use crate::disk_trend::slope_per_day;
+use crate::scan_cache::{DiskReading, ScanCache, ScanSession, SessionItem};
+use crate::status::DiskInfo;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+/// Readings from this many days back are used for the growth rate
+pub const FORECAST_WINDOW_DAYS: i64 = 30;
+/// A volume needs readings spanning at least this long before it gets a rate
+const MIN_OBSERVED_DAYS: f64 = 1.0;
+/// Categories named as drivers of a volume's growth
+const MAX_DRIVERS: usize = 3;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Sizes at points in time
+type Series = Vec<(DateTime<Utc>, u64)>;
+
+/// A category that grows on a volume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryGrowth {
+    pub category: String,
+    pub name: String,
+    pub growth_bytes_per_day: f64,
+}
+
+/// When a volume will run out of space at its current growth rate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeForecast {
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    /// Growth of used space in bytes per day (None until there's enough history)
+    pub growth_bytes_per_day: Option<f64>,
+    /// Days until the volume is full (None when it isn't filling up)
+    pub days_until_full: Option<f64>,
+    /// Days covered by the readings the rate is based on
+    pub days_observed: f64,
+    /// Categories growing on this volume, fastest first
+    pub drivers: Vec<CategoryGrowth>,
+}
+
+impl fmt::Display for VolumeForecast {
+    /// One-line summary, e.g. "C:\ full in ~23 days, mostly Build Artifacts"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.days_until_full, self.growth_bytes_per_day) {
+            (Some(days), _) => {
+                write!(f, "{} full in ~{:.0} days", self.mount_point, days.ceil())?;
+                if let Some(driver) = self.drivers.first() {
+                    write!(f, ", mostly {}", driver.name)?;
+                }
+                Ok(())
+            }
+            (None, Some(_)) => write!(f, "{} not filling up", self.mount_point),
+            (None, None) => write!(f, "{} collecting free-space history", self.mount_point),
+        }
+    }
+}
+
+/// Current free space of each volume, in the form it's stored
+pub fn readings(disks: &[DiskInfo]) -> Vec<DiskReading> {
+    disks
+        .iter()
+        .filter(|disk| disk.total_gb > 0.0)
+        .map(|disk| DiskReading {
+            mount_point: disk.mount_point.clone(),
+            total_bytes: (disk.total_gb * BYTES_PER_GB) as u64,
+            free_bytes: (disk.free_gb * BYTES_PER_GB) as u64,
+        })
+        .collect()
+}
+
+/// Record the current free space of each volume
+pub fn record_readings(cache: &mut ScanCache, disks: &[DiskInfo]) -> Result<()> {
+    cache.record_disk_readings(Utc::now(), &readings(disks))
+}
+
+/// Record a reading and forecast every volume (empty when the cache is disabled)
+pub fn refresh(disks: &[DiskInfo]) -> Result<Vec<VolumeForecast>> {
+    let config = Config::load();
+    if !config.cache.enabled {
+        return Ok(Vec::new());
+    }
+    let mut cache = ScanCache::open()?;
+    record_readings(&mut cache, disks)?;
+
+    let history = cache.get_disk_readings(Utc::now() - Duration::days(FORECAST_WINDOW_DAYS))?;
+    let mut sessions = Vec::new();
+    for session in cache.get_stored_sessions()? {
+        let items = cache.get_session_items(session.id)?;
+        sessions.push((session, items));
+    }
+    let registry = CategoryRegistry::with_config(&config);
+    Ok(forecast(&readings(disks), &history, &sessions, &registry))
+}
+
+/// Forecast each volume in `current` from its reading history and the stored scan results
+pub fn forecast(
+    current: &[DiskReading],
+    history: &[(DateTime<Utc>, DiskReading)],
+    sessions: &[(ScanSession, Vec<SessionItem>)],
+    registry: &CategoryRegistry,
+) -> Vec<VolumeForecast> {
+    let mounts: Vec<&str> = current.iter().map(|r| r.mount_point.as_str()).collect();
+    let growth = category_growth(&mounts, sessions, registry);
+
+    let mut forecasts: Vec<VolumeForecast> = current
+        .iter()
+        .map(|reading| {
+            let used: Series = history
+                .iter()
+                .filter(|(_, r)| r.mount_point == reading.mount_point)
+                .map(|(at, r)| (*at, r.total_bytes.saturating_sub(r.free_bytes)))
+                .collect();
+            let days_observed = match (used.first(), used.last()) {
+                (Some((first, _)), Some((last, _))) => {
+                    (*last - *first).num_seconds() as f64 / 86_400.0
+                }
+                _ => 0.0,
+            };
+            let growth_bytes_per_day =
+                (days_observed >= MIN_OBSERVED_DAYS).then(|| slope_per_day(&used));
+            let days_until_full = growth_bytes_per_day
+                .filter(|rate| *rate > 0.0)
+                .map(|rate| reading.free_bytes as f64 / rate);
+
+            VolumeForecast {
+                mount_point: reading.mount_point.clone(),
+                total_bytes: reading.total_bytes,
+                free_bytes: reading.free_bytes,
+                growth_bytes_per_day,
+                days_until_full,
+                days_observed,
+                drivers: growth
+                    .get(reading.mount_point.as_str())
+                    .cloned()
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    // Soonest to fill up first
+    forecasts.sort_by(|a, b| {
+        a.days_until_full
+            .unwrap_or(f64::INFINITY)
+            .total_cmp(&b.days_until_full.unwrap_or(f64::INFINITY))
+            .then_with(|| a.mount_point.cmp(&b.mount_point))
+    });
+    forecasts
+}
+
+/// The volume a path is on: the longest mount point it's below
+fn volume_of<'a>(path: &Path, mounts: &[&'a str]) -> Option<&'a str> {
+    mounts
+        .iter()
+        .filter(|mount| path.starts_with(mount))
+        .max_by_key(|mount| mount.len())
+        .copied()
+}
+
+/// Growth per category on each volume, from the totals of the stored scan sessions
+///
+/// Totals are only compared between sessions of the same scan root: scanning a
+/// project folder after the home folder isn't a category shrinking. A category
+/// grows at the fastest rate seen from any root.
+fn category_growth<'a>(
+    mounts: &[&'a str],
+    sessions: &[(ScanSession, Vec<SessionItem>)],
+    registry: &CategoryRegistry,
+) -> HashMap<&'a str, Vec<CategoryGrowth>> {
+    // Total per scan root, volume and category for each session that scanned the category
+    let mut series: BTreeMap<(Option<&Path>, &str, &str), Series> = BTreeMap::new();
+    for (session, items) in sessions {
+        let scan_root = session.scan_root.as_deref();
+        let mut totals: HashMap<(&str, &str), u64> = HashMap::new();
+        for mount in mounts {
+            for category in &session.categories {
+                totals.insert((mount, category.as_str()), 0);
+            }
+        }
+        for item in items {
+            if let Some(mount) = volume_of(&item.path, mounts) {
+                if let Some(total) = totals.get_mut(&(mount, item.category.as_str())) {
+                    *total += item.size_bytes;
+                }
+            }
+        }
+        for ((mount, category), total) in totals {
+            series
+                .entry((scan_root, mount, category))
+                .or_default()
+                .push((session.started_at, total));
+        }
+    }
+
+    let mut rates: BTreeMap<(&str, &str), f64> = BTreeMap::new();
+    for ((_, mount, category), mut totals) in series {
+        totals.sort_by_key(|(at, _)| *at);
+        let rate = slope_per_day(&totals);
+        let fastest = rates.entry((mount, category)).or_insert(rate);
+        *fastest = fastest.max(rate);
+    }
+
+    let mut growth: HashMap<&str, Vec<CategoryGrowth>> = HashMap::new();
+    for ((mount, category), rate) in rates {
+        if rate > 0.0 {
+            growth.entry(mount).or_default().push(CategoryGrowth {
+                category: category.to_string(),
+                name: registry
+                    .get(category)
+                    .map(|d| d.display_name.clone())
+                    .unwrap_or_else(|| category.to_string()),
+                growth_bytes_per_day: rate,
+            });
+        }
+    }
+
+    for drivers in growth.values_mut() {
+        drivers.sort_by(|a, b| b.growth_bytes_per_day.total_cmp(&a.growth_bytes_per_day));
+        drivers.truncate(MAX_DRIVERS);
+    }
+    growth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn reading(mount_point: &str, free_bytes: u64) -> DiskReading {
+        DiskReading {
+            mount_point: mount_point.to_string(),
+            total_bytes: 10_000,
+            free_bytes,
+        }
+    }
+
+    #[test]
+    fn test_forecasts_when_volumes_fill_up() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let day = |n| start + Duration::days(n);
+        let history = vec![
+            (day(0), reading("/", 5_000)),
+            (day(0), reading("/data", 8_000)),
+            (day(1), reading("/", 4_900)),
+            (day(2), reading("/", 4_800)),
+            (day(2), reading("/data", 8_200)),
+        ];
+        let current = [reading("/", 4_600), reading("/data", 8_200)];
+
+        let session = |n, size_bytes| {
+            let mut session = ScanSession::new("full".to_string(), vec!["build".to_string()]);
+            session.started_at = day(n);
+            let items = vec![
+                SessionItem {
+                    category: "build".to_string(),
+                    path: PathBuf::from("/home/dev/app/node_modules"),
+                    size_bytes,
+                },
+                SessionItem {
+                    category: "build".to_string(),
+                    path: PathBuf::from("/data/app/target"),
+                    size_bytes: 50,
+                },
+            ];
+            (session, items)
+        };
+        let sessions = vec![session(0, 100), session(2, 260)];
+
+        let forecasts = forecast(&current, &history, &sessions, &CategoryRegistry::builtin());
+        let root = &forecasts[0];
+        assert_eq!(root.mount_point, "/");
+        assert_eq!(root.growth_bytes_per_day, Some(100.0));
+        assert_eq!(root.days_until_full, Some(46.0));
+        assert_eq!(root.drivers[0].growth_bytes_per_day, 80.0);
+        assert_eq!(
+            root.to_string(),
+            "/ full in ~46 days, mostly Build Artifacts"
+        );
+
+        // Freed space, and the build artifacts on /data didn't grow
+        let data = &forecasts[1];
+        assert_eq!(data.days_until_full, None);
+        assert!(data.drivers.is_empty());
+        assert_eq!(data.to_string(), "/data not filling up");
+    }
+
+    #[test]
+    fn test_growth_compares_sessions_of_the_same_scan_root() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let session = |n, root: &str, size_bytes| {
+            let mut session = ScanSession::new("full".to_string(), vec!["build".to_string()]);
+            session.started_at = start + Duration::days(n);
+            session.scan_root = Some(PathBuf::from(root));
+            let items = vec![SessionItem {
+                category: "build".to_string(),
+                path: PathBuf::from("/home/dev/app/node_modules"),
+                size_bytes,
+            }];
+            (session, items)
+        };
+        // The home folder finds more than the project alone, without anything growing
+        let sessions = vec![
+            session(0, "/home/dev/app", 100),
+            session(1, "/home/dev", 1_000),
+            session(2, "/home/dev/app", 100),
+            session(3, "/home/dev", 1_000),
+        ];
+
+        let growth = category_growth(&["/"], &sessions, &CategoryRegistry::builtin());
+        assert!(growth.is_empty());
+    }
+}
@@ -1,8 +1,15 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Utc};
+use flate2::read::ZlibDecoder;
 // git2 dependency REMOVED - causes stack overflow on Windows during static init
 // use git2::Repository;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+pub mod ignore;
 
 // ============================================================================
 // Git Root Cache
@@ -104,18 +111,462 @@ pub fn find_git_root(path: &Path) -> Option<PathBuf> {
     None
 }
 
-/// Check if a git repository has uncommitted changes (dirty)
-/// DISABLED: git2 dependency removed due to Windows stack overflow
-pub fn is_dirty(_repo_path: &Path) -> Result<bool> {
-    // git2 removed - always return false
+// ============================================================================
+// Repository State
+// ============================================================================
+//
+// git2 can't be used (see above), so repository state is read in this order:
+//
+// 1. The `git` binary, when one is on PATH. It hashes file contents, reads packs
+//    and every index version, and can scope both answers to a subdirectory.
+// 2. The repository files directly: `HEAD`, loose refs, `packed-refs`, objects,
+//    reflogs and the index. Dirty checks compare index stat data against the
+//    worktree (like `git diff-files` before a refresh, so touched files count as
+//    changed). The last-commit date is the committer date of the `HEAD` commit,
+//    read from its loose or packed (non-delta) object; when that can't be read,
+//    it's only known when the reflog says `HEAD` reached its commit.
+//
+// Only changes to tracked files count as uncommitted; untracked files don't.
+// ============================================================================
+
+/// Check if the repository containing `path` has uncommitted changes to tracked files
+///
+/// When `path` is a subdirectory of the repository (e.g. one project of a
+/// monorepo), only changes below it count. Paths outside any repository are clean.
+pub fn is_dirty(path: &Path) -> Result<bool> {
+    let Some(root) = find_git_root_cached(path) else {
+        return Ok(false);
+    };
+    if git_binary_available() {
+        if let Ok(dirty) = binary_is_dirty(path) {
+            return Ok(dirty);
+        }
+    }
+    files_is_dirty(&root, path)
+}
+
+/// When the checked-out commit was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitTime {
+    /// Committer date of the `HEAD` commit
+    Committed(DateTime<Utc>),
+    /// When `HEAD` last moved to its commit (commit, clone, pull, checkout or
+    /// reset), from the reflog: the fallback when the commit can't be read
+    HeadMoved(DateTime<Utc>),
+}
+
+impl CommitTime {
+    pub fn date(self) -> DateTime<Utc> {
+        match self {
+            CommitTime::Committed(date) | CommitTime::HeadMoved(date) => date,
+        }
+    }
+}
+
+/// Get the time of the last commit in the repository containing `path`
+///
+/// `git log` answers when a `git` binary is available; otherwise the `HEAD`
+/// commit object is read from the repository files, and only when that fails
+/// is the reflog used.
+pub fn last_commit_time(path: &Path) -> Result<Option<CommitTime>> {
+    let Some(root) = find_git_root_cached(path) else {
+        return Ok(None);
+    };
+    if git_binary_available() {
+        if let Ok(output) = run_git(&root, &["log", "-1", "--format=%ct"]) {
+            if let Some(date) = output
+                .trim()
+                .parse()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            {
+                return Ok(Some(CommitTime::Committed(date)));
+            }
+        }
+    }
+    files_last_commit_time(&root)
+}
+
+/// Check whether `path` is, or contains, a file tracked by the repository containing it
+pub fn is_tracked(path: &Path) -> Result<bool> {
+    let Some(root) = find_git_root_cached(path) else {
+        return Ok(false);
+    };
+    if git_binary_available() {
+        if let Ok(files) = run_git(path, &["ls-files", "--", "."]) {
+            return Ok(!files.trim().is_empty());
+        }
+    }
+    let (git_dir, _) = git_dirs(&root)?;
+    let index = match fs::read(git_dir.join("index")) {
+        Ok(index) => index,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    let prefix = path.strip_prefix(&root).unwrap_or(Path::new(""));
+    Ok(parse_index(&index)?
+        .iter()
+        .any(|entry| Path::new(&entry.path).starts_with(prefix)))
+}
+
+/// Whether a working `git` binary is on PATH (checked once per process)
+fn git_binary_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("git")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    })
+}
+
+fn run_git(path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        // Don't let `git status` refresh (and rewrite) the index
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn binary_is_dirty(path: &Path) -> Result<bool> {
+    let status = run_git(
+        path,
+        &["status", "--porcelain", "--untracked-files=no", "--", "."],
+    )?;
+    Ok(!status.trim().is_empty())
+}
+
+/// The repository's git dir and common dir (they differ for linked worktrees)
+fn git_dirs(root: &Path) -> Result<(PathBuf, PathBuf)> {
+    let dot_git = root.join(".git");
+    let git_dir = if dot_git.is_file() {
+        // Worktrees and submodules: `gitdir: <path>`
+        let contents = fs::read_to_string(&dot_git)?;
+        let target = contents
+            .trim()
+            .strip_prefix("gitdir:")
+            .with_context(|| format!("Unrecognized {}", dot_git.display()))?;
+        root.join(target.trim())
+    } else {
+        dot_git
+    };
+    let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir.clone(),
+    };
+    Ok((git_dir, common_dir))
+}
+
+/// Resolve a ref name like `refs/heads/main` through loose refs, then `packed-refs`
+fn resolve_ref(common_dir: &Path, name: &str) -> Option<String> {
+    if let Ok(contents) = fs::read_to_string(common_dir.join(name)) {
+        let contents = contents.trim();
+        return match contents.strip_prefix("ref:") {
+            Some(target) => resolve_ref(common_dir, target.trim()),
+            None => Some(contents.to_string()),
+        };
+    }
+    let packed = fs::read_to_string(common_dir.join("packed-refs")).ok()?;
+    packed.lines().find_map(|line| {
+        let (sha, ref_name) = line.split_once(' ')?;
+        (ref_name.trim() == name && !line.starts_with('#') && !line.starts_with('^'))
+            .then(|| sha.to_string())
+    })
+}
+
+/// `(new sha, timestamp)` of every entry in a reflog, oldest first
+fn read_reflog(path: &Path) -> Vec<(String, i64)> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| {
+            // <old> <new> <name> <<email>> <timestamp> <tz>\t<message>
+            let header = line.split('\t').next()?;
+            let mut fields = header.split(' ');
+            let _old = fields.next()?;
+            let new = fields.next()?;
+            let mut tail = header.rsplitn(3, ' ');
+            let _tz = tail.next()?;
+            let timestamp = tail.next()?.parse().ok()?;
+            Some((new.to_string(), timestamp))
+        })
+        .collect()
+}
+
+fn files_last_commit_time(root: &Path) -> Result<Option<CommitTime>> {
+    let (git_dir, common_dir) = git_dirs(root)?;
+    let head = fs::read_to_string(git_dir.join("HEAD"))?;
+    let head = head.trim();
+    let (branch_ref, commit) = match head.strip_prefix("ref:") {
+        Some(name) => (Some(name.trim()), resolve_ref(&common_dir, name.trim())),
+        None => (None, Some(head.to_string())),
+    };
+    let Some(commit) = commit else {
+        // Unborn branch: no commits yet
+        return Ok(None);
+    };
+    if let Some(date) = read_commit(&common_dir.join("objects"), &commit)
+        .as_deref()
+        .and_then(committer_date)
+    {
+        return Ok(Some(CommitTime::Committed(date)));
+    }
+
+    let mut reflog = branch_ref
+        .map(|name| read_reflog(&common_dir.join("logs").join(name)))
+        .unwrap_or_default();
+    if reflog.is_empty() {
+        reflog = read_reflog(&git_dir.join("logs").join("HEAD"));
+    }
+    // When HEAD last arrived at its current commit, else the newest entry
+    let timestamp = reflog
+        .iter()
+        .rev()
+        .find(|(new, _)| *new == commit)
+        .or(reflog.last())
+        .map(|(_, ts)| *ts);
+    if let Some(date) = timestamp.and_then(|secs| DateTime::from_timestamp(secs, 0)) {
+        return Ok(Some(CommitTime::HeadMoved(date)));
+    }
+
+    // No reflog (e.g. core.logAllRefUpdates off): a loose ref is rewritten on each commit
+    let ref_file = branch_ref.map(|name| common_dir.join(name));
+    Ok(ref_file
+        .and_then(|path| fs::metadata(path).ok())
+        .and_then(|meta| meta.modified().ok())
+        .map(|modified| CommitTime::HeadMoved(modified.into())))
+}
+
+/// The contents of a commit object, loose or packed
+///
+/// Packed commits stored as deltas aren't resolved (git rarely deltifies commits).
+fn read_commit(objects: &Path, sha: &str) -> Option<Vec<u8>> {
+    if sha.len() != 40 || !sha.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    read_loose_commit(objects, sha).or_else(|| read_packed_commit(objects, sha))
+}
+
+/// `objects/ab/cdef…`: zlib-compressed `commit <size>\0<contents>`
+fn read_loose_commit(objects: &Path, sha: &str) -> Option<Vec<u8>> {
+    let file = fs::File::open(objects.join(&sha[..2]).join(&sha[2..])).ok()?;
+    let mut data = Vec::new();
+    ZlibDecoder::new(file).read_to_end(&mut data).ok()?;
+    let nul = data.iter().position(|&b| b == 0)?;
+    data.starts_with(b"commit ")
+        .then(|| data[nul + 1..].to_vec())
+}
+
+/// Look the commit up in each pack's version 2 `.idx` and inflate it from the `.pack`
+fn read_packed_commit(objects: &Path, sha: &str) -> Option<Vec<u8>> {
+    let target: Vec<u8> = (0..20)
+        .map(|i| u8::from_str_radix(&sha[i * 2..i * 2 + 2], 16))
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let be32 = |data: &[u8], at: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+    };
+
+    for entry in fs::read_dir(objects.join("pack")).ok()?.flatten() {
+        let idx_path = entry.path();
+        if idx_path.extension().and_then(|e| e.to_str()) != Some("idx") {
+            continue;
+        }
+        let Ok(idx) = fs::read(&idx_path) else {
+            continue;
+        };
+        if !idx.starts_with(b"\xfftOc") || be32(&idx, 4) != Some(2) {
+            continue;
+        }
+        // 256 cumulative counts by first byte, then the sorted names, CRCs and offsets
+        let fanout = |byte: usize| be32(&idx, 8 + byte * 4).map(|n| n as usize);
+        let Some(count) = fanout(255) else {
+            continue;
+        };
+        let first = target[0] as usize;
+        let (Some(start), Some(end)) = (
+            if first == 0 {
+                Some(0)
+            } else {
+                fanout(first - 1)
+            },
+            fanout(first),
+        ) else {
+            continue;
+        };
+        let names = 8 + 256 * 4;
+        let name = |i: usize| idx.get(names + i * 20..names + (i + 1) * 20);
+        let found = (start..end.min(count)).find(|&i| name(i) == Some(&target[..]));
+        let Some(i) = found else {
+            continue;
+        };
+        let offsets = names + count * 20 + count * 4;
+        let offset = be32(&idx, offsets + i * 4)?;
+        let offset = if offset & 0x8000_0000 == 0 {
+            offset as u64
+        } else {
+            let large = offsets + count * 4 + (offset & 0x7fff_ffff) as usize * 8;
+            u64::from_be_bytes(idx.get(large..large + 8)?.try_into().ok()?)
+        };
+        return read_pack_entry(&idx_path.with_extension("pack"), offset);
+    }
+    None
+}
+
+/// Inflate the non-delta commit at `offset` of a pack
+fn read_pack_entry(pack: &Path, offset: u64) -> Option<Vec<u8>> {
+    const OBJ_COMMIT: u8 = 1;
+    let mut file = fs::File::open(pack).ok()?;
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut reader = std::io::BufReader::new(file);
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte).ok()?;
+    // Type in bits 4-6 of the first byte, size in a little-endian varint
+    let kind = (byte[0] >> 4) & 0x7;
+    let mut size = (byte[0] & 0x0f) as u64;
+    let mut shift = 4;
+    while byte[0] & 0x80 != 0 {
+        reader.read_exact(&mut byte).ok()?;
+        size |= ((byte[0] & 0x7f) as u64) << shift;
+        shift += 7;
+    }
+    if kind != OBJ_COMMIT {
+        return None;
+    }
+    let mut data = Vec::new();
+    ZlibDecoder::new(reader)
+        .take(size)
+        .read_to_end(&mut data)
+        .ok()?;
+    Some(data)
+}
+
+/// The committer date of a commit's contents
+fn committer_date(commit: &[u8]) -> Option<DateTime<Utc>> {
+    let text = String::from_utf8_lossy(commit);
+    // Headers end at the first blank line; `committer <name> <<email>> <timestamp> <tz>`
+    let line = text
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| line.strip_prefix("committer "))?;
+    let mut tail = line.rsplitn(3, ' ');
+    let _tz = tail.next()?;
+    DateTime::from_timestamp(tail.next()?.parse().ok()?, 0)
+}
+
+/// Compare the index's stat data for tracked files below `scope` with the worktree
+fn files_is_dirty(root: &Path, scope: &Path) -> Result<bool> {
+    let (git_dir, _) = git_dirs(root)?;
+    let index = match fs::read(git_dir.join("index")) {
+        Ok(index) => index,
+        // No index yet: nothing tracked
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    let prefix = scope.strip_prefix(root).unwrap_or(Path::new(""));
+
+    for entry in parse_index(&index)? {
+        let relative = Path::new(&entry.path);
+        if !entry.comparable || !relative.starts_with(prefix) {
+            continue;
+        }
+        let Ok(meta) = fs::symlink_metadata(root.join(relative)) else {
+            return Ok(true); // Deleted
+        };
+        let mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as u32);
+        if meta.len() as u32 != entry.size || mtime != Some(entry.mtime_secs) {
+            return Ok(true);
+        }
+    }
     Ok(false)
 }
 
-/// Get the date of the last commit in a git repository
-/// DISABLED: git2 dependency removed due to Windows stack overflow
-pub fn last_commit_date(_repo_path: &Path) -> Result<Option<DateTime<Utc>>> {
-    // git2 removed - always return None
-    Ok(None)
+/// The parts of an index entry the dirty and tracked checks need
+struct IndexEntry {
+    path: String,
+    mtime_secs: u32,
+    size: u32,
+    /// False for gitlinks (submodules) and skip-worktree entries, whose
+    /// worktree state can't be compared with the index
+    comparable: bool,
+}
+
+/// Parse a version 2 or 3 index (`.git/index`)
+fn parse_index(data: &[u8]) -> Result<Vec<IndexEntry>> {
+    let u32_at = |offset: usize| -> Result<u32> {
+        let bytes = data
+            .get(offset..offset + 4)
+            .context("Truncated git index")?;
+        Ok(u32::from_be_bytes(bytes.try_into()?))
+    };
+    let u16_at = |offset: usize| -> Result<u16> {
+        let bytes = data
+            .get(offset..offset + 2)
+            .context("Truncated git index")?;
+        Ok(u16::from_be_bytes(bytes.try_into()?))
+    };
+
+    if data.get(..4) != Some(b"DIRC") {
+        bail!("Not a git index");
+    }
+    let version = u32_at(4)?;
+    if !(2..=3).contains(&version) {
+        bail!("Unsupported git index version {}", version);
+    }
+    let count = u32_at(8)? as usize;
+    // SHA-256 repositories aren't supported; their entries are 12 bytes longer
+    const SHA_LEN: usize = 20;
+
+    let mut entries = Vec::with_capacity(count);
+    let mut offset = 12;
+    for _ in 0..count {
+        let mtime_secs = u32_at(offset + 8)?;
+        let mode = u32_at(offset + 24)?;
+        let size = u32_at(offset + 36)?;
+        let flags = u16_at(offset + 40 + SHA_LEN)?;
+        let mut header_len = 40 + SHA_LEN + 2;
+        let mut skip_worktree = false;
+        if flags & 0x4000 != 0 {
+            skip_worktree = u16_at(offset + header_len)? & 0x4000 != 0;
+            header_len += 2;
+        }
+
+        let path_start = offset + header_len;
+        let path_len = data
+            .get(path_start..)
+            .and_then(|rest| rest.iter().position(|&b| b == 0))
+            .context("Truncated git index")?;
+        let path = String::from_utf8_lossy(&data[path_start..path_start + path_len]).into_owned();
+        // Entries are NUL-padded to a multiple of 8 bytes
+        offset += (header_len + path_len + 8) & !7;
+
+        let is_gitlink = mode & 0o170000 == 0o160000;
+        entries.push(IndexEntry {
+            path,
+            mtime_secs,
+            size,
+            comparable: !is_gitlink && !skip_worktree,
+        });
+    }
+    Ok(entries)
 }
 
 #[cfg(test)]
@@ -177,4 +628,130 @@ mod tests {
         // No git repo, should return Ok(false)
         assert!(!is_dirty(temp_dir.path()).unwrap());
     }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_reads_head_through_packed_refs_and_reflog() {
+        let temp_dir = create_test_dir();
+        let git_dir = temp_dir.path().join(".git");
+        let sha = "a".repeat(40);
+        fs::create_dir_all(git_dir.join("logs/refs/heads")).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(
+            git_dir.join("packed-refs"),
+            format!("# pack-refs with: peeled\n{} refs/heads/main\n", sha),
+        )
+        .unwrap();
+        fs::write(
+            git_dir.join("logs/refs/heads/main"),
+            format!(
+                "{zero} {sha} A <a@b> 1700000000 +0000\tcommit (initial): x\n\
+                 {sha} {other} A <a@b> 1700000500 +0000\tcommit: y\n\
+                 {other} {sha} A <a@b> 1700001000 +0100\treset: moving to HEAD~1\n",
+                zero = "0".repeat(40),
+                other = "b".repeat(40),
+            ),
+        )
+        .unwrap();
+
+        // No commit object to read: only when HEAD got there is known
+        let time = files_last_commit_time(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(
+            time,
+            CommitTime::HeadMoved(DateTime::from_timestamp(1700001000, 0).unwrap())
+        );
+        // No index: nothing tracked, nothing dirty
+        assert!(!files_is_dirty(temp_dir.path(), temp_dir.path()).unwrap());
+    }
+
+    #[test]
+    fn test_file_reader_agrees_with_git() {
+        if !git_binary_available() {
+            return;
+        }
+        let temp_dir = create_test_dir();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("app/src")).unwrap();
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::write(root.join("app/src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(root.join("lib/lib.rs"), "\n").unwrap();
+        git(root, &["init", "-q"]);
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "init"]);
+
+        assert!(!files_is_dirty(root, root).unwrap());
+        assert!(!binary_is_dirty(root).unwrap());
+        let committed = last_commit_time(root).unwrap().unwrap();
+        assert!(matches!(committed, CommitTime::Committed(_)));
+        assert!((Utc::now() - committed.date()).num_seconds().abs() <= 60);
+
+        fs::write(root.join("lib/lib.rs"), "pub fn changed() {}\n").unwrap();
+        assert!(files_is_dirty(root, root).unwrap());
+        assert!(files_is_dirty(root, &root.join("lib")).unwrap());
+        assert!(!files_is_dirty(root, &root.join("app")).unwrap());
+        assert!(binary_is_dirty(&root.join("lib")).unwrap());
+        assert!(!binary_is_dirty(&root.join("app")).unwrap());
+
+        fs::create_dir_all(root.join("app/bin")).unwrap();
+        fs::write(root.join("app/bin/out"), "x").unwrap();
+        assert!(is_tracked(&root.join("app/src")).unwrap());
+        assert!(!is_tracked(&root.join("app/bin")).unwrap());
+    }
+
+    #[test]
+    fn test_reads_committer_date_of_old_commit_after_clone() {
+        if !git_binary_available() {
+            return;
+        }
+        let temp_dir = create_test_dir();
+        let origin = temp_dir.path().join("origin");
+        fs::create_dir_all(&origin).unwrap();
+        fs::write(origin.join("README"), "hi\n").unwrap();
+        git(&origin, &["init", "-q"]);
+        git(&origin, &["add", "."]);
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&origin)
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(["commit", "-q", "-m", "old"])
+            .env("GIT_AUTHOR_DATE", "1500000000 +0000")
+            .env("GIT_COMMITTER_DATE", "1500000000 +0000")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let old = CommitTime::Committed(DateTime::from_timestamp(1500000000, 0).unwrap());
+
+        // Loose object
+        assert_eq!(files_last_commit_time(&origin).unwrap(), Some(old));
+
+        // Just cloned: the reflog says today, the commit says 2017
+        let clone = temp_dir.path().join("clone");
+        git(
+            temp_dir.path(),
+            &[
+                "clone",
+                "-q",
+                "--no-local",
+                origin.to_str().unwrap(),
+                "clone",
+            ],
+        );
+        assert_eq!(last_commit_time(&clone).unwrap(), Some(old));
+        assert_eq!(files_last_commit_time(&clone).unwrap(), Some(old));
+
+        // Packed object
+        git(&origin, &["repack", "-a", "-d", "-q"]);
+        git(&origin, &["prune-packed"]);
+        assert_eq!(files_last_commit_time(&origin).unwrap(), Some(old));
+    }
 }
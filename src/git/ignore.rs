@@ -0,0 +1,162 @@
+//! `.gitignore` matching without git.
+//!
+//! Supports what decides whether a build folder is ignored: `.git/info/exclude`,
+//! nested `.gitignore` files (deeper files override outer ones), negations,
+//! directory-only patterns, anchored patterns and `**`. As in git, a path inside
+//! an ignored directory stays ignored even if a later rule negates it.
+
+use globset::{GlobBuilder, GlobMatcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Rule {
+    /// Directory of the file that declared the rule
+    base: PathBuf,
+    matcher: GlobMatcher,
+    negated: bool,
+    dir_only: bool,
+    /// Match against the path relative to `base`, not just the file name
+    anchored: bool,
+}
+
+/// Ignore rules that apply to one path, outermost file first
+pub struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+impl IgnoreRules {
+    /// Load `.git/info/exclude` and every `.gitignore` from `root` down to `path`'s parent
+    pub fn for_path(root: &Path, path: &Path) -> Self {
+        let mut rules = Vec::new();
+        parse_file(
+            &root.join(".git").join("info").join("exclude"),
+            root,
+            &mut rules,
+        );
+
+        let mut dir = root.to_path_buf();
+        parse_file(&dir.join(".gitignore"), &dir, &mut rules);
+        if let Some(parent) = path.parent().and_then(|p| p.strip_prefix(root).ok()) {
+            for component in parent.components() {
+                dir.push(component);
+                parse_file(&dir.join(".gitignore"), &dir, &mut rules);
+            }
+        }
+        Self { rules }
+    }
+
+    /// Whether any ignore file was found
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Verdict of the last rule matching `path`, or `None` if no rule matches
+    fn matched(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        self.rules.iter().rev().find_map(|rule| {
+            if rule.dir_only && !is_dir {
+                return None;
+            }
+            let relative = path.strip_prefix(&rule.base).ok()?;
+            let candidate = if rule.anchored {
+                relative.to_string_lossy().replace('\\', "/")
+            } else {
+                relative.file_name()?.to_string_lossy().into_owned()
+            };
+            rule.matcher.is_match(&candidate).then_some(!rule.negated)
+        })
+    }
+
+    /// Whether `path` (below `root`) is ignored, directly or through an ignored parent
+    pub fn is_ignored(&self, root: &Path, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(root) else {
+            return false;
+        };
+        let mut current = root.to_path_buf();
+        let components: Vec<_> = relative.components().collect();
+        for (i, component) in components.iter().enumerate() {
+            current.push(component);
+            let last = i + 1 == components.len();
+            let verdict = self.matched(&current, if last { is_dir } else { true });
+            if verdict == Some(true) && !last {
+                return true;
+            }
+            if last {
+                return verdict.unwrap_or(false);
+            }
+        }
+        false
+    }
+}
+
+/// Check whether `path` is ignored by the ignore files between `root` and it
+pub fn is_ignored(root: &Path, path: &Path, is_dir: bool) -> bool {
+    IgnoreRules::for_path(root, path).is_ignored(root, path, is_dir)
+}
+
+fn parse_file(file: &Path, base: &Path, rules: &mut Vec<Rule>) {
+    let Ok(contents) = fs::read_to_string(file) else {
+        return;
+    };
+    rules.extend(contents.lines().filter_map(|line| parse_line(line, base)));
+}
+
+fn parse_line(line: &str, base: &Path) -> Option<Rule> {
+    let mut pattern = line.trim_end();
+    if pattern.is_empty() || pattern.starts_with('#') {
+        return None;
+    }
+    let negated = pattern.starts_with('!');
+    if negated {
+        pattern = &pattern[1..];
+    }
+    // `\#` and `\!` escape a leading special character
+    if pattern.starts_with("\\#") || pattern.starts_with("\\!") {
+        pattern = &pattern[1..];
+    }
+    let dir_only = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+
+    let matcher = GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .backslash_escape(true)
+        .build()
+        .ok()?
+        .compile_matcher();
+    Some(Rule {
+        base: base.to_path_buf(),
+        matcher,
+        negated,
+        dir_only,
+        anchored,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_files_and_negations() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("app/bin")).unwrap();
+        fs::create_dir_all(root.join("tools/bin")).unwrap();
+        fs::write(root.join(".gitignore"), "bin/\n/dist\n*.log\n").unwrap();
+        fs::write(root.join("tools/.gitignore"), "!bin/\n").unwrap();
+
+        assert!(is_ignored(root, &root.join("app/bin"), true));
+        assert!(!is_ignored(root, &root.join("tools/bin"), true));
+        // Anchored to the root .gitignore
+        assert!(is_ignored(root, &root.join("dist"), true));
+        assert!(!is_ignored(root, &root.join("app/dist"), true));
+        // Directory-only pattern doesn't match files, but parents still count
+        assert!(!is_ignored(root, &root.join("lib/bin"), false));
+        assert!(is_ignored(root, &root.join("app/bin/run.sh"), false));
+        assert!(is_ignored(root, &root.join("app/x/debug.log"), false));
+    }
+}
@@ -29,6 +29,10 @@ pub struct DeletionRecord {
     pub success: bool,
     /// Error message if deletion failed
     pub error: Option<String>,
+    /// Dedupe mode ("link" or "reflink") when the file was replaced with a
+    /// link to the kept copy instead of deleted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked: Option<String>,
 }
 
 impl DeletionRecord {
@@ -42,6 +46,15 @@ impl DeletionRecord {
             permanent,
             success: true,
             error: None,
+            linked: None,
+        }
+    }
+
+    /// Create a record of a duplicate replaced with a link to the kept copy
+    pub fn linked(path: &Path, size_bytes: u64, category: &str, mode: &str) -> Self {
+        Self {
+            linked: Some(mode.to_string()),
+            ..Self::success(path, size_bytes, category, false)
         }
     }
 
@@ -61,8 +74,14 @@ impl DeletionRecord {
             permanent,
             success: false,
             error: Some(error.to_string()),
+            linked: None,
         }
     }
+
+    /// Whether the file went to the Recycle Bin, so it can be restored
+    pub fn is_restorable(&self) -> bool {
+        self.success && !self.permanent && self.linked.is_none()
+    }
 }
 
 /// Log of all deletions in a session
@@ -111,6 +130,11 @@ impl DeletionLog {
         ));
     }
 
+    /// Add a duplicate that was replaced with a link instead of deleted
+    pub fn log_linked(&mut self, path: &Path, size_bytes: u64, category: &str, mode: &str) {
+        self.add_record(DeletionRecord::linked(path, size_bytes, category, mode));
+    }
+
     /// Add a failed deletion
     pub fn log_failure(
         &mut self,
@@ -261,6 +285,18 @@ mod tests {
         assert!(record.permanent);
     }
 
+    #[test]
+    fn test_linked_record_is_not_restorable() {
+        let record =
+            DeletionRecord::linked(Path::new("/test/copy.iso"), 4096, "duplicates", "link");
+
+        assert!(record.success);
+        assert!(!record.permanent);
+        assert_eq!(record.linked.as_deref(), Some("link"));
+        assert!(!record.is_restorable());
+        assert!(DeletionRecord::success(Path::new("/test/a"), 1, "cache", false).is_restorable());
+    }
+
     #[test]
     fn test_deletion_log_new() {
         let log = DeletionLog::new();
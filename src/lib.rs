@@ -8,18 +8,25 @@ pub mod cleaner;
 pub mod cli;
 pub mod config;
 pub mod debug_log;
+pub mod dedupe;
+pub mod disk_trend;
 pub mod disk_usage;
 mod disk_usage_cache;
+pub mod forecast;
 pub mod git;
 pub mod history;
+pub mod lockfiles;
 pub mod optimize;
 pub mod output;
 pub mod progress;
 pub mod project;
+pub mod projects;
 pub mod restore;
 pub mod scan_cache;
+pub mod scan_diff;
 pub mod scan_events;
 pub mod scanner;
+pub mod serve;
 pub mod size;
 pub mod spinner;
 pub mod status;
@@ -28,5 +35,7 @@ pub mod tui;
 pub mod uninstall;
 pub mod update;
 pub mod utils;
+pub mod watch;
+pub mod workspace;
 
 pub(crate) mod trash_ops;
@@ -0,0 +1,234 @@
+//! Reference counting of package versions pinned by lockfiles on disk.
+//!
+//! Package caches keep every version ever downloaded. By parsing the lockfiles of
+//! the projects found by [`project::find_project_roots`], we can tell which cached
+//! versions some project still pins and which ones nothing on disk refers to.
+//! Each lockfile format implements [`LockfileFormat`]; [`RefCounts`] does the rest.
+
+use crate::project::{self, ProjectType};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A package version pinned by a lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackageRef {
+    pub name: String,
+    pub version: String,
+}
+
+impl PackageRef {
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+        }
+    }
+}
+
+/// A lockfile format whose pinned packages can be counted
+pub trait LockfileFormat: Send + Sync {
+    /// Project type that owns this lockfile
+    fn project_type(&self) -> ProjectType;
+
+    /// File name looked for in each project root, e.g. `Cargo.lock`
+    fn file_name(&self) -> &'static str;
+
+    /// Packages pinned by the lockfile's contents
+    fn parse(&self, contents: &str) -> Result<Vec<PackageRef>>;
+}
+
+/// `Cargo.lock`; only packages from a registry are counted (not git or path deps)
+pub struct CargoLock;
+
+#[derive(Deserialize)]
+struct CargoLockFile {
+    #[serde(default)]
+    package: Vec<CargoLockPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoLockPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+}
+
+impl LockfileFormat for CargoLock {
+    fn project_type(&self) -> ProjectType {
+        ProjectType::Rust
+    }
+
+    fn file_name(&self) -> &'static str {
+        "Cargo.lock"
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<PackageRef>> {
+        let lock: CargoLockFile = toml::from_str(contents)?;
+        Ok(lock
+            .package
+            .into_iter()
+            .filter(|p| {
+                p.source
+                    .as_deref()
+                    .map(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+                    .unwrap_or(false)
+            })
+            .map(|p| PackageRef::new(p.name, p.version))
+            .collect())
+    }
+}
+
+/// How many lockfiles reference each package version
+#[derive(Debug, Default)]
+pub struct RefCounts {
+    counts: HashMap<PackageRef, usize>,
+    /// Lockfiles that were read successfully
+    pub lockfiles: Vec<PathBuf>,
+    /// Lockfiles that exist but couldn't be read or parsed
+    pub errors: Vec<String>,
+}
+
+impl RefCounts {
+    /// Count references from the lockfiles of every project below `search_roots`
+    ///
+    /// Every project root is checked for the lockfile, not only projects detected
+    /// as `format.project_type()`, so e.g. a Rust crate inside a Node project counts.
+    pub fn from_projects(
+        format: &dyn LockfileFormat,
+        search_roots: &[PathBuf],
+        config: &crate::config::Config,
+    ) -> Self {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for search_root in search_roots {
+            for project in project::find_project_roots(search_root, config) {
+                if !roots.contains(&project) {
+                    roots.push(project);
+                }
+            }
+        }
+        Self::from_roots(format, &roots)
+    }
+
+    /// Count references from the lockfiles found directly in `roots`
+    pub fn from_roots(format: &dyn LockfileFormat, roots: &[PathBuf]) -> Self {
+        let mut refs = Self::default();
+        for root in roots {
+            let lockfile = root.join(format.file_name());
+            if !lockfile.is_file() {
+                continue;
+            }
+            match read_lockfile(format, &lockfile) {
+                Ok(packages) => {
+                    for package in packages {
+                        *refs.counts.entry(package).or_insert(0) += 1;
+                    }
+                    refs.lockfiles.push(lockfile);
+                }
+                Err(e) => refs.errors.push(format!("{:#}", e)),
+            }
+        }
+        refs
+    }
+
+    /// Number of lockfiles pinning this exact version
+    pub fn count(&self, name: &str, version: &str) -> usize {
+        self.counts
+            .get(&PackageRef::new(name, version))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn is_referenced(&self, name: &str, version: &str) -> bool {
+        self.count(name, version) > 0
+    }
+
+    /// Whether any lockfile was read; without one, nothing can be called unreferenced
+    pub fn is_empty(&self) -> bool {
+        self.lockfiles.is_empty()
+    }
+}
+
+fn read_lockfile(format: &dyn LockfileFormat, path: &Path) -> Result<Vec<PackageRef>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    format
+        .parse(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Split a cached artifact name like `serde-1.0.200` or `sha-1-0.10.0` into name and version
+///
+/// The version starts at the first `-` followed by `MAJOR.MINOR.PATCH`.
+pub fn split_name_version(stem: &str) -> Option<(&str, &str)> {
+    stem.match_indices('-').find_map(|(idx, _)| {
+        let version = &stem[idx + 1..];
+        let mut numbers = version.splitn(3, '.');
+        let major = numbers.next()?;
+        let minor = numbers.next()?;
+        let patch = numbers.next()?;
+        let patch_digits = patch.chars().take_while(|c| c.is_ascii_digit()).count();
+        let is_version = !major.is_empty()
+            && major.chars().all(|c| c.is_ascii_digit())
+            && !minor.is_empty()
+            && minor.chars().all(|c| c.is_ascii_digit())
+            && patch_digits > 0;
+        (idx > 0 && is_version).then(|| (&stem[..idx], version))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_handles_dashes_in_names_and_prereleases() {
+        assert_eq!(
+            split_name_version("serde-1.0.200"),
+            Some(("serde", "1.0.200"))
+        );
+        assert_eq!(
+            split_name_version("sha-1-0.10.0"),
+            Some(("sha-1", "0.10.0"))
+        );
+        assert_eq!(
+            split_name_version("wasm-bindgen-0.2.92-alpha.1"),
+            Some(("wasm-bindgen", "0.2.92-alpha.1"))
+        );
+        assert_eq!(split_name_version("not-a-crate"), None);
+    }
+
+    #[test]
+    fn test_counts_registry_packages_across_lockfiles() {
+        let tmp = tempfile::tempdir().unwrap();
+        let a = tmp.path().join("a");
+        let b = tmp.path().join("b");
+        std::fs::create_dir_all(&a).unwrap();
+        std::fs::create_dir_all(&b).unwrap();
+        let registry = "registry+https://github.com/rust-lang/crates.io-index";
+        std::fs::write(
+            a.join("Cargo.lock"),
+            format!(
+                "version = 3\n\n[[package]]\nname = \"a\"\nversion = \"0.1.0\"\n\n\
+                 [[package]]\nname = \"serde\"\nversion = \"1.0.200\"\nsource = \"{registry}\"\n"
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            b.join("Cargo.lock"),
+            format!(
+                "[[package]]\nname = \"serde\"\nversion = \"1.0.200\"\nsource = \"{registry}\"\n\n\
+                 [[package]]\nname = \"log\"\nversion = \"0.4.21\"\nsource = \"sparse+https://index.crates.io/\"\n"
+            ),
+        )
+        .unwrap();
+
+        let refs = RefCounts::from_roots(&CargoLock, &[a, b, tmp.path().join("missing")]);
+        assert_eq!(refs.lockfiles.len(), 2);
+        assert_eq!(refs.count("serde", "1.0.200"), 2);
+        assert_eq!(refs.count("log", "0.4.21"), 1);
+        // Path dependencies aren't in any registry
+        assert!(!refs.is_referenced("a", "0.1.0"));
+    }
+}
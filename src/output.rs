@@ -1,33 +1,16 @@
+use crate::categories::registry::{CategoryDescriptor, CategoryRegistry};
 use crate::cli::ScanOptions;
+use crate::scan_diff::{self, ItemChange, ScanDiff};
 use crate::theme::Theme;
-use serde::Serialize;
-use std::path::PathBuf;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 // Forward declaration for duplicate groups
-pub use crate::categories::duplicates::DuplicateGroup;
-
-/// Get emoji for a category name in CLI output
-fn category_emoji(category_name: &str) -> &'static str {
-    match category_name {
-        "Installed Applications" | "Applications" => "📱",
-        "Old Files" => "📅",
-        "Downloads" | "Old Downloads" => "⬇️",
-        "Large Files" | "Large" => "📦",
-        "Package Cache" | "Package cache" => "📚",
-        "Application Cache" | "Application cache" => "💾",
-        "Temp Files" | "Temp" => "🗑️",
-        "Trash" => "🗑️",
-        "Build Artifacts" | "Build" => "🔨",
-        "Browser Cache" | "Browser" => "🌐",
-        "System Cache" | "System" => "⚙️",
-        "Empty Folders" | "Empty" => "📁",
-        "Duplicates" => "📋",
-        "Windows Update" => "🔄",
-        "Event Logs" => "📋",
-        _ => "📁", // Default folder emoji
-    }
-}
+pub use crate::categories::duplicates::{DuplicateGroup, DuplicateKind};
 
 /// Truncate a string to a maximum display width (adds ellipsis if needed).
 fn truncate_to_width(s: &str, max_width: usize) -> String {
@@ -96,65 +79,144 @@ pub enum OutputMode {
     VeryVerbose, // All details including file paths
 }
 
+/// Results of one scan, one entry per scanned category in registry order
 #[derive(Default, Debug, Clone)]
 pub struct ScanResults {
-    pub cache: CategoryResult,
-    pub app_cache: CategoryResult,
-    pub temp: CategoryResult,
-    pub trash: CategoryResult,
-    pub build: CategoryResult,
-    pub downloads: CategoryResult,
-    pub large: CategoryResult,
-    pub old: CategoryResult,
-    pub applications: CategoryResult,
-    pub browser: CategoryResult,
-    pub system: CategoryResult,
-    pub empty: CategoryResult,
-    pub duplicates: CategoryResult,
-    pub windows_update: CategoryResult,
-    pub event_logs: CategoryResult,
+    pub categories: Vec<CategoryScan>,
     /// Optional duplicate groups for enhanced display (only populated for duplicates category)
     pub duplicates_groups: Option<Vec<DuplicateGroup>>,
 }
 
+/// Scan result for one category
+#[derive(Debug, Clone)]
+pub struct CategoryScan {
+    pub descriptor: Arc<CategoryDescriptor>,
+    pub result: CategoryResult,
+}
+
+impl ScanResults {
+    /// Result for a category id, if it was scanned
+    pub fn get(&self, id: &str) -> Option<&CategoryResult> {
+        self.categories
+            .iter()
+            .find(|c| c.descriptor.id.eq_ignore_ascii_case(id))
+            .map(|c| &c.result)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut CategoryResult> {
+        self.categories
+            .iter_mut()
+            .find(|c| c.descriptor.id.eq_ignore_ascii_case(id))
+            .map(|c| &mut c.result)
+    }
+
+    /// Entry for a category display name, if it was scanned
+    pub fn find_by_name(&self, display_name: &str) -> Option<&CategoryScan> {
+        self.categories
+            .iter()
+            .find(|c| c.descriptor.display_name == display_name)
+    }
+
+    /// Add a category result, replacing any previous result for the same id
+    pub fn insert(&mut self, descriptor: Arc<CategoryDescriptor>, result: CategoryResult) {
+        if let Some(existing) = self
+            .categories
+            .iter_mut()
+            .find(|c| c.descriptor.id == descriptor.id)
+        {
+            existing.result = result;
+        } else {
+            self.categories.push(CategoryScan { descriptor, result });
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CategoryScan> {
+        self.categories.iter()
+    }
+
+    pub fn total_items(&self) -> usize {
+        self.categories.iter().map(|c| c.result.items).sum()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.categories.iter().map(|c| c.result.size_bytes).sum()
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct CategoryResult {
     pub items: usize,
     pub size_bytes: u64,
     pub paths: Vec<PathBuf>,
+    /// How `size_bytes` comes back after cleaning, for package caches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reclaim: Option<ReclaimSplit>,
+    /// Size of each path as the scanner measured it (stored for `--diff`)
+    #[serde(skip)]
+    pub path_sizes: HashMap<PathBuf, u64>,
+    /// Paths to remember in the scan cache instead of `paths`, for scanners
+    /// that report only some of what they found (see [`CacheCandidates`])
+    #[serde(skip)]
+    pub cache_candidates: Option<CacheCandidates>,
+}
+
+/// Everything a scanner found before limiting and filtering its results
+///
+/// Skipped unchanged directories replay these, so results that were cut or
+/// hidden last time can come back.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheCandidates {
+    /// Size threshold the paths were found with
+    pub min_size: u64,
+    pub paths: Vec<PathBuf>,
+}
+
+/// Bytes reclaimable without network access vs. bytes that are downloaded again
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReclaimSplit {
+    pub offline_bytes: u64,
+    pub download_bytes: u64,
 }
 
 impl CategoryResult {
+    /// Add a found item of `size` bytes
+    pub fn push(&mut self, path: PathBuf, size: u64) {
+        self.items += 1;
+        self.size_bytes += size;
+        self.path_sizes.insert(path.clone(), size);
+        self.paths.push(path);
+    }
+
     pub fn size_human(&self) -> String {
         bytesize::to_string(self.size_bytes, false)
     }
 }
 
 #[derive(Serialize)]
-struct JsonResults {
+struct JsonResults<'a> {
     version: String,
     timestamp: String,
     categories: JsonCategories,
     summary: JsonSummary,
+    /// Changes since an earlier scan (`--diff`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<&'a ScanDiff>,
+    /// Duplicate groups and the dedupe modes each is eligible for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicate_groups: Option<&'a [DuplicateGroup]>,
 }
 
-#[derive(Serialize)]
-struct JsonCategories {
-    cache: JsonCategory,
-    app_cache: JsonCategory,
-    temp: JsonCategory,
-    trash: JsonCategory,
-    build: JsonCategory,
-    downloads: JsonCategory,
-    large: JsonCategory,
-    old: JsonCategory,
-    applications: JsonCategory,
-    browser: JsonCategory,
-    system: JsonCategory,
-    empty: JsonCategory,
-    duplicates: JsonCategory,
-    windows_update: JsonCategory,
-    event_logs: JsonCategory,
+/// Categories keyed by id, serialized in registry order
+struct JsonCategories(Vec<(String, JsonCategory)>);
+
+impl Serialize for JsonCategories {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, category) in &self.0 {
+            map.serialize_entry(id, category)?;
+        }
+        map.end()
+    }
 }
 
 #[derive(Serialize)]
@@ -162,9 +224,27 @@ struct JsonCategory {
     items: usize,
     size_bytes: u64,
     size_human: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reclaim: Option<ReclaimSplit>,
     paths: Vec<String>,
 }
 
+impl From<&CategoryResult> for JsonCategory {
+    fn from(result: &CategoryResult) -> Self {
+        Self {
+            items: result.items,
+            size_bytes: result.size_bytes,
+            size_human: result.size_human(),
+            reclaim: result.reclaim,
+            paths: result
+                .paths
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect(),
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct JsonSummary {
     total_items: usize,
@@ -204,45 +284,17 @@ pub fn print_human_with_options(
     ]);
     print_table_separator(&col_widths, "├", "┼", "┤");
 
-    let categories = [
-        ("Package cache", &results.cache, "[OK] Safe to clean"),
-        (
-            "Application cache",
-            &results.app_cache,
-            "[OK] Safe to clean",
-        ),
-        ("Temp", &results.temp, "[OK] Safe to clean"),
-        ("Trash", &results.trash, "[OK] Safe to clean"),
-        ("Build", &results.build, "[OK] Inactive projects"),
-        ("Downloads", &results.downloads, "[OK] Old files"),
-        ("Large", &results.large, "[!] Review suggested"),
-        ("Old", &results.old, "[!] Review suggested"),
-        (
-            "Applications",
-            &results.applications,
-            "[!] Review suggested",
-        ),
-        ("Browser", &results.browser, "[OK] Safe to clean"),
-        ("System", &results.system, "[OK] Safe to clean"),
-        ("Empty", &results.empty, "[OK] Safe to clean"),
-        ("Duplicates", &results.duplicates, "[!] Review suggested"),
-        (
-            "Windows Update",
-            &results.windows_update,
-            "[!] Requires admin",
-        ),
-        ("Event Logs", &results.event_logs, "[!] Requires admin"),
-    ];
-
-    for (name, result, status) in categories {
+    for scanned in results.iter() {
+        let descriptor = &scanned.descriptor;
+        let result = &scanned.result;
         if result.items > 0 {
+            let status = descriptor.status_label();
             let status_colored = if status.starts_with("[OK]") {
                 Theme::status_safe(status)
             } else {
                 Theme::status_review(status)
             };
-            let emoji = category_emoji(name);
-            let category_display = format!("{} {}", emoji, name);
+            let category_display = format!("{} {}", descriptor.emoji, descriptor.display_name);
             print_table_row(&[
                 (Theme::category(&category_display), col_widths[0]),
                 (Theme::value(&result.items.to_string()), col_widths[1]),
@@ -251,7 +303,7 @@ pub fn print_human_with_options(
             ]);
 
             // Special handling for duplicates: show groups in verbose mode
-            if name == "Duplicates"
+            if descriptor.id == "duplicates"
                 && (mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose)
             {
                 if let Some(ref groups) = results.duplicates_groups {
@@ -263,19 +315,21 @@ pub fn print_human_with_options(
 
                     for (idx, group) in groups.iter().take(show_groups).enumerate() {
                         println!(
-                            "  {} Group {} ({} files, {} each):",
+                            "  {} Group {} ({}) {}:",
                             Theme::muted("└─"),
                             idx + 1,
-                            group.paths.len(),
-                            bytesize::to_string(group.size, false)
+                            group_summary(group),
+                            Theme::muted(&dedupe_label(group))
                         );
                         for path in &group.paths {
                             let file_type = crate::utils::detect_file_type(path);
                             let emoji = file_type.emoji();
                             println!(
-                                "     {} {}",
+                                "     {} {}{}{}",
                                 emoji,
-                                Theme::muted(&path.display().to_string())
+                                Theme::muted(&path.display().to_string()),
+                                image_label(group, path),
+                                kept_label(group, path)
                             );
                         }
                     }
@@ -342,36 +396,8 @@ pub fn print_human_with_options(
         }
     }
 
-    let total_items = results.cache.items
-        + results.app_cache.items
-        + results.temp.items
-        + results.trash.items
-        + results.build.items
-        + results.downloads.items
-        + results.large.items
-        + results.old.items
-        + results.applications.items
-        + results.browser.items
-        + results.system.items
-        + results.empty.items
-        + results.duplicates.items
-        + results.windows_update.items
-        + results.event_logs.items;
-    let total_bytes = results.cache.size_bytes
-        + results.app_cache.size_bytes
-        + results.temp.size_bytes
-        + results.trash.size_bytes
-        + results.build.size_bytes
-        + results.downloads.size_bytes
-        + results.large.size_bytes
-        + results.old.size_bytes
-        + results.applications.size_bytes
-        + results.browser.size_bytes
-        + results.system.size_bytes
-        + results.empty.size_bytes
-        + results.duplicates.size_bytes
-        + results.windows_update.size_bytes
-        + results.event_logs.size_bytes;
+    let total_items = results.total_items();
+    let total_bytes = results.total_bytes();
 
     if total_items == 0 {
         print_table_separator(&col_widths, "└", "┴", "┘");
@@ -409,79 +435,28 @@ fn build_clean_command(options: Option<&ScanOptions>) -> String {
         return "wole clean --all".to_string();
     };
 
-    // Count how many categories are enabled
-    let enabled_count = [
-        opts.cache,
-        opts.app_cache,
-        opts.temp,
-        opts.trash,
-        opts.build,
-        opts.downloads,
-        opts.large,
-        opts.old,
-        opts.applications,
-        opts.browser,
-        opts.system,
-        opts.empty,
-        opts.duplicates,
-        opts.windows_update,
-        opts.event_logs,
-    ]
-    .iter()
-    .filter(|&&x| x)
-    .count();
+    let builtin = CategoryRegistry::builtin();
+
+    let custom: Vec<&String> = opts
+        .categories
+        .iter()
+        .filter(|id| builtin.get(id).is_none())
+        .collect();
 
     // If all categories are enabled, use --all
-    if enabled_count == 15 {
+    if custom.is_empty() && builtin.iter().all(|d| opts.is_enabled(&d.id)) {
         return "wole clean --all".to_string();
     }
 
     // Build command with specific flags
-    let mut flags = Vec::new();
-    if opts.cache {
-        flags.push("--cache");
-    }
-    if opts.app_cache {
-        flags.push("--app-cache");
-    }
-    if opts.temp {
-        flags.push("--temp");
-    }
-    if opts.trash {
-        flags.push("--trash");
-    }
-    if opts.build {
-        flags.push("--build");
-    }
-    if opts.downloads {
-        flags.push("--downloads");
-    }
-    if opts.large {
-        flags.push("--large");
-    }
-    if opts.old {
-        flags.push("--old");
-    }
-    if opts.applications {
-        flags.push("--applications");
-    }
-    if opts.browser {
-        flags.push("--browser");
-    }
-    if opts.system {
-        flags.push("--system");
-    }
-    if opts.empty {
-        flags.push("--empty");
-    }
-    if opts.duplicates {
-        flags.push("--duplicates");
-    }
-    if opts.windows_update {
-        flags.push("--windows-update");
-    }
-    if opts.event_logs {
-        flags.push("--event-logs");
+    let mut flags: Vec<String> = builtin
+        .iter()
+        .filter(|d| opts.is_enabled(&d.id))
+        .map(|d| format!("--{}", d.id.replace('_', "-")))
+        .collect();
+
+    for name in custom {
+        flags.push(format!("--custom \"{}\"", name));
     }
 
     // If no flags (shouldn't happen, but be safe), fall back to --all
@@ -493,230 +468,188 @@ fn build_clean_command(options: Option<&ScanOptions>) -> String {
 }
 
 pub fn print_json(results: &ScanResults) -> anyhow::Result<()> {
-    let json_results = JsonResults {
+    print_json_with_diff(results, None)
+}
+
+/// JSON results with a `diff` section comparing them with an earlier scan
+pub fn print_json_with_diff(results: &ScanResults, diff: Option<&ScanDiff>) -> anyhow::Result<()> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&json_results(results, diff))?
+    );
+    Ok(())
+}
+
+/// The `--json` scan results as a JSON value (used by `wole serve`)
+pub fn json_value(
+    results: &ScanResults,
+    diff: Option<&ScanDiff>,
+) -> serde_json::Result<serde_json::Value> {
+    serde_json::to_value(json_results(results, diff))
+}
+
+fn json_results<'a>(results: &'a ScanResults, diff: Option<&'a ScanDiff>) -> JsonResults<'a> {
+    // Every built-in category is always present so the schema stays stable
+    let mut categories: Vec<(String, JsonCategory)> = CategoryRegistry::builtin()
+        .iter()
+        .map(|d| {
+            let result = results.get(&d.id).cloned().unwrap_or_default();
+            (d.id.clone(), JsonCategory::from(&result))
+        })
+        .collect();
+    categories.extend(
+        results
+            .iter()
+            .filter(|c| c.descriptor.custom)
+            .map(|c| (c.descriptor.id.clone(), JsonCategory::from(&c.result))),
+    );
+
+    let total_bytes = results.total_bytes();
+    JsonResults {
         version: "1.0".to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
-        categories: JsonCategories {
-            cache: JsonCategory {
-                items: results.cache.items,
-                size_bytes: results.cache.size_bytes,
-                size_human: results.cache.size_human(),
-                paths: results
-                    .cache
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            app_cache: JsonCategory {
-                items: results.app_cache.items,
-                size_bytes: results.app_cache.size_bytes,
-                size_human: results.app_cache.size_human(),
-                paths: results
-                    .app_cache
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            temp: JsonCategory {
-                items: results.temp.items,
-                size_bytes: results.temp.size_bytes,
-                size_human: results.temp.size_human(),
-                paths: results
-                    .temp
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            trash: JsonCategory {
-                items: results.trash.items,
-                size_bytes: results.trash.size_bytes,
-                size_human: results.trash.size_human(),
-                paths: results
-                    .trash
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            build: JsonCategory {
-                items: results.build.items,
-                size_bytes: results.build.size_bytes,
-                size_human: results.build.size_human(),
-                paths: results
-                    .build
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            downloads: JsonCategory {
-                items: results.downloads.items,
-                size_bytes: results.downloads.size_bytes,
-                size_human: results.downloads.size_human(),
-                paths: results
-                    .downloads
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            large: JsonCategory {
-                items: results.large.items,
-                size_bytes: results.large.size_bytes,
-                size_human: results.large.size_human(),
-                paths: results
-                    .large
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            old: JsonCategory {
-                items: results.old.items,
-                size_bytes: results.old.size_bytes,
-                size_human: results.old.size_human(),
-                paths: results
-                    .old
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            applications: JsonCategory {
-                items: results.applications.items,
-                size_bytes: results.applications.size_bytes,
-                size_human: results.applications.size_human(),
-                paths: results
-                    .applications
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            browser: JsonCategory {
-                items: results.browser.items,
-                size_bytes: results.browser.size_bytes,
-                size_human: results.browser.size_human(),
-                paths: results
-                    .browser
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            system: JsonCategory {
-                items: results.system.items,
-                size_bytes: results.system.size_bytes,
-                size_human: results.system.size_human(),
-                paths: results
-                    .system
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            empty: JsonCategory {
-                items: results.empty.items,
-                size_bytes: results.empty.size_bytes,
-                size_human: results.empty.size_human(),
-                paths: results
-                    .empty
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            duplicates: JsonCategory {
-                items: results.duplicates.items,
-                size_bytes: results.duplicates.size_bytes,
-                size_human: results.duplicates.size_human(),
-                paths: results
-                    .duplicates
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            windows_update: JsonCategory {
-                items: results.windows_update.items,
-                size_bytes: results.windows_update.size_bytes,
-                size_human: results.windows_update.size_human(),
-                paths: results
-                    .windows_update
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            event_logs: JsonCategory {
-                items: results.event_logs.items,
-                size_bytes: results.event_logs.size_bytes,
-                size_human: results.event_logs.size_human(),
-                paths: results
-                    .event_logs
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-        },
+        categories: JsonCategories(categories),
         summary: JsonSummary {
-            total_items: results.cache.items
-                + results.app_cache.items
-                + results.temp.items
-                + results.trash.items
-                + results.build.items
-                + results.downloads.items
-                + results.large.items
-                + results.old.items
-                + results.applications.items
-                + results.browser.items
-                + results.system.items
-                + results.empty.items
-                + results.duplicates.items
-                + results.windows_update.items
-                + results.event_logs.items,
-            total_bytes: results.cache.size_bytes
-                + results.app_cache.size_bytes
-                + results.temp.size_bytes
-                + results.trash.size_bytes
-                + results.build.size_bytes
-                + results.downloads.size_bytes
-                + results.large.size_bytes
-                + results.old.size_bytes
-                + results.applications.size_bytes
-                + results.browser.size_bytes
-                + results.system.size_bytes
-                + results.empty.size_bytes
-                + results.duplicates.size_bytes
-                + results.windows_update.size_bytes
-                + results.event_logs.size_bytes,
-            total_human: bytesize::to_string(
-                results.cache.size_bytes
-                    + results.app_cache.size_bytes
-                    + results.temp.size_bytes
-                    + results.trash.size_bytes
-                    + results.build.size_bytes
-                    + results.downloads.size_bytes
-                    + results.large.size_bytes
-                    + results.old.size_bytes
-                    + results.applications.size_bytes
-                    + results.browser.size_bytes
-                    + results.system.size_bytes
-                    + results.empty.size_bytes
-                    + results.duplicates.size_bytes
-                    + results.windows_update.size_bytes
-                    + results.event_logs.size_bytes,
-                true,
-            ),
+            total_items: results.total_items(),
+            total_bytes,
+            total_human: bytesize::to_string(total_bytes, true),
         },
+        diff,
+        duplicate_groups: results.duplicates_groups.as_deref(),
+    }
+}
+
+/// "3 files, 2.0 MB each", "2 folders, 1.2 GB each, 340 files" for directory trees,
+/// or "3 similar images, 4.1 MB in total"
+fn group_summary(group: &DuplicateGroup) -> String {
+    let size = bytesize::to_string(group.size, false);
+    match group.kind {
+        DuplicateKind::File => format!("{} files, {} each", group.paths.len(), size),
+        DuplicateKind::Directory => format!(
+            "{} folders, {} each, {} files",
+            group.paths.len(),
+            size,
+            group.file_count
+        ),
+        DuplicateKind::SimilarImages => format!(
+            "{} similar images, {} in total",
+            group.paths.len(),
+            bytesize::to_string(group.images.iter().map(|i| i.size).sum(), false)
+        ),
+    }
+}
+
+/// " 1920×1080, 2.1 MB" for an image of a similar-images group
+fn image_label(group: &DuplicateGroup, path: &Path) -> String {
+    group
+        .image(path)
+        .map(|image| {
+            Theme::muted(&format!(
+                " {}×{}, {}",
+                image.width,
+                image.height,
+                bytesize::to_string(image.size, false)
+            ))
+        })
+        .unwrap_or_default()
+}
+
+/// The dedupe modes a duplicate group is eligible for, e.g. "[delete, link]"
+fn dedupe_label(group: &DuplicateGroup) -> String {
+    let modes: Vec<&str> = group.dedupe_modes.iter().map(|m| m.name()).collect();
+    format!("[{}]", modes.join(", "))
+}
+
+/// " (kept: <rule>)" for a file of a duplicate group that's kept
+fn kept_label(group: &DuplicateGroup, path: &PathBuf) -> String {
+    group
+        .kept
+        .iter()
+        .find(|k| &k.path == path)
+        .map(|k| format!(" {}", Theme::success(&format!("(kept: {})", k.rule))))
+        .unwrap_or_default()
+}
+
+/// Print what changed since an earlier scan, grouped by category
+pub fn print_diff(diff: &ScanDiff, mode: OutputMode) {
+    if mode == OutputMode::Quiet {
+        return;
+    }
+
+    let since = format!(
+        "scan #{} ({})",
+        diff.baseline_session,
+        diff.baseline_started_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+    );
+    println!("{}", Theme::header(&format!("Changes since {}", since)));
+    println!("{}", Theme::divider_bold(60));
+
+    let changed: Vec<_> = diff.categories.iter().filter(|c| !c.is_empty()).collect();
+    if changed.is_empty() {
+        println!();
+        println!("{}", Theme::muted(&format!("No changes since {}.", since)));
+        println!();
+        return;
+    }
+
+    let show_count = |len: usize| match mode {
+        OutputMode::Verbose | OutputMode::VeryVerbose => len,
+        _ => 5.min(len),
+    };
+    let size = |bytes: Option<u64>| bytesize::to_string(bytes.unwrap_or(0), false);
+    let print_items = |marker: &str, items: &[ItemChange]| {
+        let shown = show_count(items.len());
+        for item in items.iter().take(shown) {
+            let sizes = match (item.old_bytes, item.new_bytes) {
+                (Some(_), Some(_)) => format!(
+                    "{} → {} ({})",
+                    size(item.old_bytes),
+                    size(item.new_bytes),
+                    scan_diff::format_delta(item.delta_bytes)
+                ),
+                (None, new) => size(new),
+                (old, None) => size(old),
+            };
+            println!(
+                "    {} {} {}",
+                Theme::muted(marker),
+                item.path.display(),
+                Theme::size(&sizes)
+            );
+        }
+        if items.len() > shown {
+            println!(
+                "    {} ... and {} more (use -v to see all)",
+                Theme::muted(marker),
+                items.len() - shown
+            );
+        }
     };
 
-    println!("{}", serde_json::to_string_pretty(&json_results)?);
-    Ok(())
+    for category in changed {
+        println!();
+        println!(
+            "{}  {} new, {} removed, {} changed  {}",
+            Theme::category(&category.name),
+            category.added.len(),
+            category.removed.len(),
+            category.changed.len(),
+            Theme::size(&scan_diff::format_delta(category.net_delta_bytes))
+        );
+        print_items("+", &category.added);
+        print_items("-", &category.removed);
+        print_items("~", &category.changed);
+    }
+
+    println!();
+    println!("{}", Theme::divider(60));
+    println!(
+        "Net change: {}",
+        Theme::size(&scan_diff::format_delta(diff.net_delta_bytes))
+    );
+    println!();
 }
 
 pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
@@ -728,28 +661,11 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
     println!("Scan Results");
     println!();
 
-    // Define categories with their display names
-    let mut categories: Vec<(&str, &CategoryResult)> = vec![
-        ("Trash", &results.trash),
-        ("Large Files", &results.large),
-        ("Windows Update", &results.windows_update),
-        ("Event Logs", &results.event_logs),
-        ("System Cache", &results.system),
-        ("Build Artifacts", &results.build),
-        ("Old Downloads", &results.downloads),
-        ("Duplicates", &results.duplicates),
-        ("Old Files", &results.old),
-        ("Applications", &results.applications),
-        ("Temp Files", &results.temp),
-        ("Package Cache", &results.cache),
-        ("Application Cache", &results.app_cache),
-        ("Browser Cache", &results.browser),
-        ("Empty Folders", &results.empty),
-    ];
+    let mut categories: Vec<&CategoryScan> = results.iter().collect();
 
     // Filter out categories with no items and sort by size descending
-    categories.retain(|(_, result)| result.items > 0);
-    categories.sort_by(|a, b| b.1.size_bytes.cmp(&a.1.size_bytes));
+    categories.retain(|c| c.result.items > 0);
+    categories.sort_by(|a, b| b.result.size_bytes.cmp(&a.result.size_bytes));
 
     // Table column widths
     // (content widths; padding handled by table helpers)
@@ -765,9 +681,10 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
     print_table_separator(&col_widths, "├", "┼", "┤");
 
     // Print category rows
-    for (name, result) in &categories {
-        let emoji = category_emoji(name);
-        let category_display = format!("{} {}", emoji, name);
+    for scanned in &categories {
+        let descriptor = &scanned.descriptor;
+        let result = &scanned.result;
+        let category_display = format!("{} {}", descriptor.emoji, descriptor.display_name);
         print_table_row(&[
             (category_display, col_widths[0]),
             (format_number(result.items as u64), col_widths[1]),
@@ -775,7 +692,8 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
         ]);
 
         // Special handling for duplicates: show groups in verbose mode
-        if *name == "Duplicates" && (mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose)
+        if descriptor.id == "duplicates"
+            && (mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose)
         {
             if let Some(ref groups) = results.duplicates_groups {
                 let show_groups = if mode == OutputMode::Verbose {
@@ -786,19 +704,21 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
 
                 for (idx, group) in groups.iter().take(show_groups).enumerate() {
                     println!(
-                        "  {} Group {} ({} files, {} each):",
+                        "  {} Group {} ({}) {}:",
                         Theme::muted("└─"),
                         idx + 1,
-                        group.paths.len(),
-                        bytesize::to_string(group.size, false)
+                        group_summary(group),
+                        Theme::muted(&dedupe_label(group))
                     );
                     for path in &group.paths {
                         let file_type = crate::utils::detect_file_type(path);
                         let emoji = file_type.emoji();
                         println!(
-                            "     {} {}",
+                            "     {} {}{}{}",
                             emoji,
-                            Theme::muted(&path.display().to_string())
+                            Theme::muted(&path.display().to_string()),
+                            image_label(group, path),
+                            kept_label(group, path)
                         );
                     }
                 }
@@ -859,36 +779,8 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
     }
 
     // Calculate totals
-    let total_items = results.cache.items
-        + results.app_cache.items
-        + results.temp.items
-        + results.trash.items
-        + results.build.items
-        + results.downloads.items
-        + results.large.items
-        + results.old.items
-        + results.applications.items
-        + results.browser.items
-        + results.system.items
-        + results.empty.items
-        + results.duplicates.items
-        + results.windows_update.items
-        + results.event_logs.items;
-    let total_bytes = results.cache.size_bytes
-        + results.app_cache.size_bytes
-        + results.temp.size_bytes
-        + results.trash.size_bytes
-        + results.build.size_bytes
-        + results.downloads.size_bytes
-        + results.large.size_bytes
-        + results.old.size_bytes
-        + results.applications.size_bytes
-        + results.browser.size_bytes
-        + results.system.size_bytes
-        + results.empty.size_bytes
-        + results.duplicates.size_bytes
-        + results.windows_update.size_bytes
-        + results.event_logs.size_bytes;
+    let total_items = results.total_items();
+    let total_bytes = results.total_bytes();
 
     // Print separator and total
     print_table_separator(&col_widths, "├", "┼", "┤");
@@ -1010,6 +902,77 @@ pub fn print_disk_insights(
     println!();
 }
 
+/// Print folder growth trends (`wole analyze --trend`)
+pub fn print_folder_trends(
+    trends: &[crate::disk_trend::FolderTrend],
+    root_path: &std::path::Path,
+    top_n: usize,
+    mode: OutputMode,
+) {
+    if mode == OutputMode::Quiet {
+        return;
+    }
+    use crate::disk_trend::{format_growth, sparkline_text};
+
+    println!();
+    println!(
+        "{}  {}",
+        Theme::header("Folder Trends"),
+        Theme::primary(&root_path.display().to_string())
+    );
+    println!();
+
+    // A trend needs samples from at least two scans
+    let tracked: Vec<_> = trends.iter().filter(|t| t.samples.len() > 1).collect();
+    if tracked.is_empty() {
+        println!(
+            "{}",
+            Theme::muted("No size history yet. Each analyze run records folder sizes; run it again on another day to see how they grow.")
+        );
+        println!();
+        return;
+    }
+
+    for trend in tracked.iter().take(top_n) {
+        let display_name = if trend.path == root_path {
+            root_path.display().to_string()
+        } else {
+            crate::utils::to_relative_path(&trend.path, root_path)
+        };
+        println!(
+            "{}  {}  {}  {}  {}",
+            Theme::size(&format!("{:>12}/day", format_growth(trend.growth_per_day))),
+            Theme::muted(&format!(
+                "{:>12}/week",
+                format_growth(trend.growth_per_week())
+            )),
+            sparkline_text(&trend.sparkline()),
+            Theme::size(&bytesize::to_string(trend.current_bytes, false)),
+            Theme::category(&display_name)
+        );
+        if mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose {
+            println!(
+                "    {}",
+                Theme::muted(&format!(
+                    "{} scans over {:.0} days",
+                    trend.samples.len(),
+                    trend.days_tracked()
+                ))
+            );
+        }
+    }
+    if tracked.len() > top_n {
+        println!(
+            "{}",
+            Theme::muted(&format!(
+                "... and {} more (use --top to see more)",
+                tracked.len() - top_n
+            ))
+        );
+    }
+    println!();
+}
+
 /// Render a progress bar with filled and empty blocks
 fn render_progress_bar(percentage: f64, width: usize) -> String {
     let filled = (percentage / 100.0 * width as f64).round() as usize;
@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::utils;
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
 use jwalk::WalkDir;
@@ -7,54 +8,172 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+pub mod policy;
+
+use policy::ProjectPolicy;
+
 // Thread-local cache for project active status to avoid repeated file system checks
 thread_local! {
-    static PROJECT_ACTIVE_CACHE: RefCell<std::collections::HashMap<(PathBuf, u64), bool>> = RefCell::new(std::collections::HashMap::new());
+    static PROJECT_ACTIVE_CACHE: RefCell<std::collections::HashMap<(PathBuf, u64), ProjectActivity>> = RefCell::new(std::collections::HashMap::new());
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProjectType {
     Node,
     Rust,
     DotNet,
     Python,
     Java,
+    Go,
+    Ruby,
+    Php,
+    Elixir,
+    /// Dart and Flutter (`pubspec.yaml`)
+    Dart,
+    /// Swift packages (`Package.swift`)
+    Swift,
+    CMake,
+    Zig,
+    Unity,
+    Unreal,
 }
 
-/// Detect project type by looking for marker files
-pub fn detect_project_type(path: &Path) -> Option<ProjectType> {
-    // Check for Node.js
-    if path.join("package.json").exists() {
-        return Some(ProjectType::Node);
+impl ProjectType {
+    pub const ALL: [ProjectType; 15] = [
+        ProjectType::Node,
+        ProjectType::Rust,
+        ProjectType::DotNet,
+        ProjectType::Python,
+        ProjectType::Java,
+        ProjectType::Go,
+        ProjectType::Ruby,
+        ProjectType::Php,
+        ProjectType::Elixir,
+        ProjectType::Dart,
+        ProjectType::Swift,
+        ProjectType::CMake,
+        ProjectType::Zig,
+        ProjectType::Unity,
+        ProjectType::Unreal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ProjectType::Node => "Node",
+            ProjectType::Rust => "Rust",
+            ProjectType::DotNet => ".NET",
+            ProjectType::Python => "Python",
+            ProjectType::Java => "Java",
+            ProjectType::Go => "Go",
+            ProjectType::Ruby => "Ruby",
+            ProjectType::Php => "PHP",
+            ProjectType::Elixir => "Elixir",
+            ProjectType::Dart => "Dart",
+            ProjectType::Swift => "Swift",
+            ProjectType::CMake => "CMake",
+            ProjectType::Zig => "Zig",
+            ProjectType::Unity => "Unity",
+            ProjectType::Unreal => "Unreal",
+        }
     }
 
-    // Check for Rust
-    if path.join("Cargo.toml").exists() {
-        return Some(ProjectType::Rust);
+    /// Parse a type name as typed on the command line (`node`, `dotnet`, `.net`, ...)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        match name.as_str() {
+            "js" | "javascript" | "typescript" | "npm" => Some(ProjectType::Node),
+            "dotnet" | "csharp" | "c#" => Some(ProjectType::DotNet),
+            "flutter" => Some(ProjectType::Dart),
+            "gradle" | "maven" | "kotlin" => Some(ProjectType::Java),
+            _ => Self::ALL
+                .into_iter()
+                .find(|t| t.name().to_lowercase() == name),
+        }
     }
+}
+
+/// Detect project type by looking for marker files
+///
+/// Returns the first of [`detect_project_types`]; game engine projects win over
+/// the `.sln`/`.csproj` files they generate.
+pub fn detect_project_type(path: &Path) -> Option<ProjectType> {
+    detect_project_types(path).into_iter().next()
+}
 
-    // Check for .NET by globbing
+/// Detect every project type whose marker files are in `path`
+///
+/// A Tauri app is both Node and Rust, a Flutter plugin may also be CMake, and so
+/// on; each type brings its own build artifacts.
+pub fn detect_project_types(path: &Path) -> Vec<ProjectType> {
+    let mut types = Vec::new();
+    let has = |name: &str| path.join(name).exists();
+
+    // One directory listing for extension-based markers
+    let (mut has_uproject, mut has_dotnet) = (false, false);
     if let Ok(entries) = std::fs::read_dir(path) {
         for entry in entries.flatten() {
             let name = entry.file_name();
             let name_str = name.to_string_lossy();
-            if name_str.ends_with(".csproj") || name_str.ends_with(".sln") {
-                return Some(ProjectType::DotNet);
-            }
+            has_uproject |= name_str.ends_with(".uproject");
+            has_dotnet |= name_str.ends_with(".csproj") || name_str.ends_with(".sln");
         }
     }
 
-    // Check for Python
-    if path.join("pyproject.toml").exists() || path.join("requirements.txt").exists() {
-        return Some(ProjectType::Python);
+    // Game engines first: they generate .sln/.csproj files next to their own markers
+    if has_uproject {
+        types.push(ProjectType::Unreal);
     }
+    if path
+        .join("ProjectSettings")
+        .join("ProjectVersion.txt")
+        .exists()
+        && has("Assets")
+    {
+        types.push(ProjectType::Unity);
+    }
+    let is_engine = !types.is_empty();
 
-    // Check for Java
-    if path.join("build.gradle").exists() || path.join("pom.xml").exists() {
-        return Some(ProjectType::Java);
+    if has("package.json") {
+        types.push(ProjectType::Node);
+    }
+    if has("Cargo.toml") {
+        types.push(ProjectType::Rust);
+    }
+    if has_dotnet && !is_engine {
+        types.push(ProjectType::DotNet);
+    }
+    if has("pyproject.toml") || has("requirements.txt") {
+        types.push(ProjectType::Python);
+    }
+    if has("build.gradle") || has("pom.xml") {
+        types.push(ProjectType::Java);
+    }
+    if has("go.mod") {
+        types.push(ProjectType::Go);
+    }
+    if has("Gemfile") {
+        types.push(ProjectType::Ruby);
+    }
+    if has("composer.json") {
+        types.push(ProjectType::Php);
+    }
+    if has("mix.exs") {
+        types.push(ProjectType::Elixir);
+    }
+    if has("pubspec.yaml") {
+        types.push(ProjectType::Dart);
+    }
+    if has("Package.swift") {
+        types.push(ProjectType::Swift);
+    }
+    if has("build.zig") {
+        types.push(ProjectType::Zig);
+    }
+    if has("CMakeLists.txt") && !is_engine {
+        types.push(ProjectType::CMake);
     }
 
-    None
+    types
 }
 
 /// Get the marker file path for a project type
@@ -94,15 +213,166 @@ fn get_marker_file(path: &Path, project_type: ProjectType) -> Option<PathBuf> {
                 None
             }
         }
+        ProjectType::Go => Some(path.join("go.mod")),
+        ProjectType::Ruby => Some(path.join("Gemfile")),
+        ProjectType::Php => Some(path.join("composer.json")),
+        ProjectType::Elixir => Some(path.join("mix.exs")),
+        ProjectType::Dart => Some(path.join("pubspec.yaml")),
+        ProjectType::Swift => Some(path.join("Package.swift")),
+        ProjectType::CMake => Some(path.join("CMakeLists.txt")),
+        ProjectType::Zig => Some(path.join("build.zig")),
+        ProjectType::Unity => Some(path.join("ProjectSettings").join("ProjectVersion.txt")),
+        ProjectType::Unreal => std::fs::read_dir(path).ok()?.flatten().find_map(|entry| {
+            let is_uproject = entry.file_name().to_string_lossy().ends_with(".uproject");
+            is_uproject.then(|| entry.path())
+        }),
+    }
+}
+
+/// The signal that decided whether a project counts as active
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActivitySignal {
+    /// Tracked files have uncommitted changes
+    UncommittedChanges,
+    /// Date of the last commit (recent if active, older than the threshold if not)
+    LastCommit(DateTime<Utc>),
+    /// When `HEAD` last moved to its commit, for a commit whose date couldn't be read
+    HeadMoved(DateTime<Utc>),
+    /// `.git/index` was modified recently
+    GitIndex,
+    /// `.git/HEAD` was modified recently
+    GitHead,
+    /// A manifest or lock file (e.g. `Cargo.lock`) was modified recently
+    ProjectFile(String),
+    /// A top-level source file was modified recently
+    SourceFile(String),
+    /// Nothing was modified within the threshold
+    NoRecentChanges,
+    /// `.wole.toml` sets `opt_out = true`
+    OptedOut,
+    /// `.wole.toml` couldn't be read or parsed (the error), so the project is
+    /// treated as opted out
+    InvalidPolicy(String),
+}
+
+impl ActivitySignal {
+    /// Whether the project's `.wole.toml` opted it out, explicitly or by being invalid
+    pub fn is_opt_out(&self) -> bool {
+        matches!(
+            self,
+            ActivitySignal::OptedOut | ActivitySignal::InvalidPolicy(_)
+        )
+    }
+}
+
+impl std::fmt::Display for ActivitySignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActivitySignal::UncommittedChanges => write!(f, "uncommitted changes"),
+            ActivitySignal::LastCommit(date) => {
+                let days = (Utc::now() - *date).num_days();
+                match days {
+                    0 => write!(f, "last commit today"),
+                    1 => write!(f, "last commit 1 day ago"),
+                    _ => write!(f, "last commit {} days ago", days),
+                }
+            }
+            ActivitySignal::HeadMoved(date) => {
+                let days = (Utc::now() - *date).num_days();
+                match days {
+                    0 => write!(f, "HEAD moved to its commit today"),
+                    1 => write!(f, "HEAD moved to its commit 1 day ago"),
+                    _ => write!(f, "HEAD moved to its commit {} days ago", days),
+                }
+            }
+            ActivitySignal::GitIndex => write!(f, "git index changed recently"),
+            ActivitySignal::GitHead => write!(f, "git HEAD moved recently"),
+            ActivitySignal::ProjectFile(name) => write!(f, "{} modified recently", name),
+            ActivitySignal::SourceFile(name) => write!(f, "{} modified recently", name),
+            ActivitySignal::NoRecentChanges => write!(f, "no recent changes"),
+            ActivitySignal::OptedOut => write!(f, "opted out in {}", policy::FILE_NAME),
+            ActivitySignal::InvalidPolicy(error) => write!(f, "opted out: {}", error),
+        }
+    }
+}
+
+/// Whether a project is active, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectActivity {
+    pub is_active: bool,
+    pub signal: ActivitySignal,
+    /// Threshold from `.wole.toml`, set when it changed the outcome
+    pub policy_days: Option<u64>,
+}
+
+impl std::fmt::Display for ProjectActivity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.signal)?;
+        if let Some(days) = self.policy_days {
+            write!(f, "; {}: inactive after {} days", policy::FILE_NAME, days)?;
+        }
+        Ok(())
     }
 }
 
 /// Check if a project is active (recently modified or has uncommitted changes)
-/// Uses smart heuristics to check multiple indicators of recent activity
+pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
+    Ok(project_activity(path, age_days).is_active)
+}
+
+/// When a project was last worked on: the newer of its last commit and the
+/// newest top-level file
+pub fn last_activity(path: &Path) -> Option<DateTime<Utc>> {
+    let last_commit = crate::git::last_commit_time(path)
+        .ok()
+        .flatten()
+        .map(crate::git::CommitTime::date);
+    let newest_file = std::fs::read_dir(path)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .filter_map(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .max()
+        .map(DateTime::<Utc>::from);
+    last_commit.max(newest_file)
+}
+
+/// Work out whether a project is active and which signal decided it
+///
+/// Git state comes first: uncommitted changes or a commit within `age_days` make
+/// a project active. Otherwise recent changes to the git index or HEAD, project
+/// files or top-level sources do. An older last commit doesn't decide on its own:
+/// it's only reported as the signal when none of those is recent either.
 ///
+/// A `.wole.toml` at `path` can opt the project out (it always counts as active)
+/// or replace `age_days` with its own `inactive_after_days`. One that can't be
+/// read or parsed opts the project out too.
+pub fn project_activity(path: &Path, age_days: u64) -> ProjectActivity {
+    let policy = ProjectPolicy::for_project(path).unwrap_or_default();
+    if policy.opt_out {
+        return ProjectActivity {
+            is_active: true,
+            signal: policy
+                .error
+                .map_or(ActivitySignal::OptedOut, ActivitySignal::InvalidPolicy),
+            policy_days: None,
+        };
+    }
+    match policy.inactive_after_days {
+        Some(days) if days != age_days => {
+            let mut activity = cached_activity(path, days);
+            if activity.is_active != cached_activity(path, age_days).is_active {
+                activity.policy_days = Some(days);
+            }
+            activity
+        }
+        _ => cached_activity(path, age_days),
+    }
+}
+
 /// PERFORMANCE: Caches results per (project_path, age_days) to avoid repeated
 /// expensive file system checks when scanning many files in the same project.
-pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
+fn cached_activity(path: &Path, age_days: u64) -> ProjectActivity {
     // Normalize path for cache key (use absolute if possible)
     let cache_key = if path.is_absolute() {
         path.to_path_buf()
@@ -116,15 +386,49 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
     // Check cache first
     let cached_result = PROJECT_ACTIVE_CACHE.with(|cache| {
         let cache_ref = cache.borrow();
-        cache_ref.get(&(cache_key.clone(), age_days)).copied()
+        cache_ref.get(&(cache_key.clone(), age_days)).cloned()
     });
 
     if let Some(cached) = cached_result {
-        return Ok(cached);
+        return cached;
     }
 
-    // Not in cache - compute result
+    let activity = compute_activity(path, age_days);
+    PROJECT_ACTIVE_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .insert((cache_key, age_days), activity.clone());
+    });
+    activity
+}
+
+fn compute_activity(path: &Path, age_days: u64) -> ProjectActivity {
     let cutoff = Utc::now() - Duration::days(age_days as i64);
+    let active = |signal| ProjectActivity {
+        is_active: true,
+        signal,
+        policy_days: None,
+    };
+
+    // Git state (binary or repository files, see git.rs)
+    let mut last_commit = None;
+    if crate::git::find_git_root_cached(path).is_some() {
+        if let Ok(true) = crate::git::is_dirty(path) {
+            return active(ActivitySignal::UncommittedChanges);
+        }
+        if let Ok(Some(time)) = crate::git::last_commit_time(path) {
+            let signal = match time {
+                crate::git::CommitTime::Committed(date) => ActivitySignal::LastCommit(date),
+                crate::git::CommitTime::HeadMoved(date) => ActivitySignal::HeadMoved(date),
+            };
+            if time.date() > cutoff {
+                return active(signal);
+            }
+            // An old commit alone doesn't make the project inactive; the
+            // checks below can still find recent work
+            last_commit = Some(signal);
+        }
+    }
 
     // Helper to check if file was modified within cutoff
     let was_modified_recently = |file_path: &Path| -> bool {
@@ -139,18 +443,12 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
 
     // Check git index (file-based, no git2 needed)
     if was_modified_recently(&path.join(".git").join("index")) {
-        PROJECT_ACTIVE_CACHE.with(|cache| {
-            cache.borrow_mut().insert((cache_key, age_days), true);
-        });
-        return Ok(true);
+        return active(ActivitySignal::GitIndex);
     }
 
     // Check git HEAD
     if was_modified_recently(&path.join(".git").join("HEAD")) {
-        PROJECT_ACTIVE_CACHE.with(|cache| {
-            cache.borrow_mut().insert((cache_key, age_days), true);
-        });
-        return Ok(true);
+        return active(ActivitySignal::GitHead);
     }
 
     // Check common project files and lock files
@@ -172,20 +470,27 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
         "composer.lock",
         "Gemfile",
         "Gemfile.lock",
+        "mix.exs",
+        "mix.lock",
+        "pubspec.yaml",
+        "pubspec.lock",
+        "Package.swift",
+        "Package.resolved",
+        "CMakeLists.txt",
+        "build.zig",
+        "build.zig.zon",
     ];
 
     for file in &project_files {
         if was_modified_recently(&path.join(file)) {
-            PROJECT_ACTIVE_CACHE.with(|cache| {
-                cache.borrow_mut().insert((cache_key, age_days), true);
-            });
-            return Ok(true);
+            return active(ActivitySignal::ProjectFile(file.to_string()));
         }
     }
 
     // Check if any source files were modified recently
     let source_extensions = [
-        "rs", "js", "ts", "tsx", "jsx", "py", "go", "java", "rb", "php", "c", "cpp", "h",
+        "rs", "js", "ts", "tsx", "jsx", "py", "go", "java", "rb", "php", "c", "cpp", "h", "ex",
+        "exs", "dart", "swift", "zig", "cs",
     ];
 
     if let Ok(entries) = std::fs::read_dir(path) {
@@ -196,35 +501,60 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
                 if source_extensions.contains(&ext.to_string_lossy().as_ref())
                     && was_modified_recently(&entry_path)
                 {
-                    PROJECT_ACTIVE_CACHE.with(|cache| {
-                        cache.borrow_mut().insert((cache_key, age_days), true);
-                    });
-                    return Ok(true);
+                    return active(ActivitySignal::SourceFile(
+                        entry.file_name().to_string_lossy().into_owned(),
+                    ));
                 }
             }
         }
     }
 
-    // Project is inactive - cache and return
-    PROJECT_ACTIVE_CACHE.with(|cache| {
-        cache.borrow_mut().insert((cache_key, age_days), false);
-    });
-    Ok(false) // Inactive
+    ProjectActivity {
+        is_active: false,
+        signal: last_commit.unwrap_or(ActivitySignal::NoRecentChanges),
+        policy_days: None,
+    }
 }
 
-/// Find all project roots in a directory tree
+/// Directories that together hold every project of the user
 ///
-/// Uses jwalk for parallel directory traversal (2-4x faster than sequential).
-pub fn find_project_roots(root: &Path, config: &Config) -> Vec<PathBuf> {
-    // Skip if root itself is a project (avoid scanning into it)
-    if detect_project_type(root).is_some() {
-        return vec![root.to_path_buf()];
+/// Caches and toolchains are shared by all projects, so something only counts
+/// as unused once all of them were searched: the `configured` directories plus
+/// the scan root, or the scan root alone when it covers `home`. Returns `None`
+/// when neither is the case and the answer would be guesswork.
+pub fn search_roots(
+    root: &Path,
+    configured: &[String],
+    home: Option<&Path>,
+) -> Option<Vec<PathBuf>> {
+    let canonical =
+        |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let root = canonical(root);
+    let mut roots: Vec<PathBuf> = configured
+        .iter()
+        .filter_map(|raw| utils::expand_path(raw))
+        .map(|path| canonical(&path))
+        .collect();
+
+    if roots.is_empty() {
+        let home = canonical(home?);
+        return home.starts_with(&root).then(|| vec![root]);
+    }
+    if !roots.iter().any(|r| root.starts_with(r)) {
+        roots.push(root);
     }
+    Some(roots)
+}
 
+/// Find all project roots in a directory tree
+///
+/// Projects nested in another one are only returned when they add a project type
+/// (see [`outermost_per_type`]). Uses jwalk for parallel directory traversal
+/// (2-4x faster than sequential).
+pub fn find_project_roots(root: &Path, config: &Config) -> Vec<PathBuf> {
     const MAX_DEPTH: usize = 5;
 
-    let projects: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
-    let seen: Mutex<HashSet<PathBuf>> = Mutex::new(HashSet::new());
+    let projects: Mutex<Vec<(PathBuf, Vec<ProjectType>)>> = Mutex::new(Vec::new());
 
     // Clone config for thread-safe access (jwalk requires 'static)
     let config_arc = Arc::new(config.clone());
@@ -265,6 +595,11 @@ pub fn find_project_roots(root: &Path, config: &Config) -> Vec<PathBuf> {
                                     | ".nuxt"
                                     | ".turbo"
                                     | ".parcel-cache"
+                                    | ".dart_tool"
+                                    | "zig-cache"
+                                    | ".zig-cache"
+                                    | "intermediate"
+                                    | "deriveddatacache"
                                     | "$recycle.bin"
                                     | "system volume information"
                                     | "windows"
@@ -297,25 +632,38 @@ pub fn find_project_roots(root: &Path, config: &Config) -> Vec<PathBuf> {
             }
 
             // Check if this is a project root
-            if detect_project_type(&path).is_some() {
-                let mut seen_guard = seen.lock().unwrap();
-                if !seen_guard.contains(&path) {
-                    seen_guard.insert(path.clone());
-                    drop(seen_guard);
-
-                    let mut projects_guard = projects.lock().unwrap();
-                    // Check it's not a subproject of an already-found project
-                    let is_subproject = projects_guard
-                        .iter()
-                        .any(|p: &PathBuf| path.starts_with(p) && path != *p);
-                    if !is_subproject {
-                        projects_guard.push(path);
-                    }
-                }
+            let types = detect_project_types(&path);
+            if !types.is_empty() {
+                projects.lock().unwrap().push((path, types));
             }
         });
 
-    projects.into_inner().unwrap()
+    outermost_per_type(projects.into_inner().unwrap())
+}
+
+/// Drop projects nested in another one unless they have a type none of the
+/// projects around them has
+///
+/// Members of a Cargo workspace or a pnpm monorepo share their root's type and
+/// are attributed to it (see [`crate::workspace`]), but a Node frontend inside a
+/// Go repository is a project of its own, with its own `node_modules`.
+fn outermost_per_type(mut found: Vec<(PathBuf, Vec<ProjectType>)>) -> Vec<PathBuf> {
+    // Ancestors sort before their descendants
+    found.sort_by(|a, b| a.0.cmp(&b.0));
+    found.dedup_by(|a, b| a.0 == b.0);
+
+    let mut kept: Vec<(PathBuf, Vec<ProjectType>)> = Vec::new();
+    for (path, types) in found {
+        let enclosing: HashSet<ProjectType> = kept
+            .iter()
+            .filter(|(outer, _)| path.starts_with(outer))
+            .flat_map(|(_, outer_types)| outer_types.iter().copied())
+            .collect();
+        if types.iter().any(|t| !enclosing.contains(t)) {
+            kept.push((path, types));
+        }
+    }
+    kept.into_iter().map(|(path, _)| path).collect()
 }
 
 #[cfg(test)]
@@ -392,4 +740,83 @@ mod tests {
         let roots = find_project_roots(temp_dir.path(), &config);
         assert_eq!(roots.len(), 2);
     }
+
+    #[test]
+    fn test_detect_project_types_multiple_and_engines() {
+        let temp_dir = create_test_dir();
+        let tauri = temp_dir.path().join("tauri");
+        fs::create_dir_all(&tauri).unwrap();
+        fs::write(tauri.join("package.json"), "{}").unwrap();
+        fs::write(tauri.join("Cargo.toml"), "[package]").unwrap();
+        assert_eq!(
+            detect_project_types(&tauri),
+            vec![ProjectType::Node, ProjectType::Rust]
+        );
+
+        // Unity generates a .sln, but it's a Unity project
+        let game = temp_dir.path().join("game");
+        fs::create_dir_all(game.join("ProjectSettings")).unwrap();
+        fs::create_dir_all(game.join("Assets")).unwrap();
+        fs::write(game.join("ProjectSettings/ProjectVersion.txt"), "").unwrap();
+        fs::write(game.join("game.sln"), "").unwrap();
+        assert_eq!(detect_project_types(&game), vec![ProjectType::Unity]);
+    }
+
+    #[test]
+    fn test_old_commit_falls_through_to_recent_git_files() {
+        let temp_dir = create_test_dir();
+        let git_dir = temp_dir.path().join(".git");
+        let sha = "a".repeat(40);
+        fs::create_dir_all(git_dir.join("logs/refs/heads")).unwrap();
+        fs::write(
+            git_dir.join("packed-refs"),
+            format!("{} refs/heads/main\n", sha),
+        )
+        .unwrap();
+        fs::write(
+            git_dir.join("logs/refs/heads/main"),
+            format!(
+                "{} {} A <a@b> 1700000000 +0000\tcommit (initial): x\n",
+                "0".repeat(40),
+                sha
+            ),
+        )
+        .unwrap();
+        // Just checked out: HEAD is fresh although the commit is old
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+
+        let activity = compute_activity(temp_dir.path(), 30);
+        assert!(activity.is_active);
+        assert_eq!(activity.signal, ActivitySignal::GitHead);
+    }
+
+    #[test]
+    fn test_nested_projects_of_other_types_are_kept() {
+        let temp_dir = create_test_dir();
+        let repo = temp_dir.path().join("service");
+        fs::create_dir_all(repo.join("web")).unwrap();
+        fs::create_dir_all(repo.join("tools/gen")).unwrap();
+        fs::write(repo.join("go.mod"), "module service").unwrap();
+        fs::write(repo.join("web/package.json"), "{}").unwrap();
+        fs::write(repo.join("tools/gen/go.mod"), "module gen").unwrap();
+
+        let found = vec![
+            (
+                repo.join("tools/gen"),
+                detect_project_types(&repo.join("tools/gen")),
+            ),
+            (repo.join("web"), detect_project_types(&repo.join("web"))),
+            (repo.clone(), detect_project_types(&repo)),
+        ];
+        assert_eq!(
+            outermost_per_type(found),
+            vec![repo.clone(), repo.join("web")]
+        );
+
+        // Also when the scan starts at the repository itself
+        let config = crate::config::Config::default();
+        let mut roots = find_project_roots(&repo, &config);
+        roots.sort();
+        assert_eq!(roots, vec![repo.clone(), repo.join("web")]);
+    }
 }
@@ -0,0 +1,248 @@
+//! Per-project cleanup policy read from a `.wole.toml` at the project root.
+//!
+//! Lets a repository say how wole should treat it:
+//!
+//! ```toml
+//! # Clean these folders too (relative to the project root)
+//! artifacts = ["out", "generated/cache"]
+//! # Never clean these, even if they look like build output
+//! protect = ["vendor", "build"]
+//! # Count the project as inactive after 3 days instead of the configured threshold
+//! inactive_after_days = 3
+//! # Never clean anything in this project
+//! opt_out = true
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+/// Name of the policy file looked for at each project root
+pub const FILE_NAME: &str = ".wole.toml";
+
+thread_local! {
+    static POLICY_CACHE: RefCell<HashMap<PathBuf, CachedPolicy>> = RefCell::new(HashMap::new());
+}
+
+/// A parsed policy with the size and modification time of the file it came from
+/// (`None` when there was no file), so an edited or new file is read again
+#[derive(Clone)]
+struct CachedPolicy {
+    stamp: Option<(u64, SystemTime)>,
+    policy: Option<ProjectPolicy>,
+}
+
+/// Size and modification time of a project's policy file, if it has one
+fn file_stamp(project_root: &Path) -> Option<(u64, SystemTime)> {
+    let metadata = fs::metadata(project_root.join(FILE_NAME)).ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+/// Clear the cache of parsed policy files
+pub fn clear_cache() {
+    POLICY_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// Cleanup policy of one project
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ProjectPolicy {
+    /// Extra artifact folders, relative to the project root
+    pub artifacts: Vec<String>,
+    /// Folders never cleaned, relative to the project root
+    pub protect: Vec<String>,
+    /// Inactivity threshold overriding `project_age_days`
+    pub inactive_after_days: Option<u64>,
+    /// Never clean anything in the project
+    pub opt_out: bool,
+    /// Why the file couldn't be used; such a project is treated as opted out
+    #[serde(skip)]
+    pub error: Option<String>,
+}
+
+impl ProjectPolicy {
+    /// Read `.wole.toml` in `project_root`; `Ok(None)` when there is none
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = project_root.join(FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let policy = toml::from_str(&contents)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e.message()))?;
+        Ok(Some(policy))
+    }
+
+    /// Cached [`load`](Self::load)
+    ///
+    /// An unreadable or invalid file opts the project out, with the reason in
+    /// `error`: a typo must not get the folders it protects cleaned. The file is
+    /// read again once it's added, removed or changed.
+    pub fn for_project(project_root: &Path) -> Option<Self> {
+        let stamp = file_stamp(project_root);
+        let cached = POLICY_CACHE.with(|cache| cache.borrow().get(project_root).cloned());
+        if let Some(cached) = cached.filter(|cached| cached.stamp == stamp) {
+            return cached.policy;
+        }
+        let policy = Self::load(project_root).unwrap_or_else(|e| {
+            Some(Self {
+                opt_out: true,
+                // toml errors continue with a source excerpt over several lines
+                error: format!("{:#}", e).lines().next().map(str::to_string),
+                ..Self::default()
+            })
+        });
+        POLICY_CACHE.with(|cache| {
+            cache.borrow_mut().insert(
+                project_root.to_path_buf(),
+                CachedPolicy {
+                    stamp,
+                    policy: policy.clone(),
+                },
+            )
+        });
+        policy
+    }
+
+    /// The nearest policy among `path` and its ancestors, up to and including `stop_at`
+    pub fn find(path: &Path, stop_at: &Path) -> Option<(PathBuf, Self)> {
+        path.ancestors()
+            .take_while(|dir| dir.starts_with(stop_at))
+            .find_map(|dir| Some((dir.to_path_buf(), Self::for_project(dir)?)))
+    }
+
+    /// Extra artifact folders that exist in `project_root`
+    ///
+    /// Entries that are absolute, contain `..` or resolve outside the project
+    /// through a symlink are ignored.
+    pub fn artifact_paths(&self, project_root: &Path) -> Vec<PathBuf> {
+        let Ok(canonical_root) = project_root.canonicalize() else {
+            return Vec::new();
+        };
+        self.artifacts
+            .iter()
+            .filter_map(|artifact| inside(project_root, artifact))
+            .filter(|path| path.is_dir())
+            .filter(|path| {
+                path.canonicalize()
+                    .is_ok_and(|canonical| canonical.starts_with(&canonical_root))
+            })
+            .collect()
+    }
+
+    /// Whether cleaning `path` would remove a protected folder (or something inside one)
+    pub fn is_protected(&self, project_root: &Path, path: &Path) -> bool {
+        self.protect.iter().any(|protected| {
+            let protected = project_root.join(protected.trim_matches('/'));
+            path.starts_with(&protected) || protected.starts_with(path)
+        })
+    }
+
+    /// Whether `path` was added by `artifacts` rather than found by project type
+    pub fn lists_artifact(&self, project_root: &Path, path: &Path) -> bool {
+        self.artifacts
+            .iter()
+            .any(|artifact| inside(project_root, artifact).as_deref() == Some(path))
+    }
+}
+
+/// `entry` joined to `project_root`, if it's a relative path that stays inside
+fn inside(project_root: &Path, entry: &str) -> Option<PathBuf> {
+    let relative = Path::new(entry.trim_end_matches('/'));
+    relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        .then(|| project_root.join(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_and_matches_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("out")).unwrap();
+        fs::create_dir_all(root.join("src/app")).unwrap();
+        fs::write(
+            root.join(FILE_NAME),
+            "artifacts = [\"out\", \"missing\"]\nprotect = [\"build/keep\", \"vendor/\"]\ninactive_after_days = 3\n",
+        )
+        .unwrap();
+
+        let policy = ProjectPolicy::load(root).unwrap().unwrap();
+        assert_eq!(policy.inactive_after_days, Some(3));
+        assert!(!policy.opt_out);
+        assert_eq!(policy.artifact_paths(root), vec![root.join("out")]);
+        assert!(policy.lists_artifact(root, &root.join("out")));
+        // Cleaning `build` would take `build/keep` with it
+        assert!(policy.is_protected(root, &root.join("build")));
+        assert!(policy.is_protected(root, &root.join("vendor/bundle")));
+        assert!(!policy.is_protected(root, &root.join("node_modules")));
+
+        let (found_root, found) = ProjectPolicy::find(&root.join("src/app"), root).unwrap();
+        assert_eq!(found_root, root);
+        assert_eq!(found, policy);
+
+        fs::write(root.join(FILE_NAME), "opt_out = \"yes\"").unwrap();
+        assert!(ProjectPolicy::load(root).is_err());
+    }
+
+    #[test]
+    fn test_invalid_file_opts_the_project_out() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join(FILE_NAME), "protect = [\"vendor\"\n").unwrap();
+        let policy = ProjectPolicy::for_project(tmp.path()).unwrap();
+        assert!(policy.opt_out);
+        assert!(policy.error.unwrap().contains("Failed to parse"));
+    }
+
+    #[test]
+    fn test_new_or_edited_file_is_read_again() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        assert_eq!(ProjectPolicy::for_project(root), None);
+
+        fs::write(root.join(FILE_NAME), "protect = [\"vendor\"]\n").unwrap();
+        let policy = ProjectPolicy::for_project(root).unwrap();
+        assert!(policy.is_protected(root, &root.join("vendor")));
+
+        fs::write(root.join(FILE_NAME), "protect = [\"vendor\", \"dist\"]\n").unwrap();
+        let policy = ProjectPolicy::for_project(root).unwrap();
+        assert!(policy.is_protected(root, &root.join("dist")));
+
+        fs::remove_file(root.join(FILE_NAME)).unwrap();
+        clear_cache();
+        assert_eq!(ProjectPolicy::for_project(root), None);
+    }
+
+    #[test]
+    fn test_artifacts_outside_the_project_are_ignored() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("project");
+        let outside = tmp.path().join("outside");
+        fs::create_dir_all(root.join("out")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        let policy = ProjectPolicy {
+            artifacts: vec![
+                "out".to_string(),
+                "..".to_string(),
+                "out/../../outside".to_string(),
+                outside.display().to_string(),
+                "link".to_string(),
+            ],
+            ..ProjectPolicy::default()
+        };
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+        assert_eq!(policy.artifact_paths(&root), vec![root.join("out")]);
+        assert!(!policy.lists_artifact(&root, &outside));
+    }
+}
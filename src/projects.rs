@@ -0,0 +1,239 @@
+//! Inventory of the projects wole finds, for `wole projects` and the TUI.
+//!
+//! Shows what the build artifact cleaner thinks about each project without
+//! cleaning anything: its types, whether it's active and why, its git root, and
+//! the size of each build artifact. Workspaces are listed as one project, like
+//! the cleaner treats them.
+
+use crate::categories::build::{self, ProjectArtifact};
+use crate::config::Config;
+use crate::git;
+use crate::project::{self, ProjectActivity, ProjectType};
+use crate::utils;
+use crate::workspace::ProjectGroup;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// One build artifact of a project
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactInfo {
+    pub path: PathBuf,
+    /// Folder name (`node_modules`, `target`, ...)
+    pub kind: String,
+    pub size_bytes: u64,
+    /// Workspace member the artifact belongs to
+    pub member: Option<PathBuf>,
+    /// How the project's `.wole.toml` affects it (e.g. "protected by .wole.toml")
+    pub policy: Option<String>,
+    /// The policy protects it or opts its project out
+    pub kept_by_policy: bool,
+}
+
+/// A project (or workspace) and what wole thinks about it
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectInfo {
+    pub path: PathBuf,
+    pub name: String,
+    pub types: Vec<String>,
+    pub is_active: bool,
+    /// The signal that decided `is_active`, e.g. "last commit 45 days ago"
+    pub signal: String,
+    /// Workspace member whose activity decided, when it wasn't the root
+    pub signal_member: Option<PathBuf>,
+    pub last_activity: Option<DateTime<Utc>>,
+    pub git_root: Option<PathBuf>,
+    /// e.g. "Cargo workspace, 4 members"
+    pub workspace: Option<String>,
+    pub artifacts: Vec<ArtifactInfo>,
+    pub total_bytes: u64,
+}
+
+impl ProjectInfo {
+    pub fn has_type(&self, project_type: ProjectType) -> bool {
+        self.types.iter().any(|t| t == project_type.name())
+    }
+}
+
+/// Sort order for the project list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectSort {
+    /// Biggest total artifact size first
+    #[default]
+    Size,
+    /// Least recently active first
+    Activity,
+    Name,
+    Path,
+}
+
+impl ProjectSort {
+    pub const ALL: [ProjectSort; 4] = [
+        ProjectSort::Size,
+        ProjectSort::Activity,
+        ProjectSort::Name,
+        ProjectSort::Path,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ProjectSort::Size => "size",
+            ProjectSort::Activity => "activity",
+            ProjectSort::Name => "name",
+            ProjectSort::Path => "path",
+        }
+    }
+
+    /// The next sort order, for cycling through them in the TUI
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl FromStr for ProjectSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("expected one of: size, activity, name, path (got '{}')", s))
+    }
+}
+
+/// Sort projects in place
+pub fn sort_projects(projects: &mut [ProjectInfo], sort: ProjectSort) {
+    match sort {
+        ProjectSort::Size => projects.sort_by_key(|p| Reverse(p.total_bytes)),
+        // Projects without a date sort first: nothing shows they were ever worked on
+        ProjectSort::Activity => projects.sort_by_key(|p| p.last_activity),
+        ProjectSort::Name => projects.sort_by_key(|p| p.name.to_lowercase()),
+        ProjectSort::Path => projects.sort_by(|a, b| a.path.cmp(&b.path)),
+    }
+}
+
+/// Find every project below `root` and size its build artifacts
+///
+/// `on_path` is called with each file visited while sizing.
+pub fn discover<F>(
+    root: &Path,
+    project_age_days: u64,
+    config: &Config,
+    on_path: &F,
+) -> Vec<ProjectInfo>
+where
+    F: Fn(&Path) + Sync,
+{
+    let groups: Mutex<Vec<(ProjectGroup, ProjectActivity, Option<PathBuf>)>> =
+        Mutex::new(Vec::new());
+    let artifacts = build::find_project_artifacts(
+        root,
+        project_age_days,
+        Some(&config.categories.build),
+        config,
+        &|group, activity, decided_by| {
+            if let Ok(mut groups) = groups.lock() {
+                groups.push((
+                    group.clone(),
+                    activity.clone(),
+                    decided_by.map(Path::to_path_buf),
+                ));
+            }
+        },
+    );
+    let groups = groups.into_inner().unwrap_or_default();
+
+    // Directories git tracks aren't build output (same rule as the cleaner)
+    let artifacts: Vec<ProjectArtifact> = artifacts
+        .into_iter()
+        .filter(|a| a.artifact_path.exists())
+        .filter(|a| build::artifact_git_status(&a.artifact_path).is_generated())
+        .collect();
+
+    groups
+        .into_iter()
+        .map(|(group, activity, decided_by)| {
+            let mut project_artifacts: Vec<ArtifactInfo> = artifacts
+                .iter()
+                .filter(|a| a.workspace_root.as_ref().unwrap_or(&a.project_path) == &group.root)
+                .map(|a| ArtifactInfo {
+                    path: a.artifact_path.clone(),
+                    kind: a.artifact_type.clone(),
+                    // Sizes one at a time; calculate_dir_size is parallel itself
+                    size_bytes: utils::calculate_dir_size_with_progress(&a.artifact_path, on_path),
+                    member: a.member.clone(),
+                    policy: a.policy.map(|p| p.to_string()),
+                    kept_by_policy: a.policy.is_some_and(|p| p.keeps()),
+                })
+                .collect();
+            project_artifacts.sort_by_key(|a| Reverse(a.size_bytes));
+
+            let types = std::iter::once(&group.root)
+                .chain(group.workspace.iter().flat_map(|w| w.members.iter()))
+                .flat_map(|path| project::detect_project_types(path))
+                .fold(Vec::new(), |mut types: Vec<String>, t| {
+                    if !types.iter().any(|name| name == t.name()) {
+                        types.push(t.name().to_string());
+                    }
+                    types
+                });
+            let workspace = group.workspace.as_ref().map(|w| {
+                let kinds: Vec<String> = w.kinds.iter().map(|k| k.to_string()).collect();
+                format!("{}, {} members", kinds.join(" + "), w.members.len())
+            });
+
+            ProjectInfo {
+                name: group
+                    .root
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| group.root.display().to_string()),
+                types,
+                is_active: activity.is_active,
+                signal: activity.to_string(),
+                signal_member: decided_by,
+                last_activity: project::last_activity(&group.root),
+                git_root: git::find_git_root_cached(&group.root),
+                workspace,
+                total_bytes: project_artifacts.iter().map(|a| a.size_bytes).sum(),
+                artifacts: project_artifacts,
+                path: group.root,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_lists_projects_with_artifact_sizes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("web/node_modules/react")).unwrap();
+        fs::write(root.join("web/package.json"), "{}").unwrap();
+        fs::write(root.join("web/node_modules/react/index.js"), [0u8; 300]).unwrap();
+        fs::create_dir_all(root.join("cli/src")).unwrap();
+        fs::write(root.join("cli/Cargo.toml"), "[package]").unwrap();
+
+        let mut projects = discover(root, 14, &Config::default(), &|_| {});
+        sort_projects(&mut projects, ProjectSort::Size);
+        let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["web", "cli"]);
+        assert_eq!(projects[0].types, vec!["Node"]);
+        assert_eq!(projects[0].total_bytes, 300);
+        assert_eq!(projects[0].artifacts[0].kind, "node_modules");
+        assert!(projects[1].has_type(ProjectType::Rust));
+        assert!(projects[1].artifacts.is_empty());
+
+        sort_projects(&mut projects, ProjectSort::Name);
+        assert_eq!(projects[0].name, "cli");
+        assert_eq!("Activity".parse::<ProjectSort>(), Ok(ProjectSort::Activity));
+    }
+}
@@ -30,7 +30,7 @@ pub fn get_restore_count() -> Result<usize> {
     let count = latest_log
         .records
         .iter()
-        .filter(|r| r.success && !r.permanent)
+        .filter(|r| r.is_restorable())
         .count();
 
     Ok(count)
@@ -121,11 +121,7 @@ pub fn restore_from_log_with_progress(
     let recycle_bin_items = trash_ops::list().context("Failed to list Recycle Bin contents")?;
 
     // Count total items to restore
-    let total_to_restore = log
-        .records
-        .iter()
-        .filter(|r| r.success && !r.permanent)
-        .count();
+    let total_to_restore = log.records.iter().filter(|r| r.is_restorable()).count();
 
     // Create a map of Recycle Bin items by original path
     // Windows Recycle Bin stores files with their original paths in metadata
@@ -146,8 +142,8 @@ pub fn restore_from_log_with_progress(
 
     // First pass: collect all items that need to be restored
     for record in &log.records {
-        if !record.success || record.permanent {
-            // Skip failed deletions and permanent deletions (can't restore those)
+        if !record.is_restorable() {
+            // Skip failed, permanent and linked deletions (can't restore those)
             continue;
         }
 
@@ -780,7 +776,7 @@ pub fn restore_file(item: &trash::TrashItem) -> Result<()> {
 }
 
 /// Result of a restore operation
-#[derive(Debug, Default)]
+#[derive(Debug, Default, serde::Serialize)]
 pub struct RestoreResult {
     pub restored: usize,
     pub restored_bytes: u64,
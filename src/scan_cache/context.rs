@@ -1,24 +1,223 @@
 //! Cache context for category scanners
 
-use crate::scan_cache::signature::FileSignature;
+use crate::scan_cache::database::normalize_path;
+use crate::scan_cache::signature::{DirSignature, FileHashes, FileSignature};
 use anyhow::Result;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub type RecordSignatureFn = dyn Fn(&FileSignature, &str) -> Result<()> + Send + Sync;
+pub type RecordDirSignatureFn = dyn Fn(&DirSignature, &str) -> Result<()> + Send + Sync;
 
 /// Context passed to category scanners for incremental scanning
 ///
 /// Provides:
 /// - Set of paths to skip (unchanged from cache)
 /// - Method to record new file signatures
+/// - Directory signatures from the last session, to prune unchanged subtrees
+/// - Content hashes from earlier duplicate scans
 pub struct CacheContext {
     /// Paths that are unchanged and can be skipped
     pub unchanged_paths: HashSet<PathBuf>,
+    /// Last session's results that still exist, changed or not
+    pub cached_paths: HashSet<PathBuf>,
+    /// Size threshold `cached_paths` were found with, when the scanner stored
+    /// every path over it rather than just the ones it reported
+    pub cached_min_size: Option<u64>,
     /// New/modified files that need scanning
     pub files_to_scan: Vec<PathBuf>,
     /// Callback to record file signatures after scanning
     pub record_signature: Box<RecordSignatureFn>,
+    /// Callback to record directory signatures after walking a directory
+    pub record_dir_signature: Box<RecordDirSignatureFn>,
+    dirs: DirPruner,
+    hashes: HashCache,
+}
+
+/// Checks directories against the signatures of the last session
+///
+/// Cheap to clone and `'static`, so parallel walkers can move it into their
+/// filter closures. Clones share the skipped-directory count.
+#[derive(Clone, Default)]
+pub struct DirPruner {
+    /// Keyed by normalized path (case-insensitive on Windows, like the database)
+    cached_dirs: Arc<HashMap<String, DirSignature>>,
+    /// Cached subdirectories of each cached directory, keyed like `cached_dirs`
+    subdirs: Arc<HashMap<String, Vec<PathBuf>>>,
+    skipped: Arc<AtomicUsize>,
+}
+
+impl DirPruner {
+    fn new(cached_dirs: HashMap<PathBuf, DirSignature>) -> Self {
+        let mut subdirs: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for path in cached_dirs.keys() {
+            if let Some(parent) = path.parent() {
+                subdirs
+                    .entry(normalize_path(parent))
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+        Self {
+            cached_dirs: Arc::new(
+                cached_dirs
+                    .into_iter()
+                    .map(|(path, sig)| (normalize_path(&path), sig))
+                    .collect(),
+            ),
+            subdirs: Arc::new(subdirs),
+            skipped: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The cached signature of `path` if nothing in its subtree has changed since it was recorded
+    ///
+    /// The directory and every cached directory below it must still have the
+    /// same listing: a file added deep down only changes its own directory.
+    /// Counts the directory as skipped: the caller is expected not to descend into it.
+    pub fn unchanged(&self, path: &Path) -> Option<DirSignature> {
+        let cached = self.cached_dirs.get(&normalize_path(path))?;
+        let current = DirSignature::from_path(path).ok()?;
+        if !current.matches(cached) || !self.descendants_unchanged(path) {
+            return None;
+        }
+        self.skipped.fetch_add(1, Ordering::Relaxed);
+        Some(DirSignature {
+            total_size: cached.total_size,
+            ..current
+        })
+    }
+
+    /// Whether every cached directory below `path` still has its recorded listing
+    fn descendants_unchanged(&self, path: &Path) -> bool {
+        let mut pending = vec![path.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Some(subdirs) = self.subdirs.get(&normalize_path(&dir)) else {
+                continue;
+            };
+            for subdir in subdirs {
+                // Left over from before it was removed, which its parent's listing shows
+                if !subdir.is_dir() {
+                    continue;
+                }
+                let cached = &self.cached_dirs[&normalize_path(subdir)];
+                match DirSignature::from_path(subdir) {
+                    Ok(current) if current.matches(cached) => pending.push(subdir.clone()),
+                    _ => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Number of directories reported unchanged so far
+    pub fn skipped(&self) -> usize {
+        self.skipped.load(Ordering::Relaxed)
+    }
+}
+
+/// Content hashes from earlier duplicate scans
+///
+/// A cached hash is reused while the file's size and mtime still match;
+/// otherwise it's computed again. Every hash reused or computed is kept for
+/// writing back to the cache. Clones share the hashes and the hit counts.
+#[derive(Clone, Default)]
+pub struct HashCache {
+    /// Keyed by normalized path (case-insensitive on Windows, like the database)
+    cached: Arc<HashMap<String, FileHashes>>,
+    seen: Arc<Mutex<HashMap<PathBuf, FileHashes>>>,
+    hits: Arc<AtomicUsize>,
+    misses: Arc<AtomicUsize>,
+}
+
+impl HashCache {
+    pub fn new(cached: HashMap<PathBuf, FileHashes>) -> Self {
+        Self {
+            cached: Arc::new(
+                cached
+                    .into_iter()
+                    .map(|(path, hashes)| (normalize_path(&path), hashes))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    /// Hash of the first 4 KB of `path`, from the cache or from `compute`
+    pub fn partial_hash(
+        &self,
+        path: &Path,
+        compute: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        self.get_or_compute(path, |h| &mut h.partial_hash, compute)
+    }
+
+    /// Hash of the whole of `path`, from the cache or from `compute`
+    pub fn full_hash(
+        &self,
+        path: &Path,
+        compute: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        self.get_or_compute(path, |h| &mut h.full_hash, compute)
+    }
+
+    fn get_or_compute(
+        &self,
+        path: &Path,
+        field: impl Fn(&mut FileHashes) -> &mut Option<String>,
+        compute: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        let Ok(sig) = FileSignature::from_path(path, false) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return compute();
+        };
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = seen.entry(path.to_path_buf()).or_insert_with(|| {
+            self.cached
+                .get(&normalize_path(path))
+                .filter(|cached| cached.matches(&sig))
+                .cloned()
+                .unwrap_or(FileHashes {
+                    path: path.to_path_buf(),
+                    size: sig.size,
+                    mtime: sig.mtime,
+                    partial_hash: None,
+                    full_hash: None,
+                })
+        });
+        if let Some(hash) = field(entry).clone() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(hash);
+        }
+        // Don't hold the lock while reading the file
+        drop(seen);
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let hash = compute()?;
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = seen.get_mut(path) {
+            *field(entry) = Some(hash.clone());
+        }
+        Ok(hash)
+    }
+
+    /// Number of hashes taken from the cache so far
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of hashes computed so far
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// The hashes of every file looked up, to write back to the cache
+    pub fn take_updates(&self) -> Vec<FileHashes> {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.drain().map(|(_, hashes)| hashes).collect()
+    }
 }
 
 impl CacheContext {
@@ -27,11 +226,18 @@ impl CacheContext {
         unchanged_paths: HashSet<PathBuf>,
         files_to_scan: Vec<PathBuf>,
         record_signature: Box<RecordSignatureFn>,
+        cached_dirs: HashMap<PathBuf, DirSignature>,
+        record_dir_signature: Box<RecordDirSignatureFn>,
     ) -> Self {
         Self {
             unchanged_paths,
+            cached_paths: HashSet::new(),
+            cached_min_size: None,
             files_to_scan,
             record_signature,
+            record_dir_signature,
+            dirs: DirPruner::new(cached_dirs),
+            hashes: HashCache::default(),
         }
     }
 
@@ -44,21 +250,66 @@ impl CacheContext {
     pub fn record(&self, sig: &FileSignature, category: &str) -> Result<()> {
         (self.record_signature)(sig, category)
     }
+
+    /// The cached signature of `dir` if its subtree can be skipped
+    pub fn unchanged_dir(&self, dir: &Path) -> Option<DirSignature> {
+        self.dirs.unchanged(dir)
+    }
+
+    /// Handle for checking directories from inside a parallel walker
+    pub fn dir_pruner(&self) -> DirPruner {
+        self.dirs.clone()
+    }
+
+    /// Last session's results inside `dir` that still exist, for a skipped subtree
+    ///
+    /// Includes files modified since: rewriting a file doesn't change its
+    /// directory's signature, so the directory is skipped all the same.
+    pub fn cached_paths_under<'a>(&'a self, dir: &'a Path) -> impl Iterator<Item = &'a PathBuf> {
+        self.cached_paths
+            .iter()
+            .filter(move |path| path.starts_with(dir))
+    }
+
+    /// Record a directory signature after walking the directory
+    pub fn record_dir(&self, sig: &DirSignature, category: &str) -> Result<()> {
+        (self.record_dir_signature)(sig, category)
+    }
+
+    /// Number of directories skipped as unchanged
+    pub fn skipped_dirs(&self) -> usize {
+        self.dirs.skipped()
+    }
+
+    /// Content hashes from earlier duplicate scans
+    pub fn hash_cache(&self) -> &HashCache {
+        &self.hashes
+    }
 }
 
 /// Builder for creating cache contexts
 pub struct CacheContextBuilder {
     unchanged_paths: HashSet<PathBuf>,
+    cached_paths: HashSet<PathBuf>,
+    cached_min_size: Option<u64>,
     files_to_scan: Vec<PathBuf>,
     record_signature: Option<Box<RecordSignatureFn>>,
+    cached_dirs: HashMap<PathBuf, DirSignature>,
+    record_dir_signature: Option<Box<RecordDirSignatureFn>>,
+    hashes: HashCache,
 }
 
 impl CacheContextBuilder {
     pub fn new() -> Self {
         Self {
             unchanged_paths: HashSet::new(),
+            cached_paths: HashSet::new(),
+            cached_min_size: None,
             files_to_scan: Vec::new(),
             record_signature: None,
+            cached_dirs: HashMap::new(),
+            record_dir_signature: None,
+            hashes: HashCache::default(),
         }
     }
 
@@ -67,6 +318,16 @@ impl CacheContextBuilder {
         self
     }
 
+    pub fn with_cached_paths(mut self, paths: HashSet<PathBuf>) -> Self {
+        self.cached_paths = paths;
+        self
+    }
+
+    pub fn with_cached_min_size(mut self, min_size: Option<u64>) -> Self {
+        self.cached_min_size = min_size;
+        self
+    }
+
     pub fn with_files_to_scan(mut self, paths: Vec<PathBuf>) -> Self {
         self.files_to_scan = paths;
         self
@@ -77,13 +338,35 @@ impl CacheContextBuilder {
         self
     }
 
+    pub fn with_cached_dirs(mut self, dirs: HashMap<PathBuf, DirSignature>) -> Self {
+        self.cached_dirs = dirs;
+        self
+    }
+
+    pub fn with_record_dir_fn(mut self, f: Box<RecordDirSignatureFn>) -> Self {
+        self.record_dir_signature = Some(f);
+        self
+    }
+
+    pub fn with_hash_cache(mut self, hashes: HashCache) -> Self {
+        self.hashes = hashes;
+        self
+    }
+
     pub fn build(self) -> CacheContext {
-        CacheContext::new(
+        let mut ctx = CacheContext::new(
             self.unchanged_paths,
             self.files_to_scan,
             self.record_signature
                 .unwrap_or_else(|| Box::new(|_sig: &FileSignature, _cat: &str| Ok(()))),
-        )
+            self.cached_dirs,
+            self.record_dir_signature
+                .unwrap_or_else(|| Box::new(|_sig: &DirSignature, _cat: &str| Ok(()))),
+        );
+        ctx.cached_paths = self.cached_paths;
+        ctx.cached_min_size = self.cached_min_size;
+        ctx.hashes = self.hashes;
+        ctx
     }
 }
 
@@ -92,3 +375,64 @@ impl Default for CacheContextBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unchanged_dir_is_counted() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("photos");
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("big.mov"), "video").unwrap();
+
+        let mut sig = DirSignature::from_path(&dir).unwrap();
+        sig.total_size = 5;
+        let ctx = CacheContextBuilder::new()
+            .with_cached_paths(HashSet::from([dir.join("big.mov")]))
+            .with_cached_dirs(HashMap::from([(dir.clone(), sig)]))
+            .build();
+
+        assert_eq!(ctx.unchanged_dir(&dir).map(|s| s.total_size), Some(5));
+        assert!(ctx.unchanged_dir(temp_dir.path()).is_none());
+        assert_eq!(ctx.dir_pruner().skipped(), 1);
+        assert_eq!(ctx.cached_paths_under(&dir).count(), 1);
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs::write(dir.join("new.mov"), "video").unwrap();
+        assert!(ctx.unchanged_dir(&dir).is_none());
+        assert_eq!(ctx.skipped_dirs(), 1);
+    }
+
+    #[test]
+    fn test_hash_cache_reuses_hashes_of_unchanged_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        fs::write(&file, "pixels").unwrap();
+
+        let first = HashCache::default();
+        assert_eq!(first.partial_hash(&file, || Ok("p".into())).unwrap(), "p");
+        assert_eq!(first.full_hash(&file, || Ok("f".into())).unwrap(), "f");
+        assert_eq!((first.hits(), first.misses()), (0, 2));
+        let recorded = first.take_updates();
+        assert_eq!(recorded[0].full_hash.as_deref(), Some("f"));
+
+        let cached: HashMap<PathBuf, FileHashes> =
+            recorded.into_iter().map(|h| (h.path.clone(), h)).collect();
+        let second = HashCache::new(cached.clone());
+        let fail = || -> Result<String> { panic!("hash should come from the cache") };
+        assert_eq!(second.partial_hash(&file, fail).unwrap(), "p");
+        assert_eq!(second.full_hash(&file, fail).unwrap(), "f");
+        assert_eq!((second.hits(), second.misses()), (2, 0));
+
+        // Changed size: hashed again
+        fs::write(&file, "more pixels").unwrap();
+        let third = HashCache::new(cached);
+        assert_eq!(third.full_hash(&file, || Ok("g".into())).unwrap(), "g");
+        assert_eq!((third.hits(), third.misses()), (0, 1));
+        assert_eq!(third.take_updates()[0].partial_hash, None);
+    }
+}
@@ -1,23 +1,35 @@
 //! SQLite database operations for scan cache
 
-use crate::scan_cache::session::{ScanSession, ScanStats};
-use crate::scan_cache::signature::{FileSignature, FileStatus};
+use crate::scan_cache::session::{
+    DiskReading, FolderSample, FsChangeStats, ScanSession, ScanStats, SessionItem,
+};
+use crate::scan_cache::signature::{DirSignature, FileHashes, FileSignature, FileStatus};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, ErrorCode};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension};
 use serde_json;
 use std::collections::HashMap;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-const SCHEMA_VERSION: i32 = 3;
+const SCHEMA_VERSION: i32 = 10;
+/// Scan sessions whose results are kept for `wole scan --diff`
+const MAX_SESSION_SNAPSHOTS: i64 = 30;
+/// How long folder size samples are kept for `wole analyze --trend`
+const FOLDER_HISTORY_DAYS: i64 = 365;
+/// How long free-space readings are kept for the disk forecast
+const DISK_READING_DAYS: i64 = 90;
+/// How long duplicate-finder hashes are kept after the file was last compared
+const FILE_HASH_DAYS: i64 = 90;
 const DB_BUSY_TIMEOUT_SECS: u64 = 30;
 
 /// Scan cache database
 pub struct ScanCache {
     db: Connection,
     current_scan_id: Option<i64>,
+    /// Whether scans store their results for `--diff` and the forecast
+    keep_results: bool,
 }
 
 impl ScanCache {
@@ -40,6 +52,7 @@ impl ScanCache {
         let mut cache = Self {
             db,
             current_scan_id: None,
+            keep_results: true,
         };
 
         // Initialize schema - if it fails due to corruption, try to recover
@@ -122,6 +135,7 @@ impl ScanCache {
         let mut cache = Self {
             db,
             current_scan_id: None,
+            keep_results: true,
         };
         cache
             .init_schema()
@@ -298,6 +312,158 @@ impl ScanCache {
             )
             .with_context(|| "Failed to create file_categories path index")?;
 
+            // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [3])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 4 {
+            // Migration to version 4: per-directory signatures so scanners can skip
+            // subtrees that haven't changed since the last session
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS dir_records (
+                    path TEXT NOT NULL,
+                    category TEXT NOT NULL,
+                    mtime_secs INTEGER NOT NULL,
+                    mtime_nsecs INTEGER NOT NULL,
+                    entry_count INTEGER NOT NULL,
+                    total_size INTEGER NOT NULL,
+                    last_scan_id INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL,
+                    PRIMARY KEY (path, category)
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create dir_records table")?;
+
+            tx.execute(
+                "ALTER TABLE scan_sessions ADD COLUMN skipped_dirs INTEGER",
+                [],
+            )
+            .with_context(|| "Failed to add skipped_dirs column")?;
+
+            // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [4])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 5 {
+            // Migration to version 5: results of each scan session, for scan-to-scan diffs
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS session_items (
+                    session_id INTEGER NOT NULL,
+                    category TEXT NOT NULL,
+                    path TEXT NOT NULL,
+                    size_bytes INTEGER NOT NULL,
+                    PRIMARY KEY (session_id, category, path)
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create session_items table")?;
+
+            // Sessions whose results are stored, including scans that found nothing
+            tx.execute(
+                "ALTER TABLE scan_sessions ADD COLUMN results_stored INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+            .with_context(|| "Failed to add results_stored column")?;
+
+            // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [5])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 6 {
+            // Migration to version 6: folder sizes from disk usage scans, for growth trends
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS folder_sizes (
+                    path TEXT NOT NULL,
+                    depth INTEGER NOT NULL,
+                    size_bytes INTEGER NOT NULL,
+                    recorded_at INTEGER NOT NULL,
+                    PRIMARY KEY (path, recorded_at)
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create folder_sizes table")?;
+
+            tx.execute(
+                "CREATE INDEX IF NOT EXISTS idx_folder_sizes_recorded ON folder_sizes(recorded_at)",
+                [],
+            )
+            .with_context(|| "Failed to create folder_sizes index")?;
+
+            // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [6])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 7 {
+            // Migration to version 7: free-space readings per volume, for the disk forecast
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS disk_readings (
+                    mount_point TEXT NOT NULL,
+                    total_bytes INTEGER NOT NULL,
+                    free_bytes INTEGER NOT NULL,
+                    recorded_at INTEGER NOT NULL,
+                    PRIMARY KEY (mount_point, recorded_at)
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create disk_readings table")?;
+
+            // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [7])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 8 {
+            // Migration to version 8: content hashes from the duplicate finder, reused
+            // while a file's size and mtime are unchanged
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS file_hashes (
+                    path TEXT PRIMARY KEY,
+                    size INTEGER NOT NULL,
+                    mtime_secs INTEGER NOT NULL,
+                    mtime_nsecs INTEGER NOT NULL,
+                    partial_hash TEXT,
+                    full_hash TEXT,
+                    updated_at INTEGER NOT NULL
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create file_hashes table")?;
+
+            tx.execute("ALTER TABLE scan_sessions ADD COLUMN hash_hits INTEGER", [])
+                .with_context(|| "Failed to add hash_hits column")?;
+            tx.execute(
+                "ALTER TABLE scan_sessions ADD COLUMN hash_misses INTEGER",
+                [],
+            )
+            .with_context(|| "Failed to add hash_misses column")?;
+
+            // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [8])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 9 {
+            // Migration to version 9: the folder each session scanned, so stored
+            // results are only compared with results of the same folder
+            tx.execute("ALTER TABLE scan_sessions ADD COLUMN scan_root TEXT", [])
+                .with_context(|| "Failed to add scan_root column")?;
+
+            // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [9])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 10 {
+            // Migration to version 10: the size threshold a category's stored
+            // paths were found with, for scanners that store more than they report
+            tx.execute("ALTER TABLE category_scans ADD COLUMN min_size INTEGER", [])
+                .with_context(|| "Failed to add min_size column")?;
+
             // Update schema version
             tx.execute("UPDATE schema_version SET version = ?1", [SCHEMA_VERSION])
                 .with_context(|| "Failed to update schema version")?;
@@ -633,10 +799,9 @@ impl ScanCache {
         )?;
 
         let mut paths = Vec::new();
-        let rows = stmt.query_map(
-            params![category, category_scan_id, category, category_scan_id],
-            |row| Ok(decode_path(&row.get::<_, String>(0)?)),
-        )?;
+        let rows = stmt.query_map(params![category, category_scan_id], |row| {
+            Ok(decode_path(&row.get::<_, String>(0)?))
+        })?;
 
         for row in rows {
             paths.push(row?);
@@ -645,6 +810,143 @@ impl ScanCache {
         Ok(paths)
     }
 
+    /// Directory signatures recorded by a category's scanner, by path
+    pub fn get_cached_dirs(&self, category: &str) -> Result<HashMap<PathBuf, DirSignature>> {
+        let mut stmt = self.db.prepare(
+            "SELECT path, mtime_secs, mtime_nsecs, entry_count, total_size
+             FROM dir_records WHERE category = ?1",
+        )?;
+        let rows = stmt.query_map([category], |row| {
+            let path = decode_path(&row.get::<_, String>(0)?);
+            let mtime_secs: i64 = row.get(1)?;
+            let mtime_nsecs: i64 = row.get(2)?;
+            let entry_count: i64 = row.get(3)?;
+            let total_size: i64 = row.get(4)?;
+            Ok(DirSignature {
+                path,
+                mtime: UNIX_EPOCH
+                    + Duration::new(mtime_secs.max(0) as u64, mtime_nsecs.max(0) as u32),
+                entry_count: entry_count.max(0) as u64,
+                total_size: total_size.max(0) as u64,
+            })
+        })?;
+
+        let mut dirs = HashMap::new();
+        for row in rows {
+            let sig = row?;
+            dirs.insert(sig.path.clone(), sig);
+        }
+        Ok(dirs)
+    }
+
+    /// Batch insert or update directory signatures
+    pub fn upsert_dirs_batch(
+        &mut self,
+        records: &[(DirSignature, String)],
+        scan_id: i64,
+    ) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let tx = self
+            .db
+            .transaction()
+            .with_context(|| "Failed to start transaction")?;
+        let now = Utc::now().timestamp();
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO dir_records (path, category, mtime_secs, mtime_nsecs, entry_count, total_size, last_scan_id, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT(path, category) DO UPDATE SET
+                    mtime_secs = ?3,
+                    mtime_nsecs = ?4,
+                    entry_count = ?5,
+                    total_size = ?6,
+                    last_scan_id = ?7,
+                    updated_at = ?8",
+            )?;
+            for (sig, category) in records {
+                let (mtime_secs, mtime_nsecs) = system_time_to_secs_nsecs(sig.mtime);
+                stmt.execute(params![
+                    normalize_path(&sig.path),
+                    category,
+                    mtime_secs,
+                    mtime_nsecs,
+                    clamp_size_to_i64(sig.entry_count),
+                    clamp_size_to_i64(sig.total_size),
+                    scan_id,
+                    now
+                ])?;
+            }
+        }
+        tx.commit()
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Content hashes recorded by the duplicate finder, by path
+    pub fn get_file_hashes(&self) -> Result<HashMap<PathBuf, FileHashes>> {
+        let mut stmt = self.db.prepare(
+            "SELECT path, size, mtime_secs, mtime_nsecs, partial_hash, full_hash FROM file_hashes",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let mtime_secs: i64 = row.get(2)?;
+            let mtime_nsecs: i64 = row.get(3)?;
+            Ok(FileHashes {
+                path: decode_path(&row.get::<_, String>(0)?),
+                size: row.get::<_, i64>(1)?.max(0) as u64,
+                mtime: UNIX_EPOCH
+                    + Duration::new(mtime_secs.max(0) as u64, mtime_nsecs.max(0) as u32),
+                partial_hash: row.get(4)?,
+                full_hash: row.get(5)?,
+            })
+        })?;
+
+        let mut hashes = HashMap::new();
+        for row in rows {
+            let record = row?;
+            hashes.insert(record.path.clone(), record);
+        }
+        Ok(hashes)
+    }
+
+    /// Batch insert or update duplicate-finder hashes
+    ///
+    /// Hashes of files that haven't been compared for 90 days are dropped.
+    pub fn upsert_file_hashes(&mut self, records: &[FileHashes]) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let tx = self
+            .db
+            .transaction()
+            .with_context(|| "Failed to start transaction")?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO file_hashes (path, size, mtime_secs, mtime_nsecs, partial_hash, full_hash, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for record in records {
+                let (mtime_secs, mtime_nsecs) = system_time_to_secs_nsecs(record.mtime);
+                stmt.execute(params![
+                    normalize_path(&record.path),
+                    clamp_size_to_i64(record.size),
+                    mtime_secs,
+                    mtime_nsecs,
+                    record.partial_hash,
+                    record.full_hash,
+                    now
+                ])?;
+            }
+        }
+        tx.execute(
+            "DELETE FROM file_hashes WHERE updated_at < ?1",
+            [now - FILE_HASH_DAYS * 86400],
+        )?;
+        tx.commit()
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
     /// Remove entries for deleted files (files that were in cache but no longer exist)
     /// With per-category scan IDs, we check each category's previous scan
     pub fn cleanup_stale(&mut self, _current_scan_session_id: i64) -> Result<usize> {
@@ -791,14 +1093,20 @@ impl ScanCache {
                 total_files = ?2,
                 new_files = ?3,
                 changed_files = ?4,
-                removed_files = ?5
-             WHERE id = ?6",
+                removed_files = ?5,
+                skipped_dirs = ?6,
+                hash_hits = ?7,
+                hash_misses = ?8
+             WHERE id = ?9",
             params![
                 finished_at,
                 stats.total_files as i64,
                 stats.new_files as i64,
                 stats.changed_files as i64,
                 stats.removed_files as i64,
+                stats.skipped_dirs as i64,
+                stats.hash_hits as i64,
+                stats.hash_misses as i64,
                 scan_id
             ],
         )?;
@@ -844,15 +1152,122 @@ impl ScanCache {
                         "DELETE FROM file_records WHERE category IN ({})",
                         placeholders
                     ),
+                    rusqlite::params_from_iter(query_params.iter()),
+                )?;
+                self.db.execute(
+                    &format!(
+                        "DELETE FROM dir_records WHERE category IN ({})",
+                        placeholders
+                    ),
                     rusqlite::params_from_iter(query_params),
                 )?;
+                if cats.contains(&"duplicates") {
+                    self.db.execute("DELETE FROM file_hashes", [])?;
+                }
             }
         } else {
             self.db.execute("DELETE FROM file_records", [])?;
+            self.db.execute("DELETE FROM dir_records", [])?;
+            self.db.execute("DELETE FROM file_hashes", [])?;
         }
         Ok(())
     }
 
+    /// Apply filesystem changes seen by `wole watch`
+    ///
+    /// Cached files that changed get their new size and mtime, deleted files and
+    /// folders are dropped, and the directory signatures of every folder above a
+    /// change are cleared, so the next scan walks those folders again and still
+    /// skips everything else.
+    pub fn apply_fs_changes(&mut self, paths: &[PathBuf]) -> Result<FsChangeStats> {
+        let mut stats = FsChangeStats::default();
+        let now = Utc::now().timestamp();
+        let mut dirty_dirs: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+        let tx = self
+            .db
+            .transaction()
+            .with_context(|| "Failed to start transaction")?;
+        {
+            let mut update_file = tx.prepare_cached(
+                "UPDATE file_records SET size = ?2, mtime_secs = ?3, mtime_nsecs = ?4,
+                    content_hash = NULL, updated_at = ?5
+                 WHERE path = ?1 AND (size != ?2 OR mtime_secs != ?3 OR mtime_nsecs != ?4)",
+            )?;
+            // The path itself and everything below it ('0' sorts right after '/')
+            let mut delete_files = tx.prepare_cached(
+                "DELETE FROM file_records
+                 WHERE path = ?1 OR (path >= ?1 || '/' AND path < ?1 || '0')",
+            )?;
+            let mut delete_categories = tx.prepare_cached(
+                "DELETE FROM file_categories
+                 WHERE path = ?1 OR (path >= ?1 || '/' AND path < ?1 || '0')",
+            )?;
+            let mut delete_dirs = tx.prepare_cached(
+                "DELETE FROM dir_records
+                 WHERE path = ?1 OR (path >= ?1 || '/' AND path < ?1 || '0')",
+            )?;
+
+            for path in paths {
+                let path_str = normalize_path(path);
+                match std::fs::symlink_metadata(path) {
+                    Ok(metadata) if metadata.is_file() => {
+                        let mtime = metadata.modified().unwrap_or(UNIX_EPOCH);
+                        let (mtime_secs, mtime_nsecs) = system_time_to_secs_nsecs(mtime);
+                        stats.updated_files += update_file.execute(params![
+                            path_str,
+                            clamp_size_to_i64(metadata.len()),
+                            mtime_secs,
+                            mtime_nsecs,
+                            now
+                        ])?;
+                        dirty_dirs.insert(path_str);
+                    }
+                    Ok(_) => {
+                        dirty_dirs.insert(path_str);
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        stats.removed_files += delete_files.execute([&path_str])?;
+                        delete_categories.execute([&path_str])?;
+                        stats.invalidated_dirs += delete_dirs.execute([&path_str])?;
+                    }
+                    Err(_) => {
+                        dirty_dirs.insert(path_str);
+                    }
+                }
+                for ancestor in path.ancestors().skip(1) {
+                    if !dirty_dirs.insert(normalize_path(ancestor)) {
+                        // Already cleared this ancestor, and with it all the ones above
+                        break;
+                    }
+                }
+            }
+
+            let mut delete_dir = tx.prepare_cached("DELETE FROM dir_records WHERE path = ?1")?;
+            for dir in &dirty_dirs {
+                stats.invalidated_dirs += delete_dir.execute([dir])?;
+            }
+        }
+        tx.commit()
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(stats)
+    }
+
+    /// Drop the directory signatures of `root` and everything below it, so the
+    /// next scan walks it in full
+    pub fn invalidate_dirs_under(&mut self, root: &Path) -> Result<usize> {
+        let removed = self.db.execute(
+            "DELETE FROM dir_records WHERE path = ?1 OR (path >= ?1 || '/' AND path < ?1 || '0')",
+            [normalize_path(root)],
+        )?;
+        Ok(removed)
+    }
+
+    /// Directory holding the cache database
+    pub fn cache_dir() -> Result<PathBuf> {
+        get_cache_dir()
+    }
+
     /// Completely clear scan cache *and* scan history.
     ///
     /// This resets first-scan detection (get_previous_scan_id() becomes None)
@@ -860,6 +1275,11 @@ impl ScanCache {
     pub fn clear_all(&mut self) -> Result<()> {
         // File signatures
         self.db.execute("DELETE FROM file_records", [])?;
+        self.db.execute("DELETE FROM dir_records", [])?;
+        self.db.execute("DELETE FROM file_hashes", [])?;
+        self.db.execute("DELETE FROM session_items", [])?;
+        self.db.execute("DELETE FROM folder_sizes", [])?;
+        self.db.execute("DELETE FROM disk_readings", [])?;
         // Scan history (used by get_previous_scan_id)
         self.db.execute("DELETE FROM scan_sessions", [])?;
         self.current_scan_id = None;
@@ -931,53 +1351,315 @@ impl ScanCache {
         Ok(result)
     }
 
+    /// Size threshold of the paths stored by the category's last scan, if it recorded one
+    pub fn get_category_min_size(&self, category: &str) -> Result<Option<u64>> {
+        let min_size: Option<i64> = self
+            .db
+            .query_row(
+                "SELECT min_size FROM category_scans WHERE category = ?1",
+                [category],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(min_size.map(|size| size.max(0) as u64))
+    }
+
+    /// Record the size threshold of the paths stored by the category's current scan
+    ///
+    /// `None` clears it, for a scan that stored its reported results only.
+    pub fn set_category_min_size(&mut self, category: &str, min_size: Option<u64>) -> Result<()> {
+        self.db.execute(
+            "UPDATE category_scans SET min_size = ?1 WHERE category = ?2",
+            params![min_size.map(clamp_size_to_i64), category],
+        )?;
+        Ok(())
+    }
+
     /// Get last scan info
     pub fn get_last_scan(&self) -> Result<Option<ScanSession>> {
-        let result: Option<ScanSession> = self.db.query_row(
-            "SELECT id, started_at, finished_at, scan_type, categories, total_files, new_files, changed_files, removed_files
-             FROM scan_sessions
-             ORDER BY id DESC LIMIT 1",
+        let result = self
+            .db
+            .query_row(
+                &format!("{} ORDER BY id DESC LIMIT 1", SESSION_QUERY),
+                [],
+                session_from_row,
+            )
+            .ok();
+        Ok(result)
+    }
+
+    /// Store the results of a scan session of `scan_root` (replacing any stored before)
+    ///
+    /// Only the most recent sessions keep their results.
+    pub fn record_session_items(
+        &mut self,
+        session_id: i64,
+        scan_root: &Path,
+        items: &[SessionItem],
+    ) -> Result<()> {
+        let tx = self
+            .db
+            .transaction()
+            .with_context(|| "Failed to start transaction")?;
+        tx.execute(
+            "DELETE FROM session_items WHERE session_id = ?1",
+            [session_id],
+        )?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO session_items (session_id, category, path, size_bytes)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for item in items {
+                stmt.execute(params![
+                    session_id,
+                    item.category,
+                    normalize_path(&item.path),
+                    clamp_size_to_i64(item.size_bytes)
+                ])?;
+            }
+        }
+        tx.execute(
+            "UPDATE scan_sessions SET results_stored = 1, scan_root = ?2 WHERE id = ?1",
+            params![session_id, normalize_path(scan_root)],
+        )?;
+        tx.execute(
+            "UPDATE scan_sessions SET results_stored = 0 WHERE results_stored = 1 AND id NOT IN (
+                SELECT id FROM scan_sessions WHERE results_stored = 1 ORDER BY id DESC LIMIT ?1
+             )",
+            [MAX_SESSION_SNAPSHOTS],
+        )?;
+        tx.execute(
+            "DELETE FROM session_items WHERE session_id NOT IN (
+                SELECT id FROM scan_sessions WHERE results_stored = 1
+             )",
             [],
-            |row| {
-                let id: i64 = row.get(0)?;
-                let started_at: i64 = row.get(1)?;
-                let finished_at: Option<i64> = row.get(2)?;
-                let scan_type: String = row.get(3)?;
-                let categories_json: String = row.get(4)?;
-                let total_files: Option<i64> = row.get(5)?;
-                let new_files: Option<i64> = row.get(6)?;
-                let changed_files: Option<i64> = row.get(7)?;
-                let removed_files: Option<i64> = row.get(8)?;
-
-                let categories: Vec<String> = serde_json::from_str(&categories_json).unwrap_or_default();
-
-                Ok(ScanSession {
-                    id,
-                    started_at: DateTime::from_timestamp(started_at, 0)
-                        .unwrap_or_else(Utc::now),
-                    finished_at: finished_at.map(|ts| {
-                        DateTime::from_timestamp(ts, 0).unwrap_or_else(Utc::now)
-                    }),
-                    scan_type,
-                    categories,
-                    stats: ScanStats {
-                        total_files: total_files.unwrap_or(0) as usize,
-                        new_files: new_files.unwrap_or(0) as usize,
-                        changed_files: changed_files.unwrap_or(0) as usize,
-                        removed_files: removed_files.unwrap_or(0) as usize,
-                    },
-                })
-            },
-        ).ok();
+        )?;
+        tx.commit()
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Results stored for a scan session (empty if it found nothing or none were stored)
+    pub fn get_session_items(&self, session_id: i64) -> Result<Vec<SessionItem>> {
+        let mut stmt = self.db.prepare(
+            "SELECT category, path, size_bytes FROM session_items WHERE session_id = ?1",
+        )?;
+        let rows = stmt.query_map([session_id], |row| {
+            Ok(SessionItem {
+                category: row.get(0)?,
+                path: decode_path(&row.get::<_, String>(1)?),
+                size_bytes: row.get::<_, i64>(2)?.max(0) as u64,
+            })
+        })?;
 
+        let mut items = Vec::new();
+        for row in rows {
+            items.push(row?);
+        }
+        Ok(items)
+    }
+
+    /// The most recent session before `before_id` that has stored results
+    ///
+    /// With `scan_root`, only sessions that scanned that folder (canonicalized,
+    /// like [`ScanCache::record_session_items`] stores it) count.
+    pub fn get_previous_stored_session(
+        &self,
+        before_id: Option<i64>,
+        scan_root: Option<&Path>,
+    ) -> Result<Option<i64>> {
+        let result: Option<i64> = self.db.query_row(
+            "SELECT MAX(id) FROM scan_sessions
+             WHERE results_stored = 1 AND id < ?1 AND (?2 IS NULL OR scan_root = ?2)",
+            params![before_id.unwrap_or(i64::MAX), scan_root.map(normalize_path)],
+            |row| row.get(0),
+        )?;
         Ok(result)
     }
 
+    /// Recent sessions that have stored results, newest first
+    pub fn get_stored_sessions(&self) -> Result<Vec<ScanSession>> {
+        let mut stmt = self.db.prepare(&format!(
+            "{} WHERE results_stored = 1 ORDER BY id DESC",
+            SESSION_QUERY
+        ))?;
+        let rows = stmt.query_map([], session_from_row)?;
+
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(row?);
+        }
+        Ok(sessions)
+    }
+
+    /// Record folder sizes measured by a disk usage scan
+    ///
+    /// Keeps one sample per folder per day (the latest) and drops samples older
+    /// than a year, so the series stays compact however often you scan.
+    pub fn record_folder_sizes(
+        &mut self,
+        recorded_at: DateTime<Utc>,
+        samples: &[FolderSample],
+    ) -> Result<()> {
+        let recorded_at = recorded_at.timestamp();
+        let tx = self
+            .db
+            .transaction()
+            .with_context(|| "Failed to start transaction")?;
+        {
+            let mut delete = tx.prepare_cached(
+                "DELETE FROM folder_sizes WHERE path = ?1 AND recorded_at / 86400 = ?2 / 86400",
+            )?;
+            let mut insert = tx.prepare_cached(
+                "INSERT OR REPLACE INTO folder_sizes (path, depth, size_bytes, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for sample in samples {
+                let path = normalize_path(&sample.path);
+                delete.execute(params![path, recorded_at])?;
+                insert.execute(params![
+                    path,
+                    sample.depth,
+                    clamp_size_to_i64(sample.size_bytes),
+                    recorded_at
+                ])?;
+            }
+        }
+        tx.execute(
+            "DELETE FROM folder_sizes WHERE recorded_at < ?1",
+            [recorded_at - FOLDER_HISTORY_DAYS * 86400],
+        )?;
+        tx.commit()
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Folder size samples for `root` and the folders below it, oldest first
+    pub fn get_folder_history(&self, root: &Path) -> Result<Vec<(DateTime<Utc>, FolderSample)>> {
+        let root = decode_path(&normalize_path(root));
+        let mut stmt = self.db.prepare(
+            "SELECT path, depth, size_bytes, recorded_at FROM folder_sizes ORDER BY recorded_at",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u8>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+
+        let mut history = Vec::new();
+        for row in rows {
+            let (path, depth, size_bytes, recorded_at) = row?;
+            let path = decode_path(&path);
+            if !path.starts_with(&root) {
+                continue;
+            }
+            let recorded_at = DateTime::from_timestamp(recorded_at, 0).unwrap_or_else(Utc::now);
+            history.push((
+                recorded_at,
+                FolderSample {
+                    path,
+                    depth,
+                    size_bytes: size_bytes.max(0) as u64,
+                },
+            ));
+        }
+        Ok(history)
+    }
+
+    /// Record the free space of each volume
+    ///
+    /// Keeps one reading per volume per hour (the latest) and drops readings
+    /// older than 90 days.
+    pub fn record_disk_readings(
+        &mut self,
+        recorded_at: DateTime<Utc>,
+        readings: &[DiskReading],
+    ) -> Result<()> {
+        let recorded_at = recorded_at.timestamp();
+        let tx = self
+            .db
+            .transaction()
+            .with_context(|| "Failed to start transaction")?;
+        {
+            let mut delete = tx.prepare_cached(
+                "DELETE FROM disk_readings WHERE mount_point = ?1 AND recorded_at / 3600 = ?2 / 3600",
+            )?;
+            let mut insert = tx.prepare_cached(
+                "INSERT OR REPLACE INTO disk_readings (mount_point, total_bytes, free_bytes, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for reading in readings {
+                delete.execute(params![reading.mount_point, recorded_at])?;
+                insert.execute(params![
+                    reading.mount_point,
+                    clamp_size_to_i64(reading.total_bytes),
+                    clamp_size_to_i64(reading.free_bytes),
+                    recorded_at
+                ])?;
+            }
+        }
+        tx.execute(
+            "DELETE FROM disk_readings WHERE recorded_at < ?1",
+            [recorded_at - DISK_READING_DAYS * 86400],
+        )?;
+        tx.commit()
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Free-space readings taken since `since`, oldest first
+    pub fn get_disk_readings(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, DiskReading)>> {
+        let mut stmt = self.db.prepare(
+            "SELECT mount_point, total_bytes, free_bytes, recorded_at FROM disk_readings
+             WHERE recorded_at >= ?1 ORDER BY recorded_at",
+        )?;
+        let rows = stmt.query_map([since.timestamp()], |row| {
+            Ok((
+                row.get::<_, i64>(3)?,
+                DiskReading {
+                    mount_point: row.get(0)?,
+                    total_bytes: row.get::<_, i64>(1)?.max(0) as u64,
+                    free_bytes: row.get::<_, i64>(2)?.max(0) as u64,
+                },
+            ))
+        })?;
+
+        let mut readings = Vec::new();
+        for row in rows {
+            let (recorded_at, reading) = row?;
+            let recorded_at = DateTime::from_timestamp(recorded_at, 0).unwrap_or_else(Utc::now);
+            readings.push((recorded_at, reading));
+        }
+        Ok(readings)
+    }
+
     /// Get current scan ID
     pub fn current_scan_id(&self) -> Option<i64> {
         self.current_scan_id
     }
 
+    /// Whether scans store their results with their session
+    pub fn keeps_results(&self) -> bool {
+        self.keep_results
+    }
+
+    /// Keep or skip storing the results of the following scans
+    ///
+    /// Scans that only refresh signatures (`wole watch`) cover a few
+    /// categories: stored, they'd become the `--diff` baseline and push out
+    /// the snapshots of real scans.
+    pub fn set_keep_results(&mut self, keep: bool) {
+        self.keep_results = keep;
+    }
+
     /// Get cache statistics: total files and total storage scanned
     pub fn get_cache_stats(&self) -> Result<(usize, u64)> {
         let total_files: i64 =
@@ -994,6 +1676,45 @@ impl ScanCache {
     }
 }
 
+const SESSION_QUERY: &str = "SELECT id, started_at, finished_at, scan_type, categories, total_files, new_files, changed_files, removed_files, skipped_dirs, hash_hits, hash_misses, scan_root
+     FROM scan_sessions";
+
+fn session_from_row(row: &rusqlite::Row) -> rusqlite::Result<ScanSession> {
+    let id: i64 = row.get(0)?;
+    let started_at: i64 = row.get(1)?;
+    let finished_at: Option<i64> = row.get(2)?;
+    let scan_type: String = row.get(3)?;
+    let categories_json: String = row.get(4)?;
+    let total_files: Option<i64> = row.get(5)?;
+    let new_files: Option<i64> = row.get(6)?;
+    let changed_files: Option<i64> = row.get(7)?;
+    let removed_files: Option<i64> = row.get(8)?;
+    let skipped_dirs: Option<i64> = row.get(9)?;
+    let hash_hits: Option<i64> = row.get(10)?;
+    let hash_misses: Option<i64> = row.get(11)?;
+    let scan_root: Option<String> = row.get(12)?;
+
+    let categories: Vec<String> = serde_json::from_str(&categories_json).unwrap_or_default();
+
+    Ok(ScanSession {
+        id,
+        started_at: DateTime::from_timestamp(started_at, 0).unwrap_or_else(Utc::now),
+        finished_at: finished_at.map(|ts| DateTime::from_timestamp(ts, 0).unwrap_or_else(Utc::now)),
+        scan_type,
+        categories,
+        stats: ScanStats {
+            total_files: total_files.unwrap_or(0) as usize,
+            new_files: new_files.unwrap_or(0) as usize,
+            changed_files: changed_files.unwrap_or(0) as usize,
+            removed_files: removed_files.unwrap_or(0) as usize,
+            skipped_dirs: skipped_dirs.unwrap_or(0) as usize,
+            hash_hits: hash_hits.unwrap_or(0) as usize,
+            hash_misses: hash_misses.unwrap_or(0) as usize,
+        },
+        scan_root: scan_root.map(|root| decode_path(&root)),
+    })
+}
+
 fn is_busy_error(err: &anyhow::Error) -> bool {
     match err.downcast_ref::<rusqlite::Error>() {
         Some(rusqlite::Error::SqliteFailure(code, _))
@@ -1030,7 +1751,7 @@ fn get_cache_dir() -> Result<PathBuf> {
 /// Normalize path for consistent storage and lookup
 /// On Windows, converts to lowercase for case-insensitive matching
 /// On Unix, preserves case
-fn normalize_path(path: &Path) -> String {
+pub(crate) fn normalize_path(path: &Path) -> String {
     #[cfg(windows)]
     {
         // Single-pass normalizer: lowercase and convert \ to / in one pass.
@@ -1269,4 +1990,203 @@ mod tests {
         let status = cache.check_file(&test_file).unwrap();
         assert!(matches!(status, FileStatus::New));
     }
+
+    #[test]
+    fn test_category_min_size() {
+        let (_temp_dir, mut cache) = setup_test_cache();
+        let session = cache.start_scan("full", &["large"]).unwrap();
+        assert_eq!(cache.get_category_min_size("large").unwrap(), None);
+
+        cache.get_category_scan_id("large", session).unwrap();
+        cache.set_category_min_size("large", Some(1024)).unwrap();
+        assert_eq!(cache.get_category_min_size("large").unwrap(), Some(1024));
+        cache.set_category_min_size("large", None).unwrap();
+        assert_eq!(cache.get_category_min_size("large").unwrap(), None);
+    }
+
+    #[test]
+    fn test_upsert_dirs() {
+        let (temp_dir, mut cache) = setup_test_cache();
+        let scan_id = cache.start_scan("full", &["large"]).unwrap();
+
+        let mut sig = DirSignature::from_path(temp_dir.path()).unwrap();
+        sig.total_size = 42;
+        cache
+            .upsert_dirs_batch(&[(sig.clone(), "large".to_string())], scan_id)
+            .unwrap();
+
+        let dirs = cache.get_cached_dirs("large").unwrap();
+        assert_eq!(dirs.get(temp_dir.path()), Some(&sig));
+        assert!(cache.get_cached_dirs("old").unwrap().is_empty());
+
+        cache.invalidate(Some(&["large"])).unwrap();
+        assert!(cache.get_cached_dirs("large").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_session_items() {
+        let (temp_dir, mut cache) = setup_test_cache();
+        let first = cache.start_scan("full", &["temp"]).unwrap();
+        let item = SessionItem {
+            category: "temp".to_string(),
+            path: temp_dir.path().join("old.log"),
+            size_bytes: 1200,
+        };
+        cache
+            .record_session_items(first, temp_dir.path(), std::slice::from_ref(&item))
+            .unwrap();
+        let second = cache.start_scan("incremental", &["temp"]).unwrap();
+
+        assert_eq!(cache.get_session_items(first).unwrap(), vec![item]);
+        assert_eq!(
+            cache
+                .get_previous_stored_session(Some(second), None)
+                .unwrap(),
+            Some(first)
+        );
+        assert_eq!(
+            cache
+                .get_previous_stored_session(None, Some(temp_dir.path()))
+                .unwrap(),
+            Some(first)
+        );
+        assert_eq!(
+            cache
+                .get_previous_stored_session(None, Some(&temp_dir.path().join("other")))
+                .unwrap(),
+            None
+        );
+        let stored = &cache.get_stored_sessions().unwrap()[0];
+        assert_eq!(stored.id, first);
+        assert_eq!(stored.scan_root.as_deref(), Some(temp_dir.path()));
+        assert!(cache.get_session_items(second).unwrap().is_empty());
+
+        // A scan that found nothing is still something to compare with
+        cache
+            .record_session_items(second, temp_dir.path(), &[])
+            .unwrap();
+        let third = cache.start_scan("incremental", &["temp"]).unwrap();
+        assert_eq!(
+            cache
+                .get_previous_stored_session(Some(third), None)
+                .unwrap(),
+            Some(second)
+        );
+    }
+
+    #[test]
+    fn test_folder_history() {
+        let (temp_dir, mut cache) = setup_test_cache();
+        let root = temp_dir.path().join("home");
+        let sample = |path: PathBuf, size_bytes| FolderSample {
+            path,
+            depth: 1,
+            size_bytes,
+        };
+        // Midnight (UTC) ten days ago
+        let day =
+            DateTime::from_timestamp((Utc::now().timestamp() / 86400 - 10) * 86400, 0).unwrap();
+
+        cache
+            .record_folder_sizes(day, &[sample(root.join("videos"), 100)])
+            .unwrap();
+        // A later scan on the same day replaces the sample
+        cache
+            .record_folder_sizes(
+                day + chrono::Duration::hours(1),
+                &[sample(root.join("videos"), 150)],
+            )
+            .unwrap();
+        cache
+            .record_folder_sizes(
+                day + chrono::Duration::days(1),
+                &[
+                    sample(root.join("videos"), 300),
+                    sample(temp_dir.path().join("elsewhere"), 5),
+                ],
+            )
+            .unwrap();
+
+        let history = cache.get_folder_history(&root).unwrap();
+        let sizes: Vec<u64> = history.iter().map(|(_, s)| s.size_bytes).collect();
+        assert_eq!(sizes, vec![150, 300]);
+        assert_eq!(history[1].0, day + chrono::Duration::days(1));
+    }
+
+    #[test]
+    fn test_apply_fs_changes() {
+        let (temp_dir, mut cache) = setup_test_cache();
+        let scan_id = cache.start_scan("full", &["large"]).unwrap();
+        let changed = temp_dir.path().join("changed");
+        let untouched = temp_dir.path().join("untouched");
+        fs::create_dir_all(&changed).unwrap();
+        fs::create_dir_all(&untouched).unwrap();
+        let file = changed.join("video.mp4");
+        let gone = changed.join("old.iso");
+        fs::write(&file, "original").unwrap();
+        fs::write(&gone, "old").unwrap();
+
+        for path in [&file, &gone] {
+            let sig = FileSignature::from_path(path, false).unwrap();
+            cache.upsert_file(&sig, "large", scan_id).unwrap();
+        }
+        let dirs: Vec<(DirSignature, String)> = [temp_dir.path(), &changed, &untouched]
+            .iter()
+            .map(|dir| (DirSignature::from_path(dir).unwrap(), "large".to_string()))
+            .collect();
+        cache.upsert_dirs_batch(&dirs, scan_id).unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs::write(&file, "modified content").unwrap();
+        fs::remove_file(&gone).unwrap();
+        let stats = cache
+            .apply_fs_changes(&[file.clone(), gone.clone()])
+            .unwrap();
+
+        assert_eq!(stats.updated_files, 1);
+        assert_eq!(stats.removed_files, 1);
+        assert!(matches!(
+            cache.check_file(&file).unwrap(),
+            FileStatus::Unchanged
+        ));
+        assert!(matches!(cache.check_file(&gone).unwrap(), FileStatus::New));
+        // Only the folders above the changes have to be walked again
+        let cached = cache.get_cached_dirs("large").unwrap();
+        assert!(!cached.contains_key(&changed));
+        assert!(!cached.contains_key(temp_dir.path()));
+        assert!(cached.contains_key(&untouched));
+
+        assert_eq!(cache.invalidate_dirs_under(temp_dir.path()).unwrap(), 1);
+        assert!(cache.get_cached_dirs("large").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_disk_readings() {
+        let (temp_dir, mut cache) = setup_test_cache();
+        // A mount point no other test or real reading uses
+        let mount_point = temp_dir.path().display().to_string();
+        let reading = |free_bytes| DiskReading {
+            mount_point: mount_point.clone(),
+            total_bytes: 1_000,
+            free_bytes,
+        };
+        let hour = DateTime::from_timestamp((Utc::now().timestamp() / 3600 - 5) * 3600, 0).unwrap();
+
+        cache.record_disk_readings(hour, &[reading(600)]).unwrap();
+        cache
+            .record_disk_readings(hour + chrono::Duration::minutes(30), &[reading(550)])
+            .unwrap();
+        cache
+            .record_disk_readings(hour + chrono::Duration::hours(2), &[reading(500)])
+            .unwrap();
+
+        let free: Vec<u64> = cache
+            .get_disk_readings(hour)
+            .unwrap()
+            .into_iter()
+            .filter(|(_, r)| r.mount_point == mount_point)
+            .map(|(_, r)| r.free_bytes)
+            .collect();
+        assert_eq!(free, vec![550, 500]);
+    }
 }
@@ -2,7 +2,11 @@
 //!
 //! Provides persistent file tracking to enable fast incremental scans by caching
 //! file signatures (metadata + optional content hash) and only rescanning files
-//! that are new or have changed.
+//! that are new or have changed. Directory signatures let scanners skip whole
+//! subtrees whose listing hasn't changed since the last session, and the
+//! duplicate finder reuses the content hashes of files that haven't changed.
+//! Disk usage scans also record the sizes of the biggest folders, for growth
+//! trends, and free-space readings per volume feed the disk forecast.
 
 pub mod context;
 pub mod database;
@@ -11,5 +15,5 @@ pub mod signature;
 
 pub use context::CacheContext;
 pub use database::ScanCache;
-pub use session::{ScanSession, ScanStats};
-pub use signature::{FileSignature, FileStatus};
+pub use session::{DiskReading, FolderSample, FsChangeStats, ScanSession, ScanStats, SessionItem};
+pub use signature::{DirSignature, FileHashes, FileSignature, FileStatus};
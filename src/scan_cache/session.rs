@@ -2,6 +2,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Statistics for a scan session
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -10,6 +11,51 @@ pub struct ScanStats {
     pub new_files: usize,
     pub changed_files: usize,
     pub removed_files: usize,
+    /// Directories whose subtree was skipped because the cache showed them unchanged
+    #[serde(default)]
+    pub skipped_dirs: usize,
+    /// Duplicate-finder hashes reused from the cache
+    #[serde(default)]
+    pub hash_hits: usize,
+    /// Duplicate-finder hashes that had to be computed
+    #[serde(default)]
+    pub hash_misses: usize,
+}
+
+/// What `wole watch` changed in the cache for one batch of filesystem events
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsChangeStats {
+    /// Cached files whose size or mtime changed
+    pub updated_files: usize,
+    /// Cached files that were deleted
+    pub removed_files: usize,
+    /// Directory signatures cleared, so the next scan walks those folders again
+    pub invalidated_dirs: usize,
+}
+
+/// One result of a scan session, kept to compare later scans against
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionItem {
+    pub category: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
+
+/// A folder size measured by a disk usage scan, kept to show growth over time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FolderSample {
+    pub path: PathBuf,
+    /// Depth below the scanned folder (0 for the folder itself)
+    pub depth: u8,
+    pub size_bytes: u64,
+}
+
+/// Free space of a volume at one point in time, for the disk forecast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskReading {
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
 }
 
 /// Scan session record
@@ -21,6 +67,9 @@ pub struct ScanSession {
     pub scan_type: String, // "full" or "incremental"
     pub categories: Vec<String>,
     pub stats: ScanStats,
+    /// Folder that was scanned (recorded with the results; None for older sessions)
+    #[serde(default)]
+    pub scan_root: Option<PathBuf>,
 }
 
 impl ScanSession {
@@ -33,6 +82,7 @@ impl ScanSession {
             scan_type,
             categories,
             stats: ScanStats::default(),
+            scan_root: None,
         }
     }
 
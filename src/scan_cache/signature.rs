@@ -130,6 +130,68 @@ impl FileSignature {
     }
 }
 
+/// Directory signature for pruning unchanged subtrees
+///
+/// A directory's mtime changes when entries are added, removed or renamed in it,
+/// so an unchanged mtime and entry count mean its listing is the same as last
+/// session. `total_size` is the aggregate size of everything below it, recorded
+/// by the scanner that walked it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirSignature {
+    pub path: PathBuf,
+    pub mtime: SystemTime,
+    pub entry_count: u64,
+    pub total_size: u64,
+}
+
+impl DirSignature {
+    /// Read mtime and entry count of a directory (`total_size` is left at 0)
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let mtime = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .with_context(|| format!("Failed to get mtime: {}", path.display()))?;
+        let entry_count = std::fs::read_dir(path)
+            .with_context(|| format!("Failed to read directory: {}", path.display()))?
+            .count() as u64;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mtime,
+            entry_count,
+            total_size: 0,
+        })
+    }
+
+    /// Whether the directory listing is the same as when `cached` was recorded
+    ///
+    /// Compares mtime and entry count only; the sizes of files deeper down
+    /// aren't checked.
+    pub fn matches(&self, cached: &Self) -> bool {
+        self.mtime == cached.mtime && self.entry_count == cached.entry_count
+    }
+}
+
+/// Content hashes the duplicate finder computed for a file
+///
+/// They're only reused while the file's size and mtime are the ones recorded
+/// here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHashes {
+    pub path: PathBuf,
+    pub size: u64,
+    pub mtime: SystemTime,
+    /// Hash of the first 4 KB
+    pub partial_hash: Option<String>,
+    pub full_hash: Option<String>,
+}
+
+impl FileHashes {
+    /// Whether the hashes still describe the file with signature `sig`
+    pub fn matches(&self, sig: &FileSignature) -> bool {
+        self.size == sig.size && self.mtime == sig.mtime
+    }
+}
+
 /// Status of a file compared to cache
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileStatus {
@@ -202,4 +264,22 @@ mod tests {
 
         assert_eq!(sig1.compare(&sig2), FileStatus::Modified);
     }
+
+    #[test]
+    fn test_dir_signature_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+
+        let sig1 = DirSignature::from_path(temp_dir.path()).unwrap();
+        assert_eq!(sig1.entry_count, 1);
+        assert!(DirSignature::from_path(temp_dir.path())
+            .unwrap()
+            .matches(&sig1));
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        assert!(!DirSignature::from_path(temp_dir.path())
+            .unwrap()
+            .matches(&sig1));
+    }
 }
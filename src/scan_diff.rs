@@ -0,0 +1,263 @@
+//! Scan-to-scan diff for `wole scan --diff`.
+//!
+//! Every cached scan stores its results (path and size per category) with its
+//! session. A diff compares the results of the current scan with those of an
+//! earlier session: items that appeared, items that disappeared and items whose
+//! size changed, grouped by category with the net change in bytes.
+
+use crate::output::ScanResults;
+use crate::scan_cache::{ScanSession, SessionItem};
+use crate::utils;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// An item that appeared, disappeared or changed size
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ItemChange {
+    pub path: PathBuf,
+    /// Size in the earlier session (None for new items)
+    pub old_bytes: Option<u64>,
+    /// Size now (None for removed items)
+    pub new_bytes: Option<u64>,
+    pub delta_bytes: i64,
+}
+
+/// Changes in one category
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryDiff {
+    pub category: String,
+    pub name: String,
+    pub added: Vec<ItemChange>,
+    pub removed: Vec<ItemChange>,
+    pub changed: Vec<ItemChange>,
+    pub net_delta_bytes: i64,
+}
+
+impl CategoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Changes since an earlier scan session
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanDiff {
+    pub baseline_session: i64,
+    pub baseline_started_at: DateTime<Utc>,
+    /// Scanned categories, in scan order (including those without changes)
+    pub categories: Vec<CategoryDiff>,
+    pub net_delta_bytes: i64,
+}
+
+/// Size of every item in the results, as stored with a scan session
+///
+/// Uses the sizes the scanners measured; items of scanners that only report a
+/// category total are sized again.
+pub fn item_sizes(results: &ScanResults) -> Vec<SessionItem> {
+    results
+        .iter()
+        .flat_map(|scanned| {
+            let category = &scanned.descriptor.id;
+            let result = &scanned.result;
+            let single = result.paths.len() == 1;
+            result.paths.iter().map(move |path| {
+                let size_bytes = if single {
+                    result.size_bytes
+                } else if let Some(&size) = result.path_sizes.get(path) {
+                    size
+                } else {
+                    // Not every scanner records per-item sizes
+                    match std::fs::symlink_metadata(path) {
+                        Ok(m) if m.is_dir() => utils::calculate_dir_size(path),
+                        Ok(m) => m.len(),
+                        Err(_) => 0,
+                    }
+                };
+                SessionItem {
+                    category: category.clone(),
+                    path: path.clone(),
+                    size_bytes,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Compare the current results with the items stored for `baseline`
+///
+/// Only categories scanned both now and in the baseline session are compared,
+/// so a category added to or left out of this scan doesn't show up as all new
+/// or all removed.
+pub fn diff(
+    baseline: &ScanSession,
+    baseline_items: &[SessionItem],
+    results: &ScanResults,
+    current_items: &[SessionItem],
+) -> ScanDiff {
+    let by_category = |items: &[SessionItem]| {
+        let mut map: HashMap<String, HashMap<PathBuf, u64>> = HashMap::new();
+        for item in items {
+            map.entry(item.category.clone())
+                .or_default()
+                .insert(item.path.clone(), item.size_bytes);
+        }
+        map
+    };
+    let old = by_category(baseline_items);
+    let new = by_category(current_items);
+    let empty = HashMap::new();
+
+    let categories: Vec<CategoryDiff> = results
+        .iter()
+        .filter(|scanned| baseline.categories.contains(&scanned.descriptor.id))
+        .map(|scanned| {
+            let id = &scanned.descriptor.id;
+            let old = old.get(id).unwrap_or(&empty);
+            let new = new.get(id).unwrap_or(&empty);
+
+            let change =
+                |path: &PathBuf, old_bytes: Option<u64>, new_bytes: Option<u64>| ItemChange {
+                    path: path.clone(),
+                    old_bytes,
+                    new_bytes,
+                    delta_bytes: new_bytes.unwrap_or(0) as i64 - old_bytes.unwrap_or(0) as i64,
+                };
+            let mut added = Vec::new();
+            let mut changed = Vec::new();
+            for (path, &size) in new {
+                match old.get(path) {
+                    None => added.push(change(path, None, Some(size))),
+                    Some(&old_size) if old_size != size => {
+                        changed.push(change(path, Some(old_size), Some(size)))
+                    }
+                    Some(_) => {}
+                }
+            }
+            let mut removed: Vec<ItemChange> = old
+                .iter()
+                .filter(|(path, _)| !new.contains_key(*path))
+                .map(|(path, &size)| change(path, Some(size), None))
+                .collect();
+
+            // Biggest changes first
+            for list in [&mut added, &mut removed, &mut changed] {
+                list.sort_by(|a, b| {
+                    b.delta_bytes
+                        .unsigned_abs()
+                        .cmp(&a.delta_bytes.unsigned_abs())
+                        .then_with(|| a.path.cmp(&b.path))
+                });
+            }
+            let net_delta_bytes = added
+                .iter()
+                .chain(&removed)
+                .chain(&changed)
+                .map(|c| c.delta_bytes)
+                .sum();
+
+            CategoryDiff {
+                category: id.clone(),
+                name: scanned.descriptor.display_name.clone(),
+                added,
+                removed,
+                changed,
+                net_delta_bytes,
+            }
+        })
+        .collect();
+
+    ScanDiff {
+        baseline_session: baseline.id,
+        baseline_started_at: baseline.started_at,
+        net_delta_bytes: categories.iter().map(|c| c.net_delta_bytes).sum(),
+        categories,
+    }
+}
+
+/// Signed human-readable size, e.g. "+1.2 GB" or "-300 KB"
+pub fn format_delta(delta_bytes: i64) -> String {
+    let sign = if delta_bytes < 0 { "-" } else { "+" };
+    format!(
+        "{}{}",
+        sign,
+        bytesize::to_string(delta_bytes.unsigned_abs(), false)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::categories::registry::CategoryRegistry;
+    use crate::output::CategoryResult;
+
+    fn item(category: &str, path: &str, size_bytes: u64) -> SessionItem {
+        SessionItem {
+            category: category.to_string(),
+            path: PathBuf::from(path),
+            size_bytes,
+        }
+    }
+
+    #[test]
+    fn test_finds_added_removed_and_changed_items() {
+        let registry = CategoryRegistry::builtin();
+        let mut results = ScanResults::default();
+        results.insert(
+            registry.get("cache").unwrap().clone(),
+            CategoryResult::default(),
+        );
+        results.insert(
+            registry.get("temp").unwrap().clone(),
+            CategoryResult::default(),
+        );
+
+        results.insert(
+            registry.get("large").unwrap().clone(),
+            CategoryResult::default(),
+        );
+
+        let categories = ["cache", "temp", "trash"].map(String::from).to_vec();
+        let baseline = ScanSession::new("full".to_string(), categories);
+        let old = vec![
+            item("cache", "/c/npm", 1_000),
+            item("cache", "/c/pip", 500),
+            item("temp", "/t/a.log", 50),
+            item("trash", "/x/gone", 9_000),
+        ];
+        let new = vec![
+            item("cache", "/c/npm", 4_000),
+            item("cache", "/c/cargo", 200),
+            item("temp", "/t/a.log", 50),
+        ];
+
+        let diff = diff(&baseline, &old, &results, &new);
+        let cache = &diff.categories[0];
+        assert_eq!(cache.added[0].path, PathBuf::from("/c/cargo"));
+        assert_eq!(cache.removed[0].delta_bytes, -500);
+        assert_eq!(cache.changed[0].delta_bytes, 3_000);
+        assert_eq!(cache.net_delta_bytes, 2_700);
+        // Unchanged; trash isn't scanned now and large wasn't scanned then
+        assert!(diff.categories[1].is_empty());
+        assert_eq!(diff.categories.len(), 2);
+        assert_eq!(diff.net_delta_bytes, 2_700);
+        assert_eq!(format_delta(-2_700), "-2.7 KB");
+    }
+
+    #[test]
+    fn test_item_sizes_use_the_sizes_scanners_measured() {
+        let mut result = CategoryResult::default();
+        // Neither exists: the sizes can only come from the scan
+        result.push(PathBuf::from("/c/npm"), 1_000);
+        result.push(PathBuf::from("/c/pip"), 500);
+        let mut results = ScanResults::default();
+        results.insert(
+            CategoryRegistry::builtin().get("cache").unwrap().clone(),
+            result,
+        );
+
+        let sizes: Vec<u64> = item_sizes(&results).iter().map(|i| i.size_bytes).collect();
+        assert_eq!(sizes, vec![1_000, 500]);
+    }
+}
@@ -1,12 +1,14 @@
 //! Progress events emitted during scanning (used by TUI)
 
+use serde::Serialize;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Real-time progress updates during scanning.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum ScanProgressEvent {
     /// A category scan has started.
     CategoryStarted {
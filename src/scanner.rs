@@ -1,10 +1,13 @@
-use crate::categories;
+use crate::categories::registry::{CategoryDescriptor, CategoryRegistry, ScanContext, ScanExtras};
 use crate::cli::ScanOptions;
 use crate::config::Config;
 use crate::git;
 use crate::output::{CategoryResult, OutputMode, ScanResults};
 use crate::progress;
-use crate::scan_cache::{FileSignature, ScanCache, ScanStats};
+use crate::scan_cache::context::{CacheContextBuilder, HashCache};
+use crate::scan_cache::{
+    CacheContext, DirSignature, FileHashes, FileSignature, ScanCache, ScanStats,
+};
 use crate::scan_events::ScanProgressEvent;
 use crate::theme::Theme;
 use crate::utils;
@@ -12,8 +15,9 @@ use anyhow::Result;
 // use rayon::prelude::*; // Disabled: using sequential scan to avoid thrashing
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug)]
 struct RecycleBinIndex {
@@ -121,25 +125,26 @@ fn save_results_to_cache_background(results: ScanResults, scan_session_id: i64)
             }
         };
 
-        add_category_paths(&results.cache.paths, "cache");
-        add_category_paths(&results.app_cache.paths, "app_cache");
-        add_category_paths(&results.temp.paths, "temp");
-        add_category_paths(&results.trash.paths, "trash");
-        add_category_paths(&results.build.paths, "build");
-        add_category_paths(&results.downloads.paths, "downloads");
-        add_category_paths(&results.large.paths, "large");
-        add_category_paths(&results.old.paths, "old");
-        add_category_paths(&results.browser.paths, "browser");
-        add_category_paths(&results.system.paths, "system");
-        add_category_paths(&results.empty.paths, "empty");
-        add_category_paths(&results.duplicates.paths, "duplicates");
-        add_category_paths(&results.applications.paths, "applications");
-        add_category_paths(&results.windows_update.paths, "windows_update");
-        add_category_paths(&results.event_logs.paths, "event_logs");
+        // Categories that store everything they found, with the threshold they used
+        let mut candidate_min_sizes = std::collections::HashMap::new();
+        for scanned in results.iter() {
+            match &scanned.result.cache_candidates {
+                Some(candidates) => {
+                    add_category_paths(&candidates.paths, &scanned.descriptor.id);
+                    candidate_min_sizes.insert(scanned.descriptor.id.clone(), candidates.min_size);
+                }
+                None => add_category_paths(&scanned.result.paths, &scanned.descriptor.id),
+            }
+        }
+        // Found nothing: still a new scan, so last session's paths aren't replayed
+        for category in candidate_min_sizes.keys() {
+            category_batches.entry(category.clone()).or_default();
+        }
 
         // Save each category's files with its category-specific scan ID
         for (category, files) in category_batches {
-            if !files.is_empty() {
+            let min_size = candidate_min_sizes.get(&category).copied();
+            if !files.is_empty() || min_size.is_some() {
                 // Get category scan ID (increments if category was scanned before)
                 if let Ok(category_scan_id) = cache.get_category_scan_id(&category, scan_session_id)
                 {
@@ -149,6 +154,7 @@ fn save_results_to_cache_background(results: ScanResults, scan_session_id: i64)
                             category, e
                         );
                     }
+                    let _ = cache.set_category_min_size(&category, min_size);
                 }
             }
         }
@@ -176,11 +182,129 @@ fn defer_finish_scan(scan_session_id: i64, stats: ScanStats) {
     });
 }
 
+/// Directory signatures recorded by scanners during a scan, written to the cache afterwards
+type DirUpdates = Arc<Mutex<Vec<(DirSignature, String)>>>;
+
+/// Scan cache state handed to a category's scanner
+///
+/// Loads the directory signatures the scanner recorded last session and, when
+/// there are any, which of its previous results are unchanged (the results of
+/// the subtrees it skips), along with the size threshold they were found with
+/// when the scanner stored more than it reported. New directory signatures are
+/// collected in `dir_updates`.
+/// The duplicates scanner also gets the content hashes of earlier scans.
+fn category_cache_context(
+    cache: &ScanCache,
+    category: &str,
+    dir_updates: &DirUpdates,
+) -> CacheContext {
+    let cached_dirs = cache.get_cached_dirs(category).unwrap_or_default();
+
+    // Last session's results are replayed for the directories skipped as
+    // unchanged: rewritten files too, since that doesn't touch their directory
+    let mut unchanged_paths = HashSet::new();
+    let mut existing_paths = HashSet::new();
+    if !cached_dirs.is_empty() {
+        if let Ok(Some(previous_scan_id)) = cache.get_previous_category_scan_id(category) {
+            let cached_paths = cache
+                .get_cached_category(category, previous_scan_id)
+                .unwrap_or_default();
+            if let Ok(status_map) = cache.check_files_batch(&cached_paths) {
+                for (path, status) in status_map {
+                    match status {
+                        crate::scan_cache::FileStatus::Unchanged => {
+                            unchanged_paths.insert(path.clone());
+                            existing_paths.insert(path);
+                        }
+                        crate::scan_cache::FileStatus::Modified => {
+                            existing_paths.insert(path);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    let updates = Arc::clone(dir_updates);
+    let mut builder = CacheContextBuilder::new()
+        .with_unchanged_paths(unchanged_paths)
+        .with_cached_paths(existing_paths)
+        .with_cached_min_size(cache.get_category_min_size(category).unwrap_or(None))
+        .with_cached_dirs(cached_dirs)
+        .with_record_dir_fn(Box::new(move |sig, category| {
+            if let Ok(mut updates) = updates.lock() {
+                updates.push((sig.clone(), category.to_string()));
+            }
+            Ok(())
+        }));
+    if category == "duplicates" {
+        let hashes = cache.get_file_hashes().unwrap_or_default();
+        builder = builder.with_hash_cache(HashCache::new(hashes));
+    }
+    builder.build()
+}
+
+/// Add what a category's cache context saw to the session stats, and collect
+/// the content hashes it looked up
+fn collect_cache_context(
+    cache_ctx: Option<CacheContext>,
+    stats: &mut ScanStats,
+    hash_updates: &mut Vec<FileHashes>,
+) {
+    if let Some(cache_ctx) = cache_ctx {
+        let hashes = cache_ctx.hash_cache();
+        stats.skipped_dirs += cache_ctx.skipped_dirs();
+        stats.hash_hits += hashes.hits();
+        stats.hash_misses += hashes.misses();
+        hash_updates.extend(hashes.take_updates());
+    }
+}
+
+/// Write the duplicate-finder hashes looked up during a scan (non-fatal)
+fn save_file_hashes(cache: &mut ScanCache, hash_updates: &[FileHashes]) {
+    if hash_updates.is_empty() {
+        return;
+    }
+    if let Err(e) = cache.upsert_file_hashes(hash_updates) {
+        eprintln!("Warning: Failed to save duplicate hashes: {}", e);
+    }
+}
+
+/// Write the directory signatures recorded during a scan (non-fatal)
+fn save_dir_signatures(cache: &mut ScanCache, dir_updates: &DirUpdates, scan_session_id: i64) {
+    let updates = std::mem::take(&mut *dir_updates.lock().unwrap_or_else(|e| e.into_inner()));
+    if let Err(e) = cache.upsert_dirs_batch(&updates, scan_session_id) {
+        eprintln!("Warning: Failed to save directory signatures: {}", e);
+    }
+}
+
+/// Record the free space of each volume for the disk forecast (non-fatal)
+fn save_disk_readings(cache: &mut ScanCache) {
+    let disks = crate::status::gather_disk_details();
+    if let Err(e) = crate::forecast::record_readings(cache, &disks) {
+        eprintln!("Warning: Failed to save free-space readings: {}", e);
+    }
+}
+
+/// Store the results of a scan session so later scans can be compared with it (non-fatal)
+fn save_session_items(
+    cache: &mut ScanCache,
+    path: &Path,
+    results: &ScanResults,
+    scan_session_id: i64,
+) {
+    let items = crate::scan_diff::item_sizes(results);
+    let root = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if let Err(e) = cache.record_session_items(scan_session_id, &root, &items) {
+        eprintln!("Warning: Failed to save scan results for --diff: {}", e);
+    }
+}
+
 /// Try incremental scan for a category
 /// Returns Ok(Some(result)) if cache was used, Ok(None) if full scan needed, Err on error
 fn try_incremental_scan(
     category_name: &str,
-    _task: &ScanTask,
     _path: &Path,
     _config: &Config,
     cache: &mut ScanCache,
@@ -257,50 +381,6 @@ fn try_incremental_scan(
     Ok(None)
 }
 
-/// Execute full category scan
-#[allow(clippy::too_many_arguments)]
-fn execute_category_scan(
-    _category_name: &str,
-    task: &ScanTask,
-    path: &Path,
-    config: &Config,
-    mode: OutputMode,
-    build_config: &crate::config::CategoryConfig,
-    duplicates_config: &crate::config::DuplicatesConfig,
-    duplicate_groups: &std::cell::RefCell<
-        Option<Vec<crate::categories::duplicates::DuplicateGroup>>,
-    >,
-) -> Result<CategoryResult> {
-    match task {
-        ScanTask::Cache => categories::cache::scan(path, config, mode),
-        ScanTask::AppCache => categories::app_cache::scan(path, config, mode),
-        ScanTask::Temp => categories::temp::scan(path, config),
-        ScanTask::Trash => categories::trash::scan(),
-        ScanTask::Build(age) => {
-            categories::build::scan(path, *age, Some(build_config), config, mode)
-        }
-        ScanTask::Downloads(age) => categories::downloads::scan(path, *age, config, mode),
-        ScanTask::Large(size) => categories::large::scan(path, *size, config, mode),
-        ScanTask::Old(age) => categories::old::scan(path, *age, config, mode),
-        ScanTask::Browser => categories::browser::scan(path, config),
-        ScanTask::System => categories::system::scan(path, config),
-        ScanTask::Empty => categories::empty::scan(path, config),
-        ScanTask::Duplicates => {
-            match categories::duplicates::scan_with_config(path, Some(duplicates_config), config) {
-                Ok(dup_result) => {
-                    // Store groups for enhanced display
-                    *duplicate_groups.borrow_mut() = Some(dup_result.groups.clone());
-                    Ok(dup_result.to_category_result())
-                }
-                Err(e) => Err(e),
-            }
-        }
-        ScanTask::Applications => categories::applications::scan(path, config, mode),
-        ScanTask::WindowsUpdate => categories::windows_update::scan(path, config),
-        ScanTask::EventLogs => categories::event_logs::scan(path, config),
-    }
-}
-
 /// Scan all requested categories and return aggregated results
 ///
 /// Optimizations:
@@ -310,6 +390,21 @@ fn execute_category_scan(
 /// - Filters out paths matching exclusion patterns from config
 /// - Supports incremental scanning via scan_cache parameter
 pub fn scan_all(
+    path: &Path,
+    options: ScanOptions,
+    mode: OutputMode,
+    config: &Config,
+    scan_cache: Option<&mut ScanCache>,
+) -> Result<ScanResults> {
+    let registry = CategoryRegistry::with_config(config);
+    scan_with_registry(&registry, path, options, mode, config, scan_cache)
+}
+
+/// Scan the categories of `registry` selected by `options`
+///
+/// Same as [`scan_all`], for callers that registered their own categories.
+pub fn scan_with_registry(
+    registry: &CategoryRegistry,
     path: &Path,
     options: ScanOptions,
     mode: OutputMode,
@@ -318,61 +413,12 @@ pub fn scan_all(
 ) -> Result<ScanResults> {
     // Clear git cache for fresh scan
     git::clear_cache();
+    crate::project::policy::clear_cache();
 
     let mut results = ScanResults::default();
 
     // Build list of enabled categories
-    let mut enabled: Vec<(&str, ScanTask)> = Vec::new();
-
-    if options.cache {
-        enabled.push(("cache", ScanTask::Cache));
-    }
-    if options.app_cache {
-        enabled.push(("app_cache", ScanTask::AppCache));
-    }
-    if options.temp {
-        enabled.push(("temp", ScanTask::Temp));
-    }
-    if options.trash {
-        enabled.push(("trash", ScanTask::Trash));
-    }
-    if options.build {
-        enabled.push(("build", ScanTask::Build(options.project_age_days)));
-    }
-    if options.downloads {
-        enabled.push(("downloads", ScanTask::Downloads(options.min_age_days)));
-    }
-    if options.large {
-        enabled.push(("large", ScanTask::Large(options.min_size_bytes)));
-    }
-    if options.old {
-        enabled.push(("old", ScanTask::Old(options.min_age_days)));
-    }
-    if options.browser {
-        enabled.push(("browser", ScanTask::Browser));
-    }
-    if options.system {
-        enabled.push(("system", ScanTask::System));
-    }
-    if options.empty {
-        enabled.push(("empty", ScanTask::Empty));
-    }
-    if options.duplicates {
-        enabled.push(("duplicates", ScanTask::Duplicates));
-    }
-
-    if options.applications {
-        enabled.push(("applications", ScanTask::Applications));
-    }
-
-    if options.windows_update {
-        enabled.push(("windows_update", ScanTask::WindowsUpdate));
-    }
-
-    if options.event_logs {
-        enabled.push(("event_logs", ScanTask::EventLogs));
-    }
-
+    let enabled = registry.enabled(&options);
     let total_categories = enabled.len();
 
     if total_categories == 0 {
@@ -387,7 +433,7 @@ pub fn scan_all(
 
     if let Some(cache) = scan_cache.as_mut() {
         if cache_enabled {
-            let categories: Vec<&str> = enabled.iter().map(|(name, _)| *name).collect();
+            let categories: Vec<&str> = enabled.iter().map(|d| d.id.as_str()).collect();
             let previous_scan_id = match cache.get_previous_scan_id() {
                 Ok(id) => id,
                 Err(e) => {
@@ -450,27 +496,22 @@ pub fn scan_all(
 
     // Progress counter for parallel tasks
     let scanned_count = AtomicUsize::new(0);
-    let path_owned = path.to_path_buf();
-
-    // Clone configs for use in parallel closure (needs to be Send + Sync)
-    let build_config = config.categories.build.clone();
-    let duplicates_config = config.categories.duplicates.clone();
-    let config_clone = config.clone(); // Clone full config for parallel access
 
-    // Store duplicate groups separately (needs to be stored after scan)
-    use std::cell::RefCell;
-    let duplicate_groups: RefCell<Option<Vec<crate::categories::duplicates::DuplicateGroup>>> =
-        RefCell::new(None);
+    // Side outputs (duplicate groups) are collected here and attached after the scan
+    let extras = ScanExtras::default();
+    let ctx = ScanContext::new(path, &options, config, mode, &extras);
+    let dir_updates = DirUpdates::default();
+    let mut cache_stats = ScanStats::default();
+    let mut hash_updates = Vec::new();
 
     // Run scans sequentially to avoid disk thrashing and thread pool explosion
     // Each individual scanner (large, duplicates, build) manages its own parallelism
     // and uses the full system resources. Running them in parallel causes massive
     // I/O contention and "loading so bad" system freezes.
-    let scan_results: Vec<(&str, Result<CategoryResult>)> = enabled
-        .iter()
-        .map(|(name, task)| {
-            // Clone config for this task
-            let config = &config_clone;
+    let scan_results: Vec<(Arc<CategoryDescriptor>, Result<CategoryResult>)> = enabled
+        .into_iter()
+        .map(|descriptor| {
+            let name = descriptor.display_name.as_str();
 
             // Update progress
             let count = scanned_count.fetch_add(1, Ordering::SeqCst) + 1;
@@ -488,35 +529,18 @@ pub fn scan_all(
             }
 
             // Try incremental scan if cache is available
-            let result = if use_incremental {
-                if let (Some(cache), Some(scan_session_id)) = (scan_cache.as_mut(), scan_id) {
+            let cached = match (use_incremental, scan_cache.as_mut(), scan_id) {
+                (true, Some(cache), Some(scan_session_id)) => {
                     // Attempt incremental scan (pass scan_session_id, not category scan_id)
                     match try_incremental_scan(
-                        name,
-                        task,
-                        &path_owned,
+                        &descriptor.id,
+                        path,
                         config,
                         cache,
                         scan_session_id,
                         mode,
                     ) {
-                        Ok(Some(cached_result)) => {
-                            // Used cache successfully
-                            Ok(cached_result)
-                        }
-                        Ok(None) => {
-                            // Need to do full scan for this category
-                            execute_category_scan(
-                                name,
-                                task,
-                                &path_owned,
-                                config,
-                                mode,
-                                &build_config,
-                                &duplicates_config,
-                                &duplicate_groups,
-                            )
-                        }
+                        Ok(cached) => cached,
                         Err(e) => {
                             // Cache error, fall back to full scan
                             if mode != OutputMode::Quiet {
@@ -525,46 +549,32 @@ pub fn scan_all(
                                     name, e
                                 );
                             }
-                            execute_category_scan(
-                                name,
-                                task,
-                                &path_owned,
-                                config,
-                                mode,
-                                &build_config,
-                                &duplicates_config,
-                                &duplicate_groups,
-                            )
+                            None
                         }
                     }
-                } else {
-                    // Full scan (no cache or cache disabled)
-                    execute_category_scan(
-                        name,
-                        task,
-                        &path_owned,
-                        config,
-                        mode,
-                        &build_config,
-                        &duplicates_config,
-                        &duplicate_groups,
-                    )
                 }
-            } else {
-                // Full scan (no cache or cache disabled)
-                execute_category_scan(
-                    name,
-                    task,
-                    &path_owned,
-                    config,
-                    mode,
-                    &build_config,
-                    &duplicates_config,
-                    &duplicate_groups,
-                )
+                _ => None,
+            };
+
+            let result = match cached {
+                // Used cache successfully
+                Some(cached_result) => Ok(cached_result),
+                // Full scan (no cache, cache disabled or cache miss); scanners that
+                // support it still skip directories unchanged since the last session
+                None => {
+                    let cache_ctx = match (scan_cache.as_deref(), scan_id) {
+                        (Some(cache), Some(_)) => {
+                            Some(category_cache_context(cache, &descriptor.id, &dir_updates))
+                        }
+                        _ => None,
+                    };
+                    let result = descriptor.scan(&ctx.with_cache(cache_ctx.as_ref()));
+                    collect_cache_context(cache_ctx, &mut cache_stats, &mut hash_updates);
+                    result
+                }
             };
 
-            (*name, result)
+            (descriptor, result)
         })
         .collect();
 
@@ -574,33 +584,34 @@ pub fn scan_all(
     }
 
     // Aggregate results
-    for (category, result) in scan_results {
-        match (category, result) {
-            ("cache", Ok(r)) => results.cache = r,
-            ("app_cache", Ok(r)) => results.app_cache = r,
-            ("temp", Ok(r)) => results.temp = r,
-            ("trash", Ok(r)) => results.trash = r,
-            ("build", Ok(r)) => results.build = r,
-            ("downloads", Ok(r)) => results.downloads = r,
-            ("large", Ok(r)) => results.large = r,
-            ("old", Ok(r)) => results.old = r,
-            ("browser", Ok(r)) => results.browser = r,
-            ("system", Ok(r)) => results.system = r,
-            ("empty", Ok(r)) => results.empty = r,
-            ("duplicates", Ok(r)) => {
-                results.duplicates = r;
-                // Store duplicate groups for enhanced display
-                results.duplicates_groups = duplicate_groups.borrow().clone();
-            }
-            ("applications", Ok(r)) => results.applications = r,
-            ("windows_update", Ok(r)) => results.windows_update = r,
-            ("event_logs", Ok(r)) => results.event_logs = r,
-            (name, Err(e)) => {
+    for (descriptor, result) in scan_results {
+        match result {
+            Ok(r) => results.insert(descriptor, r),
+            Err(e) => {
                 if mode != OutputMode::Quiet {
-                    eprintln!("[WARNING] {} scan failed: {}", name, e);
+                    eprintln!("[WARNING] {} scan failed: {}", descriptor.display_name, e);
                 }
             }
-            _ => {}
+        }
+    }
+    // Store duplicate groups for enhanced display
+    results.duplicates_groups = extras.duplicate_groups.into_inner().ok().flatten();
+
+    if matches!(mode, OutputMode::Verbose | OutputMode::VeryVerbose) {
+        if cache_stats.skipped_dirs > 0 {
+            println!(
+                "  {} Skipped {} directories unchanged since the last scan",
+                Theme::muted("→"),
+                cache_stats.skipped_dirs
+            );
+        }
+        if cache_stats.hash_hits + cache_stats.hash_misses > 0 {
+            println!(
+                "  {} Duplicate hashes: {} reused from the cache, {} computed",
+                Theme::muted("→"),
+                cache_stats.hash_hits,
+                cache_stats.hash_misses
+            );
         }
     }
 
@@ -620,29 +631,22 @@ pub fn scan_all(
     // where subsequent scans don't see this scan as finished
     if let Some(cache) = scan_cache.as_mut() {
         if let Some(scan_session_id) = cache.current_scan_id() {
+            save_dir_signatures(cache, &dir_updates, scan_session_id);
+            save_file_hashes(cache, &hash_updates);
+            if cache.keeps_results() {
+                save_session_items(cache, path, &results, scan_session_id);
+            }
+            save_disk_readings(cache);
+
             // Calculate stats synchronously (needed for finish_scan)
-            let total_files = results.cache.items
-                + results.app_cache.items
-                + results.temp.items
-                + results.trash.items
-                + results.build.items
-                + results.downloads.items
-                + results.large.items
-                + results.old.items
-                + results.applications.items
-                + results.browser.items
-                + results.system.items
-                + results.empty.items
-                + results.duplicates.items
-                + results.windows_update.items
-                + results.event_logs.items;
+            let total_files = results.total_items();
 
             // Finish scan synchronously to ensure finished_at is set before returning
             // This prevents race condition where next scan doesn't see this scan as finished
             let stats = ScanStats {
                 total_files,
                 removed_files: 0, // Will be updated by cleanup_stale in background thread
-                ..Default::default()
+                ..cache_stats
             };
             match cache.finish_scan_nonblocking(scan_session_id, stats.clone()) {
                 Ok(true) => {}
@@ -1000,131 +1004,49 @@ fn perform_full_disk_traversal(
 
 /// Scan all requested categories and emit progress events for TUI.
 pub fn scan_all_with_progress(
+    path: &Path,
+    options: ScanOptions,
+    config: &Config,
+    tx: &Sender<ScanProgressEvent>,
+    scan_cache: Option<&mut ScanCache>,
+) -> Result<ScanResults> {
+    let registry = CategoryRegistry::with_config(config);
+    scan_with_registry_and_progress(&registry, path, options, config, tx, scan_cache)
+}
+
+/// Scan the categories of `registry` selected by `options`, emitting progress events
+pub fn scan_with_registry_and_progress(
+    registry: &CategoryRegistry,
+    path: &Path,
+    options: ScanOptions,
+    config: &Config,
+    tx: &Sender<ScanProgressEvent>,
+    scan_cache: Option<&mut ScanCache>,
+) -> Result<ScanResults> {
+    let never = AtomicBool::new(false);
+    scan_with_registry_until(registry, path, options, config, tx, scan_cache, &never)
+}
+
+/// [`scan_with_registry_and_progress`] that stops before the next category once
+/// `cancelled` is set
+///
+/// A cancelled scan returns an error and stores nothing in the scan cache.
+pub fn scan_with_registry_until(
+    registry: &CategoryRegistry,
     path: &Path,
     options: ScanOptions,
     config: &Config,
     tx: &Sender<ScanProgressEvent>,
     mut scan_cache: Option<&mut ScanCache>,
+    cancelled: &AtomicBool,
 ) -> Result<ScanResults> {
     // Clear git cache for fresh scan
     git::clear_cache();
+    crate::project::policy::clear_cache();
 
     let mut results = ScanResults::default();
 
-    #[derive(Clone, Copy)]
-    struct ScanJob {
-        key: &'static str,
-        display: &'static str,
-        task: ScanTask,
-    }
-
-    let mut enabled: Vec<ScanJob> = Vec::new();
-
-    if options.cache {
-        enabled.push(ScanJob {
-            key: "cache",
-            display: "Package Cache",
-            task: ScanTask::Cache,
-        });
-    }
-    if options.app_cache {
-        enabled.push(ScanJob {
-            key: "app_cache",
-            display: "Application Cache",
-            task: ScanTask::AppCache,
-        });
-    }
-    if options.temp {
-        enabled.push(ScanJob {
-            key: "temp",
-            display: "Temp Files",
-            task: ScanTask::Temp,
-        });
-    }
-    if options.trash {
-        enabled.push(ScanJob {
-            key: "trash",
-            display: "Trash",
-            task: ScanTask::Trash,
-        });
-    }
-    if options.build {
-        enabled.push(ScanJob {
-            key: "build",
-            display: "Build Artifacts",
-            task: ScanTask::Build(options.project_age_days),
-        });
-    }
-    if options.downloads {
-        enabled.push(ScanJob {
-            key: "downloads",
-            display: "Old Downloads",
-            task: ScanTask::Downloads(options.min_age_days),
-        });
-    }
-    if options.large {
-        enabled.push(ScanJob {
-            key: "large",
-            display: "Large Files",
-            task: ScanTask::Large(options.min_size_bytes),
-        });
-    }
-    if options.old {
-        enabled.push(ScanJob {
-            key: "old",
-            display: "Old Files",
-            task: ScanTask::Old(options.min_age_days),
-        });
-    }
-    if options.browser {
-        enabled.push(ScanJob {
-            key: "browser",
-            display: "Browser Cache",
-            task: ScanTask::Browser,
-        });
-    }
-    if options.system {
-        enabled.push(ScanJob {
-            key: "system",
-            display: "System Cache",
-            task: ScanTask::System,
-        });
-    }
-    if options.empty {
-        enabled.push(ScanJob {
-            key: "empty",
-            display: "Empty Folders",
-            task: ScanTask::Empty,
-        });
-    }
-    if options.duplicates {
-        enabled.push(ScanJob {
-            key: "duplicates",
-            display: "Duplicates",
-            task: ScanTask::Duplicates,
-        });
-    }
-    if options.applications {
-        enabled.push(ScanJob {
-            key: "applications",
-            display: "Installed Applications",
-            task: ScanTask::Applications,
-        });
-    }
-    if options.windows_update {
-        enabled.push(ScanJob {
-            key: "windows_update",
-            display: "Windows Update",
-            task: ScanTask::WindowsUpdate,
-        });
-    }
-    if options.event_logs {
-        enabled.push(ScanJob {
-            key: "event_logs",
-            display: "Event Logs",
-            task: ScanTask::EventLogs,
-        });
-    }
+    let enabled = registry.enabled(&options);
 
     if enabled.is_empty() {
         return Ok(results);
@@ -1143,7 +1065,7 @@ pub fn scan_all_with_progress(
 
     if let Some(cache) = scan_cache.as_mut() {
         if cache_enabled {
-            let categories: Vec<&str> = enabled.iter().map(|job| job.key).collect();
+            let categories: Vec<&str> = enabled.iter().map(|d| d.id.as_str()).collect();
             let scan_type = if is_first_scan { "full" } else { "incremental" };
             match cache.start_scan(scan_type, &categories) {
                 Ok(id) => scan_id = Some(id),
@@ -1171,148 +1093,32 @@ pub fn scan_all_with_progress(
         }
     }
 
-    let path_owned = path.to_path_buf();
-
-    // Clone configs for use in scan tasks
-    let build_config = config.categories.build.clone();
-    let duplicates_config = config.categories.duplicates.clone();
+    // Side outputs (duplicate groups) are collected here and attached after the scan
+    let extras = ScanExtras::default();
+    let ctx = ScanContext::new(path, &options, config, OutputMode::Quiet, &extras);
+    let dir_updates = DirUpdates::default();
+    let mut cache_stats = ScanStats::default();
+    let mut hash_updates = Vec::new();
 
-    // Store duplicate groups separately (needs to be stored after scan)
-    use std::cell::RefCell;
-    let duplicate_groups: RefCell<Option<Vec<crate::categories::duplicates::DuplicateGroup>>> =
-        RefCell::new(None);
-
-    let scan_results: Vec<(&str, &str, Result<CategoryResult>)> = enabled
-        .iter()
-        .map(|job| {
-            let display = job.display;
-
-            let send_started = || {
-                let _ = tx.send(ScanProgressEvent::CategoryStarted {
-                    category: display.to_string(),
-                    total_units: None,
-                    current_path: None,
-                });
-            };
-
-            let result = match job.task {
-                ScanTask::Cache => categories::cache::scan_with_progress(&path_owned, config, tx),
-                ScanTask::AppCache => {
-                    categories::app_cache::scan_with_progress(&path_owned, config, tx)
-                }
-                ScanTask::Temp => categories::temp::scan_with_progress(&path_owned, config, tx),
-                ScanTask::Trash => {
-                    send_started();
-                    categories::trash::scan()
-                }
-                ScanTask::Build(age) => {
-                    send_started();
-                    categories::build::scan(
-                        &path_owned,
-                        age,
-                        Some(&build_config),
-                        config,
-                        OutputMode::Quiet,
-                    )
-                }
-                ScanTask::Downloads(age) => {
-                    send_started();
-                    categories::downloads::scan(&path_owned, age, config, OutputMode::Quiet)
-                }
-                ScanTask::Large(size) => {
-                    send_started();
-                    categories::large::scan(&path_owned, size, config, OutputMode::Quiet)
-                }
-                ScanTask::Old(age) => {
-                    send_started();
-                    categories::old::scan(&path_owned, age, config, OutputMode::Quiet)
-                }
-                ScanTask::Browser => {
-                    send_started();
-                    categories::browser::scan(&path_owned, config)
-                }
-                ScanTask::System => {
-                    send_started();
-                    categories::system::scan(&path_owned, config)
-                }
-                ScanTask::Empty => categories::empty::scan_with_progress(&path_owned, config, tx),
-                ScanTask::Duplicates => {
-                    send_started();
-                    match categories::duplicates::scan_with_config(
-                        &path_owned,
-                        Some(&duplicates_config),
-                        config,
-                    ) {
-                        Ok(dup_result) => {
-                            *duplicate_groups.borrow_mut() = Some(dup_result.groups.clone());
-                            Ok(dup_result.to_category_result())
-                        }
-                        Err(e) => Err(e),
-                    }
-                }
-                ScanTask::Applications => {
-                    categories::applications::scan_with_progress(&path_owned, config, tx)
-                }
-                ScanTask::WindowsUpdate => {
-                    send_started();
-                    categories::windows_update::scan(&path_owned, config)
-                }
-                ScanTask::EventLogs => {
-                    send_started();
-                    categories::event_logs::scan(&path_owned, config)
-                }
-            };
-
-            if let Ok(ref category_result) = result {
-                if !matches!(
-                    job.task,
-                    ScanTask::Cache | ScanTask::AppCache | ScanTask::Temp | ScanTask::Applications
-                ) {
-                    let _ = tx.send(ScanProgressEvent::CategoryFinished {
-                        category: display.to_string(),
-                        items: category_result.items,
-                        size_bytes: category_result.size_bytes,
-                    });
-                }
-            } else if !matches!(
-                job.task,
-                ScanTask::Cache | ScanTask::AppCache | ScanTask::Temp | ScanTask::Applications
-            ) {
-                let _ = tx.send(ScanProgressEvent::CategoryFinished {
-                    category: display.to_string(),
-                    items: 0,
-                    size_bytes: 0,
-                });
-            }
-
-            (job.key, display, result)
-        })
-        .collect();
-
-    for (category, _display, result) in scan_results {
-        match (category, result) {
-            ("cache", Ok(r)) => results.cache = r,
-            ("app_cache", Ok(r)) => results.app_cache = r,
-            ("temp", Ok(r)) => results.temp = r,
-            ("trash", Ok(r)) => results.trash = r,
-            ("build", Ok(r)) => results.build = r,
-            ("downloads", Ok(r)) => results.downloads = r,
-            ("large", Ok(r)) => results.large = r,
-            ("old", Ok(r)) => results.old = r,
-            ("browser", Ok(r)) => results.browser = r,
-            ("system", Ok(r)) => results.system = r,
-            ("empty", Ok(r)) => results.empty = r,
-            ("duplicates", Ok(r)) => {
-                results.duplicates = r;
-                results.duplicates_groups = duplicate_groups.borrow().clone();
+    for descriptor in enabled {
+        if cancelled.load(Ordering::Relaxed) {
+            anyhow::bail!("Scan cancelled");
+        }
+        let cache_ctx = match (scan_cache.as_deref(), scan_id) {
+            (Some(cache), Some(_)) => {
+                Some(category_cache_context(cache, &descriptor.id, &dir_updates))
             }
-            ("applications", Ok(r)) => results.applications = r,
-            ("windows_update", Ok(r)) => results.windows_update = r,
-            ("event_logs", Ok(r)) => results.event_logs = r,
-            (_name, Err(_e)) => {}
-            _ => {}
+            _ => None,
+        };
+        // Scanners without their own progress reporting get started/finished events
+        // from the descriptor; failed categories are left out of the results
+        let result = descriptor.scan_with_progress(&ctx.with_cache(cache_ctx.as_ref()), tx);
+        collect_cache_context(cache_ctx, &mut cache_stats, &mut hash_updates);
+        if let Ok(result) = result {
+            results.insert(descriptor, result);
         }
     }
+    results.duplicates_groups = extras.duplicate_groups.into_inner().ok().flatten();
 
     filter_exclusions(&mut results, config);
 
@@ -1326,29 +1132,22 @@ pub fn scan_all_with_progress(
     // where subsequent scans don't see this scan as finished
     if let Some(cache) = scan_cache.as_mut() {
         if let Some(scan_session_id) = cache.current_scan_id() {
+            save_dir_signatures(cache, &dir_updates, scan_session_id);
+            save_file_hashes(cache, &hash_updates);
+            if cache.keeps_results() {
+                save_session_items(cache, path, &results, scan_session_id);
+            }
+            save_disk_readings(cache);
+
             // Calculate stats synchronously (needed for finish_scan)
-            let total_files = results.cache.items
-                + results.app_cache.items
-                + results.temp.items
-                + results.trash.items
-                + results.build.items
-                + results.downloads.items
-                + results.large.items
-                + results.old.items
-                + results.applications.items
-                + results.browser.items
-                + results.system.items
-                + results.empty.items
-                + results.duplicates.items
-                + results.windows_update.items
-                + results.event_logs.items;
+            let total_files = results.total_items();
 
             // Finish scan synchronously to ensure finished_at is set before returning
             // This prevents race condition where next scan doesn't see this scan as finished
             let stats = ScanStats {
                 total_files,
                 removed_files: 0, // Will be updated by cleanup_stale in background thread
-                ..Default::default()
+                ..cache_stats
             };
             match cache.finish_scan_nonblocking(scan_session_id, stats.clone()) {
                 Ok(true) => {}
@@ -1368,26 +1167,6 @@ pub fn scan_all_with_progress(
     Ok(results)
 }
 
-/// Scan task enum for parallel execution
-#[derive(Clone, Copy)]
-enum ScanTask {
-    Cache,
-    AppCache,
-    Temp,
-    Trash,
-    Build(u64),
-    Downloads(u64),
-    Large(u64),
-    Old(u64),
-    Browser,
-    System,
-    Empty,
-    Duplicates,
-    Applications,
-    WindowsUpdate,
-    EventLogs,
-}
-
 /// Filter out files that are in the recycle bin from scan results
 /// Files in recycle bin were already cleaned, so exclude them from results
 /// but keep them tracked in cache (they can be restored)
@@ -1446,55 +1225,14 @@ fn filter_recycle_bin_files(results: &mut ScanResults) {
     };
 
     // Filter all categories EXCEPT trash (trash category scans the recycle bin itself)
-    filter_and_recalculate(&mut results.cache.paths, &mut results.cache.size_bytes);
-    filter_and_recalculate(
-        &mut results.app_cache.paths,
-        &mut results.app_cache.size_bytes,
-    );
-    filter_and_recalculate(&mut results.temp.paths, &mut results.temp.size_bytes);
-    filter_and_recalculate(&mut results.build.paths, &mut results.build.size_bytes);
-    filter_and_recalculate(
-        &mut results.downloads.paths,
-        &mut results.downloads.size_bytes,
-    );
-    filter_and_recalculate(&mut results.large.paths, &mut results.large.size_bytes);
-    filter_and_recalculate(&mut results.old.paths, &mut results.old.size_bytes);
-    filter_and_recalculate(&mut results.browser.paths, &mut results.browser.size_bytes);
-    filter_and_recalculate(&mut results.system.paths, &mut results.system.size_bytes);
-    filter_and_recalculate(&mut results.empty.paths, &mut results.empty.size_bytes);
-    filter_and_recalculate(
-        &mut results.duplicates.paths,
-        &mut results.duplicates.size_bytes,
-    );
-    filter_and_recalculate(
-        &mut results.applications.paths,
-        &mut results.applications.size_bytes,
-    );
-    filter_and_recalculate(
-        &mut results.windows_update.paths,
-        &mut results.windows_update.size_bytes,
-    );
-    filter_and_recalculate(
-        &mut results.event_logs.paths,
-        &mut results.event_logs.size_bytes,
-    );
-    // NOTE: Do NOT filter results.trash - that category scans the recycle bin itself
-
-    // Update item counts
-    results.cache.items = results.cache.paths.len();
-    results.app_cache.items = results.app_cache.paths.len();
-    results.temp.items = results.temp.paths.len();
-    results.build.items = results.build.paths.len();
-    results.downloads.items = results.downloads.paths.len();
-    results.large.items = results.large.paths.len();
-    results.old.items = results.old.paths.len();
-    results.browser.items = results.browser.paths.len();
-    results.system.items = results.system.paths.len();
-    results.empty.items = results.empty.paths.len();
-    results.duplicates.items = results.duplicates.paths.len();
-    results.applications.items = results.applications.paths.len();
-    results.windows_update.items = results.windows_update.paths.len();
-    results.event_logs.items = results.event_logs.paths.len();
+    for scanned in &mut results.categories {
+        if scanned.descriptor.id == "trash" {
+            continue;
+        }
+        filter_and_recalculate(&mut scanned.result.paths, &mut scanned.result.size_bytes);
+        // Update item counts
+        scanned.result.items = scanned.result.paths.len();
+    }
 }
 
 /// Filter out paths matching exclusion patterns
@@ -1544,48 +1282,11 @@ fn filter_exclusions(results: &mut ScanResults, config: &Config) {
         }
     };
 
-    filter_and_recalculate(&mut results.cache.paths, &mut results.cache.size_bytes);
-    filter_and_recalculate(
-        &mut results.app_cache.paths,
-        &mut results.app_cache.size_bytes,
-    );
-    filter_and_recalculate(&mut results.temp.paths, &mut results.temp.size_bytes);
-    filter_and_recalculate(&mut results.trash.paths, &mut results.trash.size_bytes);
-    filter_and_recalculate(&mut results.build.paths, &mut results.build.size_bytes);
-    filter_and_recalculate(
-        &mut results.downloads.paths,
-        &mut results.downloads.size_bytes,
-    );
-    filter_and_recalculate(&mut results.large.paths, &mut results.large.size_bytes);
-    filter_and_recalculate(&mut results.old.paths, &mut results.old.size_bytes);
-    filter_and_recalculate(&mut results.browser.paths, &mut results.browser.size_bytes);
-    filter_and_recalculate(&mut results.system.paths, &mut results.system.size_bytes);
-    filter_and_recalculate(&mut results.empty.paths, &mut results.empty.size_bytes);
-    filter_and_recalculate(
-        &mut results.duplicates.paths,
-        &mut results.duplicates.size_bytes,
-    );
-    filter_and_recalculate(
-        &mut results.applications.paths,
-        &mut results.applications.size_bytes,
-    );
-
-    // Recalculate item counts after filtering
-    results.cache.items = results.cache.paths.len();
-    results.app_cache.items = results.app_cache.paths.len();
-    results.temp.items = results.temp.paths.len();
-    results.trash.items = results.trash.paths.len();
-    results.build.items = results.build.paths.len();
-    results.downloads.items = results.downloads.paths.len();
-    results.large.items = results.large.paths.len();
-    results.old.items = results.old.paths.len();
-    results.browser.items = results.browser.paths.len();
-    results.system.items = results.system.paths.len();
-    results.empty.items = results.empty.paths.len();
-    results.duplicates.items = results.duplicates.paths.len();
-    results.applications.items = results.applications.paths.len();
-    results.windows_update.items = results.windows_update.paths.len();
-    results.event_logs.items = results.event_logs.paths.len();
+    for scanned in &mut results.categories {
+        filter_and_recalculate(&mut scanned.result.paths, &mut scanned.result.size_bytes);
+        // Recalculate item counts after filtering
+        scanned.result.items = scanned.result.paths.len();
+    }
 }
 
 /// Calculate total size of paths (files only - not used for directories)
@@ -1617,21 +1318,7 @@ mod tests {
     fn test_scan_all_no_categories() {
         let temp_dir = create_test_dir();
         let options = ScanOptions {
-            cache: false,
-            app_cache: false,
-            temp: false,
-            trash: false,
-            build: false,
-            downloads: false,
-            large: false,
-            old: false,
-            applications: false,
-            browser: false,
-            system: false,
-            empty: false,
-            duplicates: false,
-            windows_update: false,
-            event_logs: false,
+            categories: Vec::new(),
             project_age_days: 14,
             min_age_days: 30,
             min_size_bytes: 100 * 1024 * 1024,
@@ -1641,9 +1328,8 @@ mod tests {
         // Use Quiet mode in tests to avoid spinner thread issues
         let results = scan_all(temp_dir.path(), options, OutputMode::Quiet, &config, None).unwrap();
 
-        assert_eq!(results.cache.items, 0);
-        assert_eq!(results.temp.items, 0);
-        assert_eq!(results.build.items, 0);
+        assert!(results.categories.is_empty());
+        assert_eq!(results.total_items(), 0);
     }
 
     #[test]
@@ -1652,16 +1338,19 @@ mod tests {
         let mut config = Config::default();
 
         // Add some test paths
-        results
-            .cache
-            .paths
-            .push(PathBuf::from("C:/Users/test/important-project/file.txt"));
-        results
-            .cache
-            .paths
-            .push(PathBuf::from("C:/Users/test/normal/file.txt"));
-        results.cache.items = 2;
-        results.cache.size_bytes = 1000;
+        let cache = CategoryRegistry::builtin().get("cache").unwrap().clone();
+        results.insert(
+            cache,
+            CategoryResult {
+                items: 2,
+                size_bytes: 1000,
+                paths: vec![
+                    PathBuf::from("C:/Users/test/important-project/file.txt"),
+                    PathBuf::from("C:/Users/test/normal/file.txt"),
+                ],
+                ..Default::default()
+            },
+        );
 
         // Add exclusion pattern
         config
@@ -1673,10 +1362,11 @@ mod tests {
         filter_exclusions(&mut results, &config);
 
         // Should have filtered out the important-project path
-        assert_eq!(results.cache.items, 1);
-        assert_eq!(results.cache.paths.len(), 1);
+        let cache = results.get("cache").unwrap();
+        assert_eq!(cache.items, 1);
+        assert_eq!(cache.paths.len(), 1);
         assert_eq!(
-            results.cache.paths[0],
+            cache.paths[0],
             PathBuf::from("C:/Users/test/normal/file.txt")
         );
     }
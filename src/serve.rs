@@ -0,0 +1,657 @@
+//! Local JSON-RPC server (`wole serve`) for GUIs and editor integrations.
+//!
+//! Listens on a local socket: `wole.sock` in the wole data folder (next to the
+//! scan cache), or the `\\.\pipe\wole` named pipe on Windows. Clients send
+//! JSON-RPC 2.0 requests, one per line, and get responses and notifications
+//! back the same way. Requests run concurrently and call the same library
+//! functions as the CLI:
+//!
+//! | Method          | Params                                                              |
+//! | --------------- | ------------------------------------------------------------------- |
+//! | `scan`          | `path`, `all`, `categories`, `project_age_days`, `min_age_days`, `min_size_mb`, `exclude`, `no_cache` |
+//! | `clean`         | `items` (`[{"path", "category"}]`), `permanent`                     |
+//! | `restore`       | `path` (default: everything from the last clean)                    |
+//! | `status`        |                                                                     |
+//! | `disk_insights` | `path`, `depth`                                                     |
+//! | `cancel`        | `id` of the request to cancel                                       |
+//!
+//! `scan` results are the same as `wole scan --json`. While `scan` and
+//! `disk_insights` run, `progress` notifications carry a `ScanProgressEvent`
+//! and the id of the request they belong to.
+//!
+//! Only `scan` and `clean` can be cancelled. A cancelled scan is answered
+//! right away with error -32800, gets no more notifications and stops before
+//! its next category. A cancelled clean stops before its next path and answers
+//! with what it removed so far and `"cancelled": true`.
+
+use crate::categories::registry::CategoryRegistry;
+use crate::categories::{self, cache_strategy};
+use crate::cleaner::DeleteOutcome;
+use crate::cli::ScanOptions;
+use crate::config::Config;
+use crate::disk_usage::{self, ProgressCallback};
+use crate::history::DeletionLog;
+use crate::output::{self, OutputMode};
+use crate::restore;
+use crate::scan_cache::ScanCache;
+use crate::scan_events::ScanProgressEvent;
+use crate::scanner;
+use crate::status;
+use crate::utils;
+use anyhow::{Context, Result};
+use interprocess::local_socket::prelude::*;
+use interprocess::local_socket::{ListenerOptions, Name, SendHalf, Stream};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// The operation itself failed (e.g. nothing to restore)
+pub const OPERATION_FAILED: i64 = -32000;
+/// The request was cancelled (same code as LSP)
+pub const REQUEST_CANCELLED: i64 = -32800;
+
+/// Minimum time between disk insights progress notifications
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+const DEFAULT_DEPTH: u8 = 3;
+
+/// Error answered to a request
+#[derive(Debug)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl fmt::Display) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    fn invalid_params(message: impl fmt::Display) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::new(OPERATION_FAILED, format!("{:#}", e))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// None for notifications, which get no response
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// The default socket: `wole.sock` in the wole data folder, or the `wole` named pipe on Windows
+pub fn default_socket() -> String {
+    if cfg!(windows) {
+        return "wole".to_string();
+    }
+    ScanCache::cache_dir()
+        .map(|dir| dir.with_file_name("wole.sock").display().to_string())
+        .unwrap_or_else(|_| "wole.sock".to_string())
+}
+
+fn socket_name(socket: &str) -> std::io::Result<Name<'_>> {
+    #[cfg(windows)]
+    {
+        use interprocess::local_socket::GenericNamespaced;
+        socket.to_ns_name::<GenericNamespaced>()
+    }
+    #[cfg(not(windows))]
+    {
+        use interprocess::local_socket::GenericFilePath;
+        socket.to_fs_name::<GenericFilePath>()
+    }
+}
+
+/// A bound socket, ready to accept clients
+pub struct Server {
+    listener: interprocess::local_socket::Listener,
+}
+
+impl Server {
+    pub fn bind(socket: &str) -> Result<Self> {
+        if Stream::connect(socket_name(socket)?).is_ok() {
+            anyhow::bail!("wole serve is already running on {}", socket);
+        }
+        if !cfg!(windows) {
+            if let Some(parent) = Path::new(socket).parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        // A socket file left behind by a server that was killed is replaced
+        let options = ListenerOptions::new()
+            .name(socket_name(socket)?)
+            .try_overwrite(true);
+        // Only the current user may connect: clients can delete files
+        #[cfg(unix)]
+        let options = {
+            use interprocess::os::unix::local_socket::ListenerOptionsExt;
+            options.mode(0o600)
+        };
+        let listener = options
+            .create_sync()
+            .with_context(|| format!("Failed to listen on {}", socket))?;
+        Ok(Self { listener })
+    }
+
+    /// Serve clients until the process is stopped, each on its own thread
+    pub fn run(self) -> Result<()> {
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    std::thread::spawn(move || serve_connection(stream));
+                }
+                Err(e) => eprintln!("Warning: Failed to accept a connection: {}", e),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Writes messages to one client; shared by the requests running for it
+#[derive(Clone)]
+struct Outbox(Arc<Mutex<SendHalf>>);
+
+impl Outbox {
+    fn send(&self, message: &Value) {
+        let mut line = message.to_string();
+        line.push('\n');
+        let mut half = lock(&self.0);
+        // A client that went away just misses the message
+        let _ = half.write_all(line.as_bytes()).and_then(|_| half.flush());
+    }
+
+    fn respond(&self, id: &Value, result: Result<Value, RpcError>) {
+        self.send(&match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": e.code, "message": e.message },
+            }),
+        });
+    }
+}
+
+/// Requests running for a connection, keyed by their JSON id
+///
+/// Whoever removes a request's entry answers it: the request when it
+/// finishes, or `cancel` for a scan.
+type Pending = Arc<Mutex<HashMap<String, Running>>>;
+
+/// A request that hasn't been answered yet
+struct Running {
+    method: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Progress notifications of one request (dropped once it's cancelled)
+#[derive(Clone)]
+struct Progress {
+    outbox: Outbox,
+    request: Value,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Progress {
+    fn notify(&self, event: &ScanProgressEvent) {
+        if self.request.is_null() || self.cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        self.outbox.send(&json!({
+            "jsonrpc": "2.0",
+            "method": "progress",
+            "params": { "request": self.request, "event": event },
+        }));
+    }
+}
+
+fn serve_connection(stream: Stream) {
+    let (recv, send) = stream.split();
+    let outbox = Outbox(Arc::new(Mutex::new(send)));
+    let pending = Pending::default();
+
+    for line in BufReader::new(recv).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if !line.trim().is_empty() {
+            handle_line(&line, &outbox, &pending);
+        }
+    }
+
+    // The client is gone: nobody is waiting for what's still running
+    for (_, running) in lock(&pending).drain() {
+        running.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+fn handle_line(line: &str, outbox: &Outbox, pending: &Pending) {
+    let request: Request = match serde_json::from_str::<Value>(line) {
+        Err(e) => return outbox.respond(&Value::Null, Err(RpcError::new(PARSE_ERROR, e))),
+        Ok(value) => match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => return outbox.respond(&Value::Null, Err(RpcError::new(INVALID_REQUEST, e))),
+        },
+    };
+
+    if request.method == "cancel" {
+        let result = cancel(&request.params, outbox, pending);
+        if let Some(id) = &request.id {
+            outbox.respond(id, result);
+        }
+        return;
+    }
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Some(id) = &request.id {
+        let mut running = lock(pending);
+        if running.contains_key(&id.to_string()) {
+            drop(running);
+            let error = RpcError::new(INVALID_REQUEST, "A request with this id is still running");
+            return outbox.respond(id, Err(error));
+        }
+        running.insert(
+            id.to_string(),
+            Running {
+                method: request.method.clone(),
+                cancelled: Arc::clone(&cancelled),
+            },
+        );
+    }
+
+    let outbox = outbox.clone();
+    let pending = Arc::clone(pending);
+    std::thread::spawn(move || {
+        let progress = Progress {
+            outbox: outbox.clone(),
+            request: request.id.clone().unwrap_or(Value::Null),
+            cancelled,
+        };
+        let result = call(&request.method, request.params, &progress);
+        if let Some(id) = request.id {
+            // Cancelled requests were answered already
+            if lock(&pending).remove(&id.to_string()).is_some() {
+                outbox.respond(&id, result);
+            }
+        }
+    });
+}
+
+fn cancel(params: &Value, outbox: &Outbox, pending: &Pending) -> Result<Value, RpcError> {
+    let id = params
+        .get("id")
+        .ok_or_else(|| RpcError::invalid_params("Missing the id of the request to cancel"))?;
+    let key = id.to_string();
+    let mut running = lock(pending);
+    let Some(request) = running.get(&key) else {
+        return Ok(json!({ "cancelled": false }));
+    };
+    match request.method.as_str() {
+        // Stops before its next category; nothing more is sent for it
+        "scan" => {
+            if let Some(request) = running.remove(&key) {
+                request.cancelled.store(true, Ordering::Relaxed);
+            }
+            drop(running);
+            outbox.respond(
+                id,
+                Err(RpcError::new(REQUEST_CANCELLED, "Request cancelled")),
+            );
+        }
+        // Stops before its next path and answers with what it removed
+        "clean" => request.cancelled.store(true, Ordering::Relaxed),
+        method => {
+            return Err(RpcError::invalid_params(format!(
+                "'{}' requests can't be cancelled",
+                method
+            )))
+        }
+    }
+    Ok(json!({ "cancelled": true }))
+}
+
+fn call(method: &str, params: Value, progress: &Progress) -> Result<Value, RpcError> {
+    match method {
+        "scan" => scan(parse(params)?, progress),
+        "clean" => clean(parse(params)?, &progress.cancelled),
+        "restore" => restore(parse(params)?),
+        "status" => to_value(status::snapshot()?),
+        "disk_insights" => disk_insights(parse(params)?, progress),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method '{}'", method),
+        )),
+    }
+}
+
+/// Params of a request (missing params are the defaults)
+fn parse<T: DeserializeOwned + Default>(params: Value) -> Result<T, RpcError> {
+    if params.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(params).map_err(RpcError::invalid_params)
+}
+
+fn to_value(value: impl Serialize) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| anyhow::Error::from(e).into())
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ScanParams {
+    /// Folder for the categories that search one (default: the home folder)
+    path: Option<PathBuf>,
+    /// Every category, as with `--all`
+    all: bool,
+    /// Category ids, e.g. "cache", "build" or a custom category
+    categories: Vec<String>,
+    project_age_days: Option<u64>,
+    min_age_days: Option<u64>,
+    min_size_mb: Option<u64>,
+    exclude: Vec<String>,
+    no_cache: bool,
+}
+
+fn scan(params: ScanParams, progress: &Progress) -> Result<Value, RpcError> {
+    let mut config = Config::load();
+    config.apply_cli_overrides(
+        params.project_age_days,
+        params.min_age_days,
+        params.min_size_mb,
+    );
+    config.exclusions.patterns.extend(params.exclude);
+
+    let registry = CategoryRegistry::with_config(&config);
+    let mut options = ScanOptions {
+        project_age_days: config.thresholds.project_age_days,
+        min_age_days: config.thresholds.min_age_days,
+        min_size_bytes: config.thresholds.min_size_mb * 1024 * 1024,
+        ..ScanOptions::default()
+    };
+    if params.all {
+        for descriptor in registry.iter() {
+            options.set_enabled(&descriptor.id, true);
+        }
+    }
+    for id in &params.categories {
+        let descriptor = registry
+            .get(id)
+            .ok_or_else(|| RpcError::invalid_params(format!("Unknown category '{}'", id)))?;
+        options.set_enabled(&descriptor.id, true);
+    }
+    if registry.enabled(&options).is_empty() {
+        return Err(RpcError::invalid_params(
+            "No categories selected: set \"all\" or list \"categories\"",
+        ));
+    }
+
+    let path = params
+        .path
+        .or_else(utils::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let mut cache = if params.no_cache || !config.cache.enabled {
+        None
+    } else {
+        ScanCache::open().ok()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let forwarder = {
+        let progress = progress.clone();
+        std::thread::spawn(move || {
+            for event in rx {
+                progress.notify(&event);
+            }
+        })
+    };
+    let results = scanner::scan_with_registry_until(
+        &registry,
+        &path,
+        options,
+        &config,
+        &tx,
+        cache.as_mut(),
+        &progress.cancelled,
+    );
+    // All progress goes out before the result
+    drop(tx);
+    let _ = forwarder.join();
+
+    Ok(output::json_value(&results?, None).map_err(anyhow::Error::from)?)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CleanParams {
+    items: Vec<CleanItem>,
+    /// Delete instead of moving to the Recycle Bin
+    permanent: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CleanItem {
+    path: PathBuf,
+    /// Category id recorded in the deletion history
+    #[serde(default)]
+    category: Option<String>,
+}
+
+/// Delete the items one by one, stopping before the next one once `cancelled` is set
+fn clean(params: CleanParams, cancelled: &AtomicBool) -> Result<Value, RpcError> {
+    if params.items.is_empty() {
+        return Err(RpcError::invalid_params("No items to clean"));
+    }
+
+    let mut deleted = Vec::new();
+    let mut skipped = Vec::new();
+    let mut locked = Vec::new();
+    let mut permission_denied = Vec::new();
+    let mut errors = 0;
+    let mut stopped = false;
+    let mut log = DeletionLog::new();
+    for item in &params.items {
+        if cancelled.load(Ordering::Relaxed) {
+            stopped = true;
+            break;
+        }
+        let category = item.category.as_deref().unwrap_or("other");
+        // Size before deletion, for the deletion history
+        let size = cache_strategy::deletion_paths(&item.path)
+            .iter()
+            .map(|p| path_size(p))
+            .sum();
+        // A grouped package cache item stands for files inside it, which are
+        // logged one by one so restore can find them
+        let (outcome, removed) = categories::cache::clean_parts(&item.path, size, params.permanent);
+        for (part, size) in &removed {
+            log.log_success(part, *size, category, params.permanent);
+        }
+        let reason = match outcome {
+            Ok(DeleteOutcome::Deleted) => {
+                deleted.push(&item.path);
+                continue;
+            }
+            Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {
+                skipped.push(&item.path);
+                continue;
+            }
+            Ok(DeleteOutcome::SkippedLocked) => {
+                locked.push(&item.path);
+                "File is locked"
+            }
+            Ok(DeleteOutcome::SkippedPermission) => {
+                permission_denied.push(&item.path);
+                "Permission denied"
+            }
+            Err(_) => "Deletion failed",
+        };
+        errors += 1;
+        log.log_failure(&item.path, size, category, params.permanent, reason);
+    }
+    if !log.records.is_empty() {
+        if let Err(e) = log.save() {
+            eprintln!("Warning: Failed to save deletion history: {}", e);
+        }
+    }
+
+    Ok(json!({
+        "deleted": deleted,
+        "skipped": skipped,
+        "locked": locked,
+        "permission_denied": permission_denied,
+        "errors": errors,
+        "cleaned_bytes": log.total_bytes_cleaned,
+        "cancelled": stopped,
+    }))
+}
+
+fn path_size(path: &Path) -> u64 {
+    match std::fs::symlink_metadata(path) {
+        Ok(m) if m.is_dir() => utils::calculate_dir_size(path),
+        Ok(m) => m.len(),
+        Err(_) => 0,
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RestoreParams {
+    /// File or folder to restore (default: everything from the last clean)
+    path: Option<PathBuf>,
+}
+
+fn restore(params: RestoreParams) -> Result<Value, RpcError> {
+    let result = match params.path {
+        Some(path) => restore::restore_path(&path, OutputMode::Quiet)?,
+        None => restore::restore_last(OutputMode::Quiet)?,
+    };
+    to_value(result)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DiskInsightsParams {
+    /// Folder to analyze (default: the home folder)
+    path: Option<PathBuf>,
+    depth: Option<u8>,
+}
+
+fn disk_insights(params: DiskInsightsParams, progress: &Progress) -> Result<Value, RpcError> {
+    let path = params
+        .path
+        .or_else(utils::home_dir)
+        .ok_or_else(|| RpcError::invalid_params("No path given"))?;
+    if !path.is_dir() {
+        return Err(RpcError::invalid_params(format!(
+            "Not a folder: {}",
+            path.display()
+        )));
+    }
+
+    let progress = progress.clone();
+    let last_sent = Mutex::new(Instant::now());
+    let callback: ProgressCallback = Box::new(move |folder: &Path| {
+        let mut last_sent = lock(&last_sent);
+        if last_sent.elapsed() >= PROGRESS_INTERVAL {
+            *last_sent = Instant::now();
+            progress.notify(&ScanProgressEvent::ReadingFolder {
+                path: folder.to_path_buf(),
+            });
+        }
+    });
+    let insights = disk_usage::scan_directory_with_progress(
+        &path,
+        params.depth.unwrap_or(DEFAULT_DEPTH),
+        Some(callback),
+    )?;
+    to_value(insights)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_requests_over_the_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("wole.sock").display().to_string();
+        let server = Server::bind(&socket).unwrap();
+        std::thread::spawn(move || server.run());
+
+        let stream = Stream::connect(socket_name(&socket).unwrap()).unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut call = |line: &str| -> Value {
+            (&stream)
+                .write_all(format!("{}\n", line).as_bytes())
+                .unwrap();
+            let mut response = String::new();
+            reader.read_line(&mut response).unwrap();
+            serde_json::from_str(&response).unwrap()
+        };
+
+        let response = call(r#"{"jsonrpc":"2.0","id":1,"method":"defragment"}"#);
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = call("{not json");
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        let response =
+            call(r#"{"jsonrpc":"2.0","id":2,"method":"scan","params":{"categories":["nope"]}}"#);
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let response = call(r#"{"jsonrpc":"2.0","id":"c","method":"cancel","params":{"id":9}}"#);
+        assert_eq!(response["id"], "c");
+        assert_eq!(response["result"]["cancelled"], false);
+
+        // Only one server per socket
+        assert!(Server::bind(&socket).is_err());
+    }
+
+    #[test]
+    fn test_cancelled_clean_stops_before_the_next_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("old.log");
+        std::fs::write(&file, "log").unwrap();
+        let params = CleanParams {
+            items: vec![CleanItem {
+                path: file.clone(),
+                category: None,
+            }],
+            permanent: true,
+        };
+
+        let result = clean(params, &AtomicBool::new(true)).unwrap();
+        assert_eq!(result["cancelled"], true);
+        assert_eq!(result["deleted"], json!([]));
+        assert!(file.exists());
+    }
+}
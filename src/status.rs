@@ -232,6 +232,9 @@ pub struct SystemStatus {
     pub disk_breakdown: Option<DiskBreakdown>,
     #[cfg(windows)]
     pub boot_info: Option<BootInfo>,
+    /// When each volume runs out of space at its current growth rate (`wole status --json`)
+    #[serde(default)]
+    pub forecasts: Vec<crate::forecast::VolumeForecast>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -441,6 +444,20 @@ pub fn gather_status_async(sender: std::sync::mpsc::Sender<Result<SystemStatus>>
     });
 }
 
+/// Full status with free-space forecasts, as printed by `wole status --json`
+pub fn snapshot() -> Result<SystemStatus> {
+    let mut system = System::new();
+    system.refresh_all();
+
+    let mut status = gather_status(&mut system)
+        .map_err(|e| anyhow::anyhow!("Failed to gather system status: {}", e))?;
+    match crate::forecast::refresh(&status.disks) {
+        Ok(forecasts) => status.forecasts = forecasts,
+        Err(e) => eprintln!("Warning: Failed to forecast free space: {}", e),
+    }
+    Ok(status)
+}
+
 pub fn gather_status(system: &mut System) -> Result<SystemStatus> {
     gather_status_with_options(system, StatusGatherOptions::full())
 }
@@ -558,6 +575,7 @@ pub fn gather_status_with_options(
             disk_breakdown,
             #[cfg(windows)]
             boot_info,
+            forecasts: Vec::new(),
         })
     })
 }
@@ -697,7 +715,7 @@ fn gather_memory_metrics(system: &System) -> MemoryMetrics {
     }
 }
 
-fn gather_disk_details() -> Vec<DiskInfo> {
+pub fn gather_disk_details() -> Vec<DiskInfo> {
     use sysinfo::{DiskKind, Disks};
 
     let mut disks = Disks::new_with_refreshed_list();
@@ -50,6 +50,9 @@ pub fn handle_event(
             handle_optimize_event(app_state, key, modifiers)
         }
         crate::tui::state::Screen::Status { .. } => handle_status_event(app_state, key, modifiers),
+        crate::tui::state::Screen::Projects { .. } => {
+            handle_projects_event(app_state, key, modifiers)
+        }
     }
 }
 
@@ -82,6 +85,9 @@ pub fn handle_mouse_event(app_state: &mut AppState, mouse: MouseEvent) -> EventR
             crate::tui::state::Screen::Status { .. } => {
                 EventResult::Continue // Status screen doesn't need scrolling
             }
+            crate::tui::state::Screen::Projects { .. } => {
+                handle_projects_event(app_state, KeyCode::Down, KeyModifiers::empty())
+            }
             _ => EventResult::Continue,
         },
         MouseEventKind::ScrollUp => match app_state.screen {
@@ -109,6 +115,9 @@ pub fn handle_mouse_event(app_state: &mut AppState, mouse: MouseEvent) -> EventR
             crate::tui::state::Screen::Status { .. } => {
                 EventResult::Continue // Status screen doesn't need scrolling
             }
+            crate::tui::state::Screen::Projects { .. } => {
+                handle_projects_event(app_state, KeyCode::Up, KeyModifiers::empty())
+            }
             _ => EventResult::Continue,
         },
         MouseEventKind::Down(MouseButton::Left) => match app_state.screen {
@@ -304,8 +313,8 @@ fn handle_dashboard_event(
         }
         KeyCode::Down => {
             if app_state.focus_actions {
-                // Navigate in actions list (7 actions: Scan, Clean, Analyze, Restore, Config, Optimize, Status)
-                if app_state.action_cursor < 6 {
+                // Navigate in actions list (8 actions: Scan, Clean, Analyze, Restore, Optimize, Status, Projects, Config)
+                if app_state.action_cursor < 7 {
                     app_state.action_cursor += 1;
                 }
             } else {
@@ -473,6 +482,17 @@ fn handle_dashboard_event(
                     }
                 }
                 6 => {
+                    // Projects action - list projects while the inventory loads in the background
+                    let receiver = start_projects_inventory(app_state);
+                    app_state.screen = crate::tui::state::Screen::Projects {
+                        projects: Vec::new(),
+                        cursor: 0,
+                        sort: crate::projects::ProjectSort::default(),
+                        inactive_only: false,
+                        receiver: Some(receiver),
+                    };
+                }
+                7 => {
                     // Config action - show config screen
                     // Ensure config exists on disk so we can open it
                     app_state.config = crate::config::Config::load_or_create();
@@ -2333,6 +2353,7 @@ fn handle_disk_insights_event(
         ref mut cursor,
         ref mut sort_by,
         ref mut selected_paths,
+        ..
     } = app_state.screen
     {
         // Get current folder node
@@ -2922,3 +2943,78 @@ fn handle_optimize_click(app_state: &mut AppState, row: u16, _col: u16) -> Event
     }
     EventResult::Continue
 }
+
+/// Find projects under the scan path on a background thread
+fn start_projects_inventory(
+    app_state: &AppState,
+) -> std::sync::mpsc::Receiver<Vec<crate::projects::ProjectInfo>> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let scan_path = app_state.scan_path.clone();
+    let config = app_state.config.clone();
+    std::thread::spawn(move || {
+        let projects = crate::projects::discover(
+            &scan_path,
+            config.thresholds.project_age_days,
+            &config,
+            &|_| {},
+        );
+        let _ = tx.send(projects);
+    });
+    rx
+}
+
+fn handle_projects_event(
+    app_state: &mut AppState,
+    key: KeyCode,
+    _modifiers: KeyModifiers,
+) -> EventResult {
+    if matches!(key, KeyCode::Char('r') | KeyCode::Char('R')) {
+        let new_receiver = start_projects_inventory(app_state);
+        if let crate::tui::state::Screen::Projects {
+            ref mut projects,
+            ref mut cursor,
+            ref mut receiver,
+            ..
+        } = app_state.screen
+        {
+            projects.clear();
+            *cursor = 0;
+            *receiver = Some(new_receiver);
+        }
+        return EventResult::Continue;
+    }
+
+    if let crate::tui::state::Screen::Projects {
+        ref mut projects,
+        ref mut cursor,
+        ref mut sort,
+        ref mut inactive_only,
+        ..
+    } = app_state.screen
+    {
+        let visible =
+            crate::tui::screens::projects::visible_projects(projects, *inactive_only).len();
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('b') => {
+                app_state.screen = crate::tui::state::Screen::Dashboard;
+            }
+            KeyCode::Up => {
+                *cursor = cursor.saturating_sub(1);
+            }
+            KeyCode::Down if *cursor + 1 < visible => {
+                *cursor += 1;
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                *sort = sort.next();
+                crate::projects::sort_projects(projects, *sort);
+                *cursor = 0;
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => {
+                *inactive_only = !*inactive_only;
+                *cursor = 0;
+            }
+            _ => {}
+        }
+    }
+    EventResult::Continue
+}
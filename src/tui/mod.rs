@@ -23,6 +23,7 @@ use std::time::Duration;
 use self::events::{handle_event, handle_mouse_event};
 use self::screens::render;
 use self::state::AppState;
+use crate::categories::registry::{CategoryRegistry, CleanMode};
 use crate::cleaner;
 use crate::cli::ScanOptions;
 use crate::config::Config;
@@ -43,6 +44,9 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
 
     // Initialize app state (use provided or create new)
     let mut app_state = initial_state.unwrap_or_default();
+    // Free-space forecast for the dashboard (also records today's reading)
+    app_state.disk_forecasts =
+        crate::forecast::refresh(&crate::status::gather_disk_details()).unwrap_or_default();
     let mut scan_pending = false;
     let mut clean_pending = false;
     let mut last_tick_update = std::time::Instant::now();
@@ -69,6 +73,27 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
             app_state.tick = app_state.tick.wrapping_add(1);
         }
 
+        // Pick up the project inventory once the background thread is done
+        if let crate::tui::state::Screen::Projects {
+            ref mut projects,
+            ref sort,
+            ref mut receiver,
+            ..
+        } = app_state.screen
+        {
+            if let Some(ref rx) = receiver {
+                match rx.try_recv() {
+                    Ok(mut found) => {
+                        crate::projects::sort_projects(&mut found, *sort);
+                        *projects = found;
+                        *receiver = None;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => *receiver = None,
+                }
+            }
+        }
+
         // Auto-refresh Status screen every 2 seconds (using background thread)
         if let crate::tui::state::Screen::Status {
             ref mut status,
@@ -304,6 +329,8 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
                             // Show Disk Insights screen
                             app_state.screen = crate::tui::state::Screen::DiskInsights {
                                 insights,
+                                trends: crate::disk_trend::load_trends(&scan_path)
+                                    .unwrap_or_default(),
                                 current_path: scan_path,
                                 cursor: 0,
                                 sort_by: SortBy::Size,
@@ -644,29 +671,10 @@ fn perform_scan_with_progress(
         let mut running_total_bytes = 0u64;
 
         for cat_progress_name in &enabled_categories {
-            let (items, size) = match cat_progress_name.as_str() {
-                "Package Cache" => (results.cache.items, results.cache.size_bytes),
-                "Application Cache" => (results.app_cache.items, results.app_cache.size_bytes),
-                "Temp Files" => (results.temp.items, results.temp.size_bytes),
-                "Trash" => (results.trash.items, results.trash.size_bytes),
-                "Build Artifacts" => (results.build.items, results.build.size_bytes),
-                "Old Downloads" => (results.downloads.items, results.downloads.size_bytes),
-                "Large Files" => (results.large.items, results.large.size_bytes),
-                "Old Files" => (results.old.items, results.old.size_bytes),
-                "Installed Applications" => {
-                    (results.applications.items, results.applications.size_bytes)
-                }
-                "Browser Cache" => (results.browser.items, results.browser.size_bytes),
-                "System Cache" => (results.system.items, results.system.size_bytes),
-                "Empty Folders" => (results.empty.items, results.empty.size_bytes),
-                "Duplicates" => (results.duplicates.items, results.duplicates.size_bytes),
-                "Windows Update" => (
-                    results.windows_update.items,
-                    results.windows_update.size_bytes,
-                ),
-                "Event Logs" => (results.event_logs.items, results.event_logs.size_bytes),
-                _ => (0, 0),
-            };
+            let (items, size) = results
+                .find_by_name(cat_progress_name)
+                .map(|c| (c.result.items, c.result.size_bytes))
+                .unwrap_or((0, 0));
 
             running_total_items += items;
             running_total_bytes += size;
@@ -706,70 +714,22 @@ fn perform_scan_with_progress(
 
     let total_categories = enabled_categories.len();
 
-    // Build scan options from selected categories
-    let mut cache = false;
-    let mut app_cache = false;
-    let mut temp = false;
-    let mut trash = false;
-    let mut build = false;
-    let mut downloads = false;
-    let mut large = false;
-    let mut old = false;
-    let mut applications = false;
-    let mut browser = false;
-    let mut system = false;
-    let mut empty = false;
-    let mut duplicates = false;
-    let mut windows_update = false;
-    let mut event_logs = false;
-
-    for cat in &app_state.categories {
-        match cat.name.as_str() {
-            "Package Cache" => cache = cat.enabled,
-            "Application Cache" => app_cache = cat.enabled,
-            "Temp Files" => temp = cat.enabled,
-            "Trash" => trash = cat.enabled,
-            "Build Artifacts" => build = cat.enabled,
-            "Old Downloads" => downloads = cat.enabled,
-            "Large Files" => large = cat.enabled,
-            "Old Files" => old = cat.enabled,
-            "Installed Applications" => applications = cat.enabled,
-            "Browser Cache" => browser = cat.enabled,
-            "System Cache" => system = cat.enabled,
-            "Empty Folders" => empty = cat.enabled,
-            "Duplicates" => duplicates = cat.enabled,
-            "Windows Update" => windows_update = cat.enabled,
-            "Event Logs" => event_logs = cat.enabled,
-            _ => {}
-        }
-    }
-
     // Load config first to use its values (create default file if needed)
     let config = Config::load_or_create();
 
     // Use config values for thresholds
     let min_size_bytes = config.thresholds.min_size_mb * 1024 * 1024;
 
-    let options = ScanOptions {
-        cache,
-        app_cache,
-        temp,
-        trash,
-        build,
-        downloads,
-        large,
-        old,
-        applications,
-        browser,
-        system,
-        empty,
-        duplicates,
-        windows_update,
-        event_logs,
+    // Build scan options from selected categories
+    let mut options = ScanOptions {
         project_age_days: config.thresholds.project_age_days,
         min_age_days: config.thresholds.min_age_days,
         min_size_bytes,
+        ..Default::default()
     };
+    for cat in &app_state.categories {
+        options.set_enabled(&cat.id, cat.enabled);
+    }
 
     let mut first_scan_detected = false;
     if config.cache.enabled {
@@ -1033,24 +993,10 @@ fn perform_scan_with_progress(
                 return Ok(());
             }
 
-            let (items, size) = match cat_progress_name.as_str() {
-                "Package Cache" => (results.cache.items, results.cache.size_bytes),
-                "Application Cache" => (results.app_cache.items, results.app_cache.size_bytes),
-                "Temp Files" => (results.temp.items, results.temp.size_bytes),
-                "Trash" => (results.trash.items, results.trash.size_bytes),
-                "Build Artifacts" => (results.build.items, results.build.size_bytes),
-                "Old Downloads" => (results.downloads.items, results.downloads.size_bytes),
-                "Large Files" => (results.large.items, results.large.size_bytes),
-                "Old Files" => (results.old.items, results.old.size_bytes),
-                "Installed Applications" => {
-                    (results.applications.items, results.applications.size_bytes)
-                }
-                "Browser Cache" => (results.browser.items, results.browser.size_bytes),
-                "System Cache" => (results.system.items, results.system.size_bytes),
-                "Empty Folders" => (results.empty.items, results.empty.size_bytes),
-                "Duplicates" => (results.duplicates.items, results.duplicates.size_bytes),
-                _ => (0, 0),
-            };
+            let (items, size) = results
+                .find_by_name(cat_progress_name)
+                .map(|c| (c.result.items, c.result.size_bytes))
+                .unwrap_or((0, 0));
 
             running_total_items += items;
             running_total_bytes += size;
@@ -1212,9 +1158,18 @@ fn perform_cleanup(
     let mut trash_items: Vec<(usize, u64)> = Vec::new();
     let mut trash_total_bytes = 0u64;
 
+    // Each category is routed by the clean mode of its registry descriptor
+    let registry = CategoryRegistry::with_config(&app_state.config);
+    let clean_mode_of = |category: &str| {
+        registry
+            .find_by_name(category)
+            .map(|d| (d.id.clone(), d.clean_mode))
+            .unwrap_or_else(|| (String::new(), CleanMode::Batch))
+    };
+
     for &index in &app_state.selected_items {
         if let Some(item) = app_state.all_items.get(index) {
-            if item.category == "Trash" {
+            if clean_mode_of(&item.category).1 == CleanMode::RecycleBin {
                 trash_items.push((index, item.size_bytes));
                 trash_total_bytes += item.size_bytes;
             } else {
@@ -1295,7 +1250,7 @@ fn perform_cleanup(
 
     // === BATCH DELETION (10-50x faster than one-by-one) ===
     // Group items by category for optimized processing
-    // Special categories (Browser, System, Empty, Package Cache) need individual handling
+    // Per-path categories (Browser, System, Empty, ...) and Package Cache need individual handling
     // Package Cache needs individual handling to avoid Windows dialogs blocking batch deletion
     // Temp Files are processed separately with smaller batches to reduce batch failures
     // All other categories can be batch deleted together
@@ -1307,19 +1262,20 @@ fn perform_cleanup(
     let mut batch_items: Vec<(usize, std::path::PathBuf, u64)> = Vec::new();
 
     for (idx, category, path, size) in items_to_clean {
-        match category.as_str() {
-            "Installed Applications" => {
+        let (id, clean_mode) = clean_mode_of(&category);
+        match (clean_mode, id.as_str()) {
+            (CleanMode::Uninstall, _) => {
                 // Applications need a real uninstall step; don't batch-delete folders.
                 applications_items.push((idx, path, size));
             }
-            "Browser Cache" | "System Cache" | "Empty Folders" => {
-                special_items.push((idx, category, path, size));
-            }
-            "Package Cache" => {
+            (_, "cache") => {
                 // Process cache individually to handle Windows dialogs
                 cache_items.push((idx, path, size));
             }
-            "Temp Files" => {
+            (CleanMode::PerPath, _) => {
+                special_items.push((idx, category, path, size));
+            }
+            (_, "temp") => {
                 // Process temp files separately with smaller batches
                 // Temp files are more likely to be locked, so smaller batches reduce failures
                 temp_items.push((idx, path, size));
@@ -1556,12 +1512,25 @@ fn perform_cleanup(
                 let _ = terminal.draw(|f| render(f, app_state));
             }
 
-            match run_delete_with_ui(app_state, terminal, path.clone(), permanent) {
+            // Grouped items (npm's unreferenced blobs) expand to many files, each
+            // logged on its own so restore can find it
+            let (outcome, removed) = if crate::categories::cache_strategy::is_grouped(&path) {
+                crate::categories::cache::clean_parts(&path, size_bytes, permanent)
+            } else {
+                let outcome = run_delete_with_ui(app_state, terminal, path.clone(), permanent);
+                let removed = match outcome {
+                    Ok(cleaner::DeleteOutcome::Deleted) => vec![(path.clone(), size_bytes)],
+                    _ => Vec::new(),
+                };
+                (outcome, removed)
+            };
+            for (part, size) in &removed {
+                history.log_success(part, *size, "cache", permanent);
+            }
+            match outcome {
                 Ok(cleaner::DeleteOutcome::Deleted) => {
                     cleaned += 1;
                     cleaned_bytes += size_bytes;
-                    // Log success
-                    history.log_success(&path, size_bytes, "cache", permanent);
                 }
                 Ok(
                     cleaner::DeleteOutcome::SkippedMissing | cleaner::DeleteOutcome::SkippedSystem,
@@ -1946,7 +1915,7 @@ fn perform_restore(
             last_tick_update = std::time::Instant::now();
             let _ = terminal.draw(|f| render(f, app_state));
         }
-        if !record.success || record.permanent {
+        if !record.is_restorable() {
             continue;
         }
 
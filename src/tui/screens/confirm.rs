@@ -567,6 +567,10 @@ fn render_file_list(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                             }
                         }
                     }
+                    // Duplicates: which file of the group is kept instead
+                    if let Some(note) = item.display_name.as_ref() {
+                        pstr = format!("{}  → {}", pstr, note);
+                    }
                     pstr
                 };
                 let size_str = bytesize::to_string(item.size_bytes, false);
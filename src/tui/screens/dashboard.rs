@@ -60,6 +60,10 @@ fn render_actions(f: &mut Frame, area: Rect, app_state: &AppState) {
         ("Restore", "Restore files from deletion or Recycle Bin"),
         ("Optimize", "Optimize Windows system performance"),
         ("Status", "Real-time system health dashboard"),
+        (
+            "Projects",
+            "List projects, their activity and build artifacts",
+        ),
         ("Config", "View or modify settings"),
     ];
 
@@ -159,7 +163,7 @@ fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bo
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1), // Title
-            Constraint::Length(1), // Spacing
+            Constraint::Length(1), // Spacing (or the disk forecast)
             Constraint::Min(1),    // Categories list
         ])
         .split(chunks[1]);
@@ -173,6 +177,24 @@ fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bo
     .alignment(ratatui::layout::Alignment::Left);
     f.render_widget(title, category_chunks[0]);
 
+    // The volume that fills up soonest, in the spacing line
+    if let Some(forecast) = app_state
+        .disk_forecasts
+        .iter()
+        .find(|forecast| forecast.days_until_full.is_some())
+    {
+        let style = if forecast.days_until_full.unwrap_or(f64::INFINITY) < 30.0 {
+            Styles::warning()
+        } else {
+            Styles::secondary()
+        };
+        let line = Paragraph::new(Line::from(vec![
+            Span::styled("Disk forecast: ", Styles::secondary()),
+            Span::styled(forecast.to_string(), style),
+        ]));
+        f.render_widget(line, category_chunks[1]);
+    }
+
     // Helper function to determine which group a category belongs to
     fn get_category_group(cat_name: &str) -> Option<&'static str> {
         match cat_name {
@@ -196,7 +218,11 @@ fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bo
 
     for (i, cat) in app_state.categories.iter().enumerate() {
         // Check if we need to add a group header
-        let group = get_category_group(&cat.name);
+        let group = if cat.custom {
+            Some("E. Custom (config.toml)")
+        } else {
+            get_category_group(&cat.name)
+        };
         if group != current_group {
             if let Some(group_name) = group {
                 // Add group header
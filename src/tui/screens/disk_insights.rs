@@ -1,5 +1,6 @@
 //! Disk Insights screen - interactive folder navigation
 
+use crate::disk_trend::{format_growth, FolderTrend};
 use crate::disk_usage::{find_folder_by_path, SortBy};
 use crate::tui::{
     state::AppState,
@@ -14,21 +15,23 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline},
     Frame,
 };
+use std::path::{Path, PathBuf};
 
 pub fn render(f: &mut Frame, app_state: &mut AppState) {
     let area = f.area();
 
     // Extract values we need to avoid borrowing issues
-    let (insights_clone, current_path_clone, cursor, sort_by, selected_paths_clone) =
+    let (insights_clone, current_path_clone, cursor, sort_by, selected_paths_clone, trends) =
         if let crate::tui::state::Screen::DiskInsights {
             ref insights,
             ref current_path,
             cursor,
             sort_by,
             ref selected_paths,
+            ref trends,
         } = app_state.screen
         {
             (
@@ -37,6 +40,7 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
                 cursor,
                 sort_by,
                 selected_paths.clone(),
+                trends.clone(),
             )
         } else {
             return;
@@ -66,10 +70,21 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
     // Render search bar
     render_search_bar(f, chunks[2], app_state);
 
+    // Content, with the size history of the highlighted folder below it when there's room
+    let (content_area, trend_area) = if chunks[3].height >= 12 {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(TREND_HEIGHT)])
+            .split(chunks[3]);
+        (split[0], Some(split[1]))
+    } else {
+        (chunks[3], None)
+    };
+
     // Render content
-    render_content(
+    let highlighted = render_content(
         f,
-        chunks[3],
+        content_area,
         &insights_clone,
         &current_path_clone,
         cursor,
@@ -78,6 +93,10 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
         app_state,
     );
 
+    if let Some(trend_area) = trend_area {
+        render_trend(f, trend_area, &trends, &highlighted);
+    }
+
     // Render shortcuts
     let shortcuts = get_shortcuts(&app_state.screen, Some(app_state));
     render_shortcuts(f, chunks[4], &shortcuts);
@@ -159,6 +178,53 @@ fn render_search_bar(f: &mut Frame, area: Rect, app_state: &AppState) {
     f.render_widget(paragraph, area);
 }
 
+/// Height of the trend panel (sparkline plus borders)
+const TREND_HEIGHT: u16 = 5;
+
+fn render_trend(f: &mut Frame, area: Rect, trends: &[FolderTrend], path: &Path) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Styles::border());
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    let trend = trends
+        .iter()
+        .find(|t| t.path == path)
+        .filter(|t| t.samples.len() > 1);
+    let Some(trend) = trend else {
+        let empty = Paragraph::new(format!(
+            "No size history for {} yet - it builds up as you analyze again on other days",
+            name
+        ))
+        .style(Styles::secondary());
+        f.render_widget(empty.block(block.title("TREND")), area);
+        return;
+    };
+
+    let title = format!(
+        "TREND  {}  {}/day  {}/week  ({} scans over {:.0} days)",
+        name,
+        format_growth(trend.growth_per_day),
+        format_growth(trend.growth_per_week()),
+        trend.samples.len(),
+        trend.days_tracked()
+    );
+    // The most recent samples that fit
+    let data = trend.sparkline();
+    let width = area.width.saturating_sub(2) as usize;
+    let data = &data[data.len().saturating_sub(width)..];
+    let sparkline = Sparkline::default()
+        .block(block.title(title))
+        .data(data)
+        .style(Styles::emphasis());
+    f.render_widget(sparkline, area);
+}
+
+/// Renders the folder list and returns the folder the trend panel shows: the
+/// highlighted one, or the current folder when the cursor is on a file
 #[allow(clippy::too_many_arguments)]
 fn render_content(
     f: &mut Frame,
@@ -169,7 +235,7 @@ fn render_content(
     sort_by: SortBy,
     selected_paths: &std::collections::HashSet<std::path::PathBuf>,
     app_state: &AppState,
-) {
+) -> PathBuf {
     // Find current folder node
     let current_node = find_folder_by_path(&insights.root, current_path).unwrap_or(&insights.root);
 
@@ -385,6 +451,11 @@ fn render_content(
     list_state.select(Some(cursor));
 
     f.render_stateful_widget(list, area, &mut list_state);
+
+    children
+        .get(cursor)
+        .map(|child| child.path.clone())
+        .unwrap_or_else(|| current_node.path.clone())
 }
 
 fn format_number(n: u64) -> String {
@@ -6,6 +6,7 @@ pub mod dashboard;
 pub mod disk_insights;
 pub mod optimize;
 pub mod preview;
+pub mod projects;
 pub mod restore;
 pub mod restore_selection;
 pub mod results;
@@ -34,5 +35,6 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
         crate::tui::state::Screen::DiskInsights { .. } => disk_insights::render(f, app_state),
         crate::tui::state::Screen::Optimize { .. } => optimize::render(f, app_state),
         crate::tui::state::Screen::Status { .. } => status::render(f, app_state),
+        crate::tui::state::Screen::Projects { .. } => projects::render(f, app_state),
     }
 }
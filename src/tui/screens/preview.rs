@@ -1,5 +1,9 @@
 //! Preview screen with split file tree view
 
+use crate::project::{
+    self,
+    policy::{self, ProjectPolicy},
+};
 use crate::tui::{
     state::AppState,
     theme::Styles,
@@ -97,7 +101,13 @@ pub fn render(f: &mut Frame, app_state: &AppState) {
         );
 
         // Right: Will Delete preview
-        render_delete_preview(f, split_chunks[1], item, &app_state.scan_path);
+        render_delete_preview(
+            f,
+            split_chunks[1],
+            item,
+            &app_state.scan_path,
+            app_state.config.thresholds.project_age_days,
+        );
     }
 
     // Shortcuts
@@ -110,6 +120,7 @@ fn render_delete_preview(
     area: Rect,
     item: &crate::tui::state::ResultItem,
     base_path: &Path,
+    project_age_days: u64,
 ) {
     let path_display = crate::utils::to_relative_path(&item.path, base_path);
     let path_truncated = if path_display.len() > 50 {
@@ -118,7 +129,7 @@ fn render_delete_preview(
         path_display
     };
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(vec![Span::styled(
             "THIS FILE WILL BE DELETED:",
             Styles::danger(),
@@ -157,12 +168,20 @@ fn render_delete_preview(
                 },
             ),
         ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "  ⚠ Remember: [D] deletes ALL selected files, not just this one",
-            Styles::warning(),
-        )]),
     ];
+    let notes = policy_notes(item, base_path, project_age_days);
+    if !notes.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  Policy: ", Styles::header()),
+            Span::styled(notes.join("; "), Styles::warning()),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "  ⚠ Remember: [D] deletes ALL selected files, not just this one",
+        Styles::warning(),
+    )]));
 
     let paragraph = Paragraph::new(lines).block(
         Block::default()
@@ -174,3 +193,34 @@ fn render_delete_preview(
 
     f.render_widget(paragraph, area);
 }
+
+/// How the project's `.wole.toml` changed the decision to list a build artifact
+fn policy_notes(
+    item: &crate::tui::state::ResultItem,
+    base_path: &Path,
+    project_age_days: u64,
+) -> Vec<String> {
+    if item.category != "Build Artifacts" {
+        return Vec::new();
+    }
+    let Some((project_root, policy)) = item
+        .path
+        .parent()
+        .and_then(|parent| ProjectPolicy::find(parent, base_path))
+    else {
+        return Vec::new();
+    };
+
+    let mut notes = Vec::new();
+    if policy.lists_artifact(&project_root, &item.path) {
+        notes.push(format!("listed in {}", policy::FILE_NAME));
+    }
+    if let Some(days) = project::project_activity(&project_root, project_age_days).policy_days {
+        notes.push(format!(
+            "inactive after {} days per {}",
+            days,
+            policy::FILE_NAME
+        ));
+    }
+    notes
+}
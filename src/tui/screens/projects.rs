@@ -0,0 +1,230 @@
+//! Projects screen - inventory of discovered projects and their build artifacts
+
+use crate::projects::ProjectInfo;
+use crate::tui::{
+    state::AppState,
+    theme::Styles,
+    widgets::{
+        logo::{render_logo, render_tagline, LOGO_WITH_TAGLINE_HEIGHT},
+        shortcuts::{get_shortcuts, render_shortcuts},
+    },
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app_state: &AppState) {
+    let area = f.area();
+
+    let is_small = area.height < 20 || area.width < 60;
+    let shortcuts_height = if is_small { 2 } else { 3 };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(LOGO_WITH_TAGLINE_HEIGHT),
+            Constraint::Min(6),
+            Constraint::Length(shortcuts_height),
+        ])
+        .split(area);
+
+    render_logo(f, chunks[0]);
+    render_tagline(f, chunks[0]);
+    render_content(f, chunks[1], app_state);
+
+    let shortcuts = get_shortcuts(&app_state.screen, Some(app_state));
+    render_shortcuts(f, chunks[2], &shortcuts);
+}
+
+fn render_content(f: &mut Frame, area: Rect, app_state: &AppState) {
+    let crate::tui::state::Screen::Projects {
+        projects,
+        cursor,
+        sort,
+        inactive_only,
+        receiver,
+    } = &app_state.screen
+    else {
+        return;
+    };
+
+    if receiver.is_some() && projects.is_empty() {
+        let loading = Paragraph::new(format!(
+            "{} Finding projects in {}...",
+            crate::spinner::get_spinner(app_state.tick),
+            app_state.scan_path.display()
+        ))
+        .style(Styles::secondary())
+        .block(Block::default().borders(Borders::ALL).title("Projects"));
+        f.render_widget(loading, area);
+        return;
+    }
+
+    let visible = visible_projects(projects, *inactive_only);
+    let split = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+
+    let items: Vec<ListItem> = visible
+        .iter()
+        .enumerate()
+        .map(|(i, project)| {
+            let is_selected = i == *cursor;
+            let (status, status_style) = if project.is_active {
+                ("active  ", Styles::success())
+            } else {
+                ("inactive", Styles::warning())
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, Styles::selected()),
+                Span::styled(
+                    format!("{:<24} ", truncate(&project.name, 24)),
+                    if is_selected {
+                        Styles::selected()
+                    } else {
+                        Styles::emphasis()
+                    },
+                ),
+                Span::styled(format!("{} ", status), status_style),
+                Span::styled(
+                    format!("{:>10}  ", bytesize::to_string(project.total_bytes, false)),
+                    Styles::primary(),
+                ),
+                Span::styled(project.types.join(", "), Styles::secondary()),
+            ]))
+        })
+        .collect();
+
+    let inactive_count = projects.iter().filter(|p| !p.is_active).count();
+    let title = format!(
+        "Projects ({} found, {} inactive) - sorted by {}{}",
+        projects.len(),
+        inactive_count,
+        sort.name(),
+        if *inactive_only {
+            ", inactive only"
+        } else {
+            ""
+        }
+    );
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Styles::border())
+            .title(title),
+    );
+    let mut list_state = ratatui::widgets::ListState::default();
+    list_state.select(Some(*cursor));
+    f.render_stateful_widget(list, split[0], &mut list_state);
+
+    render_details(f, split[1], visible.get(*cursor).copied(), app_state);
+}
+
+fn render_details(f: &mut Frame, area: Rect, project: Option<&ProjectInfo>, app_state: &AppState) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Styles::border())
+        .title("Details")
+        .padding(ratatui::widgets::Padding::horizontal(1));
+    let Some(project) = project else {
+        let empty = Paragraph::new("No projects found").style(Styles::secondary());
+        f.render_widget(empty.block(block), area);
+        return;
+    };
+
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{}: ", label), Styles::header()),
+            Span::styled(value, Styles::primary()),
+        ])
+    };
+    let signal = match &project.signal_member {
+        Some(member) => format!(
+            "{} in {}",
+            project.signal,
+            crate::utils::to_relative_path(member, &project.path)
+        ),
+        None => project.signal.clone(),
+    };
+
+    let mut lines = vec![
+        field(
+            "Path",
+            crate::utils::to_relative_path(&project.path, &app_state.scan_path),
+        ),
+        field("Types", project.types.join(", ")),
+        field(
+            if project.is_active {
+                "Active"
+            } else {
+                "Inactive"
+            },
+            signal,
+        ),
+        field(
+            "Last activity",
+            project
+                .last_activity
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        field(
+            "Git root",
+            project
+                .git_root
+                .as_ref()
+                .map(|root| root.display().to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ),
+    ];
+    if let Some(workspace) = &project.workspace {
+        lines.push(field("Workspace", workspace.clone()));
+    }
+    lines.push(Line::from(""));
+    lines.push(field(
+        "Build artifacts",
+        bytesize::to_string(project.total_bytes, false),
+    ));
+    for artifact in &project.artifacts {
+        let mut spans = vec![
+            Span::styled(
+                format!(
+                    "  {:>10}  ",
+                    bytesize::to_string(artifact.size_bytes, false)
+                ),
+                Styles::emphasis(),
+            ),
+            Span::styled(
+                crate::utils::to_relative_path(&artifact.path, &project.path),
+                Styles::primary(),
+            ),
+        ];
+        if let Some(policy) = &artifact.policy {
+            spans.push(Span::styled(format!(" ({})", policy), Styles::warning()));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The projects shown with the current filter, in list order
+pub fn visible_projects(projects: &[ProjectInfo], inactive_only: bool) -> Vec<&ProjectInfo> {
+    projects
+        .iter()
+        .filter(|p| !inactive_only || !p.is_active)
+        .collect()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        let cut: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", cut)
+    } else {
+        text.to_string()
+    }
+}
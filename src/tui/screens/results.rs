@@ -1,5 +1,6 @@
 //! Results screen with grouped categories
 
+use crate::categories::registry::CategoryRegistry;
 use crate::tui::{
     state::AppState,
     theme::Styles,
@@ -155,24 +156,10 @@ fn spans_with_highlight(
 
 /// Get emoji for a category name
 fn category_emoji(category_name: &str) -> &'static str {
-    match category_name {
-        "Installed Applications" => "📱",
-        "Old Files" => "📅",
-        "Downloads" => "⬇️",
-        "Large Files" => "📦",
-        "Package Cache" => "📚",
-        "Application Cache" => "💾",
-        "Temp Files" => "🗑️",
-        "Trash" => "🗑️",
-        "Build Artifacts" => "🔨",
-        "Browser Cache" => "🌐",
-        "System Cache" => "⚙️",
-        "Empty Folders" => "📁",
-        "Duplicates" => "📋",
-        "Windows Update" => "🔄",
-        "Event Logs" => "📋",
-        _ => "📁", // Default folder emoji
-    }
+    CategoryRegistry::builtin()
+        .find_by_name(category_name)
+        .map(|descriptor| descriptor.emoji)
+        .unwrap_or("📁") // Default folder emoji
 }
 
 /// Get emoji for a folder based on dominant file type in its items
@@ -807,7 +794,12 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                                     crate::utils::to_relative_path(&item.path, &app_state.scan_path)
                                 })
                         } else {
-                            crate::utils::to_relative_path(&item.path, &app_state.scan_path)
+                            let path =
+                                crate::utils::to_relative_path(&item.path, &app_state.scan_path);
+                            match item.display_name.as_ref() {
+                                Some(note) => format!("{}  → {}", path, note),
+                                None => path,
+                            }
                         };
                         let size_str = bytesize::to_string(item.size_bytes, false);
                         let date_str = if item.category == "Installed Applications" {
@@ -958,7 +950,7 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                     "▸"
                 };
 
-                let header_line = Line::from(vec![
+                let mut header_spans = vec![
                     Span::styled(format!(" {} ", prefix), row_style),
                     Span::styled(checkbox, apply_sel(checkbox_style)),
                     Span::styled(" ", row_style),
@@ -982,8 +974,25 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                     } else {
                         Span::styled("  [review recommended]", apply_sel(Styles::warning()))
                     },
-                ]);
-                lines.push(header_line);
+                ];
+                // Package caches say how much comes back without network access
+                let reclaim = app_state.scan_results.as_ref().and_then(|results| {
+                    results
+                        .iter()
+                        .find(|c| c.descriptor.display_name == group.name)
+                        .and_then(|c| c.result.reclaim)
+                });
+                if let Some(reclaim) = reclaim {
+                    header_spans.push(Span::styled(
+                        format!(
+                            "  {} offline, {} re-download",
+                            bytesize::to_string(reclaim.offline_bytes, false),
+                            bytesize::to_string(reclaim.download_bytes, false)
+                        ),
+                        apply_sel(Styles::secondary()),
+                    ));
+                }
+                lines.push(Line::from(header_spans));
             }
             crate::tui::state::ResultsRow::FolderHeader {
                 group_idx,
@@ -1140,6 +1149,10 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                             }
                         }
                     }
+                    // Duplicates: which file of the group is kept instead
+                    if let Some(note) = item.display_name.as_ref() {
+                        pstr = format!("{}  → {}", pstr, note);
+                    }
                     pstr
                 };
 
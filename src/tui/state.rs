@@ -1,5 +1,6 @@
 //! Application state management for TUI
 
+use crate::categories::registry::CategoryRegistry;
 use crate::output::ScanResults;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -121,6 +122,8 @@ pub enum Screen {
         cursor: usize,
         sort_by: crate::disk_usage::SortBy,
         selected_paths: std::collections::HashSet<PathBuf>,
+        /// Size history of the scanned folders, for the sparkline
+        trends: Vec<crate::disk_trend::FolderTrend>,
     },
     Optimize {
         cursor: usize,
@@ -135,6 +138,14 @@ pub enum Screen {
         status_receiver:
             Option<std::sync::mpsc::Receiver<anyhow::Result<crate::status::SystemStatus>>>,
     },
+    Projects {
+        projects: Vec<crate::projects::ProjectInfo>,
+        cursor: usize,
+        sort: crate::projects::ProjectSort,
+        inactive_only: bool,
+        /// Pending inventory from the background thread
+        receiver: Option<std::sync::mpsc::Receiver<Vec<crate::projects::ProjectInfo>>>,
+    },
 }
 
 impl Clone for Screen {
@@ -180,12 +191,14 @@ impl Clone for Screen {
                 cursor,
                 sort_by,
                 selected_paths,
+                trends,
             } => Screen::DiskInsights {
                 insights: insights.clone(),
                 current_path: current_path.clone(),
                 cursor: *cursor,
                 sort_by: *sort_by,
                 selected_paths: selected_paths.clone(),
+                trends: trends.clone(),
             },
             Screen::Optimize {
                 cursor,
@@ -210,6 +223,19 @@ impl Clone for Screen {
                 // Receiver cannot be cloned, so set to None
                 status_receiver: None,
             },
+            Screen::Projects {
+                projects,
+                cursor,
+                sort,
+                inactive_only,
+                receiver: _,
+            } => Screen::Projects {
+                projects: projects.clone(),
+                cursor: *cursor,
+                sort: *sort,
+                inactive_only: *inactive_only,
+                receiver: None,
+            },
         }
     }
 }
@@ -267,136 +293,16 @@ pub struct RestoreProgress {
     pub restored_bytes: u64,
 }
 
-/// Category metadata for consistent naming across scan and clean
-#[derive(Debug, Clone)]
-pub struct CategoryDef {
-    pub name: &'static str,        // Display name used everywhere
-    pub scan_field: &'static str,  // Which ScanResults field to use
-    pub safe: bool,                // Safe to auto-select
-    pub default_enabled: bool,     // Enabled by default on dashboard
-    pub description: &'static str, // Description for dashboard
-}
-
-/// Central category definitions - single source of truth for all category names
-/// Ordered for dashboard: Quick Clean -> Developer Cleanup -> Space Hunters -> Advanced
-pub const CATEGORIES: &[CategoryDef] = &[
-    // A. Quick Clean (safe, minimal side effects)
-    CategoryDef {
-        name: "Trash",
-        scan_field: "trash",
-        safe: true,
-        default_enabled: true,
-        description: "Recycle Bin contents",
-    },
-    CategoryDef {
-        name: "Temp Files",
-        scan_field: "temp",
-        safe: true,
-        default_enabled: true,
-        description: "Temporary system files",
-    },
-    CategoryDef {
-        name: "Browser Cache",
-        scan_field: "browser",
-        safe: true,
-        default_enabled: true,
-        description: "Web browser data cache",
-    },
-    CategoryDef {
-        name: "Application Cache",
-        scan_field: "app_cache",
-        safe: true,
-        default_enabled: true,
-        description: "App data cache (Notion, VS Code, Slack, etc.)",
-    },
-    CategoryDef {
-        name: "System Cache",
-        scan_field: "system",
-        safe: true,
-        default_enabled: true,
-        description: "Windows system cache files",
-    },
-    CategoryDef {
-        name: "Empty Folders",
-        scan_field: "empty",
-        safe: true,
-        default_enabled: true,
-        description: "Directories with no files",
-    },
-    // B. Developer Cleanup (safe, but may trigger rebuilds / re-downloads)
-    CategoryDef {
-        name: "Build Artifacts",
-        scan_field: "build",
-        safe: true,
-        default_enabled: true,
-        description: "node_modules, target, .next",
-    },
-    CategoryDef {
-        name: "Package Cache",
-        scan_field: "cache",
-        safe: true,
-        default_enabled: false,
-        description: "Package manager cache (npm, pip, nuget, etc.)",
-    },
-    // C. Space Hunters (review required, biggest wins)
-    CategoryDef {
-        name: "Installed Applications",
-        scan_field: "applications",
-        safe: false,
-        default_enabled: false,
-        description: "Uninstallable programs",
-    },
-    CategoryDef {
-        name: "Old Downloads",
-        scan_field: "downloads",
-        safe: false,
-        default_enabled: false,
-        description: "Unused download files",
-    },
-    CategoryDef {
-        name: "Large Files",
-        scan_field: "large",
-        safe: false,
-        default_enabled: false,
-        description: "Files over size threshold",
-    },
-    CategoryDef {
-        name: "Old Files",
-        scan_field: "old",
-        safe: false,
-        default_enabled: false,
-        description: "Files not accessed in X days",
-    },
-    CategoryDef {
-        name: "Duplicates",
-        scan_field: "duplicates",
-        safe: false,
-        default_enabled: false,
-        description: "Identical file copies",
-    },
-    // D. Advanced (admin / system)
-    CategoryDef {
-        name: "Windows Update",
-        scan_field: "windows_update",
-        safe: false,
-        default_enabled: false,
-        description: "Update installation files (requires admin)",
-    },
-    CategoryDef {
-        name: "Event Logs",
-        scan_field: "event_logs",
-        safe: false,
-        default_enabled: false,
-        description: "System event logs (requires admin)",
-    },
-];
-
 /// Category selection state
 #[derive(Debug, Clone)]
 pub struct CategorySelection {
+    /// Registry id of the category (see `categories::registry`)
+    pub id: String,
     pub name: String,
     pub enabled: bool,
     pub description: String,
+    /// True for user-defined categories from config.toml
+    pub custom: bool,
 }
 
 /// Pending action after scan completes
@@ -456,10 +362,10 @@ fn folder_key_for_display(scan_path: &Path, folder_name: &str) -> String {
     crate::utils::to_relative_path(&folder_path, scan_path)
 }
 
-/// Get dashboard order index for a category (respects the order defined in CATEGORIES array)
-/// Returns the index in CATEGORIES array, or usize::MAX if not found
-fn dashboard_order_index(category_name: &str) -> usize {
-    CATEGORIES
+/// Get dashboard order index for a category (respects the registry order shown on the dashboard)
+/// Returns the index in the dashboard list, or usize::MAX if not found
+fn dashboard_order_index(categories: &[CategorySelection], category_name: &str) -> usize {
+    categories
         .iter()
         .position(|cat| cat.name == category_name)
         .unwrap_or(usize::MAX)
@@ -573,6 +479,7 @@ pub struct AppState {
     pub dashboard_message: Option<String>, // temporary message for dashboard (e.g. warnings)
     pub last_scan_categories: Option<std::collections::HashSet<String>>, // categories enabled during last scan (for result reuse)
     pub first_scan_stats: Option<(usize, u64)>, // (total_files, total_storage) for first scan summary
+    pub disk_forecasts: Vec<crate::forecast::VolumeForecast>, // free-space forecast shown on the dashboard
 }
 
 /// A single result item for display in the table
@@ -584,7 +491,7 @@ pub struct ResultItem {
     pub last_opened: Option<SystemTime>, // currently only populated for Installed Applications
     pub category: String,
     pub safe: bool, // true for cache/temp/trash, false for large/old/duplicates
-    pub display_name: Option<String>, // Optional display name (used for applications)
+    pub display_name: Option<String>, // Optional display name (used for applications; for duplicates, the file that's kept)
 }
 
 impl AppState {
@@ -642,38 +549,42 @@ impl AppState {
                 .iter()
                 .all(|cat| config_enabled.contains(cat));
 
-        // Use central CATEGORIES constant as single source of truth
+        // Use the category registry as single source of truth
         // Strategy:
         // - If config is empty OR incomplete (missing Quick Clean categories), use hardcoded defaults
         // - If config is complete, use config values (user has customized)
+        // Custom categories are opt-in: list them in default_enabled to pre-select
         let use_config = config_is_complete;
 
-        let categories = CATEGORIES
+        let categories = CategoryRegistry::with_config(&config)
             .iter()
-            .map(|cat_def| {
-                let cat_key = cat_def.name.to_lowercase().replace(" ", "_");
-                let enabled = if use_config {
+            .filter(|descriptor| descriptor.is_available())
+            .map(|descriptor| {
+                let cat_key = descriptor.display_name.to_lowercase().replace(" ", "_");
+                let enabled = if use_config || descriptor.custom {
                     // Config is complete - use config value
                     config_enabled.contains(&cat_key)
                 } else {
                     // Config is empty or incomplete - use hardcoded default
-                    cat_def.default_enabled
+                    descriptor.default_enabled
                 };
 
                 // Handle dynamic descriptions that depend on config values
-                let description = match cat_def.name {
-                    "Old Files" => format!(
+                let description = match descriptor.id.as_str() {
+                    "old" => format!(
                         "Files not accessed in {} days",
                         config.thresholds.min_age_days
                     ),
-                    "Large Files" => format!("Files over {}MB", config.thresholds.min_size_mb),
-                    _ => cat_def.description.to_string(),
+                    "large" => format!("Files over {}MB", config.thresholds.min_size_mb),
+                    _ => descriptor.description.clone(),
                 };
 
                 CategorySelection {
-                    name: cat_def.name.to_string(),
+                    id: descriptor.id.clone(),
+                    name: descriptor.display_name.clone(),
                     enabled,
                     description,
+                    custom: descriptor.custom,
                 }
             })
             .collect();
@@ -705,6 +616,7 @@ impl AppState {
             dashboard_message: None,
             last_scan_categories: None, // No previous scan initially
             first_scan_stats: None,     // No first scan stats initially
+            disk_forecasts: Vec::new(), // Loaded when the TUI starts
         }
     }
 
@@ -814,6 +726,22 @@ impl AppState {
                 })
             };
 
+            // For each duplicate copy, its size (a whole tree for folders) and the
+            // file its group keeps instead, with the rule that chose it
+            let duplicate_copies: HashMap<PathBuf, (u64, Option<String>)> = results
+                .duplicates_groups
+                .iter()
+                .flatten()
+                .flat_map(|group| {
+                    let note = group.keep_note();
+                    group
+                        .split()
+                        .1
+                        .into_iter()
+                        .map(move |path| (path.clone(), (group.copy_size(path), note.clone())))
+                })
+                .collect();
+
             // Helper to add items from a category
             let mut add_category = |paths: &[PathBuf],
                                     size_bytes: u64,
@@ -849,6 +777,11 @@ impl AppState {
                     let item_size = if category == "Installed Applications" {
                         crate::categories::applications::get_app_size(path)
                             .unwrap_or_else(|| size_bytes / paths.len().max(1) as u64)
+                    } else if let Some((size, _)) = duplicate_copies
+                        .get(path)
+                        .filter(|_| category == "Duplicates")
+                    {
+                        *size
                     } else {
                         std::fs::metadata(path)
                             .ok()
@@ -867,6 +800,10 @@ impl AppState {
                                 crate::categories::applications::get_app_display_name(&canon_path)
                             })
                         })
+                    } else if category == "Duplicates" {
+                        duplicate_copies
+                            .get(path)
+                            .and_then(|(_, note)| note.clone())
                     } else {
                         None
                     };
@@ -1320,125 +1257,16 @@ impl AppState {
 
             // Only add categories that are currently enabled
             // This allows reusing scan results when user disables some categories
-            if is_category_enabled("Package Cache") {
-                add_category(
-                    &results.cache.paths,
-                    results.cache.size_bytes,
-                    "Package Cache",
-                    true,
-                );
-            }
-            if is_category_enabled("Application Cache") {
-                add_category(
-                    &results.app_cache.paths,
-                    results.app_cache.size_bytes,
-                    "Application Cache",
-                    true,
-                );
-            }
-            if is_category_enabled("Temp Files") {
-                add_category(
-                    &results.temp.paths,
-                    results.temp.size_bytes,
-                    "Temp Files",
-                    true,
-                );
-            }
-            if is_category_enabled("Trash") {
-                add_category(
-                    &results.trash.paths,
-                    results.trash.size_bytes,
-                    "Trash",
-                    true,
-                );
-            }
-            if is_category_enabled("Build Artifacts") {
-                add_category(
-                    &results.build.paths,
-                    results.build.size_bytes,
-                    "Build Artifacts",
-                    true,
-                );
-            }
-            if is_category_enabled("Old Downloads") {
-                add_category(
-                    &results.downloads.paths,
-                    results.downloads.size_bytes,
-                    "Old Downloads",
-                    false,
-                );
-            }
-            if is_category_enabled("Large Files") {
-                add_category(
-                    &results.large.paths,
-                    results.large.size_bytes,
-                    "Large Files",
-                    false,
-                );
-            }
-            if is_category_enabled("Old Files") {
-                add_category(
-                    &results.old.paths,
-                    results.old.size_bytes,
-                    "Old Files",
-                    false,
-                );
-            }
-            if is_category_enabled("Installed Applications") {
-                add_category(
-                    &results.applications.paths,
-                    results.applications.size_bytes,
-                    "Installed Applications",
-                    false,
-                );
-            }
-            if is_category_enabled("Browser Cache") {
-                add_category(
-                    &results.browser.paths,
-                    results.browser.size_bytes,
-                    "Browser Cache",
-                    true,
-                );
-            }
-            if is_category_enabled("System Cache") {
-                add_category(
-                    &results.system.paths,
-                    results.system.size_bytes,
-                    "System Cache",
-                    true,
-                );
-            }
-            if is_category_enabled("Empty Folders") {
-                add_category(
-                    &results.empty.paths,
-                    results.empty.size_bytes,
-                    "Empty Folders",
-                    true,
-                );
-            }
-            if is_category_enabled("Duplicates") {
-                add_category(
-                    &results.duplicates.paths,
-                    results.duplicates.size_bytes,
-                    "Duplicates",
-                    false,
-                );
-            }
-            if is_category_enabled("Windows Update") {
-                add_category(
-                    &results.windows_update.paths,
-                    results.windows_update.size_bytes,
-                    "Windows Update",
-                    false,
-                );
-            }
-            if is_category_enabled("Event Logs") {
-                add_category(
-                    &results.event_logs.paths,
-                    results.event_logs.size_bytes,
-                    "Event Logs",
-                    false,
-                );
+            for scanned in results.iter() {
+                let descriptor = &scanned.descriptor;
+                if is_category_enabled(&descriptor.display_name) {
+                    add_category(
+                        &scanned.result.paths,
+                        scanned.result.size_bytes,
+                        &descriptor.display_name,
+                        descriptor.is_safe(),
+                    );
+                }
             }
 
             // Sort category groups for results screen:
@@ -1446,8 +1274,8 @@ impl AppState {
             // Then: Within dashboard order, sort by size descending
             self.category_groups.sort_by(|a, b| {
                 // Get dashboard order indices (lower = appears earlier in dashboard)
-                let a_order = dashboard_order_index(&a.name);
-                let b_order = dashboard_order_index(&b.name);
+                let a_order = dashboard_order_index(&self.categories, &a.name);
+                let b_order = dashboard_order_index(&self.categories, &b.name);
                 let order_cmp = a_order.cmp(&b_order);
                 if order_cmp != std::cmp::Ordering::Equal {
                     return order_cmp;
@@ -1491,10 +1319,10 @@ impl AppState {
             self.selected_items.clear();
 
             // Build a set of safe category names for quick lookup
-            let safe_categories: std::collections::HashSet<String> = CATEGORIES
+            let safe_categories: std::collections::HashSet<String> = results
                 .iter()
-                .filter(|cat| cat.safe)
-                .map(|cat| cat.name.to_string())
+                .filter(|c| c.descriptor.is_safe())
+                .map(|c| c.descriptor.display_name.clone())
                 .collect();
 
             // Auto-select items from safe categories only
@@ -2710,8 +2538,8 @@ impl AppState {
         // Then: Within dashboard order, sort by size descending
         groups.sort_by(|a, b| {
             // Get dashboard order indices (lower = appears earlier in dashboard)
-            let a_order = dashboard_order_index(&a.name);
-            let b_order = dashboard_order_index(&b.name);
+            let a_order = dashboard_order_index(&self.categories, &a.name);
+            let b_order = dashboard_order_index(&self.categories, &b.name);
             let order_cmp = a_order.cmp(&b_order);
             if order_cmp != std::cmp::Ordering::Equal {
                 return order_cmp;
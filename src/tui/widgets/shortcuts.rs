@@ -181,6 +181,13 @@ pub fn get_shortcuts(
             }
         }
         crate::tui::state::Screen::Status { .. } => vec![("Esc/Q", "Back"), ("R", "Refresh")],
+        crate::tui::state::Screen::Projects { .. } => vec![
+            ("↑↓", "Navigate"),
+            ("S", "Sort"),
+            ("I", "Inactive Only"),
+            ("R", "Refresh"),
+            ("Esc/Q", "Back"),
+        ],
         crate::tui::state::Screen::Optimize { .. } => {
             if app_state
                 .and_then(|s| {
@@ -52,6 +52,74 @@ pub fn display_path(path: &Path) -> String {
     path_str
 }
 
+/// Get the user's home directory (USERPROFILE on Windows, HOME elsewhere)
+pub fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("USERPROFILE")
+        .or_else(|| std::env::var_os("HOME"))
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|d| d.home_dir().to_path_buf()))
+}
+
+/// Expand `~`, `$VAR`, `${VAR}` and `%VAR%` references in a user-supplied path.
+///
+/// Returns `None` if the path references an environment variable that is not set
+/// (or the home directory cannot be determined), so callers can skip the entry
+/// instead of scanning a half-expanded path.
+pub fn expand_path(raw: &str) -> Option<PathBuf> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        out.push_str(&home_dir()?.to_string_lossy());
+        rest = &rest[1..];
+    }
+
+    let lookup =
+        |name: &str| -> Option<String> { std::env::var(name).ok().filter(|v| !v.is_empty()) };
+
+    while let Some(idx) = rest.find(['$', '%']) {
+        out.push_str(&rest[..idx]);
+        let tail = &rest[idx..];
+
+        if let Some(braced) = tail.strip_prefix("${") {
+            let end = braced.find('}')?;
+            out.push_str(&lookup(&braced[..end])?);
+            rest = &braced[end + 1..];
+        } else if let Some(after) = tail.strip_prefix('$') {
+            let len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            if len == 0 {
+                out.push('$');
+            } else {
+                out.push_str(&lookup(&after[..len])?);
+            }
+            rest = &after[len..];
+        } else {
+            let after = &tail[1..];
+            match after.find('%') {
+                Some(end) if end > 0 => {
+                    out.push_str(&lookup(&after[..end])?);
+                    rest = &after[end + 1..];
+                }
+                _ => {
+                    out.push('%');
+                    rest = after;
+                }
+            }
+        }
+    }
+    out.push_str(rest);
+
+    Some(PathBuf::from(out))
+}
+
 /// Convert to long path format for Windows (\\?\)
 ///
 /// Windows has a default path length limit of 260 characters (MAX_PATH).
@@ -1361,4 +1429,31 @@ mod tests {
         // Symlinks should be skipped
         assert!(should_skip_entry(&link));
     }
+
+    #[test]
+    fn test_expand_path_env_forms() {
+        std::env::set_var("WOLE_TEST_EXPAND", "/opt/wole");
+        assert_eq!(
+            expand_path("$WOLE_TEST_EXPAND/cache"),
+            Some(PathBuf::from("/opt/wole/cache"))
+        );
+        assert_eq!(
+            expand_path("${WOLE_TEST_EXPAND}/cache"),
+            Some(PathBuf::from("/opt/wole/cache"))
+        );
+        assert_eq!(
+            expand_path("%WOLE_TEST_EXPAND%/cache"),
+            Some(PathBuf::from("/opt/wole/cache"))
+        );
+        assert_eq!(
+            expand_path("/plain/path"),
+            Some(PathBuf::from("/plain/path"))
+        );
+    }
+
+    #[test]
+    fn test_expand_path_missing_var() {
+        assert_eq!(expand_path("$WOLE_TEST_SURELY_UNSET_VAR/x"), None);
+        assert_eq!(expand_path(""), None);
+    }
 }
@@ -0,0 +1,323 @@
+//! Watch mode (`wole watch`): keeps the scan cache warm between scans.
+//!
+//! Watches the scan roots for filesystem changes and applies them to the scan
+//! cache as they happen (see [`ScanCache::apply_fs_changes`]), so the next scan
+//! only walks the folders that changed. Events go through a bounded queue and
+//! are coalesced into batches: a batch is applied once things have been quiet
+//! for a moment, or after a maximum delay during a long storm. When the queue
+//! or the OS watcher overflows, changes may have been missed, so the cached
+//! directory signatures of the roots are dropped and rebuilt by a full rescan.
+
+use crate::cli::ScanOptions;
+use crate::config::Config;
+use crate::output::OutputMode;
+use crate::scan_cache::{FsChangeStats, ScanCache};
+use crate::scanner;
+use crate::utils;
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Events buffered between the watcher and the cache before it counts as an overflow
+pub const QUEUE_CAPACITY: usize = 4096;
+/// Changed paths kept for one batch; a bigger storm is cheaper to rescan
+pub const MAX_BATCH_PATHS: usize = 50_000;
+/// A batch is applied once no event arrived for this long...
+pub const QUIET_PERIOD: Duration = Duration::from_secs(2);
+/// ...or at the latest this long after its first event
+pub const MAX_BATCH_DELAY: Duration = Duration::from_secs(15);
+
+/// A message from the watcher thread
+#[derive(Debug)]
+pub enum WatchMessage {
+    Changed(Vec<PathBuf>),
+    /// Events were dropped: the cache can't be trusted for the roots anymore
+    Overflow,
+}
+
+/// What to do with the changes collected so far
+#[derive(Debug, PartialEq, Eq)]
+pub enum Batch {
+    Changes(Vec<PathBuf>),
+    Rescan,
+}
+
+/// Collects changed paths until a batch is due
+#[derive(Debug, Default)]
+pub struct Coalescer {
+    paths: BTreeSet<PathBuf>,
+    overflowed: bool,
+    first_event: Option<Instant>,
+    last_event: Option<Instant>,
+}
+
+impl Coalescer {
+    pub fn push(&mut self, message: WatchMessage, now: Instant) {
+        match message {
+            WatchMessage::Changed(paths) => self.paths.extend(paths),
+            WatchMessage::Overflow => self.overflowed = true,
+        }
+        if self.paths.len() > MAX_BATCH_PATHS {
+            self.overflowed = true;
+        }
+        if self.overflowed {
+            // The rescan covers everything
+            self.paths.clear();
+        }
+        self.first_event.get_or_insert(now);
+        self.last_event = Some(now);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && !self.overflowed
+    }
+
+    /// Quiet for long enough, or collecting for too long
+    pub fn is_due(&self, now: Instant) -> bool {
+        match (self.first_event, self.last_event) {
+            (Some(first), Some(last)) => {
+                !self.is_empty()
+                    && (now.duration_since(last) >= QUIET_PERIOD
+                        || now.duration_since(first) >= MAX_BATCH_DELAY)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn take(&mut self) -> Batch {
+        let batch = if self.overflowed {
+            Batch::Rescan
+        } else {
+            Batch::Changes(std::mem::take(&mut self.paths).into_iter().collect())
+        };
+        *self = Self::default();
+        batch
+    }
+}
+
+/// OS watcher on the scan roots, feeding a bounded queue
+pub struct FsWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<WatchMessage>,
+    /// Set when the queue was full and events had to be dropped
+    dropped: Arc<AtomicBool>,
+}
+
+impl FsWatcher {
+    /// Watch `roots` recursively, ignoring changes below `ignore` (wole's own files)
+    pub fn new(roots: &[PathBuf], ignore: Vec<PathBuf>) -> Result<Self> {
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+        let dropped = Arc::new(AtomicBool::new(false));
+        let handler_dropped = Arc::clone(&dropped);
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Some(message) = to_message(event, &ignore) {
+                send(&sender, message, &handler_dropped);
+            }
+        })
+        .context("Failed to start the filesystem watcher")?;
+
+        for root in roots {
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .with_context(|| format!("Failed to watch {}", root.display()))?;
+        }
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+            dropped,
+        })
+    }
+
+    /// Wait up to `timeout` for the next message
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Option<WatchMessage>> {
+        if self.dropped.swap(false, Ordering::Relaxed) {
+            return Ok(Some(WatchMessage::Overflow));
+        }
+        match self.receiver.recv_timeout(timeout) {
+            Ok(message) => Ok(Some(message)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(anyhow::anyhow!("The filesystem watcher stopped"))
+            }
+        }
+    }
+}
+
+fn send(sender: &SyncSender<WatchMessage>, message: WatchMessage, dropped: &AtomicBool) {
+    if let Err(TrySendError::Full(_)) = sender.try_send(message) {
+        dropped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The message for a watcher event (None for events that don't change anything)
+fn to_message(event: notify::Result<Event>, ignore: &[PathBuf]) -> Option<WatchMessage> {
+    let event = match event {
+        Ok(event) => event,
+        // Errors (e.g. the inotify queue overflowing) mean events may be lost
+        Err(_) => return Some(WatchMessage::Overflow),
+    };
+    if event.need_rescan() {
+        return Some(WatchMessage::Overflow);
+    }
+    if matches!(event.kind, EventKind::Access(_)) {
+        return None;
+    }
+    let paths: Vec<PathBuf> = event
+        .paths
+        .into_iter()
+        .filter(|path| !ignore.iter().any(|dir| path.starts_with(dir)))
+        .collect();
+    (!paths.is_empty()).then_some(WatchMessage::Changed(paths))
+}
+
+/// The folders to watch: `--path` arguments, else `paths.scan_roots`, else the home folder
+///
+/// `~` and environment variables in `scan_roots` are expanded; roots naming an
+/// unset variable are left out.
+pub fn watch_roots(paths: Vec<PathBuf>, config: &Config) -> Vec<PathBuf> {
+    if !paths.is_empty() {
+        return paths;
+    }
+    if !config.paths.scan_roots.is_empty() {
+        return config
+            .paths
+            .scan_roots
+            .iter()
+            .filter_map(|root| utils::expand_path(root))
+            .collect();
+    }
+    directories::UserDirs::new()
+        .map(|dirs| vec![dirs.home_dir().to_path_buf()])
+        .unwrap_or_default()
+}
+
+/// Scan options for the warm-up and fallback rescans: the categories that
+/// record directory signatures
+fn rescan_options(config: &Config) -> ScanOptions {
+    ScanOptions {
+        categories: vec!["large".to_string()],
+        project_age_days: config.thresholds.project_age_days,
+        min_age_days: config.thresholds.min_age_days,
+        min_size_bytes: config.thresholds.min_size_mb * 1024 * 1024,
+    }
+}
+
+/// Drop the cached directory signatures of the roots and scan them again
+///
+/// The results aren't stored as a scan session: they only cover the large
+/// files category, so they're no baseline for `wole scan --diff`.
+pub fn rescan(roots: &[PathBuf], config: &Config, cache: &mut ScanCache) -> Result<()> {
+    cache.set_keep_results(false);
+    let result = roots.iter().try_for_each(|root| {
+        cache.invalidate_dirs_under(root)?;
+        scanner::scan_all(
+            root,
+            rescan_options(config),
+            OutputMode::Quiet,
+            config,
+            Some(cache),
+        )
+        .map(|_| ())
+    });
+    cache.set_keep_results(true);
+    result
+}
+
+/// Apply one batch of changes to the cache
+pub fn apply(
+    batch: &Batch,
+    roots: &[PathBuf],
+    config: &Config,
+    cache: &mut ScanCache,
+) -> Result<Option<FsChangeStats>> {
+    match batch {
+        Batch::Changes(paths) => cache.apply_fs_changes(paths).map(Some),
+        Batch::Rescan => rescan(roots, config, cache).map(|_| None),
+    }
+}
+
+/// Watch until the process is stopped, calling `on_batch` after each batch
+pub fn run<F>(
+    roots: &[PathBuf],
+    config: &Config,
+    cache: &mut ScanCache,
+    initial_scan: bool,
+    mut on_batch: F,
+) -> Result<()>
+where
+    F: FnMut(&Batch, Result<Option<FsChangeStats>>),
+{
+    let mut ignore = Vec::new();
+    if let Ok(dir) = ScanCache::cache_dir() {
+        ignore.push(dir);
+    }
+    // Watch first, so nothing that changes during the warm-up scan is missed
+    let watcher = FsWatcher::new(roots, ignore)?;
+    if initial_scan {
+        rescan(roots, config, cache)?;
+    }
+
+    let mut pending = Coalescer::default();
+    loop {
+        if let Some(message) = watcher.recv_timeout(Duration::from_millis(500))? {
+            pending.push(message, Instant::now());
+        }
+        if pending.is_due(Instant::now()) {
+            let batch = pending.take();
+            let result = apply(&batch, roots, config, cache);
+            on_batch(&batch, result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coalesces_events_into_batches() {
+        let start = Instant::now();
+        let mut pending = Coalescer::default();
+        assert!(!pending.is_due(start + MAX_BATCH_DELAY));
+
+        let change = |p: &str| WatchMessage::Changed(vec![PathBuf::from(p)]);
+        pending.push(change("/home/a.txt"), start);
+        pending.push(change("/home/a.txt"), start + Duration::from_secs(1));
+        pending.push(change("/home/b.txt"), start + Duration::from_secs(2));
+        // Still busy
+        assert!(!pending.is_due(start + Duration::from_secs(3)));
+        assert!(pending.is_due(start + Duration::from_secs(2) + QUIET_PERIOD));
+        assert_eq!(
+            pending.take(),
+            Batch::Changes(vec![
+                PathBuf::from("/home/a.txt"),
+                PathBuf::from("/home/b.txt")
+            ])
+        );
+        assert!(pending.is_empty());
+
+        // A storm that never goes quiet is applied after the maximum delay
+        for second in 0..MAX_BATCH_DELAY.as_secs() {
+            pending.push(change("/home/c.txt"), start + Duration::from_secs(second));
+        }
+        assert!(pending.is_due(start + MAX_BATCH_DELAY));
+
+        pending.push(WatchMessage::Overflow, start);
+        assert_eq!(pending.take(), Batch::Rescan);
+    }
+
+    #[test]
+    fn test_expands_configured_roots() {
+        let Some(home) = utils::home_dir() else {
+            return;
+        };
+        let mut config = Config::default();
+        config.paths.scan_roots = vec!["~/code".to_string()];
+        assert_eq!(watch_roots(Vec::new(), &config), vec![home.join("code")]);
+    }
+}
@@ -0,0 +1,441 @@
+//! Workspace and monorepo detection.
+//!
+//! [`project::find_project_roots`](crate::project::find_project_roots) drops projects
+//! nested in one of the same type, so the members of a Cargo workspace or a pnpm
+//! monorepo are never projects of their own. This module reads the workspace manifests so the
+//! members can be attributed to their workspace:
+//!
+//! - Cargo: `[workspace] members` / `exclude` in `Cargo.toml`
+//! - pnpm: `packages` in `pnpm-workspace.yaml` (with `!` exclusions)
+//! - npm / yarn: `workspaces` in `package.json` (array or `{ "packages": [...] }`)
+//! - Gradle: `include` in `settings.gradle(.kts)`
+//! - .NET: project entries of `*.sln` files
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Kind of workspace manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    Cargo,
+    Pnpm,
+    /// npm or yarn `workspaces`
+    Npm,
+    Gradle,
+    DotNetSolution,
+}
+
+impl fmt::Display for WorkspaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WorkspaceKind::Cargo => "Cargo workspace",
+            WorkspaceKind::Pnpm => "pnpm workspace",
+            WorkspaceKind::Npm => "npm workspace",
+            WorkspaceKind::Gradle => "Gradle build",
+            WorkspaceKind::DotNetSolution => ".NET solution",
+        })
+    }
+}
+
+/// A workspace root and the member directories its manifests declare
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub root: PathBuf,
+    /// Every manifest kind found at the root (a repo can be pnpm and Cargo at once)
+    pub kinds: Vec<WorkspaceKind>,
+    /// Member directories below `root`, sorted, without `root` itself
+    pub members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Whether `path` is the workspace root or inside one of its members
+    pub fn contains(&self, path: &Path) -> bool {
+        path == self.root || self.member_of(path).is_some()
+    }
+
+    /// The member containing `path`, if any
+    pub fn member_of(&self, path: &Path) -> Option<&PathBuf> {
+        self.members
+            .iter()
+            .filter(|member| path.starts_with(member))
+            .max_by_key(|member| member.components().count())
+    }
+}
+
+/// A project root, or a workspace root that stands for all its members
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectGroup {
+    pub root: PathBuf,
+    pub workspace: Option<Workspace>,
+}
+
+/// Group project roots found below `scan_root` by the workspaces they belong to
+///
+/// Workspaces are looked for at each project root and at the directories between
+/// it and `scan_root`, since a workspace root (a `.sln` folder, a Gradle build with
+/// only `settings.gradle`) isn't always a project itself. Roots that are a workspace
+/// member, or inside one, are folded into the outermost workspace; others stay
+/// projects of their own.
+pub fn group_projects(scan_root: &Path, project_roots: &[PathBuf]) -> Vec<ProjectGroup> {
+    let mut candidates: Vec<PathBuf> = project_roots
+        .iter()
+        .flat_map(|project| {
+            project
+                .ancestors()
+                .take_while(|dir| dir.starts_with(scan_root))
+                .map(Path::to_path_buf)
+        })
+        .collect();
+    // Outermost first, so nested workspaces fold into the enclosing one
+    candidates.sort_by(|a, b| {
+        (a.components().count(), a.as_path()).cmp(&(b.components().count(), b.as_path()))
+    });
+    candidates.dedup();
+
+    let mut workspaces: Vec<Workspace> = Vec::new();
+    for dir in candidates {
+        if workspaces.iter().any(|w| w.contains(&dir)) {
+            continue;
+        }
+        if let Some(workspace) = detect_workspace(&dir) {
+            workspaces.push(workspace);
+        }
+    }
+
+    let mut groups: Vec<ProjectGroup> = Vec::new();
+    for project in project_roots {
+        match workspaces.iter().find(|w| w.contains(project)) {
+            Some(workspace) => {
+                if !groups.iter().any(|g| g.root == workspace.root) {
+                    groups.push(ProjectGroup {
+                        root: workspace.root.clone(),
+                        workspace: Some(workspace.clone()),
+                    });
+                }
+            }
+            None => groups.push(ProjectGroup {
+                root: project.clone(),
+                workspace: None,
+            }),
+        }
+    }
+    groups
+}
+
+/// How deep below the root member globs are expanded
+const MAX_MEMBER_DEPTH: usize = 4;
+
+/// Read the workspace manifests in `root`
+///
+/// Returns `None` when `root` declares no workspace or none of its member patterns
+/// match a directory.
+pub fn detect_workspace(root: &Path) -> Option<Workspace> {
+    let mut kinds = Vec::new();
+    let mut members = Vec::new();
+    let mut add = |kind: WorkspaceKind, found: Vec<PathBuf>| {
+        if !found.is_empty() {
+            kinds.push(kind);
+            members.extend(found);
+        }
+    };
+
+    if let Some((include, exclude)) = cargo_patterns(root) {
+        add(
+            WorkspaceKind::Cargo,
+            expand(root, &include, &exclude, "Cargo.toml"),
+        );
+    }
+    if let Some(patterns) = pnpm_patterns(root) {
+        let (exclude, include): (Vec<String>, Vec<String>) =
+            patterns.into_iter().partition(|p| p.starts_with('!'));
+        let exclude: Vec<String> = exclude.iter().map(|p| p[1..].to_string()).collect();
+        add(
+            WorkspaceKind::Pnpm,
+            expand(root, &include, &exclude, "package.json"),
+        );
+    }
+    if let Some(include) = npm_patterns(root) {
+        add(
+            WorkspaceKind::Npm,
+            expand(root, &include, &[], "package.json"),
+        );
+    }
+    add(WorkspaceKind::Gradle, gradle_members(root));
+    add(WorkspaceKind::DotNetSolution, solution_members(root));
+
+    members.retain(|member| member != root);
+    members.sort();
+    members.dedup();
+    (!members.is_empty()).then(|| Workspace {
+        root: root.to_path_buf(),
+        kinds,
+        members,
+    })
+}
+
+fn cargo_patterns(root: &Path) -> Option<(Vec<String>, Vec<String>)> {
+    #[derive(Deserialize)]
+    struct Manifest {
+        workspace: Option<CargoWorkspace>,
+    }
+    #[derive(Deserialize)]
+    struct CargoWorkspace {
+        #[serde(default)]
+        members: Vec<String>,
+        #[serde(default)]
+        exclude: Vec<String>,
+    }
+
+    let contents = fs::read_to_string(root.join("Cargo.toml")).ok()?;
+    let workspace = toml::from_str::<Manifest>(&contents).ok()?.workspace?;
+    Some((workspace.members, workspace.exclude))
+}
+
+/// `packages:` entries of `pnpm-workspace.yaml`, read line by line (no YAML parser needed)
+fn pnpm_patterns(root: &Path) -> Option<Vec<String>> {
+    let contents = fs::read_to_string(root.join("pnpm-workspace.yaml")).ok()?;
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let item = item.split(" #").next().unwrap_or(item).trim();
+            patterns.push(item.trim_matches(['\'', '"']).to_string());
+        }
+    }
+    Some(patterns)
+}
+
+fn npm_patterns(root: &Path) -> Option<Vec<String>> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Workspaces {
+        List(Vec<String>),
+        Object {
+            #[serde(default)]
+            packages: Vec<String>,
+        },
+    }
+    #[derive(Deserialize)]
+    struct PackageJson {
+        workspaces: Option<Workspaces>,
+    }
+
+    let contents = fs::read_to_string(root.join("package.json")).ok()?;
+    match serde_json::from_str::<PackageJson>(&contents)
+        .ok()?
+        .workspaces?
+    {
+        Workspaces::List(patterns) | Workspaces::Object { packages: patterns } => Some(patterns),
+    }
+}
+
+/// Projects named by `include` in `settings.gradle` or `settings.gradle.kts`
+///
+/// `include(":app", ":libs:core")` maps to `app/` and `libs/core/`.
+fn gradle_members(root: &Path) -> Vec<PathBuf> {
+    let contents = ["settings.gradle", "settings.gradle.kts"]
+        .iter()
+        .find_map(|name| fs::read_to_string(root.join(name)).ok())
+        .unwrap_or_default();
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("include"))
+        .flat_map(quoted_strings)
+        .map(|project| {
+            project
+                .trim_start_matches(':')
+                .split(':')
+                .fold(root.to_path_buf(), |dir, part| dir.join(part))
+        })
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// Directories of the projects listed in the `*.sln` files in `root`
+fn solution_members(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sln"))
+        .filter_map(|sln| fs::read_to_string(sln).ok())
+        .flat_map(|contents| {
+            contents
+                .lines()
+                .filter(|line| line.starts_with("Project("))
+                // Project("{type}") = "Name", "path\to\Name.csproj", "{guid}"
+                .filter_map(|line| quoted_strings(line).into_iter().nth(2))
+                .collect::<Vec<_>>()
+        })
+        .filter_map(|project_file| {
+            let relative = project_file.replace('\\', "/");
+            // Solution folders have no project file, only a name
+            let project = root.join(relative);
+            project
+                .is_file()
+                .then(|| project.parent().map(Path::to_path_buf))
+                .flatten()
+        })
+        .collect()
+}
+
+fn quoted_strings(line: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(['"', '\'']) {
+        let quote = rest[start..].chars().next().unwrap_or('"');
+        let after = &rest[start + 1..];
+        let Some(end) = after.find(quote) else {
+            break;
+        };
+        strings.push(after[..end].to_string());
+        rest = &after[end + 1..];
+    }
+    strings
+}
+
+/// Expand member globs to directories below `root` that contain `marker`
+fn expand(root: &Path, include: &[String], exclude: &[String], marker: &str) -> Vec<PathBuf> {
+    let build = |patterns: &[String]| -> GlobSet {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+            if let Ok(glob) = Glob::new(pattern) {
+                builder.add(glob);
+            }
+        }
+        builder.build().unwrap_or_else(|_| GlobSet::empty())
+    };
+    let include = build(include);
+    let exclude = build(exclude);
+    if include.is_empty() {
+        return Vec::new();
+    }
+
+    WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(MAX_MEMBER_DEPTH)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.file_type().is_dir()
+                && !name.starts_with('.')
+                && !matches!(name.as_ref(), "node_modules" | "target" | "build" | "dist")
+        })
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(root).ok()?;
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            (include.is_match(&relative)
+                && !exclude.is_match(&relative)
+                && e.path().join(marker).is_file())
+            .then(|| e.into_path())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_reads_cargo_and_pnpm_manifests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write(
+            &root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+        );
+        write(&root.join("crates/core/Cargo.toml"), "[package]");
+        write(&root.join("crates/old/Cargo.toml"), "[package]");
+        write(&root.join("crates/notes/README.md"), "");
+        write(
+            &root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'apps/*'\n  - \"!apps/legacy\"\ncatalog:\n  - ignored\n",
+        );
+        write(&root.join("apps/web/package.json"), "{}");
+        write(&root.join("apps/legacy/package.json"), "{}");
+
+        let workspace = detect_workspace(root).unwrap();
+        assert_eq!(
+            workspace.kinds,
+            vec![WorkspaceKind::Cargo, WorkspaceKind::Pnpm]
+        );
+        assert_eq!(
+            workspace.members,
+            vec![root.join("apps/web"), root.join("crates/core")]
+        );
+        assert_eq!(
+            workspace.member_of(&root.join("apps/web/node_modules")),
+            Some(&root.join("apps/web"))
+        );
+
+        // Members fold into the workspace; a crate outside `members` stays on its own
+        write(&root.join("examples/demo/Cargo.toml"), "[package]");
+        let projects = vec![
+            root.join("apps/web"),
+            root.join("crates/core"),
+            root.join("examples/demo"),
+        ];
+        let groups = group_projects(root, &projects);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].root, root);
+        assert_eq!(groups[0].workspace.as_ref(), Some(&workspace));
+        assert_eq!(groups[1].root, root.join("examples/demo"));
+        assert!(groups[1].workspace.is_none());
+    }
+
+    #[test]
+    fn test_reads_npm_gradle_and_solution_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write(
+            &root.join("package.json"),
+            r#"{"workspaces": {"packages": ["packages/*"]}}"#,
+        );
+        write(&root.join("packages/ui/package.json"), "{}");
+        write(
+            &root.join("settings.gradle.kts"),
+            "rootProject.name = \"x\"\ninclude(\":app\", \":libs:core\")\n",
+        );
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::create_dir_all(root.join("libs/core")).unwrap();
+        write(
+            &root.join("All.sln"),
+            "Project(\"{FAE04EC0}\") = \"Api\", \"src\\Api\\Api.csproj\", \"{1}\"\nEndProject\n\
+             Project(\"{2150E333}\") = \"Docs\", \"Docs\", \"{2}\"\nEndProject\n",
+        );
+        write(&root.join("src/Api/Api.csproj"), "<Project />");
+
+        let workspace = detect_workspace(root).unwrap();
+        assert_eq!(
+            workspace.members,
+            vec![
+                root.join("app"),
+                root.join("libs/core"),
+                root.join("packages/ui"),
+                root.join("src/Api"),
+            ]
+        );
+    }
+}
@@ -31,21 +31,7 @@ fn test_scan_build_artifacts_inactive_project() {
     fs::write(node_modules.join("test.txt"), "test").unwrap();
 
     let options = ScanOptions {
-        build: true,
-        cache: false,
-        app_cache: false,
-        temp: false,
-        trash: false,
-        downloads: false,
-        large: false,
-        old: false,
-        applications: false,
-        browser: false,
-        system: false,
-        empty: false,
-        duplicates: false,
-        windows_update: false,
-        event_logs: false,
+        categories: vec!["build".to_string()],
         project_age_days: 14,
         min_age_days: 30,
         min_size_bytes: 100 * 1024 * 1024,
@@ -79,21 +65,7 @@ fn test_scan_empty_directory() {
     let temp_dir = create_test_dir();
 
     let options = ScanOptions {
-        cache: false,
-        app_cache: false,
-        temp: false,
-        trash: false,
-        build: false,
-        downloads: false,
-        large: false,
-        old: false,
-        applications: false,
-        browser: false,
-        system: false,
-        empty: false,
-        duplicates: false,
-        windows_update: false,
-        event_logs: false,
+        categories: Vec::new(),
         project_age_days: 14,
         min_age_days: 30,
         min_size_bytes: 100 * 1024 * 1024,
@@ -105,9 +77,10 @@ fn test_scan_empty_directory() {
         scanner::scan_all(temp_dir.path(), options, OutputMode::Quiet, &config, None).unwrap();
 
     // Should return empty results
-    assert_eq!(results.cache.items, 0);
-    assert_eq!(results.temp.items, 0);
-    assert_eq!(results.build.items, 0);
+    assert!(results.get("cache").is_none());
+    assert!(results.get("temp").is_none());
+    assert!(results.get("build").is_none());
+    assert_eq!(results.total_items(), 0);
 }
 
 #[test]